@@ -1,4 +1,5 @@
 //! Generated file, do not edit by hand, see `xtask/codegen`
 
+pub mod nullability;
 pub mod safety;
-::pgls_analyse::declare_category! { pub Lint { kind : Lint , groups : [self :: safety :: Safety ,] } }
+::pgls_analyse::declare_category! { pub Lint { kind : Lint , groups : [self :: nullability :: Nullability , self :: safety :: Safety ,] } }
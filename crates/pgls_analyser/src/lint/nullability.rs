@@ -0,0 +1,7 @@
+//! Generated file, do not edit by hand, see `xtask/codegen`
+
+use pgls_analyse::declare_lint_group;
+pub mod ban_not_in_nullable_subquery;
+pub mod ban_null_comparison;
+pub mod require_coalesce_on_outer_join_aggregate;
+declare_lint_group! { pub Nullability { name : "nullability" , rules : [self :: ban_not_in_nullable_subquery :: BanNotInNullableSubquery , self :: ban_null_comparison :: BanNullComparison , self :: require_coalesce_on_outer_join_aggregate :: RequireCoalesceOnOuterJoinAggregate ,] } }
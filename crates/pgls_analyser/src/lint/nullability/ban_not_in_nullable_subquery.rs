@@ -0,0 +1,101 @@
+use pgls_analyse::{Rule, RuleDiagnostic, context::RuleContext, declare_lint_rule};
+use pgls_console::markup;
+use pgls_diagnostics::Severity;
+use pgls_query::{
+    NodeEnum, NodeRef,
+    protobuf::{BoolExprType, SubLinkType},
+};
+use pgls_schema_cache::SchemaCache;
+use pgls_type_resolver::TypeInferrer;
+
+declare_lint_rule! {
+    /// `NOT IN` with a subquery that may return NULL never matches a row.
+    ///
+    /// `x NOT IN (select y ...)` is true only if `x` differs from every `y`. As soon as the
+    /// subquery returns a single NULL, the comparison with it is unknown, so the whole expression
+    /// evaluates to NULL and the query silently returns no rows.
+    ///
+    /// A subquery column is considered nullable if it isn't declared `NOT NULL`, if it comes from
+    /// the nullable side of an outer join, or if it is an expression that may be NULL. Without a
+    /// database connection, only the latter two are detected.
+    ///
+    /// Use `NOT EXISTS` instead, or exclude NULLs in the subquery.
+    ///
+    /// ## Examples
+    ///
+    /// ### Invalid
+    ///
+    /// ```sql,expect_diagnostic
+    /// select * from users where id not in (select o.user_id from accounts a left join orders o on o.account_id = a.id);
+    /// ```
+    ///
+    /// ### Valid
+    ///
+    /// ```sql
+    /// select * from users u where not exists (select 1 from orders o where o.user_id = u.id);
+    /// ```
+    ///
+    pub BanNotInNullableSubquery {
+        version: "next",
+        name: "banNotInNullableSubquery",
+        severity: Severity::Warning,
+        recommended: true,
+    }
+}
+
+impl Rule for BanNotInNullableSubquery {
+    type Options = ();
+
+    fn run(ctx: &RuleContext<Self>) -> Vec<RuleDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let empty = SchemaCache::default();
+        let inferrer = TypeInferrer::new(ctx.stmt(), ctx.schema_cache().unwrap_or(&empty));
+
+        for node in ctx.stmt().iter() {
+            // `not in` is parsed as `not (x = any (...))`
+            let NodeRef::BoolExpr(expr) = node else {
+                continue;
+            };
+
+            if expr.boolop() != BoolExprType::NotExpr {
+                continue;
+            }
+
+            let Some(NodeEnum::SubLink(sub_link)) = expr.args.first().and_then(|a| a.node.as_ref())
+            else {
+                continue;
+            };
+
+            if sub_link.sub_link_type() != SubLinkType::AnySublink {
+                continue;
+            }
+
+            let nullable = sub_link
+                .subselect
+                .as_deref()
+                .and_then(|query| inferrer.subquery_columns(query))
+                .and_then(|columns| columns.into_iter().next())
+                .and_then(|column| column.nullable);
+
+            if nullable == Some(true) {
+                diagnostics.push(
+                    RuleDiagnostic::new(
+                        rule_category!(),
+                        None,
+                        markup! {
+                            "NOT IN with a subquery that may return NULL."
+                        },
+                    )
+                    .detail(
+                        None,
+                        "If the subquery returns a NULL, NOT IN evaluates to NULL for every row and the query returns nothing.",
+                    )
+                    .note("Use NOT EXISTS instead, or exclude NULLs in the subquery."),
+                );
+            }
+        }
+
+        diagnostics
+    }
+}
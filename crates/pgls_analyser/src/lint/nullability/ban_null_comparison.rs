@@ -0,0 +1,97 @@
+use pgls_analyse::{Rule, RuleDiagnostic, context::RuleContext, declare_lint_rule};
+use pgls_console::markup;
+use pgls_diagnostics::Severity;
+use pgls_query::{NodeEnum, NodeRef, protobuf::AExprKind};
+
+declare_lint_rule! {
+    /// Comparing a value with NULL using `=` or `<>` is never true.
+    ///
+    /// In SQL, `NULL` represents an unknown value, so any comparison with it yields `NULL` rather
+    /// than `true` or `false`. A filter such as `where deleted_at = null` therefore never matches
+    /// a single row, and `where deleted_at <> null` doesn't either.
+    ///
+    /// Use `IS NULL` and `IS NOT NULL`, or `IS [NOT] DISTINCT FROM` to compare two values that may
+    /// both be NULL.
+    ///
+    /// ## Examples
+    ///
+    /// ### Invalid
+    ///
+    /// ```sql,expect_diagnostic
+    /// select * from users where deleted_at = null;
+    /// ```
+    ///
+    /// ### Valid
+    ///
+    /// ```sql
+    /// select * from users where deleted_at is null;
+    /// ```
+    ///
+    pub BanNullComparison {
+        version: "next",
+        name: "banNullComparison",
+        severity: Severity::Warning,
+        recommended: true,
+    }
+}
+
+impl Rule for BanNullComparison {
+    type Options = ();
+
+    fn run(ctx: &RuleContext<Self>) -> Vec<RuleDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for node in ctx.stmt().iter() {
+            let NodeRef::AExpr(expr) = node else {
+                continue;
+            };
+
+            if expr.kind() != AExprKind::AexprOp {
+                continue;
+            }
+
+            let Some(operator) = expr.name.last().and_then(|n| match n.node.as_ref()? {
+                NodeEnum::String(s) => Some(s.sval.as_str()),
+                _ => None,
+            }) else {
+                continue;
+            };
+
+            if !matches!(operator, "=" | "<>" | "!=") {
+                continue;
+            }
+
+            let is_null = |operand: Option<&pgls_query::protobuf::Node>| {
+                matches!(
+                    operand.and_then(|n| n.node.as_ref()),
+                    Some(NodeEnum::AConst(c)) if c.isnull
+                )
+            };
+
+            if is_null(expr.lexpr.as_deref()) || is_null(expr.rexpr.as_deref()) {
+                let replacement = if operator == "=" {
+                    "IS NULL"
+                } else {
+                    "IS NOT NULL"
+                };
+
+                diagnostics.push(
+                    RuleDiagnostic::new(
+                        rule_category!(),
+                        None,
+                        markup! {
+                            "Comparison with NULL using "<Emphasis>{operator}</Emphasis>" is never true."
+                        },
+                    )
+                    .detail(
+                        None,
+                        "Any comparison with NULL evaluates to NULL, so this condition never matches a row.",
+                    )
+                    .note(format!("Use {replacement} instead.")),
+                );
+            }
+        }
+
+        diagnostics
+    }
+}
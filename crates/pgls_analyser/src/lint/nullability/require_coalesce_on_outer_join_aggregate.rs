@@ -0,0 +1,122 @@
+use pgls_analyse::{Rule, RuleDiagnostic, context::RuleContext, declare_lint_rule};
+use pgls_console::markup;
+use pgls_diagnostics::Severity;
+use pgls_query::{NodeEnum, NodeRef};
+use pgls_schema_cache::SchemaCache;
+use pgls_type_resolver::TypeInferrer;
+
+/// Aggregates that ignore NULL inputs and return NULL if there are no other inputs.
+const NULL_ON_EMPTY_AGGREGATES: [&str; 11] = [
+    "sum",
+    "avg",
+    "min",
+    "max",
+    "string_agg",
+    "bool_and",
+    "bool_or",
+    "every",
+    "bit_and",
+    "bit_or",
+    "bit_xor",
+];
+
+declare_lint_rule! {
+    /// Aggregates over the nullable side of an outer join should be wrapped in `COALESCE`.
+    ///
+    /// For rows without a match, a `LEFT JOIN` produces NULLs for the columns of the joined
+    /// table. Aggregates like `sum` or `max` ignore NULL inputs and return NULL if there is
+    /// nothing else to aggregate, so a user without orders gets a total of NULL rather than 0.
+    ///
+    /// Wrap the aggregate in `COALESCE` to provide a value for groups without matching rows.
+    ///
+    /// ## Examples
+    ///
+    /// ### Invalid
+    ///
+    /// ```sql,expect_diagnostic
+    /// select u.id, sum(o.total) from users u left join orders o on o.user_id = u.id group by u.id;
+    /// ```
+    ///
+    /// ### Valid
+    ///
+    /// ```sql
+    /// select u.id, coalesce(sum(o.total), 0) from users u left join orders o on o.user_id = u.id group by u.id;
+    /// ```
+    ///
+    pub RequireCoalesceOnOuterJoinAggregate {
+        version: "next",
+        name: "requireCoalesceOnOuterJoinAggregate",
+        severity: Severity::Warning,
+        recommended: false,
+    }
+}
+
+impl Rule for RequireCoalesceOnOuterJoinAggregate {
+    type Options = ();
+
+    fn run(ctx: &RuleContext<Self>) -> Vec<RuleDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let empty = SchemaCache::default();
+        let inferrer = TypeInferrer::new(ctx.stmt(), ctx.schema_cache().unwrap_or(&empty));
+
+        // aggregates within a `coalesce` already handle the NULL case
+        let coalesced: Vec<i32> = ctx
+            .stmt()
+            .iter()
+            .filter_map(|n| match n {
+                NodeRef::CoalesceExpr(_) => Some(n.iter().filter_map(|n| match n {
+                    NodeRef::FuncCall(f) => Some(f.location),
+                    _ => None,
+                })),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+
+        for node in ctx.stmt().iter() {
+            let NodeRef::FuncCall(call) = node else {
+                continue;
+            };
+
+            if call.over.is_some() || coalesced.contains(&call.location) {
+                continue;
+            }
+
+            let Some(name) = call.funcname.last().and_then(|n| match n.node.as_ref()? {
+                NodeEnum::String(s) => Some(s.sval.as_str()),
+                _ => None,
+            }) else {
+                continue;
+            };
+
+            if !NULL_ON_EMPTY_AGGREGATES.contains(&name) {
+                continue;
+            }
+
+            let outer_joined = node.iter().any(|n| match n {
+                NodeRef::ColumnRef(c) => inferrer.is_outer_joined(c),
+                _ => false,
+            });
+
+            if outer_joined {
+                diagnostics.push(
+                    RuleDiagnostic::new(
+                        rule_category!(),
+                        None,
+                        markup! {
+                            "Aggregate "<Emphasis>{name}</Emphasis>" over an outer join is NULL for rows without a match."
+                        },
+                    )
+                    .detail(
+                        None,
+                        "The aggregated column comes from the nullable side of an outer join, so groups without a matching row aggregate only NULLs.",
+                    )
+                    .note("Wrap the aggregate in COALESCE to provide a default value."),
+                );
+            }
+        }
+
+        diagnostics
+    }
+}
@@ -19,6 +19,9 @@ pub type BanDropDatabase =
 pub type BanDropNotNull =
     <lint::safety::ban_drop_not_null::BanDropNotNull as pgls_analyse::Rule>::Options;
 pub type BanDropTable = <lint::safety::ban_drop_table::BanDropTable as pgls_analyse::Rule>::Options;
+pub type BanNotInNullableSubquery = < lint :: nullability :: ban_not_in_nullable_subquery :: BanNotInNullableSubquery as pgls_analyse :: Rule > :: Options ;
+pub type BanNullComparison =
+    <lint::nullability::ban_null_comparison::BanNullComparison as pgls_analyse::Rule>::Options;
 pub type BanTruncateCascade =
     <lint::safety::ban_truncate_cascade::BanTruncateCascade as pgls_analyse::Rule>::Options;
 pub type ChangingColumnType =
@@ -47,6 +50,7 @@ pub type RenamingColumn =
     <lint::safety::renaming_column::RenamingColumn as pgls_analyse::Rule>::Options;
 pub type RenamingTable =
     <lint::safety::renaming_table::RenamingTable as pgls_analyse::Rule>::Options;
+pub type RequireCoalesceOnOuterJoinAggregate = < lint :: nullability :: require_coalesce_on_outer_join_aggregate :: RequireCoalesceOnOuterJoinAggregate as pgls_analyse :: Rule > :: Options ;
 pub type RequireConcurrentIndexCreation = < lint :: safety :: require_concurrent_index_creation :: RequireConcurrentIndexCreation as pgls_analyse :: Rule > :: Options ;
 pub type RequireConcurrentIndexDeletion = < lint :: safety :: require_concurrent_index_deletion :: RequireConcurrentIndexDeletion as pgls_analyse :: Rule > :: Options ;
 pub type RunningStatementWhileHoldingAccessExclusive = < lint :: safety :: running_statement_while_holding_access_exclusive :: RunningStatementWhileHoldingAccessExclusive as pgls_analyse :: Rule > :: Options ;
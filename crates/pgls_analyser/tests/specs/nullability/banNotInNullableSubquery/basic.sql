@@ -0,0 +1,2 @@
+-- expect_only_lint/nullability/banNotInNullableSubquery
+select * from users where id not in (select o.user_id from accounts a left join orders o on o.account_id = a.id);
@@ -0,0 +1,2 @@
+-- expect_no_diagnostics
+select * from users where id in (select o.user_id from accounts a left join orders o on o.account_id = a.id);
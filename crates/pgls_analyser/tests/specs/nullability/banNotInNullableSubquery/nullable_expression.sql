@@ -0,0 +1,2 @@
+-- expect_only_lint/nullability/banNotInNullableSubquery
+delete from users where id not in (select nullif(user_id, 0) from orders);
@@ -0,0 +1,2 @@
+-- expect_only_lint/nullability/banNullComparison
+select * from users where deleted_at = null;
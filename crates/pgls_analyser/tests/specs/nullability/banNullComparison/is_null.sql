@@ -0,0 +1,2 @@
+-- expect_no_diagnostics
+select * from users where deleted_at is null and email is not distinct from null;
@@ -0,0 +1,2 @@
+-- expect_only_lint/nullability/banNullComparison
+update users set name = null where null <> email;
@@ -0,0 +1,2 @@
+-- expect_only_lint/nullability/requireCoalesceOnOuterJoinAggregate
+select u.id, sum(o.total) from users u left join orders o on o.user_id = u.id group by u.id;
@@ -0,0 +1,2 @@
+-- expect_no_diagnostics
+select u.id, coalesce(sum(o.total), 0), count(o.id) from users u left join orders o on o.user_id = u.id group by u.id;
@@ -0,0 +1,2 @@
+-- expect_no_diagnostics
+select u.id, sum(o.total) from users u join orders o on o.user_id = u.id group by u.id;
@@ -0,0 +1,2 @@
+-- expect_only_lint/nullability/requireCoalesceOnOuterJoinAggregate
+select o.id, max(u.created_at) from users u right join orders o on o.user_id = u.id group by o.id;
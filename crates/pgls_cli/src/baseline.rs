@@ -0,0 +1,237 @@
+//! Baseline files record the diagnostics of a project at a given point in time, so that
+//! `check --baseline` only reports the diagnostics that were introduced afterwards.
+
+use crate::diagnostics::CliDiagnostic;
+use crate::reporter::Report;
+use crate::reporter::fingerprint::attempt_to_relativize;
+use pgls_diagnostics::{Diagnostic, Error, Resource};
+use pgls_fs::{FileSystem, OpenOptions};
+use pgls_text_size::TextRange;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The version of the baseline file format.
+const BASELINE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Baseline {
+    version: u32,
+    entries: Vec<BaselineEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BaselineEntry {
+    #[serde(flatten)]
+    key: BaselineKey,
+    /// How many diagnostics share the same key.
+    count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BaselineKey {
+    /// The path of the file, relative to the working directory.
+    file: String,
+    /// The category of the diagnostic, e.g. `lint/safety/banDropTable`.
+    category: String,
+    /// The fingerprint of the statement the diagnostic was reported on. It only changes when
+    /// the statement itself changes, and not when it moves within the file.
+    ///
+    /// Statements that can't be parsed don't have a fingerprint, their diagnostics are matched
+    /// by file and category only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fingerprint: Option<String>,
+}
+
+impl Baseline {
+    /// Records all diagnostics of the report.
+    pub(crate) fn from_report(report: &Report) -> Self {
+        let mut keys = KeyBuilder::new(report);
+
+        let mut counts: BTreeMap<BaselineKey, u32> = BTreeMap::new();
+        for diagnostic in &report.diagnostics {
+            if let Some(key) = keys.key(diagnostic) {
+                *counts.entry(key).or_default() += 1;
+            }
+        }
+
+        Self {
+            version: BASELINE_VERSION,
+            entries: counts
+                .into_iter()
+                .map(|(key, count)| BaselineEntry { key, count })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn read(fs: &dyn FileSystem, path: &Path) -> Result<Self, CliDiagnostic> {
+        let mut content = String::new();
+        fs.open_with_options(path, OpenOptions::default().read(true))?
+            .read_to_string(&mut content)?;
+
+        let baseline: Self = serde_json::from_str(&content).map_err(|error| {
+            CliDiagnostic::io_error(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid baseline file {}: {error}", path.display()),
+            ))
+        })?;
+
+        if baseline.version != BASELINE_VERSION {
+            return Err(CliDiagnostic::io_error(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "Unsupported baseline version {} in {}, expected {BASELINE_VERSION}. Please regenerate it with --write-baseline.",
+                    baseline.version,
+                    path.display()
+                ),
+            )));
+        }
+
+        Ok(baseline)
+    }
+
+    pub(crate) fn write(&self, fs: &dyn FileSystem, path: &Path) -> Result<(), CliDiagnostic> {
+        let mut content = serde_json::to_string_pretty(self)
+            .map_err(|error| CliDiagnostic::io_error(std::io::Error::other(error)))?;
+        content.push('\n');
+
+        fs.open_with_options(
+            path,
+            OpenOptions::default()
+                .write(true)
+                .create(true)
+                .truncate(true),
+        )?
+        .set_content(content.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Removes the diagnostics recorded in the baseline from the report, and adds a diagnostic
+    /// for every baseline entry that no longer occurs. At most `max_diagnostics` diagnostics are
+    /// kept, the remaining ones are counted as skipped.
+    pub(crate) fn apply(self, report: Report, max_diagnostics: u32) -> Report {
+        let mut keys = KeyBuilder::new(&report);
+
+        let mut remaining: BTreeMap<BaselineKey, u32> = self
+            .entries
+            .into_iter()
+            .map(|entry| (entry.key, entry.count))
+            .collect();
+
+        let mut diagnostics: Vec<Error> = Vec::new();
+        for diagnostic in report.diagnostics {
+            let count = keys
+                .key(&diagnostic)
+                .and_then(|key| remaining.get_mut(&key))
+                .filter(|count| **count > 0);
+
+            match count {
+                Some(count) => *count -= 1,
+                None => diagnostics.push(diagnostic),
+            }
+        }
+
+        diagnostics.extend(remaining.into_iter().filter(|(_, count)| *count > 0).map(
+            |(key, _)| {
+                Error::from(StaleBaselineEntry {
+                    file: key.file,
+                    category: key.category,
+                })
+            },
+        ));
+
+        let mut filtered = Report::new(
+            diagnostics,
+            report.duration,
+            report.skipped_diagnostics,
+            report.traversal,
+        )
+        .with_suppressed_diagnostics(report.suppressed_diagnostics);
+
+        let max_diagnostics = max_diagnostics as usize;
+        if filtered.diagnostics.len() > max_diagnostics {
+            filtered.skipped_diagnostics += (filtered.diagnostics.len() - max_diagnostics) as u32;
+            filtered.diagnostics.truncate(max_diagnostics);
+        }
+
+        filtered
+    }
+}
+
+/// Computes the baseline keys of the diagnostics in a report. Statement ranges are cached per
+/// file, since most files report more than one diagnostic.
+struct KeyBuilder {
+    repository_root: Option<PathBuf>,
+    statements: FxHashMap<String, Vec<TextRange>>,
+}
+
+impl KeyBuilder {
+    fn new(report: &Report) -> Self {
+        Self {
+            repository_root: report
+                .traversal
+                .as_ref()
+                .and_then(|traversal| traversal.workspace_root.clone()),
+            statements: FxHashMap::default(),
+        }
+    }
+
+    /// Diagnostics that don't belong to a file or don't have a category can't be baselined.
+    fn key(&mut self, diagnostic: &Error) -> Option<BaselineKey> {
+        let location = diagnostic.location();
+        let Some(Resource::File(file)) = location.resource else {
+            return None;
+        };
+        let category = diagnostic.category()?;
+
+        let fingerprint =
+            location
+                .span
+                .zip(location.source_code)
+                .and_then(|(span, source_code)| {
+                    let statements = self
+                        .statements
+                        .entry(file.to_string())
+                        .or_insert_with(|| pgls_statement_splitter::split(source_code.text).ranges);
+                    let statement = statements
+                        .iter()
+                        .find(|statement| statement.contains_range(span))?;
+
+                    pgls_query::fingerprint(&source_code.text[*statement])
+                        .ok()
+                        .map(|fingerprint| fingerprint.hex)
+                });
+
+        Some(BaselineKey {
+            file: attempt_to_relativize(file, self.repository_root.as_deref())
+                .and_then(|path| path.to_str().map(|p| p.replace('\\', "/")))
+                .unwrap_or_else(|| file.replace('\\', "/")),
+            category: category.name().to_string(),
+            fingerprint,
+        })
+    }
+}
+
+#[derive(Debug, Diagnostic)]
+#[diagnostic(
+    category = "baseline",
+    severity = Information,
+    message(
+        description = "The baseline entry for {category} no longer occurs and can be removed.",
+        message("The baseline entry for "<Emphasis>{self.category}</Emphasis>" no longer occurs and can be removed.")
+    ),
+)]
+struct StaleBaselineEntry {
+    #[location(resource)]
+    file: String,
+    category: String,
+}
@@ -51,7 +51,7 @@ pub struct CliOptions {
     /// Allows to change how diagnostics and summary are reported.
     #[bpaf(
         long("reporter"),
-        argument("json|json-pretty|github|junit|summary|gitlab"),
+        argument("json|json-pretty|github|junit|summary|gitlab|sarif|checkstyle"),
         fallback(CliReporter::default())
     )]
     pub reporter: CliReporter,
@@ -129,6 +129,14 @@ pub enum CliReporter {
     Junit,
     /// Reports linter diagnostics using the [GitLab Code Quality report](https://docs.gitlab.com/ee/ci/testing/code_quality.html#implement-a-custom-tool).
     GitLab,
+    /// Reports diagnostics in the [SARIF](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) format, e.g. for GitHub code scanning.
+    Sarif,
+    /// Diagnostics and summary are printed as JSON, following a versioned schema
+    Json,
+    /// Same as `json`, but pretty-printed
+    JsonPretty,
+    /// Diagnostics are printed in the [Checkstyle](https://checkstyle.org) XML format
+    Checkstyle,
 }
 
 impl CliReporter {
@@ -145,6 +153,10 @@ impl FromStr for CliReporter {
             "github" => Ok(Self::GitHub),
             "junit" => Ok(Self::Junit),
             "gitlab" => Ok(Self::GitLab),
+            "sarif" => Ok(Self::Sarif),
+            "json" => Ok(Self::Json),
+            "json-pretty" => Ok(Self::JsonPretty),
+            "checkstyle" => Ok(Self::Checkstyle),
             _ => Err(format!(
                 "value {s:?} is not valid for the --reporter argument"
             )),
@@ -159,6 +171,10 @@ impl Display for CliReporter {
             CliReporter::GitHub => f.write_str("github"),
             CliReporter::Junit => f.write_str("junit"),
             CliReporter::GitLab => f.write_str("gitlab"),
+            CliReporter::Sarif => f.write_str("sarif"),
+            CliReporter::Json => f.write_str("json"),
+            CliReporter::JsonPretty => f.write_str("json-pretty"),
+            CliReporter::Checkstyle => f.write_str("checkstyle"),
         }
     }
 }
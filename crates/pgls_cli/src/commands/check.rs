@@ -1,3 +1,4 @@
+use crate::baseline::Baseline;
 use crate::cli_options::CliOptions;
 use crate::commands::get_files_to_process_with_cli_options;
 use crate::execute::{StdinPayload, run_files, run_stdin};
@@ -5,11 +6,12 @@ use crate::reporter::Report;
 use crate::{CliDiagnostic, CliSession, VcsIntegration};
 use crate::{ExecutionConfig, ExecutionMode, VcsTargeting};
 use pgls_configuration::PartialConfiguration;
-use pgls_console::Console;
+use pgls_console::{Console, ConsoleExt, markup};
 use pgls_diagnostics::category;
 use pgls_fs::FileSystem;
 use pgls_workspace::DynRef;
 use std::ffi::OsString;
+use std::path::PathBuf;
 
 pub struct CheckArgs {
     pub configuration: Option<PartialConfiguration>,
@@ -18,6 +20,8 @@ pub struct CheckArgs {
     pub staged: bool,
     pub changed: bool,
     pub since: Option<String>,
+    pub write_baseline: Option<PathBuf>,
+    pub baseline: Option<PathBuf>,
 }
 
 pub fn check(
@@ -43,14 +47,41 @@ pub fn check(
         u32::MAX
     };
 
+    let baseline = args
+        .baseline
+        .as_deref()
+        .map(|path| Baseline::read(&**session.fs(), path))
+        .transpose()?;
+
     let mode = ExecutionMode::Check { vcs };
-    let execution = ExecutionConfig::new(mode, max_diagnostics);
+    // Diagnostics are matched against the baseline before they are capped
+    let execution = if args.write_baseline.is_some() || baseline.is_some() {
+        ExecutionConfig::new(mode, u32::MAX)
+    } else {
+        ExecutionConfig::new(mode, max_diagnostics)
+    };
 
     if let Some(stdin_path) = args.stdin_file_path.as_deref() {
         let payload = read_stdin_payload(stdin_path, session.console())?;
         run_stdin(&mut session, &execution, payload)
     } else {
-        let report: Report = run_files(&mut session, &execution, paths)?;
+        let mut report: Report = run_files(&mut session, &execution, paths)?;
+
+        if let Some(path) = args.write_baseline.as_deref() {
+            let baseline = Baseline::from_report(&report);
+            baseline.write(&**session.fs(), path)?;
+
+            let entries = baseline.len();
+            let path = path.display().to_string();
+            session.console().log(markup! {
+                "Wrote "{entries}" baseline entries to "<Emphasis>{path}</Emphasis>"."
+            });
+            return Ok(());
+        }
+
+        if let Some(baseline) = baseline {
+            report = baseline.apply(report, max_diagnostics);
+        }
 
         let exit_result = enforce_exit_codes(cli_options, &report);
         session.report("check", cli_options, &report)?;
@@ -134,5 +165,27 @@ fn validate_args(args: &CheckArgs) -> Result<(), CliDiagnostic> {
         return Err(CliDiagnostic::incompatible_arguments("changed", "staged"));
     }
 
+    if args.write_baseline.is_some() {
+        if args.baseline.is_some() {
+            return Err(CliDiagnostic::incompatible_arguments(
+                "write-baseline",
+                "baseline",
+            ));
+        }
+        if args.stdin_file_path.is_some() {
+            return Err(CliDiagnostic::incompatible_arguments(
+                "write-baseline",
+                "stdin-file-path",
+            ));
+        }
+    }
+
+    if args.baseline.is_some() && args.stdin_file_path.is_some() {
+        return Err(CliDiagnostic::incompatible_arguments(
+            "baseline",
+            "stdin-file-path",
+        ));
+    }
+
     Ok(())
 }
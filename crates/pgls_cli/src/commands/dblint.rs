@@ -27,6 +27,7 @@ pub fn dblint(
     let PullDiagnosticsResult {
         diagnostics,
         skipped_diagnostics,
+        ..
     } = workspace.pull_db_diagnostics(PullDatabaseDiagnosticsParams { max_diagnostics })?;
 
     let report = Report::new(
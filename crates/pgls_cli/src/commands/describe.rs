@@ -0,0 +1,87 @@
+use std::path::Path;
+
+use crate::cli_options::CliOptions;
+use crate::{CliDiagnostic, CliSession, VcsIntegration};
+use pgls_configuration::PartialConfiguration;
+use pgls_console::{ConsoleExt, markup};
+use pgls_fs::{OpenOptions, PgLSPath};
+use pgls_workspace::features::describe::{
+    DescribeStatementParams, DescriptionSource, StatementSelector,
+};
+use pgls_workspace::workspace::{FileGuard, OpenFileParams};
+
+/// Handle of the `describe` command. Prints the result columns of the `statement`-th
+/// statement in the file.
+pub(crate) fn describe(
+    mut session: CliSession,
+    cli_options: &CliOptions,
+    cli_configuration: Option<PartialConfiguration>,
+    path: &Path,
+    statement: usize,
+) -> Result<(), CliDiagnostic> {
+    let configuration = session.prepare_with_config(cli_options, cli_configuration)?;
+    session.setup_workspace(configuration, VcsIntegration::Disabled)?;
+
+    let mut content = String::new();
+    session
+        .fs()
+        .open_with_options(path, OpenOptions::default().read(true))?
+        .read_to_string(&mut content)?;
+
+    let pgls_path = PgLSPath::new(path);
+    let result = {
+        let _guard = FileGuard::open(
+            session.workspace(),
+            OpenFileParams {
+                path: pgls_path.clone(),
+                content,
+                version: 0,
+            },
+        )?;
+
+        session
+            .workspace()
+            .describe_statement(DescribeStatementParams {
+                path: pgls_path,
+                statement: StatementSelector::Index(statement - 1),
+            })?
+    };
+
+    let console = session.console();
+
+    let Some(description) = result.description.filter(|d| !d.columns.is_empty()) else {
+        console.log(markup! {
+            "Statement "{statement}" does not return any rows."
+        });
+        return Ok(());
+    };
+
+    let name_width = description
+        .columns
+        .iter()
+        .map(|c| c.name.len())
+        .max()
+        .unwrap_or_default();
+
+    for column in &description.columns {
+        let name = format!("{:name_width$}", column.name);
+        let type_name = column.type_name.as_deref().unwrap_or("unknown");
+        let nullability = match column.nullable {
+            Some(true) => "nullable",
+            Some(false) => "not null",
+            None => "",
+        };
+
+        console.log(markup! {
+            <Emphasis>{name}</Emphasis>"  "{type_name}"  "<Dim>{nullability}</Dim>
+        });
+    }
+
+    if description.source == DescriptionSource::SchemaCache {
+        console.log(markup! {
+            <Warn>"Postgres could not describe the statement, the types were inferred from the schema cache."</Warn>
+        });
+    }
+
+    Ok(())
+}
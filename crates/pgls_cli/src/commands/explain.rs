@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use crate::cli_options::CliOptions;
+use crate::{CliDiagnostic, CliSession, VcsIntegration};
+use pgls_configuration::PartialConfiguration;
+use pgls_console::{ConsoleExt, markup};
+use pgls_diagnostics::PrintDiagnostic;
+use pgls_fs::{OpenOptions, PgLSPath};
+use pgls_workspace::features::describe::StatementSelector;
+use pgls_workspace::features::explain::ExplainStatementParams;
+use pgls_workspace::workspace::{FileGuard, OpenFileParams};
+
+/// Handle of the `explain` command. Prints the query plan of the `statement`-th statement in
+/// the file, followed by the findings in the plan.
+pub(crate) fn explain(
+    mut session: CliSession,
+    cli_options: &CliOptions,
+    cli_configuration: Option<PartialConfiguration>,
+    path: &Path,
+    statement: usize,
+    analyze: bool,
+) -> Result<(), CliDiagnostic> {
+    let configuration = session.prepare_with_config(cli_options, cli_configuration)?;
+    session.setup_workspace(configuration, VcsIntegration::Disabled)?;
+
+    let mut content = String::new();
+    session
+        .fs()
+        .open_with_options(path, OpenOptions::default().read(true))?
+        .read_to_string(&mut content)?;
+
+    let pgls_path = PgLSPath::new(path);
+    let result = {
+        let _guard = FileGuard::open(
+            session.workspace(),
+            OpenFileParams {
+                path: pgls_path.clone(),
+                content,
+                version: 0,
+            },
+        )?;
+
+        session
+            .workspace()
+            .explain_statement(ExplainStatementParams {
+                path: pgls_path,
+                statement: StatementSelector::Index(statement - 1),
+                analyze,
+            })?
+    };
+
+    let console = session.console();
+
+    if let Some(message) = &result.message {
+        console.log(markup! {
+            <Warn>{message}</Warn>
+        });
+    }
+
+    let Some(explanation) = result.explanation else {
+        return Ok(());
+    };
+
+    console.log(markup! {
+        {explanation.plan}
+    });
+
+    for diagnostic in &explanation.diagnostics {
+        console.error(markup! {
+            {PrintDiagnostic::simple(diagnostic)}
+        });
+    }
+
+    Ok(())
+}
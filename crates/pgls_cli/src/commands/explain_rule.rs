@@ -0,0 +1,319 @@
+use std::str::FromStr;
+
+use crate::{CliDiagnostic, CliSession};
+use pgls_analyse::{GroupCategory, RegistryVisitor, Rule, RuleCategory, RuleGroup, RuleMetadata};
+use pgls_console::fmt::{Display, Formatter};
+use pgls_console::{ConsoleExt, markup};
+use pgls_diagnostics::Severity;
+use serde::Serialize;
+
+#[derive(Debug, Default, Clone)]
+pub enum RuleFormat {
+    /// Human readable output
+    #[default]
+    Text,
+    /// Machine readable output, e.g. for editor integrations
+    Json,
+}
+
+impl FromStr for RuleFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "value {s:?} is not valid for the --format argument"
+            )),
+        }
+    }
+}
+
+/// Handle of the `explain-rule` command. Prints the documentation of a single rule, or lists
+/// all rules if no rule name is given.
+pub(crate) fn explain_rule(
+    mut session: CliSession,
+    rule_name: Option<String>,
+    format: RuleFormat,
+) -> Result<(), CliDiagnostic> {
+    let mut visitor = LintRulesVisitor::default();
+    pgls_analyser::visit_registry(&mut visitor);
+
+    let console = session.console();
+
+    let Some(rule_name) = rule_name else {
+        match format {
+            RuleFormat::Text => {
+                let name_width = visitor
+                    .rules
+                    .iter()
+                    .map(|rule| rule.metadata.name.len())
+                    .max()
+                    .unwrap_or_default();
+
+                let mut current_group = None;
+                for rule in &visitor.rules {
+                    if current_group != Some(rule.group) {
+                        current_group = Some(rule.group);
+                        console.log(markup! {
+                            <Emphasis>{rule.group}</Emphasis>
+                        });
+                    }
+
+                    let name = format!("{:name_width$}", rule.metadata.name);
+                    let recommended = if rule.metadata.recommended {
+                        "recommended"
+                    } else {
+                        "           "
+                    };
+                    let sources = rule
+                        .metadata
+                        .sources
+                        .iter()
+                        .map(|source| source.to_namespaced_rule_name())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    console.log(markup! {
+                        "  "{name}"  "<Success>{recommended}</Success>"  "{SeverityLabel(rule.metadata.severity)}"  "<Dim>{sources}</Dim>
+                    });
+                }
+            }
+            RuleFormat::Json => {
+                let rules: Vec<_> = visitor
+                    .rules
+                    .iter()
+                    .map(|rule| JsonRule::new(rule, false))
+                    .collect();
+                print_json(console, &rules)?;
+            }
+        }
+
+        return Ok(());
+    };
+
+    let Some(rule) = visitor.find(&rule_name) else {
+        return Err(CliDiagnostic::unknown_rule(rule_name));
+    };
+
+    match format {
+        RuleFormat::Text => {
+            let category = rule.category();
+            console.log(markup! {
+                <Emphasis>{category}</Emphasis>"\n"
+            });
+
+            console.log(markup! {
+                "Severity:     "{SeverityLabel(rule.metadata.severity)}
+            });
+            console.log(markup! {
+                "Recommended:  "{if rule.metadata.recommended { "yes" } else { "no" }}
+            });
+            for source in rule.metadata.sources {
+                let (url, name) = source.as_url_and_rule_name();
+                console.log(markup! {
+                    "Source:       "{source.to_string()}" "<Emphasis>{name}</Emphasis>" "<Dim>{url}</Dim>
+                });
+            }
+            if let Some(reason) = rule.metadata.deprecated {
+                console.log(markup! {
+                    <Warn>"Deprecated:   "{reason}</Warn>
+                });
+            }
+
+            console.log(markup! {
+                "\n"{RuleDocs(rule.metadata.docs)}
+            });
+        }
+        RuleFormat::Json => print_json(console, &JsonRule::new(rule, true))?,
+    }
+
+    Ok(())
+}
+
+fn print_json(
+    console: &mut dyn pgls_console::Console,
+    value: &impl Serialize,
+) -> Result<(), CliDiagnostic> {
+    let serialized = serde_json::to_string_pretty(value)
+        .map_err(|error| CliDiagnostic::io_error(std::io::Error::other(error)))?;
+    console.log(markup!({ serialized }));
+    Ok(())
+}
+
+struct LintRule {
+    group: &'static str,
+    metadata: RuleMetadata,
+}
+
+impl LintRule {
+    fn category(&self) -> String {
+        format!("lint/{}/{}", self.group, self.metadata.name)
+    }
+}
+
+/// Collects the metadata of all lint rules in the order of the registry.
+#[derive(Default)]
+struct LintRulesVisitor {
+    rules: Vec<LintRule>,
+}
+
+impl LintRulesVisitor {
+    /// Finds a rule by its name, e.g. `addingFieldWithDefault`, optionally prefixed with its
+    /// group or full category, e.g. `safety/addingFieldWithDefault`.
+    fn find(&self, name: &str) -> Option<&LintRule> {
+        let name = name.strip_prefix("lint/").unwrap_or(name);
+        self.rules.iter().find(|rule| match name.split_once('/') {
+            Some((group, name)) => rule.group == group && rule.metadata.name == name,
+            None => rule.metadata.name == name,
+        })
+    }
+}
+
+impl RegistryVisitor for LintRulesVisitor {
+    fn record_category<C: GroupCategory>(&mut self) {
+        if matches!(C::CATEGORY, RuleCategory::Lint) {
+            C::record_groups(self);
+        }
+    }
+
+    fn record_rule<R>(&mut self)
+    where
+        R: Rule + 'static,
+    {
+        self.rules.push(LintRule {
+            group: <R::Group as RuleGroup>::NAME,
+            metadata: R::METADATA,
+        });
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonRule {
+    name: &'static str,
+    group: &'static str,
+    category: String,
+    recommended: bool,
+    severity: Severity,
+    version: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deprecated: Option<&'static str>,
+    sources: Vec<JsonRuleSource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    docs: Option<&'static str>,
+}
+
+impl JsonRule {
+    fn new(rule: &LintRule, with_docs: bool) -> Self {
+        Self {
+            name: rule.metadata.name,
+            group: rule.group,
+            category: rule.category(),
+            recommended: rule.metadata.recommended,
+            severity: rule.metadata.severity,
+            version: rule.metadata.version,
+            deprecated: rule.metadata.deprecated,
+            sources: rule
+                .metadata
+                .sources
+                .iter()
+                .map(|source| JsonRuleSource {
+                    source: source.to_string(),
+                    name: source.as_rule_name(),
+                    url: source.to_rule_url(),
+                })
+                .collect(),
+            docs: with_docs.then_some(rule.metadata.docs),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonRuleSource {
+    source: String,
+    name: &'static str,
+    url: String,
+}
+
+struct SeverityLabel(Severity);
+
+impl Display for SeverityLabel {
+    fn fmt(&self, fmt: &mut Formatter) -> std::io::Result<()> {
+        let label = format!("{:7}", self.0.to_string());
+        match self.0 {
+            Severity::Hint | Severity::Information => fmt.write_markup(markup! {
+                <Info>{label}</Info>
+            }),
+            Severity::Warning => fmt.write_markup(markup! {
+                <Warn>{label}</Warn>
+            }),
+            Severity::Error | Severity::Fatal => fmt.write_markup(markup! {
+                <Error>{label}</Error>
+            }),
+        }
+    }
+}
+
+/// Renders the markdown of the rule documentation for the terminal. Headings are emphasized,
+/// code blocks are indented and their examples labeled, everything else is printed as is.
+struct RuleDocs(&'static str);
+
+impl Display for RuleDocs {
+    fn fmt(&self, fmt: &mut Formatter) -> std::io::Result<()> {
+        let mut in_code_block = false;
+
+        for line in self.0.trim().lines() {
+            let line = line.strip_prefix(' ').unwrap_or(line);
+
+            if let Some(info) = line.trim_start().strip_prefix("```") {
+                if !in_code_block && info.contains("expect_diagnostic") {
+                    fmt.write_markup(markup! {
+                        "    "<Dim>"-- reports a diagnostic"</Dim>"\n"
+                    })?;
+                }
+                in_code_block = !in_code_block;
+                continue;
+            }
+
+            if in_code_block {
+                fmt.write_markup(markup! {
+                    "    "<Info>{line}</Info>"\n"
+                })?;
+            } else if let Some(heading) = line.strip_prefix('#') {
+                fmt.write_markup(markup! {
+                    <Emphasis>{heading.trim_start_matches('#').trim()}</Emphasis>"\n"
+                })?;
+            } else {
+                fmt.write_str(line)?;
+                fmt.write_str("\n")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LintRulesVisitor;
+
+    #[test]
+    fn finds_rules_by_name_group_and_category() {
+        let mut visitor = LintRulesVisitor::default();
+        pgls_analyser::visit_registry(&mut visitor);
+
+        for name in [
+            "addingFieldWithDefault",
+            "safety/addingFieldWithDefault",
+            "lint/safety/addingFieldWithDefault",
+        ] {
+            let rule = visitor.find(name).expect("rule not found");
+            assert_eq!(rule.category(), "lint/safety/addingFieldWithDefault");
+        }
+
+        assert!(visitor.find("nullability/addingFieldWithDefault").is_none());
+        assert!(visitor.find("unknownRule").is_none());
+    }
+}
@@ -3,16 +3,22 @@ use crate::cli_options::{CliOptions, CliReporter, ColorsArg, cli_options};
 use crate::logging::LoggingKind;
 use crate::{CliDiagnostic, LoggingLevel, VERSION};
 use bpaf::Bpaf;
+use explain_rule::RuleFormat;
 use pgls_configuration::{PartialConfiguration, partial_configuration};
 use pgls_fs::FileSystem;
 use pgls_workspace::DynRef;
+use run::ResultFormat;
 use std::ffi::OsString;
 use std::path::PathBuf;
 pub(crate) mod check;
 pub(crate) mod clean;
 pub(crate) mod daemon;
 pub(crate) mod dblint;
+pub(crate) mod describe;
+pub(crate) mod explain;
+pub(crate) mod explain_rule;
 pub(crate) mod init;
+pub(crate) mod run;
 pub(crate) mod version;
 
 #[derive(Debug, Clone, Bpaf)]
@@ -66,11 +72,137 @@ pub enum PgLSCommand {
         #[bpaf(long("since"), argument("REF"))]
         since: Option<String>,
 
+        /// Records the diagnostics of this run in the given baseline file instead of reporting
+        /// them. Use it together with `--baseline` to only fail on new diagnostics.
+        #[bpaf(long("write-baseline"), argument("PATH"))]
+        write_baseline: Option<PathBuf>,
+
+        /// Hides the diagnostics recorded in the given baseline file, and reports baseline
+        /// entries that no longer occur.
+        #[bpaf(long("baseline"), argument("PATH"))]
+        baseline: Option<PathBuf>,
+
         /// Single file, single path or list of paths
         #[bpaf(positional("PATH"), many)]
         paths: Vec<OsString>,
     },
 
+    /// Describes the columns of the rows a statement returns.
+    #[bpaf(command)]
+    Describe {
+        #[bpaf(external(partial_configuration), hide_usage, optional)]
+        configuration: Option<PartialConfiguration>,
+
+        #[bpaf(external, hide_usage)]
+        cli_options: CliOptions,
+
+        /// The number of the statement within the file, starting at 1.
+        #[bpaf(
+            long("statement"),
+            argument("N"),
+            guard(|n| *n > 0, "Statements are numbered starting at 1"),
+            fallback(1),
+            display_fallback
+        )]
+        statement: usize,
+
+        /// The file that contains the statement
+        #[bpaf(positional("PATH"))]
+        path: PathBuf,
+    },
+
+    /// Prints the query plan of a statement and the problems found in it.
+    #[bpaf(command)]
+    Explain {
+        #[bpaf(external(partial_configuration), hide_usage, optional)]
+        configuration: Option<PartialConfiguration>,
+
+        #[bpaf(external, hide_usage)]
+        cli_options: CliOptions,
+
+        /// The number of the statement within the file, starting at 1.
+        #[bpaf(
+            long("statement"),
+            argument("N"),
+            guard(|n| *n > 0, "Statements are numbered starting at 1"),
+            fallback(1),
+            display_fallback
+        )]
+        statement: usize,
+
+        /// Run `EXPLAIN ANALYZE`. The statement is executed within a transaction that is
+        /// rolled back, and only if statement executions are allowed against the database.
+        #[bpaf(long("analyze"), switch)]
+        analyze: bool,
+
+        /// The file that contains the statement
+        #[bpaf(positional("PATH"))]
+        path: PathBuf,
+    },
+
+    /// Executes a statement against the database and prints the rows it returns.
+    #[bpaf(command)]
+    Run {
+        #[bpaf(external(partial_configuration), hide_usage, optional)]
+        configuration: Option<PartialConfiguration>,
+
+        #[bpaf(external, hide_usage)]
+        cli_options: CliOptions,
+
+        /// The number of the statement within the file, starting at 1.
+        #[bpaf(
+            long("statement"),
+            argument("N"),
+            guard(|n| *n > 0, "Statements are numbered starting at 1"),
+            fallback(1),
+            display_fallback
+        )]
+        statement: usize,
+
+        /// How to print the returned rows.
+        #[bpaf(
+            long("format"),
+            argument("table|csv|json"),
+            fallback(ResultFormat::Table),
+            hide_usage
+        )]
+        format: ResultFormat,
+
+        /// Execute the statement within a transaction that is rolled back afterwards.
+        #[bpaf(long("dry-run"), switch)]
+        dry_run: bool,
+
+        /// Execute the statement even if it destroys data, e.g. `drop table` or
+        /// `truncate ... cascade`.
+        #[bpaf(long("confirm"), switch)]
+        confirm: bool,
+
+        /// The file that contains the statement
+        #[bpaf(positional("PATH"))]
+        path: PathBuf,
+    },
+
+    /// Prints the documentation of a rule, or lists all rules if no rule is given.
+    #[bpaf(command("explain-rule"))]
+    ExplainRule {
+        #[bpaf(external, hide_usage)]
+        cli_options: CliOptions,
+
+        /// The format of the output. `json` is meant for tooling.
+        #[bpaf(
+            long("format"),
+            argument("text|json"),
+            fallback(RuleFormat::Text),
+            hide_usage
+        )]
+        format: RuleFormat,
+
+        /// The name of the rule, e.g. `addingFieldWithDefault` or
+        /// `lint/safety/addingFieldWithDefault`
+        #[bpaf(positional("RULE"), optional)]
+        rule: Option<String>,
+    },
+
     /// Starts the daemon server process.
     #[bpaf(command)]
     Start {
@@ -223,7 +355,11 @@ impl PgLSCommand {
         match self {
             PgLSCommand::Version(cli_options)
             | PgLSCommand::Check { cli_options, .. }
-            | PgLSCommand::Dblint { cli_options, .. } => Some(cli_options),
+            | PgLSCommand::Dblint { cli_options, .. }
+            | PgLSCommand::Describe { cli_options, .. }
+            | PgLSCommand::Explain { cli_options, .. }
+            | PgLSCommand::ExplainRule { cli_options, .. }
+            | PgLSCommand::Run { cli_options, .. } => Some(cli_options),
             PgLSCommand::LspProxy { .. }
             | PgLSCommand::Start { .. }
             | PgLSCommand::Stop
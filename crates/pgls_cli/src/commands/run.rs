@@ -0,0 +1,284 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::cli_options::CliOptions;
+use crate::{CliDiagnostic, CliSession, VcsIntegration};
+use pgls_configuration::PartialConfiguration;
+use pgls_console::{ConsoleExt, markup};
+use pgls_fs::{OpenOptions, PgLSPath};
+use pgls_workspace::features::code_actions::{
+    ExecuteStatementOptions, ExecuteStatementParams, ResultSet,
+};
+use pgls_workspace::features::describe::StatementSelector;
+use pgls_workspace::workspace::{FileGuard, OpenFileParams};
+
+#[derive(Debug, Default, Clone)]
+pub enum ResultFormat {
+    /// An aligned table, similar to `psql`
+    #[default]
+    Table,
+    /// Comma-separated values with a header row
+    Csv,
+    /// The columns and rows as JSON
+    Json,
+}
+
+impl FromStr for ResultFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Self::Table),
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "value {s:?} is not valid for the --format argument"
+            )),
+        }
+    }
+}
+
+/// Handle of the `run` command. Executes the `statement`-th statement in the file and prints
+/// the rows it returns. Statements that destroy data are only executed if `confirm` is set.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run(
+    mut session: CliSession,
+    cli_options: &CliOptions,
+    cli_configuration: Option<PartialConfiguration>,
+    path: &Path,
+    statement: usize,
+    format: ResultFormat,
+    mut options: ExecuteStatementOptions,
+    confirm: bool,
+) -> Result<(), CliDiagnostic> {
+    let configuration = session.prepare_with_config(cli_options, cli_configuration)?;
+    session.setup_workspace(configuration, VcsIntegration::Disabled)?;
+
+    let mut content = String::new();
+    session
+        .fs()
+        .open_with_options(path, OpenOptions::default().read(true))?
+        .read_to_string(&mut content)?;
+
+    let statements = pgls_statement_splitter::split(&content).ranges.len();
+    if statement > statements {
+        return Err(CliDiagnostic::statement_not_found(statement, statements));
+    }
+
+    let pgls_path = PgLSPath::new(path);
+    let result = {
+        let _guard = FileGuard::open(
+            session.workspace(),
+            OpenFileParams {
+                path: pgls_path.clone(),
+                content,
+                version: 0,
+            },
+        )?;
+
+        let execute = |options| {
+            session
+                .workspace()
+                .execute_statement(ExecuteStatementParams {
+                    statement: StatementSelector::Index(statement - 1),
+                    path: pgls_path.clone(),
+                    options,
+                })
+        };
+
+        let result = execute(options)?;
+        match result.confirmation_required {
+            Some(confirmation) if confirm => {
+                options.confirmation = Some(confirmation.token);
+                execute(options)?
+            }
+            _ => result,
+        }
+    };
+
+    if let Some(confirmation) = result.confirmation_required {
+        return Err(CliDiagnostic::unconfirmed_execution(confirmation.reason));
+    }
+
+    let console = session.console();
+
+    let Some(result_set) = result.result_set else {
+        console.log(markup! {
+            {result.message}
+        });
+        return Ok(());
+    };
+
+    let output = match format {
+        ResultFormat::Table => format_table(&result_set),
+        ResultFormat::Csv => format_csv(&result_set),
+        ResultFormat::Json => serde_json::to_string_pretty(&result_set)
+            .map_err(|error| CliDiagnostic::io_error(std::io::Error::other(error)))?,
+    };
+
+    console.log(markup! {
+        {output}
+    });
+
+    if result_set.truncated {
+        console.error(markup! {
+            <Warn>{result.message}</Warn>
+        });
+    }
+
+    Ok(())
+}
+
+/// The text of a value as it is printed in tables and CSV. `NULL` is printed as nothing.
+fn value_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn format_table(result_set: &ResultSet) -> String {
+    let rows: Vec<Vec<String>> = result_set
+        .rows
+        .iter()
+        .map(|row| row.iter().map(value_text).collect())
+        .collect();
+
+    let widths: Vec<usize> = result_set
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            rows.iter()
+                .map(|row| row[index].chars().count())
+                .chain(std::iter::once(column.name.chars().count()))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let format_line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!(" {cell:width$} "))
+            .collect::<Vec<_>>()
+            .join("|")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![
+        format_line(
+            result_set
+                .columns
+                .iter()
+                .map(|column| column.name.as_str())
+                .collect(),
+        ),
+        widths
+            .iter()
+            .map(|width| "-".repeat(width + 2))
+            .collect::<Vec<_>>()
+            .join("+"),
+    ];
+
+    lines.extend(
+        rows.iter()
+            .map(|row| format_line(row.iter().map(String::as_str).collect())),
+    );
+
+    lines.push(match rows.len() {
+        1 => "(1 row)".to_string(),
+        n => format!("({n} rows)"),
+    });
+
+    lines.join("\n")
+}
+
+fn format_csv(result_set: &ResultSet) -> String {
+    let header = result_set
+        .columns
+        .iter()
+        .map(|column| csv_field(&column.name))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    std::iter::once(header)
+        .chain(result_set.rows.iter().map(|row| {
+            row.iter()
+                .map(|value| csv_field(&value_text(value)))
+                .collect::<Vec<_>>()
+                .join(",")
+        }))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_csv, format_table};
+    use pgls_workspace::features::code_actions::{ResultSet, ResultSetColumn};
+    use serde_json::json;
+
+    fn result_set() -> ResultSet {
+        ResultSet {
+            columns: vec![
+                ResultSetColumn {
+                    name: "id".into(),
+                    type_name: "int4".into(),
+                },
+                ResultSetColumn {
+                    name: "email".into(),
+                    type_name: "text".into(),
+                },
+            ],
+            rows: vec![
+                vec![json!(1), json!("a@example.com")],
+                vec![json!(2), json!(null)],
+                vec![json!(10), json!("quote \"me\", please")],
+            ],
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn formats_tables() {
+        assert_eq!(
+            format_table(&result_set()),
+            [
+                " id | email",
+                "----+--------------------",
+                " 1  | a@example.com",
+                " 2  |",
+                " 10 | quote \"me\", please",
+                "(3 rows)",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn formats_csv() {
+        assert_eq!(
+            format_csv(&result_set()),
+            [
+                "id,email",
+                "1,a@example.com",
+                "2,",
+                "10,\"quote \"\"me\"\", please\"",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -21,6 +21,12 @@ fn command_name() -> String {
 pub enum CliDiagnostic {
     /// Returned when it is called with a subcommand it doesn't know
     UnknownCommand(UnknownCommand),
+    /// Returned by the `explain-rule` command when it is called with a rule it doesn't know
+    UnknownRule(UnknownRule),
+    /// Returned by the `run` command when the statement destroys data and `--confirm` is missing
+    UnconfirmedExecution(UnconfirmedExecution),
+    /// Returned by the `run` command when the file has fewer statements than `--statement`
+    StatementNotFound(StatementNotFound),
     /// Return by the help command when it is called with a subcommand it doesn't know
     UnknownCommandHelp(UnknownCommandHelp),
     /// Returned when the value of a command line argument could not be parsed
@@ -70,6 +76,46 @@ pub struct UnknownCommand {
     command_name: String,
 }
 
+#[derive(Debug, Diagnostic)]
+#[diagnostic(
+    category = "flags/invalid",
+    severity = Error,
+    message(
+        description = "Unknown rule {rule_name}",
+        message("Unknown rule "<Emphasis>{self.rule_name}</Emphasis>". Run "<Emphasis>"explain-rule"</Emphasis>" without arguments to list all rules.")
+    ),
+)]
+pub struct UnknownRule {
+    rule_name: String,
+}
+
+#[derive(Debug, Diagnostic)]
+#[diagnostic(
+    category = "flags/invalid",
+    severity = Error,
+    message(
+        description = "{reason} Pass --confirm to execute it anyway.",
+        message({self.reason}" Pass "<Emphasis>"--confirm"</Emphasis>" to execute it anyway.")
+    ),
+)]
+pub struct UnconfirmedExecution {
+    reason: String,
+}
+
+#[derive(Debug, Diagnostic)]
+#[diagnostic(
+    category = "flags/invalid",
+    severity = Error,
+    message(
+        description = "Statement {statement} was not found, the file contains {found}.",
+        message("Statement "<Emphasis>{self.statement}</Emphasis>" was not found, the file contains "{self.found}".")
+    ),
+)]
+pub struct StatementNotFound {
+    statement: usize,
+    found: String,
+}
+
 #[derive(Debug, Diagnostic)]
 #[diagnostic(
 category = "flags/invalid",
@@ -292,6 +338,31 @@ impl CliDiagnostic {
         })
     }
 
+    /// Returned when it is called with a rule it doesn't know
+    pub fn unknown_rule(rule_name: impl Into<String>) -> Self {
+        Self::UnknownRule(UnknownRule {
+            rule_name: rule_name.into(),
+        })
+    }
+
+    /// Returned when a statement that destroys data is executed without confirmation
+    pub fn unconfirmed_execution(reason: impl Into<String>) -> Self {
+        Self::UnconfirmedExecution(UnconfirmedExecution {
+            reason: reason.into(),
+        })
+    }
+
+    /// Returned when the file has fewer than `statement` statements
+    pub fn statement_not_found(statement: usize, statements: usize) -> Self {
+        Self::StatementNotFound(StatementNotFound {
+            statement,
+            found: match statements {
+                1 => "1 statement".to_string(),
+                n => format!("{n} statements"),
+            },
+        })
+    }
+
     /// Returned when a subcommand is called without any arguments
     pub fn empty_arguments() -> Self {
         Self::EmptyArguments(EmptyArguments)
@@ -50,6 +50,8 @@ pub(crate) enum Message {
         content: String,
         diagnostics: Vec<Error>,
         skipped_diagnostics: u32,
+        /// Diagnostics silenced by suppression comments, which only some reporters include
+        suppressed_diagnostics: Vec<Error>,
     },
 }
 
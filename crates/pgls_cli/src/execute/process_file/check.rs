@@ -43,7 +43,8 @@ pub(crate) fn check_with_guard<'ctx>(
                 )?;
 
             let no_diagnostics = pull_diagnostics_result.diagnostics.is_empty()
-                && pull_diagnostics_result.skipped_diagnostics == 0;
+                && pull_diagnostics_result.skipped_diagnostics == 0
+                && pull_diagnostics_result.suppressed_diagnostics.is_empty();
 
             if !no_diagnostics {
                 ctx.push_message(Message::Diagnostics {
@@ -55,6 +56,11 @@ pub(crate) fn check_with_guard<'ctx>(
                         .map(Error::from)
                         .collect(),
                     skipped_diagnostics: pull_diagnostics_result.skipped_diagnostics,
+                    suppressed_diagnostics: pull_diagnostics_result
+                        .suppressed_diagnostics
+                        .into_iter()
+                        .map(Error::from)
+                        .collect(),
                 });
             }
 
@@ -57,7 +57,7 @@ pub(crate) fn traverse(
 
     let printer = DiagnosticsPrinter::new(config).with_max_diagnostics(max_diagnostics);
 
-    let (duration, evaluated_paths, diagnostics) = thread::scope(|s| {
+    let (duration, evaluated_paths, (diagnostics, suppressed_diagnostics)) = thread::scope(|s| {
         let handler = thread::Builder::new()
             .name(String::from("pgls::console"))
             .spawn_scoped(s, || printer.run(receiver, recv_files))
@@ -111,7 +111,8 @@ pub(crate) fn traverse(
         duration,
         diagnostics_not_printed,
         Some(traversal),
-    ))
+    )
+    .with_suppressed_diagnostics(suppressed_diagnostics))
 }
 
 /// This function will setup the global Rayon thread pool the first time it's called
@@ -212,9 +213,15 @@ impl<'ctx> DiagnosticsPrinter<'ctx> {
         should_print
     }
 
-    fn run(&self, receiver: Receiver<Message>, interner: Receiver<PathBuf>) -> Vec<Error> {
+    /// Collects the diagnostics to report, along with the suppressed ones
+    fn run(
+        &self,
+        receiver: Receiver<Message>,
+        interner: Receiver<PathBuf>,
+    ) -> (Vec<Error>, Vec<Error>) {
         let mut paths: FxHashSet<String> = FxHashSet::default();
         let mut diagnostics = vec![];
+        let mut suppressed_diagnostics = vec![];
 
         while let Ok(msg) = receiver.recv() {
             match msg {
@@ -256,6 +263,7 @@ impl<'ctx> DiagnosticsPrinter<'ctx> {
                     content,
                     diagnostics: diag_list,
                     skipped_diagnostics,
+                    suppressed_diagnostics: suppressed_list,
                 } => {
                     self.not_printed_diagnostics
                         .fetch_add(skipped_diagnostics, Ordering::Relaxed);
@@ -266,11 +274,17 @@ impl<'ctx> DiagnosticsPrinter<'ctx> {
                             diagnostics.push(diag);
                         }
                     }
+
+                    suppressed_diagnostics.extend(
+                        suppressed_list
+                            .into_iter()
+                            .map(|diag| diag.with_file_path(&name).with_file_source_code(&content)),
+                    );
                 }
             }
         }
 
-        diagnostics
+        (diagnostics, suppressed_diagnostics)
     }
 }
 
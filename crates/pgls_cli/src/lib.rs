@@ -10,8 +10,10 @@ use commands::check::{self, CheckArgs};
 use pgls_configuration::PartialConfiguration;
 use pgls_console::{ColorMode, Console, ConsoleExt, markup};
 use pgls_fs::{ConfigName, FileSystem, OsFileSystem};
+use pgls_workspace::features::code_actions::ExecuteStatementOptions;
 use pgls_workspace::{App, DynRef, Workspace, WorkspaceRef};
 
+mod baseline;
 mod changed;
 mod cli_options;
 mod commands;
@@ -31,7 +33,7 @@ use crate::reporter::Report;
 pub use diagnostics::CliDiagnostic;
 pub use execute::{ExecutionConfig, ExecutionMode, VcsTargeting};
 pub use panic::setup_panic_handler;
-pub use reporter::{ReportConfig, Reporter, TraversalData};
+pub use reporter::{JSON_REPORT_VERSION, JsonReport, ReportConfig, Reporter, TraversalData};
 pub use service::{SocketTransport, open_transport};
 
 pub(crate) use pgls_env::VERSION;
@@ -69,6 +71,50 @@ impl<'app> CliSession<'app> {
                 cli_options,
                 configuration,
             } => commands::dblint::dblint(self, &cli_options, configuration),
+            PgLSCommand::Describe {
+                cli_options,
+                configuration,
+                statement,
+                path,
+            } => commands::describe::describe(self, &cli_options, configuration, &path, statement),
+            PgLSCommand::Explain {
+                cli_options,
+                configuration,
+                statement,
+                analyze,
+                path,
+            } => commands::explain::explain(
+                self,
+                &cli_options,
+                configuration,
+                &path,
+                statement,
+                analyze,
+            ),
+            PgLSCommand::Run {
+                cli_options,
+                configuration,
+                statement,
+                format,
+                dry_run,
+                confirm,
+                path,
+            } => commands::run::run(
+                self,
+                &cli_options,
+                configuration,
+                &path,
+                statement,
+                format,
+                ExecuteStatementOptions {
+                    dry_run,
+                    confirmation: None,
+                },
+                confirm,
+            ),
+            PgLSCommand::ExplainRule { format, rule, .. } => {
+                commands::explain_rule::explain_rule(self, rule, format)
+            }
             PgLSCommand::Check {
                 cli_options,
                 configuration,
@@ -77,6 +123,8 @@ impl<'app> CliSession<'app> {
                 staged,
                 changed,
                 since,
+                write_baseline,
+                baseline,
             } => check::check(
                 self,
                 &cli_options,
@@ -87,6 +135,8 @@ impl<'app> CliSession<'app> {
                     staged,
                     changed,
                     since,
+                    write_baseline,
+                    baseline,
                 },
             ),
             PgLSCommand::Clean => commands::clean::clean(self),
@@ -0,0 +1,106 @@
+use crate::diagnostics::CliDiagnostic;
+use crate::reporter::fingerprint::attempt_to_relativize;
+use crate::reporter::{Report, ReportConfig, ReportWriter};
+use pgls_console::{Console, ConsoleExt, markup};
+use pgls_diagnostics::display::SourceFile;
+use pgls_diagnostics::{Error, PrintDescription, Resource, Severity};
+use std::fmt::Write;
+
+pub(crate) struct CheckstyleReportWriter;
+
+impl ReportWriter for CheckstyleReportWriter {
+    fn write(
+        &mut self,
+        console: &mut dyn Console,
+        _command_name: &str,
+        report: &Report,
+        config: &ReportConfig,
+    ) -> Result<(), CliDiagnostic> {
+        let repository_root = report
+            .traversal
+            .as_ref()
+            .and_then(|traversal| traversal.workspace_root.clone());
+
+        // Checkstyle groups errors by file, files are listed in order of their first diagnostic.
+        let mut files: Vec<(String, Vec<&Error>)> = Vec::new();
+        for diagnostic in report
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| config.should_emit(diagnostic))
+        {
+            let Some(Resource::File(file)) = diagnostic.location().resource else {
+                continue;
+            };
+
+            let path = attempt_to_relativize(file, repository_root.as_deref())
+                .and_then(|path| path.to_str().map(|p| p.replace('\\', "/")))
+                .unwrap_or_else(|| file.to_string());
+
+            match files.iter_mut().find(|(name, _)| *name == path) {
+                Some((_, diagnostics)) => diagnostics.push(diagnostic),
+                None => files.push((path, vec![diagnostic])),
+            }
+        }
+
+        let mut output = String::new();
+        output.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        output.push_str("<checkstyle version=\"4.3\">\n");
+        for (path, diagnostics) in files {
+            let _ = writeln!(output, "  <file name=\"{}\">", escape(&path));
+            for diagnostic in diagnostics {
+                let location = diagnostic.location();
+                let (line, column) = location
+                    .span
+                    .zip(location.source_code)
+                    .and_then(|(span, source_code)| {
+                        SourceFile::new(source_code).location(span.start()).ok()
+                    })
+                    .map(|start| (start.line_number.get(), start.column_number.get()))
+                    .unwrap_or((1, 1));
+
+                let _ = writeln!(
+                    output,
+                    "    <error line=\"{line}\" column=\"{column}\" severity=\"{}\" message=\"{}\" source=\"{}\" />",
+                    severity(diagnostic.severity()),
+                    escape(&PrintDescription(diagnostic).to_string()),
+                    escape(
+                        diagnostic
+                            .category()
+                            .map(|category| category.name())
+                            .unwrap_or_default()
+                    ),
+                );
+            }
+            output.push_str("  </file>\n");
+        }
+        output.push_str("</checkstyle>");
+
+        console.log(markup!({ output }));
+        Ok(())
+    }
+}
+
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Hint | Severity::Information => "info",
+        Severity::Warning => "warning",
+        Severity::Error | Severity::Fatal => "error",
+    }
+}
+
+/// Escapes the characters that are not allowed in XML attribute values.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
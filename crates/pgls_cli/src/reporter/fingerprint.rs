@@ -0,0 +1,76 @@
+use path_absolutize::Absolutize;
+use pgls_diagnostics::Error;
+use std::{
+    collections::HashSet,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+/// Hands out fingerprints that are unique within a single report, so that identical
+/// diagnostics in the same file can still be told apart.
+#[derive(Default)]
+pub(crate) struct FingerprintHasher(HashSet<u64>);
+
+impl FingerprintHasher {
+    pub(crate) fn rehash_until_unique(&mut self, fingerprint: u64) -> u64 {
+        let mut current = fingerprint;
+        while self.0.contains(&current) {
+            let mut hasher = DefaultHasher::new();
+            current.hash(&mut hasher);
+            current = hasher.finish();
+        }
+
+        self.0.insert(current);
+        current
+    }
+}
+
+#[derive(Hash)]
+struct Fingerprint<'a> {
+    code: &'a str,
+    check_name: &'a str,
+    path: &'a str,
+}
+
+/// Computes a fingerprint from the category of the diagnostic, the file it belongs to and the
+/// code it points at. It stays the same when unrelated lines of the file change.
+pub(crate) fn compute_initial_fingerprint(diagnostic: &Error, path: &str) -> u64 {
+    let location = diagnostic.location();
+    let code = match location.span {
+        Some(span) => match location.source_code {
+            Some(source_code) => &source_code.text[span],
+            None => "",
+        },
+        None => "",
+    };
+
+    let check_name = diagnostic
+        .category()
+        .map(|category| category.name())
+        .unwrap_or_default();
+
+    let mut hasher = DefaultHasher::new();
+    Fingerprint {
+        check_name,
+        path,
+        code,
+    }
+    .hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Makes `subject` relative to the repository root, if it is within it.
+pub(crate) fn attempt_to_relativize(
+    subject: &str,
+    repository_root: Option<&Path>,
+) -> Option<PathBuf> {
+    let Ok(resolved) = Path::new(subject).absolutize() else {
+        return None;
+    };
+
+    let Ok(relativized) = resolved.strip_prefix(repository_root?) else {
+        return None;
+    };
+
+    Some(relativized.to_path_buf())
+}
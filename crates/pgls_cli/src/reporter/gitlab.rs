@@ -1,17 +1,15 @@
 use crate::diagnostics::CliDiagnostic;
+use crate::reporter::fingerprint::{
+    FingerprintHasher, attempt_to_relativize, compute_initial_fingerprint,
+};
 use crate::reporter::{Report, ReportConfig, ReportWriter};
-use path_absolutize::Absolutize;
 use pgls_console::fmt::{Display, Formatter};
 use pgls_console::{Console, ConsoleExt, markup};
 use pgls_diagnostics::display::SourceFile;
 use pgls_diagnostics::{Error, PrintDescription, Resource, Severity};
 use serde::Serialize;
+use std::path::Path;
 use std::sync::RwLock;
-use std::{
-    collections::HashSet,
-    hash::{DefaultHasher, Hash, Hasher},
-    path::{Path, PathBuf},
-};
 
 pub(crate) struct GitLabReportWriter;
 
@@ -40,66 +38,13 @@ impl ReportWriter for GitLabReportWriter {
     }
 }
 
-#[derive(Default)]
-struct GitLabHasher(HashSet<u64>);
-
-impl GitLabHasher {
-    fn rehash_until_unique(&mut self, fingerprint: u64) -> u64 {
-        let mut current = fingerprint;
-        while self.0.contains(&current) {
-            let mut hasher = DefaultHasher::new();
-            current.hash(&mut hasher);
-            current = hasher.finish();
-        }
-
-        self.0.insert(current);
-        current
-    }
-}
-
 struct GitLabDiagnostics<'a> {
     report: &'a Report,
     config: &'a ReportConfig,
-    hasher: &'a RwLock<GitLabHasher>,
+    hasher: &'a RwLock<FingerprintHasher>,
     repository_root: Option<&'a Path>,
 }
 
-impl<'a> GitLabDiagnostics<'a> {
-    fn attempt_to_relativize(&self, subject: &str) -> Option<PathBuf> {
-        let Ok(resolved) = Path::new(subject).absolutize() else {
-            return None;
-        };
-
-        let Ok(relativized) = resolved.strip_prefix(self.repository_root?) else {
-            return None;
-        };
-
-        Some(relativized.to_path_buf())
-    }
-
-    fn compute_initial_fingerprint(&self, diagnostic: &Error, path: &str) -> u64 {
-        let location = diagnostic.location();
-        let code = match location.span {
-            Some(span) => match location.source_code {
-                Some(source_code) => &source_code.text[span],
-                None => "",
-            },
-            None => "",
-        };
-
-        let check_name = diagnostic
-            .category()
-            .map(|category| category.name())
-            .unwrap_or_default();
-
-        calculate_hash(&Fingerprint {
-            check_name,
-            path,
-            code,
-        })
-    }
-}
-
 impl Display for GitLabDiagnostics<'_> {
     fn fmt(&self, fmt: &mut Formatter) -> std::io::Result<()> {
         let mut hasher = self.hasher.write().unwrap();
@@ -121,13 +66,13 @@ impl Display for GitLabDiagnostics<'_> {
                     _ => None,
                 }
                 .unwrap_or_default();
-                let path_buf = self.attempt_to_relativize(absolute_path);
+                let path_buf = attempt_to_relativize(absolute_path, self.repository_root);
                 let path = match path_buf {
                     Some(buf) => buf.to_str().unwrap_or(absolute_path).to_owned(),
                     None => absolute_path.to_owned(),
                 };
 
-                let initial_fingerprint = self.compute_initial_fingerprint(pgls_diagnostic, &path);
+                let initial_fingerprint = compute_initial_fingerprint(pgls_diagnostic, &path);
                 let fingerprint = hasher.rehash_until_unique(initial_fingerprint);
 
                 GitLabDiagnostic::try_from_diagnostic(
@@ -207,16 +152,3 @@ pub struct Location {
 pub struct GitLabLines {
     begin: usize,
 }
-
-#[derive(Hash, Serialize)]
-pub struct Fingerprint<'a> {
-    code: &'a str,
-    check_name: &'a str,
-    path: &'a str,
-}
-
-fn calculate_hash<T: Hash>(t: &T) -> u64 {
-    let mut hasher = DefaultHasher::new();
-    t.hash(&mut hasher);
-    hasher.finish()
-}
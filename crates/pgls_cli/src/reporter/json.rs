@@ -0,0 +1,289 @@
+use crate::diagnostics::CliDiagnostic;
+use crate::reporter::fingerprint::attempt_to_relativize;
+use crate::reporter::{Report, ReportConfig, ReportWriter};
+use pgls_console::fmt::Display;
+use pgls_console::{Console, ConsoleExt, StdDisplay, markup};
+use pgls_diagnostics::display::SourceFile;
+use pgls_diagnostics::{Error, LogCategory, PrintDescription, Resource, Severity, Visit};
+use pgls_text_size::TextRange;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The version of the JSON report format. Bump it whenever a change to [JsonReport] is not
+/// backwards compatible, and regenerate the schema.
+pub const JSON_REPORT_VERSION: u32 = 1;
+
+pub(crate) struct JsonReportWriter {
+    pub(crate) pretty: bool,
+}
+
+impl ReportWriter for JsonReportWriter {
+    fn write(
+        &mut self,
+        console: &mut dyn Console,
+        command_name: &str,
+        report: &Report,
+        config: &ReportConfig,
+    ) -> Result<(), CliDiagnostic> {
+        let repository_root = report
+            .traversal
+            .as_ref()
+            .and_then(|traversal| traversal.workspace_root.clone());
+
+        let mut statements = StatementRanges::default();
+        let diagnostics = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic, false))
+            .chain(
+                report
+                    .suppressed_diagnostics
+                    .iter()
+                    .map(|diagnostic| (diagnostic, true)),
+            )
+            .filter(|(diagnostic, _)| config.should_emit(diagnostic))
+            .map(|(diagnostic, suppressed)| {
+                JsonDiagnostic::from_diagnostic(
+                    diagnostic,
+                    suppressed,
+                    repository_root.as_deref(),
+                    &mut statements,
+                )
+            })
+            .collect();
+
+        let json_report = JsonReport {
+            version: JSON_REPORT_VERSION,
+            command: command_name.to_string(),
+            summary: JsonSummary {
+                errors: report.errors,
+                warnings: report.warnings,
+                suppressed: report.suppressed_diagnostics.len() as u32,
+                skipped: report.skipped_diagnostics,
+            },
+            diagnostics,
+        };
+
+        let serialized = if self.pretty {
+            serde_json::to_string_pretty(&json_report)
+        } else {
+            serde_json::to_string(&json_report)
+        }
+        .map_err(|error| CliDiagnostic::io_error(std::io::Error::other(error)))?;
+
+        console.log(markup!({ serialized }));
+        Ok(())
+    }
+}
+
+/// The report printed by `--reporter=json` and `--reporter=json-pretty`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct JsonReport {
+    /// The version of the report format.
+    pub version: u32,
+    /// The command that produced the report, e.g. `check`.
+    pub command: String,
+    pub summary: JsonSummary,
+    pub diagnostics: Vec<JsonDiagnostic>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct JsonSummary {
+    /// The number of diagnostics with error or fatal severity.
+    pub errors: u32,
+    /// The number of diagnostics with warning severity.
+    pub warnings: u32,
+    /// The number of diagnostics silenced by suppression comments.
+    pub suppressed: u32,
+    /// The number of diagnostics that were not included because of `--max-diagnostics`.
+    pub skipped: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct JsonDiagnostic {
+    /// The path of the file, relative to the working directory when possible.
+    pub file: Option<String>,
+    pub range: Option<JsonRange>,
+    /// The category of the diagnostic, e.g. `lint/safety/banDropTable`.
+    pub category: Option<String>,
+    pub severity: Severity,
+    pub message: String,
+    pub advices: Vec<JsonAdvice>,
+    /// The text of the statement the diagnostic was reported on.
+    pub source: Option<String>,
+    /// Whether the diagnostic was silenced by a suppression comment.
+    pub suppressed: bool,
+}
+
+impl JsonDiagnostic {
+    fn from_diagnostic(
+        diagnostic: &Error,
+        suppressed: bool,
+        repository_root: Option<&Path>,
+        statements: &mut StatementRanges,
+    ) -> Self {
+        let location = diagnostic.location();
+
+        let file = match location.resource {
+            Some(Resource::File(file)) => Some(
+                attempt_to_relativize(file, repository_root)
+                    .and_then(|path| path.to_str().map(|p| p.replace('\\', "/")))
+                    .unwrap_or_else(|| file.to_string()),
+            ),
+            _ => None,
+        };
+
+        let range = location.span.and_then(|span| {
+            let source_file = SourceFile::new(location.source_code?);
+            Some(JsonRange {
+                start: JsonPosition::new(&source_file, span.start().into())?,
+                end: JsonPosition::new(&source_file, span.end().into())?,
+            })
+        });
+
+        let source = location.span.and_then(|span| {
+            let text = location.source_code?.text;
+            statements
+                .get(file.as_deref(), text)
+                .iter()
+                .find(|range| range.contains_range(span))
+                .map(|range| text[*range].to_string())
+        });
+
+        let mut advices = JsonAdvices::default();
+        // SAFETY: The advices visitor never returns an error
+        diagnostic.advices(&mut advices).unwrap();
+
+        Self {
+            file,
+            range,
+            category: diagnostic
+                .category()
+                .map(|category| category.name().to_string()),
+            severity: diagnostic.severity(),
+            message: PrintDescription(diagnostic).to_string(),
+            advices: advices.0,
+            source,
+            suppressed,
+        }
+    }
+}
+
+/// The statement ranges of each file, so that files with many diagnostics are only split once.
+#[derive(Default)]
+struct StatementRanges(HashMap<String, Vec<TextRange>>);
+
+impl StatementRanges {
+    fn get(&mut self, file: Option<&str>, text: &str) -> Cow<'_, [TextRange]> {
+        let split = || pgls_statement_splitter::split(text).ranges;
+        match file {
+            Some(file) => Cow::Borrowed(self.0.entry(file.to_string()).or_insert_with(split)),
+            None => Cow::Owned(split()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct JsonRange {
+    pub start: JsonPosition,
+    pub end: JsonPosition,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct JsonPosition {
+    /// The one-based line number.
+    pub line: usize,
+    /// The one-based column number.
+    pub column: usize,
+    /// The zero-based byte offset into the file.
+    pub offset: u32,
+}
+
+impl JsonPosition {
+    fn new(source_file: &SourceFile, offset: u32) -> Option<Self> {
+        let location = source_file.location(offset.into()).ok()?;
+        Some(Self {
+            line: location.line_number.get(),
+            column: location.column_number.get(),
+            offset,
+        })
+    }
+}
+
+/// A textual representation of an advice attached to a diagnostic.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum JsonAdvice {
+    Log {
+        category: LogCategory,
+        text: String,
+    },
+    List {
+        items: Vec<String>,
+    },
+    Command {
+        command: String,
+    },
+    Group {
+        title: String,
+        advices: Vec<JsonAdvice>,
+    },
+}
+
+/// Collects the advices of a diagnostic as plain text. Code frames, diffs and backtraces are
+/// left out, the range and source of the diagnostic already cover them.
+#[derive(Default)]
+struct JsonAdvices(Vec<JsonAdvice>);
+
+impl Visit for JsonAdvices {
+    fn record_log(&mut self, category: LogCategory, text: &dyn Display) -> std::io::Result<()> {
+        self.0.push(JsonAdvice::Log {
+            category,
+            text: StdDisplay(text).to_string(),
+        });
+        Ok(())
+    }
+
+    fn record_list(&mut self, list: &[&dyn Display]) -> std::io::Result<()> {
+        self.0.push(JsonAdvice::List {
+            items: list
+                .iter()
+                .map(|item| StdDisplay(item).to_string())
+                .collect(),
+        });
+        Ok(())
+    }
+
+    fn record_command(&mut self, command: &str) -> std::io::Result<()> {
+        self.0.push(JsonAdvice::Command {
+            command: command.to_string(),
+        });
+        Ok(())
+    }
+
+    fn record_group(
+        &mut self,
+        title: &dyn Display,
+        advice: &dyn pgls_diagnostics::Advices,
+    ) -> std::io::Result<()> {
+        let mut advices = JsonAdvices::default();
+        advice.record(&mut advices)?;
+
+        self.0.push(JsonAdvice::Group {
+            title: StdDisplay(title).to_string(),
+            advices: advices.0,
+        });
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
+pub(crate) mod checkstyle;
+pub(crate) mod fingerprint;
 pub(crate) mod github;
 pub(crate) mod gitlab;
+pub(crate) mod json;
 pub(crate) mod junit;
+pub(crate) mod sarif;
 pub(crate) mod terminal;
 
 use crate::cli_options::{CliOptions, CliReporter};
@@ -12,6 +16,8 @@ use std::collections::BTreeSet;
 use std::path::PathBuf;
 use std::time::Duration;
 
+pub use json::{JSON_REPORT_VERSION, JsonReport};
+
 #[derive(Debug, Clone)]
 pub struct ReportConfig {
     pub mode: ReportMode,
@@ -31,6 +37,20 @@ impl ReportConfig {
             no_errors_on_unmatched: cli_options.no_errors_on_unmatched,
         }
     }
+
+    /// Whether a reporter should include the diagnostic, based on its severity and whether it
+    /// is only shown in verbose mode.
+    pub(crate) fn should_emit(&self, diagnostic: &Error) -> bool {
+        if diagnostic.severity() < self.diagnostic_level {
+            return false;
+        }
+
+        if diagnostic.tags().is_verbose() {
+            self.verbose
+        } else {
+            true
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,6 +59,10 @@ pub enum ReportMode {
     GitHub,
     GitLab,
     Junit,
+    Sarif,
+    Json,
+    JsonPretty,
+    Checkstyle,
 }
 
 impl From<CliReporter> for ReportMode {
@@ -48,6 +72,10 @@ impl From<CliReporter> for ReportMode {
             CliReporter::GitHub => Self::GitHub,
             CliReporter::Junit => Self::Junit,
             CliReporter::GitLab => Self::GitLab,
+            CliReporter::Sarif => Self::Sarif,
+            CliReporter::Json => Self::Json,
+            CliReporter::JsonPretty => Self::JsonPretty,
+            CliReporter::Checkstyle => Self::Checkstyle,
         }
     }
 }
@@ -72,6 +100,8 @@ pub struct Report {
     pub warnings: u32,
     pub skipped_diagnostics: u32,
     pub traversal: Option<TraversalData>,
+    /// Diagnostics silenced by suppression comments. They don't count towards errors or warnings.
+    pub suppressed_diagnostics: Vec<Error>,
 }
 
 impl Report {
@@ -89,8 +119,14 @@ impl Report {
             warnings,
             skipped_diagnostics,
             traversal,
+            suppressed_diagnostics: Vec::new(),
         }
     }
+
+    pub fn with_suppressed_diagnostics(mut self, suppressed_diagnostics: Vec<Error>) -> Self {
+        self.suppressed_diagnostics = suppressed_diagnostics;
+        self
+    }
 }
 
 pub trait ReportWriter {
@@ -129,6 +165,10 @@ impl Reporter {
             ReportMode::GitHub => Box::new(github::GithubReportWriter),
             ReportMode::GitLab => Box::new(gitlab::GitLabReportWriter),
             ReportMode::Junit => Box::new(junit::JunitReportWriter),
+            ReportMode::Sarif => Box::new(sarif::SarifReportWriter),
+            ReportMode::Json => Box::new(json::JsonReportWriter { pretty: false }),
+            ReportMode::JsonPretty => Box::new(json::JsonReportWriter { pretty: true }),
+            ReportMode::Checkstyle => Box::new(checkstyle::CheckstyleReportWriter),
         };
 
         writer.write(console, command_name, payload, &self.config)
@@ -0,0 +1,372 @@
+use crate::VERSION;
+use crate::diagnostics::CliDiagnostic;
+use crate::reporter::fingerprint::{
+    FingerprintHasher, attempt_to_relativize, compute_initial_fingerprint,
+};
+use crate::reporter::{Report, ReportConfig, ReportWriter};
+use pgls_analyse::{GroupCategory, RegistryVisitor, Rule, RuleCategory, RuleGroup, RuleMetadata};
+use pgls_console::{Console, ConsoleExt, markup};
+use pgls_diagnostics::display::SourceFile;
+use pgls_diagnostics::{Error, PrintDescription, Resource, Severity};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+pub(crate) struct SarifReportWriter;
+
+impl ReportWriter for SarifReportWriter {
+    fn write(
+        &mut self,
+        console: &mut dyn Console,
+        _command_name: &str,
+        report: &Report,
+        config: &ReportConfig,
+    ) -> Result<(), CliDiagnostic> {
+        let repository_root = report
+            .traversal
+            .as_ref()
+            .and_then(|traversal| traversal.workspace_root.clone());
+
+        let mut visitor = LintRulesVisitor::default();
+        pgls_analyser::visit_registry(&mut visitor);
+
+        let mut builder = SarifBuilder {
+            config,
+            repository_root: repository_root.as_deref(),
+            rule_metadata: visitor.rules,
+            hasher: FingerprintHasher::default(),
+            rules: Vec::new(),
+            results: Vec::new(),
+        };
+
+        for diagnostic in &report.diagnostics {
+            builder.push(diagnostic, false);
+        }
+        for diagnostic in &report.suppressed_diagnostics {
+            builder.push(diagnostic, true);
+        }
+
+        let log = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "postgres-language-server",
+                        information_uri: "https://pg-language-server.com",
+                        version: VERSION,
+                        rules: builder.rules,
+                    },
+                },
+                column_kind: "unicodeCodePoints",
+                results: builder.results,
+            }],
+        };
+
+        let serialized = serde_json::to_string_pretty(&log)
+            .map_err(|error| CliDiagnostic::io_error(std::io::Error::other(error)))?;
+        console.log(markup!({ serialized }));
+        Ok(())
+    }
+}
+
+/// Collects the metadata of all lint rules, keyed by their diagnostic category.
+#[derive(Default)]
+struct LintRulesVisitor {
+    rules: BTreeMap<String, RuleMetadata>,
+}
+
+impl RegistryVisitor for LintRulesVisitor {
+    fn record_category<C: GroupCategory>(&mut self) {
+        if matches!(C::CATEGORY, RuleCategory::Lint) {
+            C::record_groups(self);
+        }
+    }
+
+    fn record_rule<R>(&mut self)
+    where
+        R: Rule + 'static,
+    {
+        self.rules.insert(
+            format!(
+                "lint/{}/{}",
+                <R::Group as RuleGroup>::NAME,
+                R::METADATA.name
+            ),
+            R::METADATA,
+        );
+    }
+}
+
+struct SarifBuilder<'a> {
+    config: &'a ReportConfig,
+    repository_root: Option<&'a Path>,
+    rule_metadata: BTreeMap<String, RuleMetadata>,
+    hasher: FingerprintHasher,
+    rules: Vec<SarifRule>,
+    results: Vec<SarifResult>,
+}
+
+impl SarifBuilder<'_> {
+    fn push(&mut self, diagnostic: &Error, suppressed: bool) {
+        if diagnostic.severity() < self.config.diagnostic_level {
+            return;
+        }
+
+        if self.config.verbose && !diagnostic.tags().is_verbose() {
+            return;
+        }
+
+        let Some(category) = diagnostic.category() else {
+            return;
+        };
+
+        let location = diagnostic.location();
+        let path = match location.resource {
+            Some(Resource::File(file)) => Some(
+                attempt_to_relativize(file, self.repository_root)
+                    .and_then(|path| path.to_str().map(|p| p.replace('\\', "/")))
+                    .unwrap_or_else(|| file.to_string()),
+            ),
+            _ => None,
+        };
+
+        let fingerprint = self.hasher.rehash_until_unique(compute_initial_fingerprint(
+            diagnostic,
+            path.as_deref().unwrap_or_default(),
+        ));
+
+        let region = location.span.and_then(|span| {
+            let source_code = location.source_code?;
+            let text = source_code.text;
+            let source_file = SourceFile::new(source_code);
+            let start = source_file.location(span.start()).ok()?;
+            let end = source_file.location(span.end()).ok()?;
+            // the columns are counted in code points, so are the offsets
+            let char_offset = text.get(..usize::from(span.start()))?.chars().count();
+            let char_length = text
+                .get(std::ops::Range::<usize>::from(span))?
+                .chars()
+                .count();
+            Some(SarifRegion {
+                start_line: start.line_number.get(),
+                start_column: start.column_number.get(),
+                end_line: end.line_number.get(),
+                end_column: end.column_number.get(),
+                char_offset,
+                char_length,
+            })
+        });
+
+        let locations = path
+            .map(|uri| SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation { uri },
+                    region,
+                },
+            })
+            .into_iter()
+            .collect();
+
+        let rule_index = self.rule_index(category.name(), category.link());
+        self.results.push(SarifResult {
+            rule_id: category.name(),
+            rule_index,
+            level: level(diagnostic.severity()),
+            message: SarifMessage {
+                text: PrintDescription(diagnostic).to_string(),
+            },
+            locations,
+            partial_fingerprints: BTreeMap::from([(
+                "pglsFingerprint/v1",
+                format!("{fingerprint:016x}"),
+            )]),
+            suppressions: if suppressed {
+                vec![SarifSuppression { kind: "inSource" }]
+            } else {
+                vec![]
+            },
+        });
+    }
+
+    /// Returns the index of the rule descriptor for `id`, adding it on first use.
+    fn rule_index(&mut self, id: &'static str, link: Option<&'static str>) -> usize {
+        if let Some(index) = self.rules.iter().position(|rule| rule.id == id) {
+            return index;
+        }
+
+        let rule = match self.rule_metadata.get(id) {
+            Some(metadata) => SarifRule {
+                id,
+                name: Some(metadata.name),
+                short_description: Some(SarifMessage {
+                    text: summary(metadata.docs),
+                }),
+                full_description: Some(SarifMessage {
+                    text: metadata.docs.trim().to_string(),
+                }),
+                help_uri: link,
+                default_configuration: Some(SarifConfiguration {
+                    level: level(metadata.severity),
+                }),
+                properties: Some(SarifRuleProperties {
+                    recommended: metadata.recommended,
+                    sources: metadata
+                        .sources
+                        .iter()
+                        .map(|source| source.to_namespaced_rule_name())
+                        .collect(),
+                }),
+            },
+            None => SarifRule {
+                id,
+                name: None,
+                short_description: None,
+                full_description: None,
+                help_uri: link,
+                default_configuration: None,
+                properties: None,
+            },
+        };
+
+        self.rules.push(rule);
+        self.rules.len() - 1
+    }
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Hint | Severity::Information => "note",
+        Severity::Warning => "warning",
+        Severity::Error | Severity::Fatal => "error",
+    }
+}
+
+/// The first paragraph of the rule documentation.
+fn summary(docs: &str) -> String {
+    docs.trim()
+        .split("\n\n")
+        .next()
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The root of a [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) log.
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRun {
+    tool: SarifTool,
+    column_kind: &'static str,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver {
+    name: &'static str,
+    information_uri: &'static str,
+    version: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    short_description: Option<SarifMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    full_description: Option<SarifMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_configuration: Option<SarifConfiguration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<SarifRuleProperties>,
+}
+
+#[derive(Serialize)]
+struct SarifConfiguration {
+    level: &'static str,
+}
+
+#[derive(Serialize)]
+struct SarifRuleProperties {
+    recommended: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sources: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: &'static str,
+    rule_index: usize,
+    level: &'static str,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+    partial_fingerprints: BTreeMap<&'static str, String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suppressions: Vec<SarifSuppression>,
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<SarifRegion>,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+    char_offset: usize,
+    char_length: usize,
+}
+
+#[derive(Serialize)]
+struct SarifSuppression {
+    kind: &'static str,
+}
@@ -41,6 +41,54 @@ fn check_gitlab_reporter_snapshot() {
     ]));
 }
 
+#[test]
+#[cfg_attr(
+    target_os = "windows",
+    ignore = "snapshot expectations only validated on unix-like platforms"
+)]
+fn check_sarif_reporter_snapshot() {
+    assert_snapshot!(run_check(&[
+        "--reporter",
+        "sarif",
+        "tests/fixtures/test.sql"
+    ]));
+}
+
+#[test]
+fn check_sarif_reporter_counts_code_points() {
+    let output = run_check(&["--reporter", "sarif", "tests/fixtures/unicode.sql"]);
+
+    // "-- café\n" is 9 bytes long, but 8 code points
+    assert!(output.contains("\"charOffset\": 8,"), "{output}");
+    assert!(output.contains("\"charLength\": 36"), "{output}");
+}
+
+#[test]
+#[cfg_attr(
+    target_os = "windows",
+    ignore = "snapshot expectations only validated on unix-like platforms"
+)]
+fn check_json_pretty_reporter_snapshot() {
+    assert_snapshot!(run_check(&[
+        "--reporter",
+        "json-pretty",
+        "tests/fixtures/test.sql"
+    ]));
+}
+
+#[test]
+#[cfg_attr(
+    target_os = "windows",
+    ignore = "snapshot expectations only validated on unix-like platforms"
+)]
+fn check_checkstyle_reporter_snapshot() {
+    assert_snapshot!(run_check(&[
+        "--reporter",
+        "checkstyle",
+        "tests/fixtures/test.sql"
+    ]));
+}
+
 #[test]
 #[cfg_attr(
     target_os = "windows",
@@ -88,6 +136,60 @@ fn check_directory_traversal_snapshot() {
     ));
 }
 
+#[test]
+fn check_baseline_hides_recorded_diagnostics() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let baseline = dir.path().join("pgls-baseline.json");
+    let baseline = baseline.to_str().unwrap();
+
+    let unfiltered = run_check(&["tests/fixtures/test.sql"]);
+    assert!(unfiltered.starts_with("status: failure"), "{unfiltered}");
+    assert!(unfiltered.contains("tqjable"), "{unfiltered}");
+
+    let written = run_check(&["--write-baseline", baseline, "tests/fixtures/test.sql"]);
+    assert!(written.starts_with("status: success"), "{written}");
+
+    let content = std::fs::read_to_string(baseline).expect("baseline was not written");
+    assert!(content.contains("\"category\": \"syntax\""), "{content}");
+    assert!(
+        content.contains("\"file\": \"tests/fixtures/test.sql\""),
+        "{content}"
+    );
+
+    let checked = run_check(&["--baseline", baseline, "tests/fixtures/test.sql"]);
+    assert!(checked.starts_with("status: success"), "{checked}");
+    assert!(!checked.contains("tqjable"), "{checked}");
+}
+
+#[test]
+fn run_reports_statements_that_are_not_in_the_file() {
+    let output = Command::cargo_bin(BIN)
+        .expect("binary not built")
+        .args([
+            "run",
+            "--config-path",
+            CONFIG_PATH,
+            "--log-level",
+            "none",
+            "--statement",
+            "3",
+            "tests/fixtures/test.sql",
+        ])
+        .output()
+        .expect("failed to run CLI");
+
+    let output = normalize_output(
+        output.status,
+        &String::from_utf8_lossy(&output.stdout),
+        &String::from_utf8_lossy(&output.stderr),
+    );
+    assert!(output.starts_with("status: failure"), "{output}");
+    assert!(
+        output.contains("Statement 3 was not found, the file contains 1 statement."),
+        "{output}"
+    );
+}
+
 fn run_check(args: &[&str]) -> String {
     let mut full_args = vec!["--config-path", CONFIG_PATH, "--log-level", "none"];
     full_args.extend_from_slice(args);
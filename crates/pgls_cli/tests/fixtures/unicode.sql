@@ -0,0 +1,2 @@
+-- café
+alter tqjable test drop column café;
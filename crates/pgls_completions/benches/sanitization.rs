@@ -30,6 +30,8 @@ fn to_params<'a>(
         schema: cache,
         text,
         tree,
+        usage: None,
+        search_path: None,
     }
 }
 
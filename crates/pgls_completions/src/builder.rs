@@ -1,5 +1,5 @@
 use crate::{
-    CompletionItemKind, CompletionText,
+    CompletionItemKind, CompletionItemReference, CompletionText, CompletionUsage,
     item::CompletionItem,
     relevance::{filtering::CompletionFilter, scoring::CompletionScore},
 };
@@ -20,11 +20,16 @@ pub(crate) struct PossibleCompletionItem<'a> {
 pub(crate) struct CompletionBuilder<'a> {
     items: Vec<PossibleCompletionItem<'a>>,
     ctx: &'a TreesitterContext<'a>,
+    usage: Option<&'a CompletionUsage>,
 }
 
 impl<'a> CompletionBuilder<'a> {
-    pub fn new(ctx: &'a TreesitterContext) -> Self {
-        CompletionBuilder { items: vec![], ctx }
+    pub fn new(ctx: &'a TreesitterContext, usage: Option<&'a CompletionUsage>) -> Self {
+        CompletionBuilder {
+            items: vec![],
+            ctx,
+            usage,
+        }
     }
 
     pub fn add_item(&mut self, item: PossibleCompletionItem<'a>) {
@@ -39,7 +44,7 @@ impl<'a> CompletionBuilder<'a> {
             .collect();
 
         for item in items.iter_mut() {
-            item.score.calc_score(self.ctx);
+            item.score.calc_score(self.ctx, self.usage);
         }
 
         items.sort_by(|a, b| {
@@ -68,6 +73,8 @@ impl<'a> CompletionBuilder<'a> {
             .map(|(idx, item)| {
                 let preselected = idx == 0 && should_preselect_first_item;
 
+                let reference = CompletionItemReference::from(item.score.get_data());
+
                 CompletionItem {
                     description: item.description,
                     kind: item.kind,
@@ -78,6 +85,7 @@ impl<'a> CompletionBuilder<'a> {
                     // wonderous Rust syntax ftw
                     sort_text: format!("{idx:0>max_padding$}"),
                     completion_text: item.completion_text,
+                    reference,
                 }
             })
             .collect()
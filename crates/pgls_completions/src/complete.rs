@@ -6,10 +6,11 @@ use crate::{
     builder::CompletionBuilder,
     item::CompletionItem,
     providers::{
-        complete_columns, complete_functions, complete_policies, complete_roles, complete_schemas,
-        complete_tables,
+        complete_columns, complete_functions, complete_join_conditions, complete_policies,
+        complete_roles, complete_schemas, complete_tables, helper::qualify_public_objects,
     },
     sanitization::SanitizedCompletionParams,
+    usage::CompletionUsage,
 };
 
 pub const LIMIT: usize = 50;
@@ -20,6 +21,11 @@ pub struct CompletionParams<'a> {
     pub schema: &'a pgls_schema_cache::SchemaCache,
     pub text: String,
     pub tree: &'a tree_sitter::Tree,
+    /// Accepted completions of the current project, used to boost frequently used items.
+    pub usage: Option<&'a CompletionUsage>,
+    /// The schemas on the search path. Tables and functions in `public` are qualified if it
+    /// isn't on it. If `None`, `public` is assumed to be on the search path.
+    pub search_path: Option<&'a [&'a str]>,
 }
 
 #[tracing::instrument(level = "debug", skip_all, fields(
@@ -27,6 +33,8 @@ pub struct CompletionParams<'a> {
     position = params.position.to_string()
 ))]
 pub fn complete(params: CompletionParams) -> Vec<CompletionItem> {
+    let usage = params.usage;
+    let search_path = params.search_path;
     let sanitized_params = SanitizedCompletionParams::from(params);
 
     let ctx = TreesitterContext::new(TreeSitterContextParams {
@@ -35,14 +43,21 @@ pub fn complete(params: CompletionParams) -> Vec<CompletionItem> {
         tree: &sanitized_params.tree,
     });
 
-    let mut builder = CompletionBuilder::new(&ctx);
+    let mut builder = CompletionBuilder::new(&ctx, usage);
 
     complete_tables(&ctx, sanitized_params.schema, &mut builder);
     complete_functions(&ctx, sanitized_params.schema, &mut builder);
     complete_columns(&ctx, sanitized_params.schema, &mut builder);
+    complete_join_conditions(&ctx, sanitized_params.schema, &mut builder);
     complete_schemas(&ctx, sanitized_params.schema, &mut builder);
     complete_policies(&ctx, sanitized_params.schema, &mut builder);
     complete_roles(&ctx, sanitized_params.schema, &mut builder);
 
-    builder.finish()
+    let mut items = builder.finish();
+
+    if search_path.is_some_and(|search_path| !search_path.contains(&"public")) {
+        qualify_public_objects(&ctx, &mut items);
+    }
+
+    items
 }
@@ -3,7 +3,7 @@ use std::fmt::Display;
 use pgls_text_size::TextRange;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum CompletionItemKind {
@@ -13,6 +13,7 @@ pub enum CompletionItemKind {
     Schema,
     Policy,
     Role,
+    JoinCondition,
 }
 
 impl Display for CompletionItemKind {
@@ -24,6 +25,7 @@ impl Display for CompletionItemKind {
             CompletionItemKind::Schema => "Schema",
             CompletionItemKind::Policy => "Policy",
             CompletionItemKind::Role => "Role",
+            CompletionItemKind::JoinCondition => "Join Condition",
         };
 
         write!(f, "{txt}")
@@ -59,4 +61,21 @@ pub struct CompletionItem {
     pub detail: Option<String>,
 
     pub completion_text: Option<CompletionText>,
+
+    /// The database object this item refers to.
+    pub reference: CompletionItemReference,
+}
+
+/// Identifies the database object behind a [CompletionItem].
+///
+/// Completion lists are kept lightweight; editors send the reference back
+/// when they want to resolve documentation or additional edits for an item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CompletionItemReference {
+    pub kind: CompletionItemKind,
+    pub name: String,
+    pub schema: Option<String>,
+    /// Only set for columns, policies and join conditions.
+    pub table: Option<String>,
 }
@@ -3,11 +3,15 @@ mod complete;
 mod item;
 mod providers;
 mod relevance;
+mod resolve;
 mod sanitization;
+mod usage;
 
 #[cfg(test)]
 mod test_helper;
 
 pub use complete::*;
 pub use item::*;
+pub use resolve::*;
 pub use sanitization::*;
+pub use usage::*;
@@ -1,7 +1,10 @@
+use pgls_schema_cache::{ForeignKey, Table};
 use pgls_text_size::TextRange;
 use pgls_treesitter::TreesitterContext;
 
-use crate::{is_sanitized_token_with_quote, remove_sanitized_token};
+use crate::{
+    CompletionItem, CompletionItemKind, is_sanitized_token_with_quote, remove_sanitized_token,
+};
 
 pub(crate) fn node_text_surrounded_by_quotes(ctx: &TreesitterContext) -> bool {
     ctx.get_node_under_cursor_content()
@@ -64,3 +67,50 @@ pub(crate) fn with_schema_or_alias(
         }
     }
 }
+
+/// Qualifies the tables and functions in `public`, which [with_schema_or_alias] leaves
+/// unqualified. Used when `public` is not on the search path.
+pub(crate) fn qualify_public_objects(ctx: &TreesitterContext, items: &mut [CompletionItem]) {
+    if ctx.has_any_qualifier() {
+        return;
+    }
+
+    let with_quotes = node_text_surrounded_by_quotes(ctx) || only_leading_quote(ctx);
+
+    for item in items.iter_mut().filter(|item| {
+        matches!(
+            item.kind,
+            CompletionItemKind::Table | CompletionItemKind::Function
+        ) && item.reference.schema.as_deref() == Some("public")
+    }) {
+        if let Some(completion_text) = item.completion_text.as_mut() {
+            // the opening quote is part of the document, in front of the replaced range
+            let qualifier = if with_quotes {
+                r#"public".""#
+            } else {
+                "public."
+            };
+            completion_text.text.insert_str(0, qualifier);
+        }
+    }
+}
+
+/// Builds `a.x = b.y and ...` for the columns of the foreign key.
+pub(crate) fn join_condition(
+    fk: &ForeignKey,
+    left: &Table,
+    left_prefix: &str,
+    right: &Table,
+    right_prefix: &str,
+) -> String {
+    fk.column_pairs()
+        .map(|(col, ref_col)| {
+            if fk.table_oid == left.id && fk.referenced_table_oid == right.id {
+                format!("{left_prefix}.{col} = {right_prefix}.{ref_col}")
+            } else {
+                format!("{left_prefix}.{ref_col} = {right_prefix}.{col}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
@@ -0,0 +1,282 @@
+use pgls_schema_cache::{ForeignKey, SchemaCache, Table};
+use pgls_treesitter::{TreesitterContext, context::WrappingClause, parts_of_reference_query};
+
+use crate::{
+    CompletionItemKind, CompletionText,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    providers::helper::{get_range_to_replace, join_condition},
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+pub fn complete_join_conditions<'a>(
+    ctx: &TreesitterContext<'a>,
+    schema_cache: &'a SchemaCache,
+    builder: &mut CompletionBuilder<'a>,
+) {
+    let Some(WrappingClause::Join {
+        on_node: Some(on_node),
+    }) = ctx.wrapping_clause_type
+    else {
+        return;
+    };
+
+    // only conditions that join the relation of the current JOIN make sense here
+    let Some(joined_prefix) = joined_relation_prefix(ctx, on_node) else {
+        return;
+    };
+
+    // the tables in the statement, along with the texts we'll use to reference them
+    let mentioned_tables: Vec<(&Table, Vec<String>)> = ctx
+        .iter_mentioned_relations()
+        .filter_map(|(schema, table)| {
+            let found = schema_cache.find_tables(table, schema).into_iter().next()?;
+
+            let mut prefixes = ctx.get_used_aliases_for_table(table);
+            if prefixes.is_empty() {
+                prefixes.push(table.to_string());
+            }
+
+            Some((found, prefixes))
+        })
+        .collect();
+
+    for fk in &schema_cache.foreign_keys {
+        let Some((table, table_prefixes)) =
+            mentioned_tables.iter().find(|(t, _)| t.id == fk.table_oid)
+        else {
+            continue;
+        };
+
+        let Some((referenced, referenced_prefixes)) = mentioned_tables
+            .iter()
+            .find(|(t, _)| t.id == fk.referenced_table_oid)
+        else {
+            continue;
+        };
+
+        // a table that references itself can only be joined through two different aliases,
+        // and either of them can be the referencing side
+        let is_self_reference = table.id == referenced.id;
+
+        for table_prefix in table_prefixes {
+            for referenced_prefix in referenced_prefixes {
+                if is_self_reference && table_prefix == referenced_prefix {
+                    continue;
+                }
+
+                if *table_prefix != joined_prefix && *referenced_prefix != joined_prefix {
+                    continue;
+                }
+
+                let condition =
+                    join_condition(fk, table, table_prefix, referenced, referenced_prefix);
+
+                add_join_condition(ctx, builder, fk, condition);
+            }
+        }
+    }
+}
+
+/// The text used to reference the relation of the JOIN that `on_node` belongs to, e.g. `c` in
+/// `join customers c on` and `customers` in `join public.customers on`. `None` if the JOIN
+/// is not on a table.
+fn joined_relation_prefix(ctx: &TreesitterContext, on_node: tree_sitter::Node) -> Option<String> {
+    let join = on_node.parent()?;
+    let relation = join
+        .children(&mut join.walk())
+        .find(|child| child.kind() == "relation")?;
+
+    let mut cursor = relation.walk();
+    let children: Vec<_> = relation.children(&mut cursor).collect();
+
+    let prefix = match children.iter().find(|child| child.kind() == "alias") {
+        Some(alias) => alias.child_by_field_name("alias")?,
+        None => {
+            let table_reference = children
+                .iter()
+                .find(|child| child.kind() == "table_reference")?;
+            parts_of_reference_query(*table_reference, ctx.text)?.2
+        }
+    };
+
+    prefix
+        .utf8_text(ctx.text.as_bytes())
+        .ok()
+        .map(str::to_string)
+}
+
+fn add_join_condition<'a>(
+    ctx: &TreesitterContext<'a>,
+    builder: &mut CompletionBuilder<'a>,
+    fk: &'a ForeignKey,
+    condition: String,
+) {
+    let relevance = CompletionRelevanceData::JoinCondition(fk);
+
+    let item = PossibleCompletionItem {
+        label: condition.clone(),
+        score: CompletionScore::from(relevance.clone()),
+        filter: CompletionFilter::from(relevance),
+        description: fk.name.clone(),
+        kind: CompletionItemKind::JoinCondition,
+        completion_text: Some(CompletionText {
+            text: condition,
+            range: get_range_to_replace(ctx),
+            is_snippet: false,
+        }),
+        detail: None,
+    };
+
+    builder.add_item(item);
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Executor, PgPool};
+
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{
+            CompletionAssertion, assert_complete_results, get_test_deps, get_test_params,
+        },
+    };
+
+    use pgls_test_utils::QueryWithCursorPosition;
+
+    static SETUP: &str = r#"
+        create table customers (
+            id serial primary key,
+            name text
+        );
+
+        create table orders (
+            id serial primary key,
+            customer_id int references customers(id),
+            total numeric
+        );
+    "#;
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn suggests_join_condition_after_on(pool: PgPool) {
+        pool.execute(SETUP).await.unwrap();
+
+        assert_complete_results(
+            format!(
+                "select * from orders o join customers c on {}",
+                QueryWithCursorPosition::cursor_marker()
+            )
+            .as_str(),
+            vec![CompletionAssertion::LabelAndKind(
+                "o.customer_id = c.id".into(),
+                CompletionItemKind::JoinCondition,
+            )],
+            None,
+            &pool,
+        )
+        .await;
+    }
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn uses_table_names_without_aliases(pool: PgPool) {
+        pool.execute(SETUP).await.unwrap();
+
+        assert_complete_results(
+            format!(
+                "select * from customers join orders on {}",
+                QueryWithCursorPosition::cursor_marker()
+            )
+            .as_str(),
+            vec![CompletionAssertion::LabelAndKind(
+                "orders.customer_id = customers.id".into(),
+                CompletionItemKind::JoinCondition,
+            )],
+            None,
+            &pool,
+        )
+        .await;
+    }
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn no_join_conditions_outside_of_on(pool: PgPool) {
+        pool.execute(SETUP).await.unwrap();
+
+        assert_complete_results(
+            format!(
+                "select * from orders o join customers c on o.customer_id = c.id where {}",
+                QueryWithCursorPosition::cursor_marker()
+            )
+            .as_str(),
+            vec![CompletionAssertion::KindNotExists(
+                CompletionItemKind::JoinCondition,
+            )],
+            None,
+            &pool,
+        )
+        .await;
+    }
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn only_suggests_conditions_for_the_joined_table(pool: PgPool) {
+        let setup = r#"
+            create table customers (
+                id serial primary key
+            );
+
+            create table orders (
+                id serial primary key,
+                customer_id int references customers(id)
+            );
+
+            create table order_items (
+                id serial primary key,
+                order_id int references orders(id)
+            );
+        "#;
+
+        let query = format!(
+            "select * from customers c join orders o on o.customer_id = c.id join order_items i on {}",
+            QueryWithCursorPosition::cursor_marker()
+        );
+
+        let (tree, cache) = get_test_deps(Some(setup), query.as_str().into(), &pool).await;
+        let items = complete(get_test_params(&tree, &cache, query.as_str().into()));
+
+        let conditions: Vec<String> = items
+            .into_iter()
+            .filter(|item| item.kind == CompletionItemKind::JoinCondition)
+            .map(|item| item.label)
+            .collect();
+
+        assert_eq!(conditions, vec!["i.order_id = o.id"]);
+    }
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn suggests_self_join_conditions(pool: PgPool) {
+        let setup = r#"
+            create table employees (
+                id serial primary key,
+                manager_id int references employees(id)
+            );
+        "#;
+
+        let query = format!(
+            "select * from employees e join employees m on {}",
+            QueryWithCursorPosition::cursor_marker()
+        );
+
+        let (tree, cache) = get_test_deps(Some(setup), query.as_str().into(), &pool).await;
+        let items = complete(get_test_params(&tree, &cache, query.as_str().into()));
+
+        let mut conditions: Vec<String> = items
+            .into_iter()
+            .filter(|item| item.kind == CompletionItemKind::JoinCondition)
+            .map(|item| item.label)
+            .collect();
+        conditions.sort();
+
+        assert_eq!(
+            conditions,
+            vec!["e.manager_id = m.id", "m.manager_id = e.id"]
+        );
+    }
+}
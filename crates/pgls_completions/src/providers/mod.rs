@@ -1,6 +1,7 @@
 mod columns;
 mod functions;
-mod helper;
+pub(crate) mod helper;
+mod join_conditions;
 mod policies;
 mod roles;
 mod schemas;
@@ -8,6 +9,7 @@ mod tables;
 
 pub use columns::*;
 pub use functions::*;
+pub use join_conditions::*;
 pub use policies::*;
 pub use roles::*;
 pub use schemas::*;
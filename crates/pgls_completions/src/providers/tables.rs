@@ -709,4 +709,45 @@ mod tests {
         )
         .await;
     }
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn qualifies_public_tables_outside_of_search_path(pool: PgPool) {
+        let setup = r#"
+            create schema private;
+
+            create table public.customers (
+                id serial primary key
+            );
+        "#;
+
+        let query = format!(
+            "select * from cust{}",
+            QueryWithCursorPosition::cursor_marker()
+        );
+
+        let (tree, cache) = get_test_deps(Some(setup), query.as_str().into(), &pool).await;
+
+        let mut params = get_test_params(&tree, &cache, query.as_str().into());
+        params.search_path = Some(&["private"]);
+        let items = complete(params);
+
+        let customers = items
+            .iter()
+            .find(|item| item.label == "customers")
+            .expect("customers should be suggested");
+        let text = customers.completion_text.as_ref().unwrap();
+
+        // the qualifier is part of the main edit, which replaces the typed prefix
+        assert_eq!(text.text, "public.customers");
+        assert_eq!(text.range, TextRange::new(14.into(), 18.into()));
+
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+        let customers = items.iter().find(|item| item.label == "customers").unwrap();
+
+        assert_eq!(
+            customers.completion_text.as_ref().unwrap().text,
+            "customers"
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use crate::{CompletionItemKind, CompletionItemReference};
+
 pub(crate) mod filtering;
 pub(crate) mod scoring;
 
@@ -9,4 +11,54 @@ pub(crate) enum CompletionRelevanceData<'a> {
     Schema(&'a pgls_schema_cache::Schema),
     Policy(&'a pgls_schema_cache::Policy),
     Role(&'a pgls_schema_cache::Role),
+    JoinCondition(&'a pgls_schema_cache::ForeignKey),
+}
+
+impl From<&CompletionRelevanceData<'_>> for CompletionItemReference {
+    fn from(value: &CompletionRelevanceData<'_>) -> Self {
+        match value {
+            CompletionRelevanceData::Table(t) => Self {
+                kind: CompletionItemKind::Table,
+                name: t.name.clone(),
+                schema: Some(t.schema.clone()),
+                table: None,
+            },
+            CompletionRelevanceData::Function(f) => Self {
+                kind: CompletionItemKind::Function,
+                name: f.name.clone(),
+                schema: Some(f.schema.clone()),
+                table: None,
+            },
+            CompletionRelevanceData::Column(c) => Self {
+                kind: CompletionItemKind::Column,
+                name: c.name.clone(),
+                schema: Some(c.schema_name.clone()),
+                table: Some(c.table_name.clone()),
+            },
+            CompletionRelevanceData::Schema(s) => Self {
+                kind: CompletionItemKind::Schema,
+                name: s.name.clone(),
+                schema: None,
+                table: None,
+            },
+            CompletionRelevanceData::Policy(p) => Self {
+                kind: CompletionItemKind::Policy,
+                name: p.name.clone(),
+                schema: Some(p.schema_name.clone()),
+                table: Some(p.table_name.clone()),
+            },
+            CompletionRelevanceData::Role(r) => Self {
+                kind: CompletionItemKind::Role,
+                name: r.name.clone(),
+                schema: None,
+                table: None,
+            },
+            CompletionRelevanceData::JoinCondition(fk) => Self {
+                kind: CompletionItemKind::JoinCondition,
+                name: fk.name.clone(),
+                schema: Some(fk.schema.clone()),
+                table: Some(fk.table_name.clone()),
+            },
+        }
+    }
 }
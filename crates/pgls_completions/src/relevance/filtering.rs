@@ -320,6 +320,15 @@ impl CompletionFilter<'_> {
                         ) && ctx.before_cursor_matches_kind(&["keyword_policy", "keyword_exists"])
                     }
 
+                    CompletionRelevanceData::JoinCondition(_) => match clause {
+                        // only at the start of the predicate: `join posts p on |`
+                        WrappingClause::Join { on_node: Some(on) } => {
+                            ctx.node_under_cursor.start_byte() >= on.end_byte()
+                                && ctx.before_cursor_matches_kind(&["keyword_on"])
+                        }
+                        _ => false,
+                    },
+
                     CompletionRelevanceData::Role(_) => match clause {
                         WrappingClause::DropRole | WrappingClause::AlterRole => true,
 
@@ -385,6 +394,8 @@ impl CompletionFilter<'_> {
             CompletionRelevanceData::Schema(_) => false,
             // no policy or row completion if user typed a schema node first.
             CompletionRelevanceData::Policy(_) | CompletionRelevanceData::Role(_) => false,
+            // join conditions bring their own qualifiers.
+            CompletionRelevanceData::JoinCondition(_) => false,
         };
 
         if !matches {
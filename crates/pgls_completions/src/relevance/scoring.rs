@@ -2,7 +2,7 @@ use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
 
 use pgls_treesitter::context::{TreesitterContext, WrappingClause, WrappingNode};
 
-use crate::sanitization;
+use crate::{CompletionItemReference, CompletionUsage, sanitization};
 
 use super::CompletionRelevanceData;
 
@@ -26,7 +26,11 @@ impl CompletionScore<'_> {
         self.score
     }
 
-    pub fn calc_score(&mut self, ctx: &TreesitterContext) {
+    pub fn get_data(&self) -> &CompletionRelevanceData<'_> {
+        &self.data
+    }
+
+    pub fn calc_score(&mut self, ctx: &TreesitterContext, usage: Option<&CompletionUsage>) {
         self.check_is_user_defined();
         self.check_matches_schema(ctx);
         self.check_matches_query_input(ctx);
@@ -36,6 +40,7 @@ impl CompletionScore<'_> {
         self.check_relations_in_stmt(ctx);
         self.check_columns_in_stmt(ctx);
         self.check_is_not_wellknown_migration(ctx);
+        self.check_usage(usage);
     }
 
     fn check_matches_query_input(&mut self, ctx: &TreesitterContext) {
@@ -51,6 +56,11 @@ impl CompletionScore<'_> {
             CompletionRelevanceData::Schema(s) => s.name.as_str().to_ascii_lowercase(),
             CompletionRelevanceData::Policy(p) => p.name.as_str().to_ascii_lowercase(),
             CompletionRelevanceData::Role(r) => r.name.as_str().to_ascii_lowercase(),
+            CompletionRelevanceData::JoinCondition(fk) => fk
+                .columns
+                .first()
+                .map(|c| c.to_ascii_lowercase())
+                .unwrap_or_default(),
         };
 
         let fz_matcher = SkimMatcherV2::default();
@@ -134,6 +144,12 @@ impl CompletionScore<'_> {
                 WrappingClause::DropRole | WrappingClause::AlterRole => 25,
                 _ => -50,
             },
+
+            // A join condition matching a foreign key is most likely what the user wants.
+            CompletionRelevanceData::JoinCondition(_) => match clause_type {
+                WrappingClause::Join { .. } => 50,
+                _ => -50,
+            },
         }
     }
 
@@ -172,6 +188,7 @@ impl CompletionScore<'_> {
             },
             CompletionRelevanceData::Policy(_) => 0,
             CompletionRelevanceData::Role(_) => 0,
+            CompletionRelevanceData::JoinCondition(_) => 0,
         }
     }
 
@@ -194,7 +211,9 @@ impl CompletionScore<'_> {
                 ctx.head_qualifier_sanitized()
             }
 
-            CompletionRelevanceData::Schema(_) | CompletionRelevanceData::Role(_) => None,
+            CompletionRelevanceData::Schema(_)
+            | CompletionRelevanceData::Role(_)
+            | CompletionRelevanceData::JoinCondition(_) => None,
         };
 
         if schema_from_qualifier.is_none() {
@@ -223,6 +242,7 @@ impl CompletionScore<'_> {
             CompletionRelevanceData::Schema(s) => s.name.as_str(),
             CompletionRelevanceData::Policy(p) => p.name.as_str(),
             CompletionRelevanceData::Role(r) => r.name.as_str(),
+            CompletionRelevanceData::JoinCondition(fk) => fk.name.as_str(),
         }
     }
 
@@ -234,6 +254,7 @@ impl CompletionScore<'_> {
             CompletionRelevanceData::Schema(s) => Some(s.name.as_str()),
             CompletionRelevanceData::Policy(p) => Some(p.schema_name.as_str()),
             CompletionRelevanceData::Role(_) => None,
+            CompletionRelevanceData::JoinCondition(fk) => Some(fk.schema.as_str()),
         }
     }
 
@@ -248,7 +269,9 @@ impl CompletionScore<'_> {
 
     fn check_relations_in_stmt(&mut self, ctx: &TreesitterContext) {
         match self.data {
-            CompletionRelevanceData::Table(_) | CompletionRelevanceData::Function(_) => return,
+            CompletionRelevanceData::Table(_)
+            | CompletionRelevanceData::Function(_)
+            | CompletionRelevanceData::JoinCondition(_) => return,
             _ => {}
         }
 
@@ -376,4 +399,15 @@ impl CompletionScore<'_> {
             }
         }
     }
+
+    fn check_usage(&mut self, usage: Option<&CompletionUsage>) {
+        let Some(usage) = usage else {
+            return;
+        };
+
+        let count = usage.count(&CompletionItemReference::from(&self.data));
+
+        // frequently used items get a boost, but they shouldn't outweigh the context
+        self.score += count.min(5) as i32 * 3;
+    }
 }
@@ -0,0 +1,268 @@
+use pgls_schema_cache::{SchemaCache, Table};
+use pgls_text_size::{TextRange, TextSize};
+use pgls_treesitter::{TreeSitterContextParams, context::TreesitterContext};
+
+use crate::{
+    CompletionItemKind, CompletionItemReference, CompletionText, providers::helper::join_condition,
+};
+
+#[derive(Debug)]
+pub struct CompletionResolveParams<'a> {
+    pub position: TextSize,
+    pub schema: &'a SchemaCache,
+    pub text: String,
+    pub tree: &'a tree_sitter::Tree,
+    pub reference: &'a CompletionItemReference,
+    /// The schemas on the search path. Objects outside of them need to be qualified.
+    pub search_path: &'a [&'a str],
+}
+
+/// Computes the edits that have to be applied alongside a completion item,
+/// e.g. a missing `JOIN` for a column of an unmentioned table.
+///
+/// Ranges are relative to the passed `text`.
+#[tracing::instrument(level = "debug", skip_all, fields(
+    text = params.text,
+    position = params.position.to_string()
+))]
+pub fn resolve_additional_edits(params: CompletionResolveParams) -> Vec<CompletionText> {
+    let mut edits = vec![];
+
+    if params.reference.kind == CompletionItemKind::Column {
+        edits.extend(missing_join_edit(&params));
+    }
+
+    edits
+}
+
+/// If the column belongs to a table that is not part of the statement yet,
+/// we add a `JOIN` based on a foreign key to one of the mentioned tables.
+fn missing_join_edit(params: &CompletionResolveParams) -> Option<CompletionText> {
+    let column_schema = params.reference.schema.as_deref()?;
+    let column_table = params.reference.table.as_deref()?;
+
+    // We do not sanitize here: the `FROM` clause usually comes after the cursor,
+    // and sanitizing would shift its position.
+    let ctx = TreesitterContext::new(TreeSitterContextParams {
+        position: params.position,
+        text: &params.text,
+        tree: params.tree,
+    });
+
+    let mentioned: Vec<(Option<&str>, &str)> = ctx.iter_mentioned_relations().collect();
+
+    if mentioned.is_empty() {
+        return None;
+    }
+
+    let is_mentioned = mentioned.iter().any(|(schema, table)| {
+        unquote(table) == column_table && schema.is_none_or(|s| unquote(s) == column_schema)
+    });
+
+    if is_mentioned {
+        return None;
+    }
+
+    let target = params
+        .schema
+        .find_tables(column_table, Some(column_schema))
+        .into_iter()
+        .next()?;
+
+    let (joined, fk) = mentioned.iter().find_map(|(schema, table)| {
+        let mentioned_table = find_mentioned_table(params, *schema, table)?;
+        let fk = params
+            .schema
+            .foreign_keys
+            .iter()
+            .find(|fk| fk.connects(mentioned_table.id, target.id))?;
+        Some(((mentioned_table, *table), fk))
+    })?;
+
+    let (joined_table, joined_text) = joined;
+    let joined_prefix = ctx
+        .get_used_alias_for_table(joined_text)
+        .unwrap_or_else(|| joined_text.to_string());
+
+    let condition = join_condition(fk, target, &target.name, joined_table, &joined_prefix);
+
+    let qualified_target = if params.search_path.contains(&target.schema.as_str()) {
+        target.name.clone()
+    } else {
+        format!("{}.{}", target.schema, target.name)
+    };
+
+    let insert_at = end_of_from_relations(params.tree, params.position)?;
+
+    Some(CompletionText {
+        text: format!(" join {qualified_target} on {condition}"),
+        range: TextRange::empty(insert_at),
+        is_snippet: false,
+    })
+}
+
+fn find_mentioned_table<'a>(
+    params: &CompletionResolveParams<'a>,
+    schema: Option<&str>,
+    table: &str,
+) -> Option<&'a Table> {
+    match schema {
+        Some(schema) => params
+            .schema
+            .find_tables(table, Some(schema))
+            .into_iter()
+            .next(),
+        None => params.search_path.iter().find_map(|schema| {
+            params
+                .schema
+                .find_tables(table, Some(schema))
+                .into_iter()
+                .next()
+        }),
+    }
+}
+
+/// Returns the position after the last relation or join of the `FROM` clause
+/// in the statement surrounding `position`.
+fn end_of_from_relations(tree: &tree_sitter::Tree, position: TextSize) -> Option<TextSize> {
+    let position: usize = position.into();
+
+    let mut statement = tree
+        .root_node()
+        .descendant_for_byte_range(position, position)?;
+
+    while statement.kind() != "statement" {
+        statement = statement.parent()?;
+    }
+
+    let from = find_first_of_kind(statement, "from")?;
+
+    let mut cursor = from.walk();
+    let last = from
+        .children(&mut cursor)
+        .filter(|c| {
+            matches!(
+                c.kind(),
+                "relation"
+                    | "index_hint"
+                    | "join"
+                    | "cross_join"
+                    | "lateral_join"
+                    | "lateral_cross_join"
+            )
+        })
+        .last()?;
+
+    TextSize::try_from(last.end_byte()).ok()
+}
+
+fn find_first_of_kind<'a>(
+    node: tree_sitter::Node<'a>,
+    kind: &str,
+) -> Option<tree_sitter::Node<'a>> {
+    if node.kind() == kind {
+        return Some(node);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if let Some(found) = find_first_of_kind(child, kind) {
+            return Some(found);
+        }
+    }
+
+    None
+}
+
+fn unquote(ident: &str) -> &str {
+    ident.trim_matches('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use pgls_test_utils::QueryWithCursorPosition;
+    use sqlx::PgPool;
+
+    use crate::{
+        CompletionItemKind, CompletionItemReference,
+        resolve::{CompletionResolveParams, resolve_additional_edits},
+        test_helper::get_test_deps,
+    };
+
+    static SETUP: &str = r#"
+        create schema private;
+
+        create table public.customers (
+            id serial primary key,
+            name text
+        );
+
+        create table private.orders (
+            id serial primary key,
+            customer_id int references public.customers(id),
+            total numeric
+        );
+    "#;
+
+    fn column(schema: &str, table: &str, name: &str) -> CompletionItemReference {
+        CompletionItemReference {
+            kind: CompletionItemKind::Column,
+            name: name.into(),
+            schema: Some(schema.into()),
+            table: Some(table.into()),
+        }
+    }
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn adds_join_for_column_of_unmentioned_table(pool: PgPool) {
+        let query = format!(
+            "select {} from customers c",
+            QueryWithCursorPosition::cursor_marker()
+        );
+
+        let (tree, cache) = get_test_deps(Some(SETUP), query.as_str().into(), &pool).await;
+        let (position, text) =
+            QueryWithCursorPosition::from(query.as_str()).get_text_and_position();
+        let reference = column("private", "orders", "total");
+
+        let edits = resolve_additional_edits(CompletionResolveParams {
+            position: (position as u32).into(),
+            schema: &cache,
+            text: text.clone(),
+            tree: &tree,
+            reference: &reference,
+            search_path: &["public"],
+        });
+
+        assert_eq!(edits.len(), 1);
+        assert_eq!(
+            edits[0].text,
+            " join private.orders on orders.customer_id = c.id"
+        );
+        assert_eq!(usize::from(edits[0].range.start()), text.len());
+    }
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn no_join_for_mentioned_table(pool: PgPool) {
+        let query = format!(
+            "select {} from private.orders",
+            QueryWithCursorPosition::cursor_marker()
+        );
+
+        let (tree, cache) = get_test_deps(Some(SETUP), query.as_str().into(), &pool).await;
+        let (position, text) =
+            QueryWithCursorPosition::from(query.as_str()).get_text_and_position();
+        let reference = column("private", "orders", "total");
+
+        let edits = resolve_additional_edits(CompletionResolveParams {
+            position: (position as u32).into(),
+            schema: &cache,
+            text,
+            tree: &tree,
+            reference: &reference,
+            search_path: &["public"],
+        });
+
+        assert!(edits.is_empty());
+    }
+}
@@ -328,6 +328,8 @@ mod tests {
             schema: leaked_cache,
             text: input.into(),
             tree: leaked_tree,
+            usage: None,
+            search_path: None,
         }
     }
 
@@ -68,6 +68,8 @@ pub(crate) fn get_test_params<'a>(
         schema: schema_cache,
         tree,
         text,
+        usage: None,
+        search_path: None,
     }
 }
 
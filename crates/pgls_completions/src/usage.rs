@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{CompletionItemKind, CompletionItemReference};
+
+/// We only keep the most used items around so the persisted model stays small.
+const MAX_ENTRIES: usize = 500;
+
+/// A frequency model of accepted completion items.
+///
+/// Items the user picks often are boosted when scoring completions.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletionUsage {
+    counts: HashMap<String, u32>,
+    /// The value of `clock` when an item was last recorded, used to break ties on eviction.
+    #[serde(default)]
+    last_used: HashMap<String, u64>,
+    /// Incremented on every recorded item.
+    #[serde(default)]
+    clock: u64,
+}
+
+impl CompletionUsage {
+    pub fn record(&mut self, reference: &CompletionItemReference) {
+        let Some(key) = Self::key(reference) else {
+            return;
+        };
+
+        // make room before inserting, so a new item is never the one evicted
+        if !self.counts.contains_key(&key) && self.counts.len() >= MAX_ENTRIES {
+            self.evict_least_used();
+        }
+
+        self.clock += 1;
+        *self.counts.entry(key.clone()).or_default() += 1;
+        self.last_used.insert(key, self.clock);
+    }
+
+    /// Evicts the least used item, and of those the one that was recorded longest ago.
+    fn evict_least_used(&mut self) {
+        let least_used = self
+            .counts
+            .iter()
+            .min_by_key(|(key, count)| (**count, self.last_used.get(*key).copied()))
+            .map(|(key, _)| key.clone());
+
+        if let Some(key) = least_used {
+            self.counts.remove(&key);
+            self.last_used.remove(&key);
+        }
+    }
+
+    pub fn count(&self, reference: &CompletionItemReference) -> u32 {
+        Self::key(reference)
+            .and_then(|key| self.counts.get(&key).copied())
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Only tables, columns and functions are tracked.
+    fn key(reference: &CompletionItemReference) -> Option<String> {
+        let kind = match reference.kind {
+            CompletionItemKind::Table => "table",
+            CompletionItemKind::Column => "column",
+            CompletionItemKind::Function => "function",
+            _ => return None,
+        };
+
+        let mut key = format!("{kind}:");
+
+        if let Some(schema) = reference.schema.as_ref() {
+            key.push_str(schema);
+            key.push('.');
+        }
+
+        if let Some(table) = reference.table.as_ref() {
+            key.push_str(table);
+            key.push('.');
+        }
+
+        key.push_str(&reference.name);
+
+        Some(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pgls_test_utils::QueryWithCursorPosition;
+    use sqlx::PgPool;
+
+    use crate::{
+        CompletionItemKind, CompletionItemReference, complete,
+        test_helper::{get_test_deps, get_test_params},
+    };
+
+    use super::{CompletionUsage, MAX_ENTRIES};
+
+    fn table(name: &str) -> CompletionItemReference {
+        CompletionItemReference {
+            kind: CompletionItemKind::Table,
+            name: name.into(),
+            schema: Some("public".into()),
+            table: None,
+        }
+    }
+
+    #[test]
+    fn counts_accepted_items() {
+        let mut usage = CompletionUsage::default();
+
+        usage.record(&table("users"));
+        usage.record(&table("users"));
+        usage.record(&table("posts"));
+
+        assert_eq!(usage.count(&table("users")), 2);
+        assert_eq!(usage.count(&table("posts")), 1);
+        assert_eq!(usage.count(&table("comments")), 0);
+    }
+
+    #[test]
+    fn ignores_untracked_kinds() {
+        let mut usage = CompletionUsage::default();
+
+        usage.record(&CompletionItemReference {
+            kind: CompletionItemKind::Schema,
+            name: "public".into(),
+            schema: None,
+            table: None,
+        });
+
+        assert!(usage.is_empty());
+    }
+
+    #[test]
+    fn evicts_least_used_items() {
+        let mut usage = CompletionUsage::default();
+
+        usage.record(&table("users"));
+        usage.record(&table("users"));
+
+        for i in 0..MAX_ENTRIES {
+            usage.record(&table(&format!("table_{i}")));
+        }
+
+        assert_eq!(usage.counts.len(), MAX_ENTRIES);
+        assert_eq!(usage.count(&table("users")), 2);
+        // the oldest of the items used once made room
+        assert_eq!(usage.count(&table("table_0")), 0);
+    }
+
+    #[test]
+    fn keeps_new_items_at_capacity() {
+        let mut usage = CompletionUsage::default();
+
+        for i in 0..MAX_ENTRIES {
+            usage.record(&table(&format!("table_{i}")));
+        }
+
+        usage.record(&table("users"));
+        assert_eq!(usage.count(&table("users")), 1);
+
+        usage.record(&table("posts"));
+        assert_eq!(usage.count(&table("users")), 1);
+        assert_eq!(usage.count(&table("posts")), 1);
+        assert_eq!(usage.counts.len(), MAX_ENTRIES);
+    }
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn boosts_frequently_used_items(pool: PgPool) {
+        let setup = r#"
+            create table aardvarks (id serial primary key);
+            create table zebras (id serial primary key);
+        "#;
+
+        let query = format!("select * from {}", QueryWithCursorPosition::cursor_marker());
+
+        let (tree, cache) = get_test_deps(Some(setup), query.as_str().into(), &pool).await;
+
+        let mut usage = CompletionUsage::default();
+        for _ in 0..3 {
+            usage.record(&table("zebras"));
+        }
+
+        let mut params = get_test_params(&tree, &cache, query.as_str().into());
+        params.usage = Some(&usage);
+
+        let items = complete(params);
+
+        let zebras = items.iter().position(|i| i.label == "zebras").unwrap();
+        let aardvarks = items.iter().position(|i| i.label == "aardvarks").unwrap();
+
+        assert!(zebras < aardvarks);
+    }
+}
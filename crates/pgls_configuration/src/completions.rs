@@ -0,0 +1,23 @@
+use biome_deserialize_macros::{Merge, Partial};
+use bpaf::Bpaf;
+use serde::{Deserialize, Serialize};
+
+/// The configuration for completions.
+#[derive(Clone, Debug, Deserialize, Eq, Partial, PartialEq, Serialize)]
+#[partial(derive(Bpaf, Clone, Eq, PartialEq, Merge))]
+#[partial(cfg_attr(feature = "schema", derive(schemars::JsonSchema)))]
+#[partial(serde(rename_all = "camelCase", default, deny_unknown_fields))]
+pub struct CompletionsConfiguration {
+    /// if `false`, accepted completions are not recorded and won't affect the ranking of
+    /// future completions. `true` by default
+    #[partial(bpaf(hide))]
+    pub learn_from_usage: bool,
+}
+
+impl Default for CompletionsConfiguration {
+    fn default() -> Self {
+        Self {
+            learn_from_usage: true,
+        }
+    }
+}
@@ -1,5 +1,7 @@
-use biome_deserialize::StringSet;
-use biome_deserialize_macros::{Merge, Partial};
+use std::collections::BTreeMap;
+
+use biome_deserialize::{Merge, StringSet};
+use biome_deserialize_macros::{Deserializable, Merge, Partial};
 use bpaf::Bpaf;
 use serde::{Deserialize, Serialize};
 
@@ -36,6 +38,32 @@ pub struct DatabaseConfiguration {
     #[partial(bpaf(long("database")))]
     pub database: String,
 
+    /// The name of a service in the connection service file (`pg_service.conf`) to take
+    /// the connection parameters from.
+    #[partial(bpaf(long("service")))]
+    pub service: Option<String>,
+
+    /// A command whose output is used as the password, e.g. to read it from a secret manager.
+    #[partial(bpaf(long("password-command")))]
+    pub password_command: Option<String>,
+
+    /// The SSL mode of the connection: `disable`, `allow`, `prefer`, `require`, `verify-ca`
+    /// or `verify-full`.
+    #[partial(bpaf(long("ssl-mode")))]
+    pub ssl_mode: Option<String>,
+
+    /// The path to the certificate of the authority to verify the server certificate with.
+    #[partial(bpaf(long("ssl-root-cert")))]
+    pub ssl_root_cert: Option<String>,
+
+    /// The path to the client certificate.
+    #[partial(bpaf(long("ssl-cert")))]
+    pub ssl_cert: Option<String>,
+
+    /// The path to the private key of the client certificate.
+    #[partial(bpaf(long("ssl-key")))]
+    pub ssl_key: Option<String>,
+
     #[partial(bpaf(long("allow_statement_executions_against")))]
     pub allow_statement_executions_against: StringSet,
 
@@ -43,6 +71,29 @@ pub struct DatabaseConfiguration {
     #[partial(bpaf(long("conn_timeout_secs"), fallback(Some(10)), debug_fallback))]
     pub conn_timeout_secs: u16,
 
+    /// The maximum number of rows returned when executing a statement.
+    #[partial(bpaf(long("execution_row_limit"), fallback(Some(1000)), debug_fallback))]
+    pub execution_row_limit: u32,
+
+    /// The time in seconds after which the execution of a statement is cancelled.
+    #[partial(bpaf(long("execution_timeout_secs"), fallback(Some(30)), debug_fallback))]
+    pub execution_timeout_secs: u16,
+
+    /// The time in seconds an executed statement waits for a lock before it is cancelled.
+    /// Set it to 0 to wait indefinitely.
+    #[partial(bpaf(long("execution_lock_timeout_secs"), fallback(Some(5)), debug_fallback))]
+    pub execution_lock_timeout_secs: u16,
+
+    /// An SSH tunnel to connect through, e.g. if the database is only reachable via a bastion host.
+    #[partial(bpaf(pure(Default::default()), optional, hide))]
+    pub ssh: SshTunnelConfiguration,
+
+    /// Additional database connections by name. Files use the connection set by a
+    /// `-- pgls-connection: <name>` comment, or the one `files.connections` assigns to them,
+    /// and the connection configured here otherwise.
+    #[partial(bpaf(pure(Default::default()), optional, hide))]
+    pub connections: DatabaseConnections,
+
     /// Actively disable all database-related features.
     #[partial(bpaf(long("disable-db"), switch, fallback(Some(false))))]
     #[partial(cfg_attr(feature = "schema", schemars(skip)))]
@@ -59,8 +110,109 @@ impl Default for DatabaseConfiguration {
             username: "postgres".to_string(),
             password: "postgres".to_string(),
             database: "postgres".to_string(),
+            service: None,
+            password_command: None,
+            ssl_mode: None,
+            ssl_root_cert: None,
+            ssl_cert: None,
+            ssl_key: None,
             allow_statement_executions_against: Default::default(),
             conn_timeout_secs: 10,
+            execution_row_limit: 1000,
+            execution_timeout_secs: 30,
+            execution_lock_timeout_secs: 5,
+            ssh: Default::default(),
+            connections: Default::default(),
+        }
+    }
+}
+
+/// An SSH tunnel to the database. The database host and port are resolved by the SSH server.
+#[derive(Clone, Debug, Default, Deserialize, Eq, Merge, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct SshTunnelConfiguration {
+    /// The host of the SSH server.
+    pub host: Option<String>,
+
+    /// The port of the SSH server. Defaults to 22.
+    pub port: Option<u16>,
+
+    /// The user to log in to the SSH server with.
+    pub user: Option<String>,
+
+    /// The path to the private key to authenticate with. Uses the SSH agent and the keys
+    /// `ssh` finds by default if not set.
+    pub identity_file: Option<String>,
+
+    /// How to verify the host key of the SSH server. Defaults to `strict`.
+    pub known_hosts: Option<KnownHostsPolicy>,
+
+    /// The known hosts file to verify the host key with. Defaults to `~/.ssh/known_hosts`.
+    pub known_hosts_file: Option<String>,
+}
+
+/// How to verify the host key of an SSH server.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Deserializable, Eq, Hash, Merge, PartialEq, Serialize,
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum KnownHostsPolicy {
+    #[default]
+    /// Only connect to servers whose key is in the known hosts file
+    Strict,
+    /// Add the keys of unknown servers to the known hosts file, but reject changed keys
+    AcceptNew,
+    /// Do not verify the host key
+    Off,
+}
+
+/// Named database connections.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct DatabaseConnections(pub BTreeMap<String, DatabaseConnectionConfiguration>);
+
+impl Merge for DatabaseConnections {
+    fn merge_with(&mut self, other: Self) {
+        for (name, connection) in other.0 {
+            self.0.entry(name).or_default().merge_with(connection);
         }
     }
 }
+
+/// A named database connection. Fields that are not set are taken from the `db`
+/// configuration, except for `connectionString`.
+#[derive(Clone, Debug, Default, Deserialize, Eq, Merge, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct DatabaseConnectionConfiguration {
+    /// A connection string that encodes the full connection setup.
+    /// When provided, it takes precedence over the individual fields.
+    pub connection_string: Option<String>,
+
+    /// The host of the database.
+    pub host: Option<String>,
+
+    /// The port of the database.
+    pub port: Option<u16>,
+
+    /// The username to connect to the database.
+    pub username: Option<String>,
+
+    /// The password to connect to the database.
+    pub password: Option<String>,
+
+    /// The name of the database.
+    pub database: Option<String>,
+
+    /// The name of a service in the connection service file (`pg_service.conf`).
+    pub service: Option<String>,
+
+    /// A command whose output is used as the password.
+    pub password_command: Option<String>,
+
+    /// An SSH tunnel to connect through. Defaults to the tunnel of the `db` configuration.
+    pub ssh: Option<SshTunnelConfiguration>,
+}
@@ -1,6 +1,6 @@
 use std::num::NonZeroU64;
 
-use biome_deserialize::StringSet;
+use biome_deserialize::{Merge, StringSet};
 use biome_deserialize_macros::{Merge, Partial};
 use bpaf::Bpaf;
 use serde::{Deserialize, Serialize};
@@ -30,6 +30,11 @@ pub struct FilesConfiguration {
     /// match these patterns.
     #[partial(bpaf(hide))]
     pub include: StringSet,
+
+    /// Assigns named database connections (see `db.connections`) to files. The first entry
+    /// whose patterns match a file decides its connection.
+    #[partial(bpaf(pure(Default::default()), optional, hide))]
+    pub connections: FileConnections,
 }
 
 impl Default for FilesConfiguration {
@@ -38,6 +43,32 @@ impl Default for FilesConfiguration {
             max_size: DEFAULT_FILE_SIZE_LIMIT,
             ignore: Default::default(),
             include: Default::default(),
+            connections: Default::default(),
         }
     }
 }
+
+/// Assignments of database connections to files, in order of precedence.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct FileConnections(pub Vec<FileConnection>);
+
+impl Merge for FileConnections {
+    /// The entries of `other` take precedence over the existing ones.
+    fn merge_with(&mut self, mut other: Self) {
+        other.0.append(&mut self.0);
+        *self = other;
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FileConnection {
+    /// A list of Unix shell style patterns. Files matching any of them use the connection.
+    pub include: StringSet,
+
+    /// The name of the connection in `db.connections`.
+    pub connection: String,
+}
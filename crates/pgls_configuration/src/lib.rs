@@ -2,6 +2,7 @@
 //!
 //! The configuration is divided by "tool".
 
+pub mod completions;
 pub mod database;
 pub mod diagnostics;
 pub mod files;
@@ -20,6 +21,9 @@ use crate::vcs::{PartialVcsConfiguration, VcsConfiguration, partial_vcs_configur
 use biome_deserialize::StringSet;
 use biome_deserialize_macros::{Merge, Partial};
 use bpaf::Bpaf;
+use completions::{
+    CompletionsConfiguration, PartialCompletionsConfiguration, partial_completions_configuration,
+};
 use database::{
     DatabaseConfiguration, PartialDatabaseConfiguration, partial_database_configuration,
 };
@@ -93,6 +97,10 @@ pub struct Configuration {
     #[partial(type, bpaf(external(partial_pl_pg_sql_check_configuration), optional))]
     pub plpgsql_check: PlPgSqlCheckConfiguration,
 
+    /// The configuration for completions
+    #[partial(type, bpaf(external(partial_completions_configuration), optional))]
+    pub completions: CompletionsConfiguration,
+
     /// The configuration of the database connection
     #[partial(
         type,
@@ -132,6 +140,9 @@ impl PartialConfiguration {
             plpgsql_check: Some(PartialPlPgSqlCheckConfiguration {
                 ..Default::default()
             }),
+            completions: Some(PartialCompletionsConfiguration {
+                ..Default::default()
+            }),
             db: Some(PartialDatabaseConfiguration {
                 connection_string: None,
                 host: Some("127.0.0.1".to_string()),
@@ -139,8 +150,19 @@ impl PartialConfiguration {
                 username: Some("postgres".to_string()),
                 password: Some("postgres".to_string()),
                 database: Some("postgres".to_string()),
+                service: None,
+                password_command: None,
+                ssl_mode: None,
+                ssl_root_cert: None,
+                ssl_cert: None,
+                ssl_key: None,
                 allow_statement_executions_against: Default::default(),
                 conn_timeout_secs: Some(10),
+                execution_row_limit: Some(1000),
+                execution_timeout_secs: Some(30),
+                execution_lock_timeout_secs: Some(5),
+                ssh: None,
+                connections: None,
                 disable_connection: Some(false),
             }),
         }
@@ -25,11 +25,13 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum RuleGroup {
+    Nullability,
     Safety,
 }
 impl RuleGroup {
     pub const fn as_str(self) -> &'static str {
         match self {
+            Self::Nullability => Nullability::GROUP_NAME,
             Self::Safety => Safety::GROUP_NAME,
         }
     }
@@ -38,6 +40,7 @@ impl std::str::FromStr for RuleGroup {
     type Err = &'static str;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
+            Nullability::GROUP_NAME => Ok(Self::Nullability),
             Safety::GROUP_NAME => Ok(Self::Safety),
             _ => Err("This rule group doesn't exist."),
         }
@@ -54,6 +57,8 @@ pub struct Rules {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub all: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullability: Option<Nullability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub safety: Option<Safety>,
 }
 impl Rules {
@@ -61,6 +66,7 @@ impl Rules {
     #[doc = r" Usually the code is built like {group}/{rule_name}"]
     pub fn has_rule(group: RuleGroup, rule_name: &str) -> Option<&'static str> {
         match group {
+            RuleGroup::Nullability => Nullability::has_rule(rule_name),
             RuleGroup::Safety => Safety::has_rule(rule_name),
         }
     }
@@ -77,6 +83,15 @@ impl Rules {
         let rule_name = split_code.next()?;
         let rule_name = Self::has_rule(group, rule_name)?;
         let severity = match group {
+            RuleGroup::Nullability => self
+                .nullability
+                .as_ref()
+                .and_then(|group| group.get_rule_configuration(rule_name))
+                .filter(|(level, _)| !matches!(level, RulePlainConfiguration::Off))
+                .map_or_else(
+                    || Nullability::severity(rule_name),
+                    |(level, _)| level.into(),
+                ),
             RuleGroup::Safety => self
                 .safety
                 .as_ref()
@@ -91,6 +106,9 @@ impl Rules {
         if self.all != Some(true) && self.recommended == Some(false) {
             self.recommended = Some(true)
         }
+        if let Some(group) = &mut self.nullability {
+            group.recommended = None;
+        }
         if let Some(group) = &mut self.safety {
             group.recommended = None;
         }
@@ -107,6 +125,19 @@ impl Rules {
     pub fn as_enabled_rules(&self) -> FxHashSet<RuleFilter<'static>> {
         let mut enabled_rules = FxHashSet::default();
         let mut disabled_rules = FxHashSet::default();
+        if let Some(group) = self.nullability.as_ref() {
+            group.collect_preset_rules(
+                self.is_all_true(),
+                !self.is_recommended_false(),
+                &mut enabled_rules,
+            );
+            enabled_rules.extend(&group.get_enabled_rules());
+            disabled_rules.extend(&group.get_disabled_rules());
+        } else if self.is_all_true() {
+            enabled_rules.extend(Nullability::all_rules_as_filters());
+        } else if !self.is_recommended_false() {
+            enabled_rules.extend(Nullability::recommended_rules_as_filters());
+        }
         if let Some(group) = self.safety.as_ref() {
             group.collect_preset_rules(
                 self.is_all_true(),
@@ -125,6 +156,9 @@ impl Rules {
     #[doc = r" It returns the disabled rules by configuration."]
     pub fn as_disabled_rules(&self) -> FxHashSet<RuleFilter<'static>> {
         let mut disabled_rules = FxHashSet::default();
+        if let Some(group) = self.nullability.as_ref() {
+            disabled_rules.extend(&group.get_disabled_rules());
+        }
         if let Some(group) = self.safety.as_ref() {
             disabled_rules.extend(&group.get_disabled_rules());
         }
@@ -135,6 +169,150 @@ impl Rules {
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "camelCase", default, deny_unknown_fields)]
 #[doc = r" A list of rules that belong to this group"]
+pub struct Nullability {
+    #[doc = r" It enables the recommended rules for this group"]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recommended: Option<bool>,
+    #[doc = r" It enables ALL rules for this group."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all: Option<bool>,
+    #[doc = "NOT IN with a subquery that may return NULL never matches a row."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ban_not_in_nullable_subquery:
+        Option<RuleConfiguration<pgls_analyser::options::BanNotInNullableSubquery>>,
+    #[doc = "Comparing a value with NULL using = or \\<> is never true."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ban_null_comparison: Option<RuleConfiguration<pgls_analyser::options::BanNullComparison>>,
+    #[doc = "Aggregates over the nullable side of an outer join should be wrapped in COALESCE."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_coalesce_on_outer_join_aggregate:
+        Option<RuleConfiguration<pgls_analyser::options::RequireCoalesceOnOuterJoinAggregate>>,
+}
+impl Nullability {
+    const GROUP_NAME: &'static str = "nullability";
+    pub(crate) const GROUP_RULES: &'static [&'static str] = &[
+        "banNotInNullableSubquery",
+        "banNullComparison",
+        "requireCoalesceOnOuterJoinAggregate",
+    ];
+    const RECOMMENDED_RULES_AS_FILTERS: &'static [RuleFilter<'static>] = &[
+        RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[0]),
+        RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[1]),
+    ];
+    const ALL_RULES_AS_FILTERS: &'static [RuleFilter<'static>] = &[
+        RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[0]),
+        RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[1]),
+        RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[2]),
+    ];
+    #[doc = r" Retrieves the recommended rules"]
+    pub(crate) fn is_recommended_true(&self) -> bool {
+        matches!(self.recommended, Some(true))
+    }
+    pub(crate) fn is_recommended_unset(&self) -> bool {
+        self.recommended.is_none()
+    }
+    pub(crate) fn is_all_true(&self) -> bool {
+        matches!(self.all, Some(true))
+    }
+    pub(crate) fn is_all_unset(&self) -> bool {
+        self.all.is_none()
+    }
+    pub(crate) fn get_enabled_rules(&self) -> FxHashSet<RuleFilter<'static>> {
+        let mut index_set = FxHashSet::default();
+        if let Some(rule) = self.ban_not_in_nullable_subquery.as_ref() {
+            if rule.is_enabled() {
+                index_set.insert(RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[0]));
+            }
+        }
+        if let Some(rule) = self.ban_null_comparison.as_ref() {
+            if rule.is_enabled() {
+                index_set.insert(RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[1]));
+            }
+        }
+        if let Some(rule) = self.require_coalesce_on_outer_join_aggregate.as_ref() {
+            if rule.is_enabled() {
+                index_set.insert(RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[2]));
+            }
+        }
+        index_set
+    }
+    pub(crate) fn get_disabled_rules(&self) -> FxHashSet<RuleFilter<'static>> {
+        let mut index_set = FxHashSet::default();
+        if let Some(rule) = self.ban_not_in_nullable_subquery.as_ref() {
+            if rule.is_disabled() {
+                index_set.insert(RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[0]));
+            }
+        }
+        if let Some(rule) = self.ban_null_comparison.as_ref() {
+            if rule.is_disabled() {
+                index_set.insert(RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[1]));
+            }
+        }
+        if let Some(rule) = self.require_coalesce_on_outer_join_aggregate.as_ref() {
+            if rule.is_disabled() {
+                index_set.insert(RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[2]));
+            }
+        }
+        index_set
+    }
+    #[doc = r" Checks if, given a rule name, matches one of the rules contained in this category"]
+    pub(crate) fn has_rule(rule_name: &str) -> Option<&'static str> {
+        Some(Self::GROUP_RULES[Self::GROUP_RULES.binary_search(&rule_name).ok()?])
+    }
+    pub(crate) fn recommended_rules_as_filters() -> &'static [RuleFilter<'static>] {
+        Self::RECOMMENDED_RULES_AS_FILTERS
+    }
+    pub(crate) fn all_rules_as_filters() -> &'static [RuleFilter<'static>] {
+        Self::ALL_RULES_AS_FILTERS
+    }
+    #[doc = r" Select preset rules"]
+    pub(crate) fn collect_preset_rules(
+        &self,
+        parent_is_all: bool,
+        parent_is_recommended: bool,
+        enabled_rules: &mut FxHashSet<RuleFilter<'static>>,
+    ) {
+        if self.is_all_true() || self.is_all_unset() && parent_is_all {
+            enabled_rules.extend(Self::all_rules_as_filters());
+        } else if self.is_recommended_true()
+            || self.is_recommended_unset() && self.is_all_unset() && parent_is_recommended
+        {
+            enabled_rules.extend(Self::recommended_rules_as_filters());
+        }
+    }
+    pub(crate) fn severity(rule_name: &str) -> Severity {
+        match rule_name {
+            "banNotInNullableSubquery" => Severity::Warning,
+            "banNullComparison" => Severity::Warning,
+            "requireCoalesceOnOuterJoinAggregate" => Severity::Warning,
+            _ => unreachable!(),
+        }
+    }
+    pub(crate) fn get_rule_configuration(
+        &self,
+        rule_name: &str,
+    ) -> Option<(RulePlainConfiguration, Option<RuleOptions>)> {
+        match rule_name {
+            "banNotInNullableSubquery" => self
+                .ban_not_in_nullable_subquery
+                .as_ref()
+                .map(|conf| (conf.level(), conf.get_options())),
+            "banNullComparison" => self
+                .ban_null_comparison
+                .as_ref()
+                .map(|conf| (conf.level(), conf.get_options())),
+            "requireCoalesceOnOuterJoinAggregate" => self
+                .require_coalesce_on_outer_join_aggregate
+                .as_ref()
+                .map(|conf| (conf.level(), conf.get_options())),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Debug, Default, Deserialize, Eq, Merge, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+#[doc = r" A list of rules that belong to this group"]
 pub struct Safety {
     #[doc = r" It enables the recommended rules for this group"]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -909,6 +1087,15 @@ pub fn push_to_analyser_rules(
     metadata: &pgls_analyse::MetadataRegistry,
     analyser_rules: &mut pgls_analyse::AnalyserRules,
 ) {
+    if let Some(rules) = rules.nullability.as_ref() {
+        for rule_name in Nullability::GROUP_RULES {
+            if let Some((_, Some(rule_options))) = rules.get_rule_configuration(rule_name) {
+                if let Some(rule_key) = metadata.find_rule("nullability", rule_name) {
+                    analyser_rules.push_rule(rule_key, rule_options);
+                }
+            }
+        }
+    }
     if let Some(rules) = rules.safety.as_ref() {
         for rule_name in Safety::GROUP_RULES {
             if let Some((_, Some(rule_options))) = rules.get_rule_configuration(rule_name) {
@@ -921,6 +1108,9 @@ pub fn push_to_analyser_rules(
 }
 #[test]
 fn test_order() {
+    for items in Nullability::GROUP_RULES.windows(2) {
+        assert!(items[0] < items[1], "{} < {}", items[0], items[1]);
+    }
     for items in Safety::GROUP_RULES.windows(2) {
         assert!(items[0] < items[1], "{} < {}", items[0], items[1]);
     }
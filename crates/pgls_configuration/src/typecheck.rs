@@ -17,6 +17,11 @@ pub struct TypecheckConfiguration {
     /// If not specified, defaults to ["public"].
     #[partial(bpaf(long("search_path")))]
     pub search_path: StringSet,
+    /// if `true`, the DDL statements that precede a statement in the same file are executed
+    /// before it is typechecked, inside a transaction that is always rolled back. Requires
+    /// statement executions to be allowed against the database. `false` by default
+    #[partial(bpaf(hide))]
+    pub replay_prior_ddl: bool,
 }
 
 impl Default for TypecheckConfiguration {
@@ -24,6 +29,7 @@ impl Default for TypecheckConfiguration {
         Self {
             enabled: true,
             search_path: ["public".to_string()].into_iter().collect(),
+            replay_prior_ddl: false,
         }
     }
 }
@@ -13,6 +13,9 @@
 // must be between `define_categories! {\n` and `\n    ;\n`.
 
 define_categories! {
+    "lint/nullability/banNotInNullableSubquery": "https://pg-language-server.com/latest/reference/rules/ban-not-in-nullable-subquery/",
+    "lint/nullability/banNullComparison": "https://pg-language-server.com/latest/reference/rules/ban-null-comparison/",
+    "lint/nullability/requireCoalesceOnOuterJoinAggregate": "https://pg-language-server.com/latest/reference/rules/require-coalesce-on-outer-join-aggregate/",
     "lint/safety/addSerialColumn": "https://pg-language-server.com/latest/reference/rules/add-serial-column/",
     "lint/safety/addingFieldWithDefault": "https://pg-language-server.com/latest/reference/rules/adding-field-with-default/",
     "lint/safety/addingForeignKeyConstraint": "https://pg-language-server.com/latest/reference/rules/adding-foreign-key-constraint/",
@@ -75,6 +78,7 @@ define_categories! {
     // General categories
     "stdin",
     "check",
+    "baseline",
     "configuration",
     "database/connection",
     "internalError/io",
@@ -83,6 +87,9 @@ define_categories! {
     "flags/invalid",
     "project",
     "typecheck",
+    "explain/missingIndex",
+    "explain/nestedLoop",
+    "explain/seqScan",
     "plpgsql_check",
     "internalError/panic",
     "syntax",
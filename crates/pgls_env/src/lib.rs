@@ -34,6 +34,7 @@ pub struct PgLSEnv {
     pub pgls_log_level: PgLSEnvVariable,
     pub pgls_log_prefix: PgLSEnvVariable,
     pub pgls_config_path: PgLSEnvVariable,
+    pub pgls_allow_password_command: PgLSEnvVariable,
 
     // DEPRECATED - kept for backward compatibility
     pub pgt_log_path: PgLSEnvVariable,
@@ -63,6 +64,10 @@ impl PgLSEnv {
                 "PGLS_CONFIG_PATH",
                 "A path to the configuration file",
             ),
+            pgls_allow_password_command: PgLSEnvVariable::new(
+                "PGLS_ALLOW_PASSWORD_COMMAND",
+                "Set to `true` to run the `db.passwordCommand` of the configuration. Password commands are ignored by default, so that opening a project doesn't run commands from its configuration.",
+            ),
 
             pgt_log_path: PgLSEnvVariable::new(
                 "PGT_LOG_PATH",
@@ -155,6 +160,22 @@ impl Display for PgLSEnv {
                     .fmt(fmt)?;
             }
         };
+        match self.pgls_allow_password_command.value() {
+            None => {
+                KeyValuePair(
+                    self.pgls_allow_password_command.name,
+                    markup! { <Dim>"unset"</Dim> },
+                )
+                .fmt(fmt)?;
+            }
+            Some(value) => {
+                KeyValuePair(
+                    self.pgls_allow_password_command.name,
+                    markup! {{DebugDisplay(value)}},
+                )
+                .fmt(fmt)?;
+            }
+        };
 
         match self.pgt_log_path.value() {
             None => {
@@ -4,6 +4,29 @@ pub(crate) trait ContextualPriority {
     fn relevance_score(&self, ctx: &TreesitterContext) -> f32;
 }
 
+/// Scores objects whose names are only unique per table, like policies or triggers: the ones
+/// on tables mentioned in the statement are preferred.
+pub(crate) fn table_object_relevance_score(
+    ctx: &TreesitterContext,
+    schema: &str,
+    table: &str,
+) -> f32 {
+    let is_mentioned = |schema: Option<String>| {
+        ctx.get_mentioned_relations(&schema)
+            .is_some_and(|t| t.contains(table))
+    };
+
+    if is_mentioned(Some(schema.to_string())) {
+        200.0
+    } else if is_mentioned(None) {
+        150.0
+    } else if schema == "public" {
+        10.0
+    } else {
+        0.0
+    }
+}
+
 /// Will first sort the items by a score and then filter out items with a score gap algorithm.  
 ///
 /// `[200, 180, 150, 140]` => all items are returned
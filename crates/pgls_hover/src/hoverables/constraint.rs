@@ -0,0 +1,60 @@
+use std::fmt::Write;
+
+use pgls_schema_cache::{Constraint, SchemaCache};
+use pgls_treesitter::TreesitterContext;
+
+use crate::{
+    contextual_priority::{ContextualPriority, table_object_relevance_score},
+    to_markdown::ToHoverMarkdown,
+};
+
+impl ToHoverMarkdown for Constraint {
+    fn hover_headline<W: Write>(
+        &self,
+        writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<(), std::fmt::Error> {
+        write!(
+            writer,
+            "`{}` ({}) on `{}.{}`",
+            self.name, self.kind, self.schema, self.table_name
+        )?;
+
+        Ok(())
+    }
+
+    fn hover_body<W: Write>(
+        &self,
+        writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<bool, std::fmt::Error> {
+        write!(writer, "{}", self.definition)?;
+        writeln!(writer)?;
+
+        Ok(true)
+    }
+
+    fn hover_footer<W: Write>(
+        &self,
+        writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<bool, std::fmt::Error> {
+        if let Some(comment) = self.comment.as_ref() {
+            write!(writer, "Comment: '{comment}'")?;
+            writeln!(writer)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn body_markdown_type(&self) -> &'static str {
+        "sql"
+    }
+}
+
+impl ContextualPriority for Constraint {
+    fn relevance_score(&self, ctx: &TreesitterContext) -> f32 {
+        table_object_relevance_score(ctx, &self.schema, &self.table_name)
+    }
+}
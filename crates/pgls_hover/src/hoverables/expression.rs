@@ -0,0 +1,100 @@
+use std::fmt::Write;
+
+use pgls_schema_cache::{Behavior, SchemaCache};
+use pgls_treesitter::TreesitterContext;
+use pgls_type_resolver::{ExpressionKind, ResolvedExpression};
+
+use crate::{contextual_priority::ContextualPriority, to_markdown::ToHoverMarkdown};
+
+impl ToHoverMarkdown for ResolvedExpression<'_> {
+    fn hover_headline<W: Write>(
+        &self,
+        writer: &mut W,
+        schema_cache: &SchemaCache,
+    ) -> Result<(), std::fmt::Error> {
+        let type_name = |id: Option<i64>| {
+            id.and_then(|id| schema_cache.find_type_by_id(id))
+                .map(|t| t.format.as_str())
+                .unwrap_or("unknown")
+        };
+
+        if let Some(operator) = self.operator {
+            write!(writer, "`{}` (", operator.name)?;
+
+            if operator.left_type_id.is_some() {
+                write!(writer, "{}, ", type_name(operator.left_type_id))?;
+            }
+
+            write!(
+                writer,
+                "{}) → `{}`",
+                type_name(operator.right_type_id),
+                type_name(Some(operator.result_type_id))
+            )?;
+
+            return Ok(());
+        }
+
+        write!(writer, "`{}`", type_name(self.type_.map(|t| t.id)))?;
+
+        match self.kind {
+            ExpressionKind::Literal => write!(writer, " - Literal")?,
+            ExpressionKind::Parameter(number) => write!(writer, " - Parameter ${number}")?,
+            ExpressionKind::TypeCast => write!(writer, " - Type Cast")?,
+            ExpressionKind::Operator => write!(writer, " - Expression")?,
+        }
+
+        Ok(())
+    }
+
+    fn hover_body<W: Write>(
+        &self,
+        writer: &mut W,
+        schema_cache: &SchemaCache,
+    ) -> Result<bool, std::fmt::Error> {
+        if let Some(operator) = self.operator {
+            if let Some(comment) = operator.comment.as_ref() {
+                write!(writer, "Comment: '{comment}'")?;
+                writeln!(writer)?;
+                writeln!(writer)?;
+            }
+
+            let volatility = match operator.behavior {
+                Behavior::Immutable => "immutable",
+                Behavior::Stable => "stable",
+                Behavior::Volatile => "volatile",
+            };
+
+            write!(
+                writer,
+                "Function: {}.{}",
+                operator.function_schema, operator.function_name
+            )?;
+            writeln!(writer)?;
+            write!(writer, "Volatility: {volatility}")?;
+            writeln!(writer)?;
+
+            return Ok(true);
+        }
+
+        match self.type_ {
+            Some(type_) => ToHoverMarkdown::hover_body(type_, writer, schema_cache),
+            None => Ok(false),
+        }
+    }
+
+    fn hover_footer<W: Write>(
+        &self,
+        _writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<bool, std::fmt::Error> {
+        Ok(false)
+    }
+}
+
+impl ContextualPriority for ResolvedExpression<'_> {
+    // there is only ever one expression at a position.
+    fn relevance_score(&self, _ctx: &TreesitterContext) -> f32 {
+        0.0
+    }
+}
@@ -0,0 +1,60 @@
+use std::fmt::Write;
+
+use pgls_schema_cache::{Extension, SchemaCache};
+use pgls_treesitter::TreesitterContext;
+
+use crate::{contextual_priority::ContextualPriority, to_markdown::ToHoverMarkdown};
+
+impl ToHoverMarkdown for Extension {
+    fn hover_headline<W: Write>(
+        &self,
+        writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<(), std::fmt::Error> {
+        write!(writer, "`{}`", self.name)?;
+
+        if let Some(schema) = self.schema.as_ref() {
+            write!(writer, " - installed in `{schema}`")?;
+        }
+
+        Ok(())
+    }
+
+    fn hover_body<W: Write>(
+        &self,
+        writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<bool, std::fmt::Error> {
+        if let Some(comment) = self.comment.as_ref() {
+            write!(writer, "Comment: '{comment}'")?;
+            writeln!(writer)?;
+            writeln!(writer)?;
+        }
+
+        match self.installed_version.as_ref() {
+            Some(version) => write!(writer, "Installed version: {version}")?,
+            None => write!(writer, "Not installed.")?,
+        }
+        writeln!(writer)?;
+
+        write!(writer, "Available version: {}", self.default_version)?;
+        writeln!(writer)?;
+
+        Ok(true)
+    }
+
+    fn hover_footer<W: Write>(
+        &self,
+        _writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<bool, std::fmt::Error> {
+        Ok(false)
+    }
+}
+
+impl ContextualPriority for Extension {
+    // there are no extensions with duplicate names.
+    fn relevance_score(&self, _ctx: &TreesitterContext) -> f32 {
+        0.0
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt::Write;
+
+use humansize::DECIMAL;
+use pgls_schema_cache::{Index, SchemaCache};
+use pgls_treesitter::TreesitterContext;
+
+use crate::{contextual_priority::ContextualPriority, to_markdown::ToHoverMarkdown};
+
+impl ToHoverMarkdown for Index {
+    fn hover_headline<W: Write>(
+        &self,
+        writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<(), std::fmt::Error> {
+        write!(writer, "`{}.{}`", self.schema, self.name)?;
+
+        if self.is_primary {
+            write!(writer, " (Primary Key)")?;
+        } else if self.is_unique {
+            write!(writer, " (Unique)")?;
+        }
+
+        write!(writer, " on `{}`", self.table_name)?;
+
+        Ok(())
+    }
+
+    fn hover_body<W: Write>(
+        &self,
+        writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<bool, std::fmt::Error> {
+        write!(writer, "{}", self.definition)?;
+        writeln!(writer)?;
+
+        Ok(true)
+    }
+
+    fn hover_footer<W: Write>(
+        &self,
+        writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<bool, std::fmt::Error> {
+        write!(
+            writer,
+            "{}",
+            humansize::format_size(self.bytes as u64, DECIMAL)
+        )?;
+        Ok(true)
+    }
+
+    fn body_markdown_type(&self) -> &'static str {
+        "sql"
+    }
+}
+
+impl ContextualPriority for Index {
+    // index names are unique per schema.
+    fn relevance_score(&self, ctx: &TreesitterContext) -> f32 {
+        let mut score = 0.0;
+
+        if ctx
+            .get_mentioned_relations(&Some(self.schema.clone()))
+            .is_some_and(|t| t.contains(&self.table_name))
+        {
+            score += 200.0;
+        }
+
+        if self.schema == "public" && score == 0.0 {
+            score += 10.0;
+        }
+
+        score
+    }
+}
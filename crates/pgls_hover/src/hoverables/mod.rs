@@ -3,11 +3,17 @@ use pgls_schema_cache::SchemaCache;
 use crate::{contextual_priority::ContextualPriority, to_markdown::ToHoverMarkdown};
 
 mod column;
+mod constraint;
+mod expression;
+mod extension;
 mod function;
+mod index;
+mod policy;
 mod postgres_type;
 mod role;
 mod schema;
 mod table;
+mod trigger;
 
 mod test_helper;
 
@@ -20,6 +26,12 @@ pub enum Hoverable<'a> {
     Role(&'a pgls_schema_cache::Role),
     Schema(&'a pgls_schema_cache::Schema),
     PostgresType(&'a pgls_schema_cache::PostgresType),
+    Policy(&'a pgls_schema_cache::Policy),
+    Trigger(&'a pgls_schema_cache::Trigger),
+    Extension(&'a pgls_schema_cache::Extension),
+    Index(&'a pgls_schema_cache::Index),
+    Constraint(&'a pgls_schema_cache::Constraint),
+    Expression(pgls_type_resolver::ResolvedExpression<'a>),
 }
 
 impl<'a> From<&'a pgls_schema_cache::Schema> for Hoverable<'a> {
@@ -58,6 +70,42 @@ impl<'a> From<&'a pgls_schema_cache::PostgresType> for Hoverable<'a> {
     }
 }
 
+impl<'a> From<&'a pgls_schema_cache::Policy> for Hoverable<'a> {
+    fn from(value: &'a pgls_schema_cache::Policy) -> Self {
+        Hoverable::Policy(value)
+    }
+}
+
+impl<'a> From<&'a pgls_schema_cache::Trigger> for Hoverable<'a> {
+    fn from(value: &'a pgls_schema_cache::Trigger) -> Self {
+        Hoverable::Trigger(value)
+    }
+}
+
+impl<'a> From<&'a pgls_schema_cache::Extension> for Hoverable<'a> {
+    fn from(value: &'a pgls_schema_cache::Extension) -> Self {
+        Hoverable::Extension(value)
+    }
+}
+
+impl<'a> From<&'a pgls_schema_cache::Index> for Hoverable<'a> {
+    fn from(value: &'a pgls_schema_cache::Index) -> Self {
+        Hoverable::Index(value)
+    }
+}
+
+impl<'a> From<&'a pgls_schema_cache::Constraint> for Hoverable<'a> {
+    fn from(value: &'a pgls_schema_cache::Constraint) -> Self {
+        Hoverable::Constraint(value)
+    }
+}
+
+impl<'a> From<pgls_type_resolver::ResolvedExpression<'a>> for Hoverable<'a> {
+    fn from(value: pgls_type_resolver::ResolvedExpression<'a>) -> Self {
+        Hoverable::Expression(value)
+    }
+}
+
 impl ContextualPriority for Hoverable<'_> {
     fn relevance_score(&self, ctx: &pgls_treesitter::TreesitterContext) -> f32 {
         match self {
@@ -67,6 +115,12 @@ impl ContextualPriority for Hoverable<'_> {
             Hoverable::Role(role) => role.relevance_score(ctx),
             Hoverable::Schema(schema) => schema.relevance_score(ctx),
             Hoverable::PostgresType(type_) => type_.relevance_score(ctx),
+            Hoverable::Policy(policy) => policy.relevance_score(ctx),
+            Hoverable::Trigger(trigger) => trigger.relevance_score(ctx),
+            Hoverable::Extension(extension) => extension.relevance_score(ctx),
+            Hoverable::Index(index) => index.relevance_score(ctx),
+            Hoverable::Constraint(constraint) => constraint.relevance_score(ctx),
+            Hoverable::Expression(expression) => expression.relevance_score(ctx),
         }
     }
 }
@@ -94,6 +148,24 @@ impl ToHoverMarkdown for Hoverable<'_> {
             Hoverable::PostgresType(type_) => {
                 ToHoverMarkdown::hover_headline(*type_, writer, schema_cache)
             }
+            Hoverable::Policy(policy) => {
+                ToHoverMarkdown::hover_headline(*policy, writer, schema_cache)
+            }
+            Hoverable::Trigger(trigger) => {
+                ToHoverMarkdown::hover_headline(*trigger, writer, schema_cache)
+            }
+            Hoverable::Extension(extension) => {
+                ToHoverMarkdown::hover_headline(*extension, writer, schema_cache)
+            }
+            Hoverable::Index(index) => {
+                ToHoverMarkdown::hover_headline(*index, writer, schema_cache)
+            }
+            Hoverable::Constraint(constraint) => {
+                ToHoverMarkdown::hover_headline(*constraint, writer, schema_cache)
+            }
+            Hoverable::Expression(expression) => {
+                ToHoverMarkdown::hover_headline(expression, writer, schema_cache)
+            }
         }
     }
 
@@ -113,6 +185,20 @@ impl ToHoverMarkdown for Hoverable<'_> {
             Hoverable::PostgresType(type_) => {
                 ToHoverMarkdown::hover_body(*type_, writer, schema_cache)
             }
+            Hoverable::Policy(policy) => ToHoverMarkdown::hover_body(*policy, writer, schema_cache),
+            Hoverable::Trigger(trigger) => {
+                ToHoverMarkdown::hover_body(*trigger, writer, schema_cache)
+            }
+            Hoverable::Extension(extension) => {
+                ToHoverMarkdown::hover_body(*extension, writer, schema_cache)
+            }
+            Hoverable::Index(index) => ToHoverMarkdown::hover_body(*index, writer, schema_cache),
+            Hoverable::Constraint(constraint) => {
+                ToHoverMarkdown::hover_body(*constraint, writer, schema_cache)
+            }
+            Hoverable::Expression(expression) => {
+                ToHoverMarkdown::hover_body(expression, writer, schema_cache)
+            }
         }
     }
 
@@ -136,6 +222,22 @@ impl ToHoverMarkdown for Hoverable<'_> {
             Hoverable::PostgresType(type_) => {
                 ToHoverMarkdown::hover_footer(*type_, writer, schema_cache)
             }
+            Hoverable::Policy(policy) => {
+                ToHoverMarkdown::hover_footer(*policy, writer, schema_cache)
+            }
+            Hoverable::Trigger(trigger) => {
+                ToHoverMarkdown::hover_footer(*trigger, writer, schema_cache)
+            }
+            Hoverable::Extension(extension) => {
+                ToHoverMarkdown::hover_footer(*extension, writer, schema_cache)
+            }
+            Hoverable::Index(index) => ToHoverMarkdown::hover_footer(*index, writer, schema_cache),
+            Hoverable::Constraint(constraint) => {
+                ToHoverMarkdown::hover_footer(*constraint, writer, schema_cache)
+            }
+            Hoverable::Expression(expression) => {
+                ToHoverMarkdown::hover_footer(expression, writer, schema_cache)
+            }
         }
     }
 
@@ -147,6 +249,12 @@ impl ToHoverMarkdown for Hoverable<'_> {
             Hoverable::Role(role) => role.body_markdown_type(),
             Hoverable::Schema(schema) => schema.body_markdown_type(),
             Hoverable::PostgresType(type_) => type_.body_markdown_type(),
+            Hoverable::Policy(policy) => policy.body_markdown_type(),
+            Hoverable::Trigger(trigger) => trigger.body_markdown_type(),
+            Hoverable::Extension(extension) => extension.body_markdown_type(),
+            Hoverable::Index(index) => index.body_markdown_type(),
+            Hoverable::Constraint(constraint) => constraint.body_markdown_type(),
+            Hoverable::Expression(expression) => expression.body_markdown_type(),
         }
     }
 
@@ -158,6 +266,12 @@ impl ToHoverMarkdown for Hoverable<'_> {
             Hoverable::Role(role) => role.footer_markdown_type(),
             Hoverable::Schema(schema) => schema.footer_markdown_type(),
             Hoverable::PostgresType(type_) => type_.footer_markdown_type(),
+            Hoverable::Policy(policy) => policy.footer_markdown_type(),
+            Hoverable::Trigger(trigger) => trigger.footer_markdown_type(),
+            Hoverable::Extension(extension) => extension.footer_markdown_type(),
+            Hoverable::Index(index) => index.footer_markdown_type(),
+            Hoverable::Constraint(constraint) => constraint.footer_markdown_type(),
+            Hoverable::Expression(expression) => expression.footer_markdown_type(),
         }
     }
 }
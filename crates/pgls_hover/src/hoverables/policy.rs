@@ -0,0 +1,87 @@
+use std::fmt::Write;
+
+use pgls_schema_cache::{Policy, PolicyCommand, SchemaCache};
+use pgls_treesitter::TreesitterContext;
+
+use crate::{
+    contextual_priority::{ContextualPriority, table_object_relevance_score},
+    to_markdown::ToHoverMarkdown,
+};
+
+impl ToHoverMarkdown for Policy {
+    fn hover_headline<W: Write>(
+        &self,
+        writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<(), std::fmt::Error> {
+        write!(
+            writer,
+            "`{}` on `{}.{}`",
+            self.name, self.schema_name, self.table_name
+        )?;
+
+        Ok(())
+    }
+
+    fn hover_body<W: Write>(
+        &self,
+        writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<bool, std::fmt::Error> {
+        let command = match self.command {
+            PolicyCommand::Select => "SELECT",
+            PolicyCommand::Insert => "INSERT",
+            PolicyCommand::Update => "UPDATE",
+            PolicyCommand::Delete => "DELETE",
+            PolicyCommand::All => "ALL",
+        };
+
+        let kind = if self.is_permissive {
+            "Permissive"
+        } else {
+            "Restrictive"
+        };
+
+        write!(writer, "{kind} policy for {command}")?;
+        writeln!(writer)?;
+        writeln!(writer)?;
+
+        if !self.role_names.is_empty() {
+            write!(writer, "Applies to:")?;
+            writeln!(writer)?;
+
+            for role in &self.role_names {
+                write!(writer, "- {role}")?;
+                writeln!(writer)?;
+            }
+
+            writeln!(writer)?;
+        }
+
+        if let Some(using) = self.security_qualification.as_ref() {
+            write!(writer, "USING {using}")?;
+            writeln!(writer)?;
+        }
+
+        if let Some(check) = self.with_check.as_ref() {
+            write!(writer, "WITH CHECK {check}")?;
+            writeln!(writer)?;
+        }
+
+        Ok(true)
+    }
+
+    fn hover_footer<W: Write>(
+        &self,
+        _writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<bool, std::fmt::Error> {
+        Ok(false)
+    }
+}
+
+impl ContextualPriority for Policy {
+    fn relevance_score(&self, ctx: &TreesitterContext) -> f32 {
+        table_object_relevance_score(ctx, &self.schema_name, &self.table_name)
+    }
+}
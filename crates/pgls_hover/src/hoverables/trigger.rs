@@ -0,0 +1,85 @@
+use std::fmt::Write;
+
+use pgls_schema_cache::{SchemaCache, Trigger, TriggerAffected, TriggerEvent, TriggerTiming};
+use pgls_treesitter::TreesitterContext;
+
+use crate::{
+    contextual_priority::{ContextualPriority, table_object_relevance_score},
+    to_markdown::ToHoverMarkdown,
+};
+
+impl ToHoverMarkdown for Trigger {
+    fn hover_headline<W: Write>(
+        &self,
+        writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<(), std::fmt::Error> {
+        write!(
+            writer,
+            "`{}` on `{}.{}`",
+            self.name, self.table_schema, self.table_name
+        )?;
+
+        Ok(())
+    }
+
+    fn hover_body<W: Write>(
+        &self,
+        writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<bool, std::fmt::Error> {
+        let timing = match self.timing {
+            TriggerTiming::Before => "BEFORE",
+            TriggerTiming::After => "AFTER",
+            TriggerTiming::Instead => "INSTEAD OF",
+        };
+
+        let events = self
+            .events
+            .iter()
+            .map(|e| match e {
+                TriggerEvent::Insert => "INSERT",
+                TriggerEvent::Delete => "DELETE",
+                TriggerEvent::Update => "UPDATE",
+                TriggerEvent::Truncate => "TRUNCATE",
+            })
+            .collect::<Vec<_>>()
+            .join(" OR ");
+
+        let affected = match self.affected {
+            TriggerAffected::Row => "ROW",
+            TriggerAffected::Statement => "STATEMENT",
+        };
+
+        write!(writer, "{timing} {events}")?;
+        writeln!(writer)?;
+        write!(writer, "FOR EACH {affected}")?;
+        writeln!(writer)?;
+        write!(
+            writer,
+            "EXECUTE FUNCTION {}.{}()",
+            self.proc_schema, self.proc_name
+        )?;
+        writeln!(writer)?;
+
+        Ok(true)
+    }
+
+    fn hover_footer<W: Write>(
+        &self,
+        _writer: &mut W,
+        _schema_cache: &SchemaCache,
+    ) -> Result<bool, std::fmt::Error> {
+        Ok(false)
+    }
+
+    fn body_markdown_type(&self) -> &'static str {
+        "sql"
+    }
+}
+
+impl ContextualPriority for Trigger {
+    fn relevance_score(&self, ctx: &TreesitterContext) -> f32 {
+        table_object_relevance_score(ctx, &self.table_schema, &self.table_name)
+    }
+}
@@ -8,24 +8,39 @@ pub(crate) enum HoveredNode {
     Column((Option<String>, Option<String>, String)),
     Role(String),
     PostgresType((Option<String>, String)),
-
-    #[allow(unused)]
-    Trigger((Option<String>, String)),
-    #[allow(dead_code)]
-    Policy((Option<String>, String)),
+    Trigger(String),
+    Policy(String),
+    Extension(String),
+    Index(String),
+    Constraint(String),
+
+    /// A literal, parameter, cast or operator, identified by its start in the statement.
+    Expression(usize),
 }
 
 impl HoveredNode {
     pub(crate) fn get(ctx: &pgls_treesitter::context::TreesitterContext) -> Option<Self> {
         let node_content = ctx.get_node_under_cursor_content()?;
 
+        let under_cursor = &ctx.node_under_cursor;
+
+        // positional parameters such as `$1` get their type from where they are used
+        if under_cursor.kind() == "parameter" && node_content.starts_with('$') {
+            return Some(HoveredNode::Expression(under_cursor.start_byte()));
+        }
+
         if looks_like_sql_param(node_content.as_str()) {
             return None;
         }
 
-        let under_cursor = &ctx.node_under_cursor;
-
         match under_cursor.kind() {
+            // the index name in `create index` is parsed as a column
+            "column_identifier"
+                if ctx.history_ends_with(&["create_index", "column_identifier"]) =>
+            {
+                Some(HoveredNode::Index(node_content))
+            }
+
             "column_identifier" => Some(HoveredNode::Column((
                 ctx.head_qualifier_sanitized(),
                 ctx.tail_qualifier_sanitized(),
@@ -35,10 +50,7 @@ impl HoveredNode {
                 ctx.tail_qualifier_sanitized(),
                 node_content,
             ))),
-            "policy_identifier" => Some(HoveredNode::Policy((
-                ctx.tail_qualifier_sanitized(),
-                node_content,
-            ))),
+            "policy_identifier" => Some(HoveredNode::Policy(node_content)),
             "table_identifier" => Some(HoveredNode::Table((
                 ctx.tail_qualifier_sanitized(),
                 node_content,
@@ -47,6 +59,42 @@ impl HoveredNode {
             "schema_identifier" => Some(HoveredNode::Schema(node_content)),
             "role_identifier" => Some(HoveredNode::Role(node_content)),
 
+            "any_identifier" if ctx.history_ends_with(&["create_policy", "any_identifier"]) => {
+                Some(HoveredNode::Policy(node_content))
+            }
+
+            "any_identifier"
+                if ctx.history_ends_with(&[
+                    "create_trigger",
+                    "object_reference",
+                    "any_identifier",
+                ]) =>
+            {
+                Some(HoveredNode::Trigger(node_content))
+            }
+
+            "any_identifier"
+                if ctx.history_ends_with(&["create_extension", "any_identifier"])
+                    || ctx.history_ends_with(&["drop_extension", "any_identifier"]) =>
+            {
+                Some(HoveredNode::Extension(node_content))
+            }
+
+            "any_identifier"
+                if ctx.history_ends_with(&["drop_index", "any_identifier"])
+                    || ctx.history_ends_with(&["alter_index", "any_identifier"]) =>
+            {
+                Some(HoveredNode::Index(node_content))
+            }
+
+            "any_identifier"
+                if ctx.history_ends_with(&["constraint", "any_identifier"])
+                    || ctx.history_ends_with(&["add_constraint", "any_identifier"])
+                    || ctx.history_ends_with(&["drop_constraint", "any_identifier"]) =>
+            {
+                Some(HoveredNode::Constraint(node_content))
+            }
+
             "any_identifier" if ctx.history_ends_with(&["table_reference", "any_identifier"]) => {
                 Some(HoveredNode::Table((
                     ctx.tail_qualifier_sanitized(),
@@ -131,11 +179,32 @@ impl HoveredNode {
                 )))
             }
 
+            "literal" | "::" => Some(HoveredNode::Expression(under_cursor.start_byte())),
+
+            _ if ctx.node_under_cursor_is_within_field(&["binary_expr_operator"]) => {
+                Some(HoveredNode::Expression(operator_start(*under_cursor)))
+            }
+
             _ => None,
         }
     }
 }
 
+/// Operators such as `is distinct from` consist of multiple nodes, so we
+/// use the start of the whole operator.
+fn operator_start(node: tree_sitter::Node) -> usize {
+    let mut node = node;
+
+    while let Some(parent) = node.parent() {
+        if parent.kind() == "binary_expression" {
+            break;
+        }
+        node = parent;
+    }
+
+    node.start_byte()
+}
+
 fn looks_like_sql_param(content: &str) -> bool {
     (content.starts_with("$") && !content.starts_with("$$"))
         || (content.starts_with(":") && !content.starts_with("::"))
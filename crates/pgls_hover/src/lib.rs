@@ -1,9 +1,10 @@
 use pgls_schema_cache::SchemaCache;
 use pgls_text_size::TextSize;
 use pgls_treesitter::TreeSitterContextParams;
+use pgls_type_resolver::TypeInferrer;
 
 use crate::{
-    contextual_priority::prioritize_by_context, hoverables::Hoverable, hovered_node::HoveredNode,
+    contextual_priority::prioritize_by_context, hovered_node::HoveredNode,
     to_markdown::format_hover_markdown,
 };
 
@@ -12,6 +13,8 @@ mod hoverables;
 mod hovered_node;
 mod to_markdown;
 
+pub use hoverables::Hoverable;
+
 pub struct OnHoverParams<'a> {
     pub position: TextSize,
     pub schema_cache: &'a SchemaCache,
@@ -20,6 +23,12 @@ pub struct OnHoverParams<'a> {
     pub ts_tree: &'a tree_sitter::Tree,
 }
 
+/// Renders the hover markdown for a single database object, e.g. to lazily
+/// document a completion item.
+pub fn to_markdown(item: &Hoverable, schema_cache: &SchemaCache) -> Option<String> {
+    format_hover_markdown(item, schema_cache).ok()
+}
+
 #[tracing::instrument(level = "debug", skip_all, fields(
     text = params.stmt_sql,
     position = params.position.to_string()
@@ -83,14 +92,12 @@ pub fn on_hover(params: OnHoverParams) -> Vec<String> {
                 _ => vec![],
             },
 
-            HoveredNode::Function(node_identification) => match node_identification {
-                (maybe_schema, function_name) => params
-                    .schema_cache
-                    .find_functions(&function_name, maybe_schema.as_deref())
-                    .into_iter()
-                    .map(Hoverable::from)
-                    .collect(),
-            },
+            HoveredNode::Function((maybe_schema, function_name)) => params
+                .schema_cache
+                .find_functions(&function_name, maybe_schema.as_deref())
+                .into_iter()
+                .map(Hoverable::from)
+                .collect(),
 
             HoveredNode::Role(role_name) => params
                 .schema_cache
@@ -122,7 +129,50 @@ pub fn on_hover(params: OnHoverParams) -> Vec<String> {
                     .unwrap_or_default(),
             },
 
-            _ => todo!(),
+            HoveredNode::Policy(policy_name) => params
+                .schema_cache
+                .find_policies(&policy_name, None)
+                .into_iter()
+                .map(Hoverable::from)
+                .collect(),
+
+            HoveredNode::Trigger(trigger_name) => params
+                .schema_cache
+                .find_triggers(&trigger_name, None)
+                .into_iter()
+                .map(Hoverable::from)
+                .collect(),
+
+            HoveredNode::Extension(extension_name) => params
+                .schema_cache
+                .find_extension(&extension_name)
+                .map(Hoverable::from)
+                .map(|e| vec![e])
+                .unwrap_or_default(),
+
+            HoveredNode::Index(index_name) => params
+                .schema_cache
+                .find_indexes(&index_name, None)
+                .into_iter()
+                .map(Hoverable::from)
+                .collect(),
+
+            HoveredNode::Constraint(constraint_name) => params
+                .schema_cache
+                .find_constraints(&constraint_name, None, None)
+                .into_iter()
+                .map(Hoverable::from)
+                .collect(),
+
+            HoveredNode::Expression(offset) => params
+                .ast
+                .and_then(|ast| {
+                    TypeInferrer::new(ast, params.schema_cache).resolve_expression_at(offset)
+                })
+                .filter(|expr| expr.type_.is_some() || expr.operator.is_some())
+                .map(Hoverable::from)
+                .into_iter()
+                .collect(),
         };
 
         prioritize_by_context(items, &ctx)
@@ -481,6 +481,136 @@ async fn test_policy_table_hover(test_db: PgPool) {
     test_hover_at_cursor("create_policy", query, None, &test_db).await;
 }
 
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_policy_name_hover(test_db: PgPool) {
+    let setup = r#"
+        create table users (
+            id serial primary key,
+            name text
+        );
+
+        create policy owner_policy on users for update to public using (id > 0) with check (name is not null);
+    "#;
+
+    let query = format!(
+        "drop policy owner_po{}licy on users;",
+        QueryWithCursorPosition::cursor_marker()
+    );
+
+    test_hover_at_cursor("policy_name", query, Some(setup), &test_db).await;
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_trigger_name_hover(test_db: PgPool) {
+    let setup = r#"
+        create table users (
+            id serial primary key,
+            name text
+        );
+
+        create function audit() returns trigger language plpgsql as $$ begin return new; end; $$;
+
+        create trigger audit_trigger before insert or update on users for each row execute function audit();
+    "#;
+
+    let query = format!(
+        "create or replace trigger audit_tr{}igger before insert or update on users for each row execute function audit();",
+        QueryWithCursorPosition::cursor_marker()
+    );
+
+    test_hover_at_cursor("trigger_name", query, Some(setup), &test_db).await;
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_extension_hover(test_db: PgPool) {
+    let query = format!(
+        "drop extension plpg{}sql;",
+        QueryWithCursorPosition::cursor_marker()
+    );
+
+    test_hover_at_cursor("extension", query, None, &test_db).await;
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_index_hover(test_db: PgPool) {
+    let setup = r#"
+        create table users (
+            id serial primary key,
+            email text
+        );
+
+        create unique index users_email_idx on users (lower(email));
+    "#;
+
+    let query = format!(
+        "drop index users_em{}ail_idx;",
+        QueryWithCursorPosition::cursor_marker()
+    );
+
+    test_hover_at_cursor("index_drop", query, Some(setup), &test_db).await;
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_constraint_hover(test_db: PgPool) {
+    let setup = r#"
+        create table products (
+            id serial primary key,
+            price numeric constraint positive_price check (price > 0)
+        );
+    "#;
+
+    let query = format!(
+        "alter table products drop constraint positive{}_price;",
+        QueryWithCursorPosition::cursor_marker()
+    );
+
+    test_hover_at_cursor("constraint_drop", query, Some(setup), &test_db).await;
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_operator_hover(test_db: PgPool) {
+    let setup = r#"
+        create table events (
+            id serial primary key,
+            created_at timestamptz not null
+        );
+    "#;
+
+    let query = format!(
+        "select created_at {}- interval '1 day' from events;",
+        QueryWithCursorPosition::cursor_marker()
+    );
+
+    test_hover_at_cursor("operator", query, Some(setup), &test_db).await;
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_parameter_hover(test_db: PgPool) {
+    let setup = r#"
+        create table users (
+            id serial primary key,
+            name text
+        );
+    "#;
+
+    let query = format!(
+        "select * from users where id = ${}1;",
+        QueryWithCursorPosition::cursor_marker()
+    );
+
+    test_hover_at_cursor("positional_parameter", query, Some(setup), &test_db).await;
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_cast_hover(test_db: PgPool) {
+    let query = format!(
+        "select '2024-01-01'{}::date;",
+        QueryWithCursorPosition::cursor_marker()
+    );
+
+    test_hover_at_cursor("type_cast", query, None, &test_db).await;
+}
+
 #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
 async fn test_revoke_table_hover(test_db: PgPool) {
     let setup = r#"
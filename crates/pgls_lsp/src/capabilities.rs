@@ -1,12 +1,14 @@
 use crate::adapters::{PositionEncoding, WideEncoding, negotiated_encoding};
 use crate::handlers::code_actions::command_id;
+use crate::handlers::completions::COMPLETION_ACCEPTED_COMMAND;
 use pgls_workspace::features::code_actions::CommandActionCategory;
 use strum::IntoEnumIterator;
 use tower_lsp::lsp_types::{
-    ClientCapabilities, CompletionOptions, ExecuteCommandOptions, HoverProviderCapability,
-    PositionEncodingKind, SaveOptions, ServerCapabilities, TextDocumentSyncCapability,
-    TextDocumentSyncKind, TextDocumentSyncOptions, TextDocumentSyncSaveOptions,
-    WorkDoneProgressOptions,
+    ClientCapabilities, CodeLensOptions, CompletionOptions, DiagnosticOptions,
+    DiagnosticServerCapabilities, ExecuteCommandOptions, FoldingRangeProviderCapability,
+    HoverProviderCapability, PositionEncodingKind, SaveOptions, SelectionRangeProviderCapability,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    TextDocumentSyncSaveOptions, WorkDoneProgressOptions,
 };
 
 /// The capabilities to send from server as part of [`InitializeResult`]
@@ -33,9 +35,8 @@ pub(crate) fn server_capabilities(capabilities: &ClientCapabilities) -> ServerCa
             },
         )),
         completion_provider: Some(CompletionOptions {
-            // currently not supporting the completionItem/resolve request.
-            // The request is used to get more information about a simple CompletionItem.
-            resolve_provider: None,
+            // documentation and additional edits are computed lazily via completionItem/resolve
+            resolve_provider: Some(true),
 
             trigger_characters: Some(vec![
                 ".".to_owned(),
@@ -58,6 +59,7 @@ pub(crate) fn server_capabilities(capabilities: &ClientCapabilities) -> ServerCa
         execute_command_provider: Some(ExecuteCommandOptions {
             commands: CommandActionCategory::iter()
                 .map(|c| command_id(&c))
+                .chain([COMPLETION_ACCEPTED_COMMAND.to_string()])
                 .collect::<Vec<String>>(),
             ..Default::default()
         }),
@@ -67,8 +69,26 @@ pub(crate) fn server_capabilities(capabilities: &ClientCapabilities) -> ServerCa
         code_action_provider: Some(tower_lsp::lsp_types::CodeActionProviderCapability::Simple(
             true,
         )),
+        code_lens_provider: Some(CodeLensOptions {
+            resolve_provider: Some(false),
+        }),
+        folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+        selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
         rename_provider: None,
         hover_provider: Some(HoverProviderCapability::Simple(true)),
+        // diagnostics are published to clients that don't pull them
+        diagnostic_provider: capabilities
+            .text_document
+            .as_ref()
+            .and_then(|c| c.diagnostic.as_ref())
+            .map(|_| {
+                DiagnosticServerCapabilities::Options(DiagnosticOptions {
+                    identifier: Some("postgres-language-server".to_string()),
+                    inter_file_dependencies: false,
+                    workspace_diagnostics: true,
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                })
+            }),
         ..Default::default()
     }
 }
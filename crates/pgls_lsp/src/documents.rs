@@ -1,4 +1,7 @@
+use std::sync::Arc;
+
 use crate::adapters::line_index::LineIndex;
+use tower_lsp::lsp_types::Diagnostic;
 
 /// Represents an open [`textDocument`]. Can be cheaply cloned.
 ///
@@ -7,6 +10,8 @@ use crate::adapters::line_index::LineIndex;
 pub(crate) struct Document {
     pub(crate) version: i32,
     pub(crate) line_index: LineIndex,
+    /// The diagnostics computed for this version of the document, if any
+    pub(crate) diagnostics: Option<Arc<[Diagnostic]>>,
 }
 
 impl Document {
@@ -14,6 +19,7 @@ impl Document {
         Self {
             version,
             line_index: LineIndex::new(text),
+            diagnostics: None,
         }
     }
 }
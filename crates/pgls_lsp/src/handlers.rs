@@ -1,4 +1,8 @@
 pub(crate) mod code_actions;
+pub(crate) mod code_lenses;
 pub(crate) mod completions;
+pub(crate) mod diagnostics;
+pub(crate) mod folding_ranges;
 pub(crate) mod hover;
+pub(crate) mod selection_ranges;
 pub(crate) mod text_document;
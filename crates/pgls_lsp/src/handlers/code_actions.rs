@@ -1,14 +1,51 @@
-use crate::{adapters::get_cursor_position, session::Session};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{
+    adapters::{self, get_cursor_position},
+    handlers::completions::{COMPLETION_ACCEPTED_COMMAND, record_completion_usage},
+    session::Session,
+};
 use anyhow::{Result, anyhow};
+use pgls_diagnostics::PrintDescription;
+use pgls_text_size::TextRange;
 use tower_lsp::lsp_types::{
     self, CodeAction, CodeActionDisabled, CodeActionOrCommand, Command, ExecuteCommandParams,
-    MessageType,
+    MessageActionItem, MessageType, NumberOrString, ProgressParams, ProgressParamsValue,
+    ShowDocumentParams, WorkDoneProgress, WorkDoneProgressBegin, WorkDoneProgressCreateParams,
+    WorkDoneProgressEnd, WorkDoneProgressReport, notification::Notification,
+    request::WorkDoneProgressCreate,
 };
 
-use pgls_workspace::features::code_actions::{
-    CodeActionKind, CodeActionsParams, CommandActionCategory, ExecuteStatementParams,
+use pgls_workspace::{Workspace, WorkspaceError};
+
+use pgls_workspace::features::{
+    code_actions::{
+        CancelStatementExecutionParams, CodeActionKind, CodeActionsParams, CommandActionCategory,
+        ExecuteStatementOptions, ExecuteStatementParams, ExecuteStatementsParams,
+        ExecuteStatementsResult, ResultSet, SelectConnectionParams,
+        StatementExecutionProgressParams,
+    },
+    describe::{DescribeStatementParams, StatementSelector},
+    explain::ExplainStatementParams,
+    functions::{CheckFunctionParams, FunctionCallerKind, FunctionCallersParams},
 };
 
+/// Sent after a statement was executed that returned rows, so that editors can render them.
+pub(crate) enum StatementResultNotification {}
+
+impl Notification for StatementResultNotification {
+    type Params = StatementResultParams;
+    const METHOD: &'static str = "pgls/statementResult";
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct StatementResultParams {
+    /// The document that contains the statement.
+    pub uri: lsp_types::Url,
+    pub result_set: ResultSet,
+}
+
 #[tracing::instrument(level = "debug", skip(session), err)]
 pub fn get_actions(
     session: &Session,
@@ -18,10 +55,19 @@ pub fn get_actions(
     let path = session.file_path(&url)?;
 
     let cursor_position = get_cursor_position(session, &url, params.range.start)?;
+    let selection = if params.range.start == params.range.end {
+        None
+    } else {
+        Some(TextRange::new(
+            cursor_position,
+            get_cursor_position(session, &url, params.range.end)?,
+        ))
+    };
 
     let workspace_actions = session.workspace.pull_code_actions(CodeActionsParams {
         path,
         cursor_position,
+        selection,
         only: vec![],
         skip: vec![],
     })?;
@@ -35,7 +81,13 @@ pub fn get_actions(
                 let title = action.title;
 
                 match command.category {
-                    CommandActionCategory::ExecuteStatement(stmt_id) => Some(CodeAction {
+                    CommandActionCategory::ExecuteStatement(stmt_id)
+                    | CommandActionCategory::DryRunStatement(stmt_id)
+                    | CommandActionCategory::DescribeStatement(stmt_id)
+                    | CommandActionCategory::ExplainStatement(stmt_id)
+                    | CommandActionCategory::ExplainAnalyzeStatement(stmt_id)
+                    | CommandActionCategory::CheckFunction(stmt_id)
+                    | CommandActionCategory::ShowFunctionCallers(stmt_id) => Some(CodeAction {
                         title: title.clone(),
                         kind: Some(lsp_types::CodeActionKind::EMPTY),
                         command: Some({
@@ -53,6 +105,58 @@ pub fn get_actions(
                             .map(|reason| CodeActionDisabled { reason }),
                         ..Default::default()
                     }),
+                    CommandActionCategory::ExecuteRange(range) => Some(CodeAction {
+                        title: title.clone(),
+                        kind: Some(lsp_types::CodeActionKind::EMPTY),
+                        command: Some({
+                            Command {
+                                title: title.clone(),
+                                command: command_id,
+                                arguments: Some(vec![
+                                    serde_json::to_value(range).unwrap(),
+                                    serde_json::to_value(&url).unwrap(),
+                                ]),
+                            }
+                        }),
+                        disabled: action
+                            .disabled_reason
+                            .map(|reason| CodeActionDisabled { reason }),
+                        ..Default::default()
+                    }),
+                    CommandActionCategory::SelectConnection(connection) => Some(CodeAction {
+                        title: title.clone(),
+                        kind: Some(lsp_types::CodeActionKind::EMPTY),
+                        command: Some({
+                            Command {
+                                title: title.clone(),
+                                command: command_id,
+                                arguments: Some(vec![
+                                    serde_json::to_value(&connection).unwrap(),
+                                    serde_json::to_value(&url).unwrap(),
+                                ]),
+                            }
+                        }),
+                        disabled: action
+                            .disabled_reason
+                            .map(|reason| CodeActionDisabled { reason }),
+                        ..Default::default()
+                    }),
+                    CommandActionCategory::ExecuteFile
+                    | CommandActionCategory::CancelStatementExecution => Some(CodeAction {
+                        title: title.clone(),
+                        kind: Some(lsp_types::CodeActionKind::EMPTY),
+                        command: Some({
+                            Command {
+                                title: title.clone(),
+                                command: command_id,
+                                arguments: Some(vec![serde_json::to_value(&url).unwrap()]),
+                            }
+                        }),
+                        disabled: action
+                            .disabled_reason
+                            .map(|reason| CodeActionDisabled { reason }),
+                        ..Default::default()
+                    }),
                     CommandActionCategory::InvalidateSchemaCache => Some(CodeAction {
                         title: title.clone(),
                         kind: Some(lsp_types::CodeActionKind::EMPTY),
@@ -84,7 +188,17 @@ pub fn get_actions(
 pub fn command_id(command: &CommandActionCategory) -> String {
     match command {
         CommandActionCategory::ExecuteStatement(_) => "pgls.executeStatement".into(),
+        CommandActionCategory::DryRunStatement(_) => "pgls.dryRunStatement".into(),
+        CommandActionCategory::ExecuteFile => "pgls.executeFile".into(),
+        CommandActionCategory::ExecuteRange(_) => "pgls.executeRange".into(),
+        CommandActionCategory::CancelStatementExecution => "pgls.cancelStatementExecution".into(),
+        CommandActionCategory::SelectConnection(_) => "pgls.selectConnection".into(),
+        CommandActionCategory::DescribeStatement(_) => "pgls.describeStatement".into(),
+        CommandActionCategory::ExplainStatement(_) => "pgls.explainStatement".into(),
+        CommandActionCategory::ExplainAnalyzeStatement(_) => "pgls.explainAnalyzeStatement".into(),
         CommandActionCategory::InvalidateSchemaCache => "pgls.invalidateSchemaCache".into(),
+        CommandActionCategory::CheckFunction(_) => "pgls.checkFunction".into(),
+        CommandActionCategory::ShowFunctionCallers(_) => "pgls.showFunctionCallers".into(),
     }
 }
 
@@ -96,7 +210,7 @@ pub async fn execute_command(
     let command = params.command;
 
     match command.as_str() {
-        "pgls.executeStatement" => {
+        "pgls.executeStatement" | "pgls.dryRunStatement" => {
             let statement_id = serde_json::from_value::<pgls_workspace::workspace::StatementId>(
                 params.arguments[0].clone(),
             )?;
@@ -104,9 +218,32 @@ pub async fn execute_command(
 
             let path = session.file_path(&doc_url)?;
 
-            let result = session
-                .workspace
-                .execute_statement(ExecuteStatementParams { statement_id, path })?;
+            let mut options = ExecuteStatementOptions {
+                dry_run: command == "pgls.dryRunStatement",
+                confirmation: None,
+            };
+
+            let result = loop {
+                let params = ExecuteStatementParams {
+                    statement: StatementSelector::Id(statement_id.clone()),
+                    path: path.clone(),
+                    options,
+                };
+                let result = run_blocking(session, move |workspace| {
+                    workspace.execute_statement(params)
+                })
+                .await?;
+
+                let Some(confirmation) = &result.confirmation_required else {
+                    break result;
+                };
+
+                if confirm_execution(session, &confirmation.reason).await? {
+                    options.confirmation = Some(confirmation.token);
+                } else {
+                    return Ok(Some(serde_json::to_value(result)?));
+                }
+            };
 
             /*
              * Updating all diagnostics: the changes caused by the statement execution
@@ -116,13 +253,290 @@ pub async fn execute_command(
 
             session
                 .client
-                .show_message(MessageType::INFO, result.message)
+                .show_message(MessageType::INFO, &result.message)
+                .await;
+
+            if let Some(result_set) = &result.result_set {
+                session
+                    .client
+                    .send_notification::<StatementResultNotification>(StatementResultParams {
+                        uri: doc_url,
+                        result_set: result_set.clone(),
+                    })
+                    .await;
+            }
+
+            Ok(Some(serde_json::to_value(result)?))
+        }
+        "pgls.executeFile" | "pgls.executeRange" => {
+            let (range, doc_url) = if command == "pgls.executeRange" {
+                (
+                    Some(serde_json::from_value::<TextRange>(
+                        params.arguments[0].clone(),
+                    )?),
+                    serde_json::from_value::<lsp_types::Url>(params.arguments[1].clone())?,
+                )
+            } else {
+                (
+                    None,
+                    serde_json::from_value::<lsp_types::Url>(params.arguments[0].clone())?,
+                )
+            };
+
+            let path = session.file_path(&doc_url)?;
+
+            let mut confirmation = None;
+            let result = loop {
+                let result = execute_statements_with_progress(
+                    session,
+                    ExecuteStatementsParams {
+                        path: path.clone(),
+                        range,
+                        confirmation,
+                    },
+                )
+                .await?;
+
+                let Some(confirmation_required) = &result.confirmation_required else {
+                    break result;
+                };
+
+                if confirm_execution(session, &confirmation_required.reason).await? {
+                    confirmation = Some(confirmation_required.token);
+                } else {
+                    return Ok(Some(serde_json::to_value(result)?));
+                }
+            };
+
+            if result.executed > 0 {
+                session.update_all_diagnostics().await;
+            }
+
+            let message_type = match &result.error {
+                Some(error) => {
+                    // reveal the part of the statement the error refers to
+                    let doc = session
+                        .document(&doc_url)
+                        .map_err(|_| anyhow!("Document not found."))?;
+                    let selection = adapters::to_lsp::range(
+                        &doc.line_index,
+                        error.range,
+                        session.position_encoding(),
+                    )?;
+                    let _ = session
+                        .client
+                        .show_document(ShowDocumentParams {
+                            uri: doc_url,
+                            external: None,
+                            take_focus: Some(true),
+                            selection: Some(selection),
+                        })
+                        .await;
+
+                    MessageType::ERROR
+                }
+                None => MessageType::INFO,
+            };
+
+            session
+                .client
+                .show_message(message_type, &result.message)
+                .await;
+
+            Ok(Some(serde_json::to_value(result)?))
+        }
+        "pgls.cancelStatementExecution" => {
+            let doc_url: lsp_types::Url = serde_json::from_value(params.arguments[0].clone())?;
+
+            let path = session.file_path(&doc_url)?;
+
+            let result = run_blocking(session, move |workspace| {
+                workspace.cancel_statement_execution(CancelStatementExecutionParams { path })
+            })
+            .await?;
+
+            let message = match result.cancelled {
+                0 => "No statement is running.".to_string(),
+                1 => "Cancelled the running statement.".to_string(),
+                n => format!("Cancelled {n} running statements."),
+            };
+
+            session
+                .client
+                .show_message(MessageType::INFO, message)
+                .await;
+
+            Ok(Some(serde_json::to_value(result)?))
+        }
+        "pgls.describeStatement" => {
+            let statement_id = serde_json::from_value::<pgls_workspace::workspace::StatementId>(
+                params.arguments[0].clone(),
+            )?;
+            let doc_url: lsp_types::Url = serde_json::from_value(params.arguments[1].clone())?;
+
+            let path = session.file_path(&doc_url)?;
+
+            let result = run_blocking(session, move |workspace| {
+                workspace.describe_statement(DescribeStatementParams {
+                    path,
+                    statement: StatementSelector::Id(statement_id),
+                })
+            })
+            .await?;
+
+            let message = match &result.description {
+                Some(description) if !description.columns.is_empty() => description.to_text(),
+                _ => "Statement does not return any rows.".to_string(),
+            };
+
+            session
+                .client
+                .show_message(MessageType::INFO, message)
+                .await;
+
+            Ok(Some(serde_json::to_value(result)?))
+        }
+        "pgls.explainStatement" | "pgls.explainAnalyzeStatement" => {
+            let statement_id = serde_json::from_value::<pgls_workspace::workspace::StatementId>(
+                params.arguments[0].clone(),
+            )?;
+            let doc_url: lsp_types::Url = serde_json::from_value(params.arguments[1].clone())?;
+
+            let path = session.file_path(&doc_url)?;
+
+            let analyze = command == "pgls.explainAnalyzeStatement";
+            let result = run_blocking(session, move |workspace| {
+                workspace.explain_statement(ExplainStatementParams {
+                    path,
+                    statement: StatementSelector::Id(statement_id),
+                    analyze,
+                })
+            })
+            .await?;
+
+            let mut message = result.message.clone().unwrap_or_default();
+            if let Some(explanation) = &result.explanation {
+                for diagnostic in &explanation.diagnostics {
+                    message = format!("{message}\n{}", PrintDescription(diagnostic));
+                }
+                message = format!("{message}\n\n{}", explanation.plan);
+            }
+
+            session
+                .client
+                .show_message(MessageType::INFO, message.trim())
+                .await;
+
+            Ok(Some(serde_json::to_value(result)?))
+        }
+        "pgls.checkFunction" => {
+            let statement_id = serde_json::from_value::<pgls_workspace::workspace::StatementId>(
+                params.arguments[0].clone(),
+            )?;
+            let doc_url: lsp_types::Url = serde_json::from_value(params.arguments[1].clone())?;
+
+            let path = session.file_path(&doc_url)?;
+
+            let result = run_blocking(session, move |workspace| {
+                workspace.check_function(CheckFunctionParams {
+                    path,
+                    statement: StatementSelector::Id(statement_id),
+                })
+            })
+            .await?;
+
+            let message = match (&result.diagnostics, &result.message) {
+                (_, Some(message)) => message.clone(),
+                (Some(diagnostics), None) if diagnostics.is_empty() => {
+                    "plpgsql_check found no issues.".to_string()
+                }
+                (Some(diagnostics), None) => diagnostics
+                    .iter()
+                    .map(|diagnostic| PrintDescription(diagnostic).to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                (None, None) => "Statement was not found in document.".to_string(),
+            };
+
+            session
+                .client
+                .show_message(MessageType::INFO, message)
+                .await;
+
+            Ok(Some(serde_json::to_value(result)?))
+        }
+        "pgls.showFunctionCallers" => {
+            let statement_id = serde_json::from_value::<pgls_workspace::workspace::StatementId>(
+                params.arguments[0].clone(),
+            )?;
+            let doc_url: lsp_types::Url = serde_json::from_value(params.arguments[1].clone())?;
+
+            let path = session.file_path(&doc_url)?;
+
+            let result = run_blocking(session, move |workspace| {
+                workspace.function_callers(FunctionCallersParams {
+                    path,
+                    statement: StatementSelector::Id(statement_id),
+                })
+            })
+            .await?;
+
+            let message = match (&result.callers, &result.message) {
+                (_, Some(message)) => message.clone(),
+                (Some(callers), None) if callers.is_empty() => {
+                    "No function or trigger in the database calls this function.".to_string()
+                }
+                (Some(callers), None) => {
+                    let callers = callers
+                        .iter()
+                        .map(|caller| match caller.kind {
+                            FunctionCallerKind::Function => format!("function {}", caller.name),
+                            FunctionCallerKind::Trigger => format!("trigger {}", caller.name),
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    format!("Called by:\n{callers}")
+                }
+                (None, None) => "Statement was not found in document.".to_string(),
+            };
+
+            session
+                .client
+                .show_message(MessageType::INFO, message)
+                .await;
+
+            Ok(Some(serde_json::to_value(result)?))
+        }
+        "pgls.selectConnection" => {
+            let connection: Option<String> = serde_json::from_value(params.arguments[0].clone())?;
+            let doc_url: lsp_types::Url = serde_json::from_value(params.arguments[1].clone())?;
+
+            let path = session.file_path(&doc_url)?;
+
+            session
+                .workspace
+                .select_connection(SelectConnectionParams {
+                    path,
+                    connection: connection.clone(),
+                })?;
+
+            // the diagnostics of the file depend on the schema of the database
+            session.invalidate_diagnostics(doc_url).await;
+
+            let message = match connection {
+                Some(name) => format!("Using the database connection \"{name}\""),
+                None => "Using the default database connection".to_string(),
+            };
+
+            session
+                .client
+                .show_message(MessageType::INFO, message)
                 .await;
 
             Ok(None)
         }
         "pgls.invalidateSchemaCache" => {
-            session.workspace.invalidate_schema_cache(true)?;
+            run_blocking(session, |workspace| workspace.invalidate_schema_cache(true)).await?;
 
             session
                 .client
@@ -131,6 +545,147 @@ pub async fn execute_command(
 
             Ok(None)
         }
+        COMPLETION_ACCEPTED_COMMAND => {
+            record_completion_usage(session, params.arguments)?;
+
+            Ok(None)
+        }
         any => Err(anyhow!(format!("Unknown command: {}", any))),
     }
 }
+
+/// Runs a workspace call that talks to the database on the blocking thread pool. Blocking the
+/// request loop instead would hold up all other requests, including the one to cancel the call.
+async fn run_blocking<T: Send + 'static>(
+    session: &Session,
+    call: impl FnOnce(&dyn Workspace) -> Result<T, WorkspaceError> + Send + 'static,
+) -> Result<T> {
+    let workspace = Arc::clone(&session.workspace);
+    Ok(tokio::task::spawn_blocking(move || call(&*workspace)).await??)
+}
+
+/// Asks the user whether to execute statements that destroy data.
+async fn confirm_execution(session: &Session, reason: &str) -> Result<bool> {
+    let execute = MessageActionItem {
+        title: "Execute".into(),
+        properties: Default::default(),
+    };
+
+    let choice = session
+        .client
+        .show_message_request(
+            MessageType::WARNING,
+            format!("{reason}\n\nExecute anyway?"),
+            Some(vec![
+                execute.clone(),
+                MessageActionItem {
+                    title: "Cancel".into(),
+                    properties: Default::default(),
+                },
+            ]),
+        )
+        .await?;
+
+    Ok(choice.is_some_and(|choice| choice.title == execute.title))
+}
+
+static NEXT_PROGRESS_TOKEN: AtomicU64 = AtomicU64::new(0);
+
+/// Executes the statements on a blocking thread, and reports which statement is running via
+/// `$/progress` if the client supports it.
+async fn execute_statements_with_progress(
+    session: &Session,
+    params: ExecuteStatementsParams,
+) -> Result<ExecuteStatementsResult> {
+    let path = params.path.clone();
+
+    let workspace = Arc::clone(&session.workspace);
+    let mut execution = tokio::task::spawn_blocking(move || workspace.execute_statements(params));
+
+    let supports_progress = session
+        .client_capabilities()
+        .and_then(|capabilities| capabilities.window.as_ref())
+        .and_then(|window| window.work_done_progress)
+        .unwrap_or_default();
+
+    let token = NumberOrString::String(format!(
+        "pgls/executeStatements/{}",
+        NEXT_PROGRESS_TOKEN.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let report_progress = supports_progress
+        && session
+            .client
+            .send_request::<WorkDoneProgressCreate>(WorkDoneProgressCreateParams {
+                token: token.clone(),
+            })
+            .await
+            .is_ok();
+
+    let send_progress = |progress: WorkDoneProgress| {
+        session
+            .client
+            .send_notification::<lsp_types::notification::Progress>(ProgressParams {
+                token: token.clone(),
+                value: ProgressParamsValue::WorkDone(progress),
+            })
+    };
+
+    if report_progress {
+        send_progress(WorkDoneProgress::Begin(WorkDoneProgressBegin {
+            title: "Executing statements".into(),
+            cancellable: Some(false),
+            message: None,
+            percentage: Some(0),
+        }))
+        .await;
+    }
+
+    // the workspace answers as soon as the execution loop moves on to the next statement
+    let mut known_executed = None;
+    let result = loop {
+        if !report_progress {
+            break (&mut execution).await;
+        }
+
+        let workspace = Arc::clone(&session.workspace);
+        let progress_params = StatementExecutionProgressParams {
+            path: path.clone(),
+            known_executed,
+        };
+        let progress = tokio::task::spawn_blocking(move || {
+            workspace.statement_execution_progress(progress_params)
+        });
+
+        tokio::select! {
+            result = &mut execution => break result,
+            progress = progress => {
+                let Ok(Ok(Some(progress))) = progress else {
+                    continue;
+                };
+                if known_executed == Some(progress.executed) {
+                    continue;
+                }
+                known_executed = Some(progress.executed);
+
+                send_progress(WorkDoneProgress::Report(WorkDoneProgressReport {
+                    cancellable: Some(false),
+                    message: Some(format!(
+                        "Statement {} of {}",
+                        progress.executed + 1,
+                        progress.total
+                    )),
+                    percentage: u32::try_from(progress.executed * 100 / progress.total.max(1))
+                        .ok(),
+                }))
+                .await;
+            }
+        }
+    };
+
+    if report_progress {
+        send_progress(WorkDoneProgress::End(WorkDoneProgressEnd { message: None })).await;
+    }
+
+    Ok(result??)
+}
@@ -0,0 +1,115 @@
+use pgls_workspace::features::{
+    code_actions::CommandActionCategory, code_lenses::CodeLensesParams,
+};
+use tower_lsp::lsp_types::{self, Command, DiagnosticSeverity};
+
+use crate::{
+    adapters, diagnostics::LspError, handlers::code_actions::command_id, session::Session,
+};
+
+/// Handler for the `textDocument/codeLens` LSP request. Shows the commands available for
+/// each statement and a summary of its diagnostics.
+#[tracing::instrument(level = "debug", skip_all, fields(url = %params.text_document.uri), err)]
+pub(crate) fn get_code_lenses(
+    session: &Session,
+    params: lsp_types::CodeLensParams,
+) -> Result<Vec<lsp_types::CodeLens>, LspError> {
+    let url = params.text_document.uri;
+    let path = session.file_path(&url)?;
+    let doc = session.document(&url)?;
+
+    let result = session
+        .workspace
+        .pull_code_lenses(CodeLensesParams { path })?;
+
+    // computing the diagnostics can take a while, so the summaries are left pending until
+    // they are published or pulled
+    let diagnostics = doc.diagnostics.clone();
+    if diagnostics.is_none() {
+        session.set_code_lenses_pending();
+    }
+
+    let mut lenses = Vec::new();
+    for statement in result.statements {
+        let range = adapters::to_lsp::range(
+            &doc.line_index,
+            statement.range,
+            session.position_encoding(),
+        )?;
+
+        for lens in statement.lenses {
+            let argument = match &lens.command {
+                CommandActionCategory::ExecuteStatement(id)
+                | CommandActionCategory::DryRunStatement(id)
+                | CommandActionCategory::ExplainStatement(id)
+                | CommandActionCategory::CheckFunction(id)
+                | CommandActionCategory::ShowFunctionCallers(id) => {
+                    serde_json::to_value(id).unwrap()
+                }
+                _ => continue,
+            };
+
+            lenses.push(lsp_types::CodeLens {
+                range,
+                command: Some(Command {
+                    title: lens.title,
+                    command: command_id(&lens.command),
+                    arguments: Some(vec![argument, serde_json::to_value(&url).unwrap()]),
+                }),
+                data: None,
+            });
+        }
+
+        let summary = match &diagnostics {
+            Some(diagnostics) => summarize(
+                diagnostics
+                    .iter()
+                    .filter(|d| d.range.start >= range.start && d.range.start <= range.end),
+            ),
+            None => "…".to_string(),
+        };
+
+        lenses.push(lsp_types::CodeLens {
+            range,
+            command: Some(Command {
+                title: summary,
+                // informational only
+                command: String::new(),
+                arguments: None,
+            }),
+            data: None,
+        });
+    }
+
+    Ok(lenses)
+}
+
+/// Summarizes diagnostics, e.g. "1 error, 2 warnings"
+fn summarize<'a>(diagnostics: impl Iterator<Item = &'a lsp_types::Diagnostic>) -> String {
+    let (mut errors, mut warnings, mut others) = (0, 0, 0);
+    for diagnostic in diagnostics {
+        match diagnostic.severity {
+            Some(DiagnosticSeverity::ERROR) => errors += 1,
+            Some(DiagnosticSeverity::WARNING) => warnings += 1,
+            _ => others += 1,
+        }
+    }
+
+    let counts: Vec<String> = [(errors, "error"), (warnings, "warning"), (others, "info")]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, kind)| {
+            if count == 1 || kind == "info" {
+                format!("{count} {kind}")
+            } else {
+                format!("{count} {kind}s")
+            }
+        })
+        .collect();
+
+    if counts.is_empty() {
+        "No problems".to_string()
+    } else {
+        counts.join(", ")
+    }
+}
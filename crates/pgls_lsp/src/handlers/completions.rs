@@ -4,11 +4,29 @@ use crate::{
     session::Session,
 };
 use anyhow::Result;
-use pgls_workspace::{WorkspaceError, features::completions::GetCompletionsParams};
+use pgls_completions::CompletionItemReference;
+use pgls_workspace::{
+    WorkspaceError,
+    features::completions::{
+        GetCompletionsParams, RecordCompletionUsageParams, ResolveCompletionItemParams,
+    },
+};
 use tower_lsp::lsp_types::{
-    self, CompletionItem, CompletionItemLabelDetails, InsertTextFormat, TextEdit,
+    self, CompletionItem, CompletionItemLabelDetails, Documentation, InsertTextFormat,
+    MarkupContent, MarkupKind, TextEdit,
 };
 
+/// Executed by the client once a completion item is accepted.
+pub const COMPLETION_ACCEPTED_COMMAND: &str = "pgls.completionAccepted";
+
+/// Stored in [CompletionItem::data] so we can find the item's origin when resolving it.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct CompletionItemData {
+    uri: lsp_types::Url,
+    position: lsp_types::Position,
+    reference: CompletionItemReference,
+}
+
 #[tracing::instrument(level = "debug", skip(session), err)]
 pub fn get_completions(
     session: &Session,
@@ -38,6 +56,20 @@ pub fn get_completions(
     let items: Vec<CompletionItem> = completion_result
         .into_iter()
         .map(|i| CompletionItem {
+            command: Some(lsp_types::Command {
+                title: "".into(),
+                command: COMPLETION_ACCEPTED_COMMAND.into(),
+                arguments: Some(vec![
+                    serde_json::to_value(&url).unwrap(),
+                    serde_json::to_value(&i.reference).unwrap(),
+                ]),
+            }),
+            data: serde_json::to_value(CompletionItemData {
+                uri: url.clone(),
+                position: params.text_document_position.position,
+                reference: i.reference,
+            })
+            .ok(),
             label: i.label,
             label_details: Some(CompletionItemLabelDetails {
                 description: Some(i.description),
@@ -67,6 +99,83 @@ pub fn get_completions(
     Ok(lsp_types::CompletionResponse::Array(items))
 }
 
+#[tracing::instrument(level = "debug", skip(session), err)]
+pub fn resolve_completion_item(
+    session: &Session,
+    mut item: CompletionItem,
+) -> Result<CompletionItem, LspError> {
+    let Some(data) = item
+        .data
+        .take()
+        .and_then(|d| serde_json::from_value::<CompletionItemData>(d).ok())
+    else {
+        return Ok(item);
+    };
+
+    let path = session.file_path(&data.uri)?;
+    let doc = session.document(&data.uri)?;
+    let encoding = adapters::negotiated_encoding(session.client_capabilities().unwrap());
+
+    let result = match session
+        .workspace
+        .resolve_completion_item(ResolveCompletionItemParams {
+            path,
+            position: get_cursor_position(session, &data.uri, data.position)?,
+            reference: data.reference,
+        }) {
+        Ok(result) => result,
+        Err(e) => match e {
+            WorkspaceError::DatabaseConnectionError(_) => return Ok(item),
+            _ => return Err(e.into()),
+        },
+    };
+
+    item.documentation = result.documentation.map(|value| {
+        Documentation::MarkupContent(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        })
+    });
+
+    if !result.additional_edits.is_empty() {
+        item.additional_text_edits = Some(
+            result
+                .additional_edits
+                .into_iter()
+                .map(|e| {
+                    Ok(TextEdit {
+                        new_text: e.text,
+                        range: adapters::to_lsp::range(&doc.line_index, e.range, encoding)?,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+        );
+    }
+
+    Ok(item)
+}
+
+#[tracing::instrument(level = "debug", skip(session), err)]
+pub fn record_completion_usage(session: &Session, arguments: Vec<serde_json::Value>) -> Result<()> {
+    let [url, reference] = arguments.as_slice() else {
+        return Err(anyhow::anyhow!(
+            "Expected a document url and a completion reference"
+        ));
+    };
+
+    let url: lsp_types::Url = serde_json::from_value(url.clone())?;
+    let reference: CompletionItemReference = serde_json::from_value(reference.clone())?;
+
+    session
+        .workspace
+        .record_completion_usage(RecordCompletionUsageParams {
+            path: session.file_path(&url)?,
+            reference,
+        })?;
+
+    Ok(())
+}
+
 fn to_lsp_types_completion_item_kind(
     pg_comp_kind: pgls_completions::CompletionItemKind,
 ) -> lsp_types::CompletionItemKind {
@@ -77,5 +186,8 @@ fn to_lsp_types_completion_item_kind(
         pgls_completions::CompletionItemKind::Schema => lsp_types::CompletionItemKind::CLASS,
         pgls_completions::CompletionItemKind::Policy => lsp_types::CompletionItemKind::CONSTANT,
         pgls_completions::CompletionItemKind::Role => lsp_types::CompletionItemKind::CONSTANT,
+        pgls_completions::CompletionItemKind::JoinCondition => {
+            lsp_types::CompletionItemKind::REFERENCE
+        }
     }
 }
@@ -0,0 +1,458 @@
+use std::collections::{BTreeSet, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use pgls_diagnostics::Error;
+use pgls_fs::{FileSystem, PathInterner, PgLSPath, TraversalContext, TraversalScope};
+use pgls_workspace::workspace::IsPathIgnoredParams;
+use pgls_workspace::{Workspace, WorkspaceError};
+use rustc_hash::FxHashSet;
+use tower_lsp::lsp_types::{
+    self, DocumentDiagnosticReport, DocumentDiagnosticReportResult, FullDocumentDiagnosticReport,
+    NumberOrString, ProgressParams, ProgressParamsValue, RelatedFullDocumentDiagnosticReport,
+    RelatedUnchangedDocumentDiagnosticReport, UnchangedDocumentDiagnosticReport, WorkDoneProgress,
+    WorkDoneProgressBegin, WorkDoneProgressCancelParams, WorkDoneProgressCreateParams,
+    WorkDoneProgressEnd, WorkDoneProgressReport, WorkspaceDiagnosticReport,
+    WorkspaceDiagnosticReportResult, WorkspaceDocumentDiagnosticReport,
+    WorkspaceFullDocumentDiagnosticReport, WorkspaceUnchangedDocumentDiagnosticReport,
+    request::WorkDoneProgressCreate,
+};
+
+use crate::{
+    diagnostics::LspError,
+    session::{Session, SessionHandle},
+};
+
+const PROGRESS_TOKEN_PREFIX: &str = "pgls/workspaceDiagnostics/";
+
+/// Handler for the `textDocument/diagnostic` LSP request. Responds with an unchanged report
+/// if the document did not change since the client pulled its diagnostics.
+#[tracing::instrument(level = "debug", skip_all, fields(url = %params.text_document.uri), err)]
+pub(crate) async fn pull_document_diagnostics(
+    session: &Session,
+    params: lsp_types::DocumentDiagnosticParams,
+) -> Result<DocumentDiagnosticReportResult, LspError> {
+    let url = params.text_document.uri;
+    let path = session.file_path(&url)?;
+    let doc = session.document(&url)?;
+
+    session.notify_broken_configuration().await;
+
+    let result_id = session.diagnostics_result_id(doc.version);
+
+    let report = if params.previous_result_id.as_ref() == Some(&result_id) {
+        DocumentDiagnosticReport::Unchanged(RelatedUnchangedDocumentDiagnosticReport {
+            related_documents: None,
+            unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport { result_id },
+        })
+    } else {
+        let items = session.compute_diagnostics(&url, path, &doc.line_index)?;
+        session.set_document_diagnostics(&url, doc.version, &items);
+        session.refresh_pending_code_lenses().await;
+
+        DocumentDiagnosticReport::Full(RelatedFullDocumentDiagnosticReport {
+            related_documents: None,
+            full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                result_id: Some(result_id),
+                items,
+            },
+        })
+    };
+
+    Ok(DocumentDiagnosticReportResult::Report(report))
+}
+
+/// Handler for the `workspace/diagnostic` LSP request. Reports the diagnostics of the open
+/// documents and the lint diagnostics of all SQL files in the project folders that are not open.
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn pull_workspace_diagnostics(
+    session: &SessionHandle,
+    params: lsp_types::WorkspaceDiagnosticParams,
+) -> Result<WorkspaceDiagnosticReportResult, LspError> {
+    session.notify_broken_configuration().await;
+
+    // checking the files of the whole project takes a while
+    let items = {
+        let session = Arc::clone(session);
+        tokio::task::spawn_blocking(move || workspace_diagnostics(&session, params))
+            .await
+            .map_err(WorkspaceError::from)??
+    };
+
+    session.refresh_pending_code_lenses().await;
+
+    Ok(WorkspaceDiagnosticReportResult::Report(
+        WorkspaceDiagnosticReport { items },
+    ))
+}
+
+fn workspace_diagnostics(
+    session: &Session,
+    params: lsp_types::WorkspaceDiagnosticParams,
+) -> Result<Vec<WorkspaceDocumentDiagnosticReport>, LspError> {
+    let previous_result_ids: HashMap<_, _> = params
+        .previous_result_ids
+        .into_iter()
+        .map(|previous| (previous.uri, previous.value))
+        .collect();
+
+    let mut items = Vec::new();
+
+    let documents = session.documents();
+    for (url, doc) in &documents {
+        let result_id = session.diagnostics_result_id(doc.version);
+        if previous_result_ids.get(url) == Some(&result_id) {
+            items.push(unchanged_report(url.clone(), Some(doc.version), result_id));
+            continue;
+        }
+
+        match session.compute_diagnostics(url, session.file_path(url)?, &doc.line_index) {
+            Ok(diagnostics) => {
+                session.set_document_diagnostics(url, doc.version, &diagnostics);
+                items.push(full_report(
+                    url.clone(),
+                    Some(doc.version),
+                    result_id,
+                    diagnostics,
+                ));
+            }
+            Err(err) => tracing::error!("Failed to pull diagnostics of {url}: {err}"),
+        }
+    }
+
+    let open_paths = documents
+        .iter()
+        .map(|(url, _)| session.file_path(url))
+        .collect::<Result<BTreeSet<_>, _>>()?;
+
+    for path in sql_files(session) {
+        if open_paths.contains(&path) {
+            continue;
+        }
+
+        let Ok(url) = lsp_types::Url::from_file_path(path.as_path()) else {
+            continue;
+        };
+
+        let content = match session.fs.read_file_from_path(&path.to_path_buf()) {
+            Ok(content) => content,
+            Err(err) => {
+                tracing::error!("Failed to read {}: {err:?}", path.display());
+                continue;
+            }
+        };
+
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        let result_id = session.diagnostics_result_id(format!("{:x}", hasher.finish()));
+
+        if previous_result_ids.get(&url) == Some(&result_id) {
+            items.push(unchanged_report(url, None, result_id));
+            continue;
+        }
+
+        match session.compute_content_diagnostics(&url, path, content) {
+            Ok(diagnostics) => items.push(full_report(url, None, result_id, diagnostics)),
+            Err(err) => tracing::error!("Failed to pull diagnostics of {url}: {err}"),
+        }
+    }
+
+    Ok(items)
+}
+
+/// Lints the SQL files in the project folders that are not open and publishes their
+/// diagnostics, so that the client shows the problems of the whole project. Reports the
+/// progress via `$/progress` if the client supports it. Stops when the user cancels the
+/// progress or another computation starts.
+///
+/// Does nothing if the client pulls diagnostics, since `workspace/diagnostic` covers these files.
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) async fn publish_workspace_diagnostics(session: SessionHandle) {
+    if session.pull_diagnostics_enabled() {
+        return;
+    }
+
+    let run = session.start_workspace_diagnostics();
+
+    let supports_progress = session
+        .client_capabilities()
+        .and_then(|capabilities| capabilities.window.as_ref())
+        .and_then(|window| window.work_done_progress)
+        .unwrap_or_default();
+
+    let token = NumberOrString::String(format!("{PROGRESS_TOKEN_PREFIX}{run}"));
+
+    let report_progress = supports_progress
+        && session
+            .client
+            .send_request::<WorkDoneProgressCreate>(WorkDoneProgressCreateParams {
+                token: token.clone(),
+            })
+            .await
+            .is_ok();
+
+    let send_progress = |progress: WorkDoneProgress| {
+        session
+            .client
+            .send_notification::<lsp_types::notification::Progress>(ProgressParams {
+                token: token.clone(),
+                value: ProgressParamsValue::WorkDone(progress),
+            })
+    };
+
+    if report_progress {
+        send_progress(WorkDoneProgress::Begin(WorkDoneProgressBegin {
+            title: "Checking SQL files".into(),
+            cancellable: Some(true),
+            message: None,
+            percentage: Some(0),
+        }))
+        .await;
+    }
+
+    let paths = {
+        let session = Arc::clone(&session);
+        tokio::task::spawn_blocking(move || sql_files(&session))
+            .await
+            .unwrap_or_default()
+    };
+    let total = paths.len();
+
+    let mut checked = FxHashSet::default();
+    let mut percentage = 0;
+    let mut cancelled = false;
+
+    for (index, path) in paths.into_iter().enumerate() {
+        if !session.is_workspace_diagnostics_run(run) {
+            cancelled = true;
+            break;
+        }
+
+        if let Ok(url) = lsp_types::Url::from_file_path(path.as_path()) {
+            publish_file_diagnostics(&session, url.clone()).await;
+            checked.insert(url);
+        }
+
+        let done = u32::try_from((index + 1) * 100 / total).unwrap_or(100);
+        if report_progress && done > percentage {
+            percentage = done;
+            send_progress(WorkDoneProgress::Report(WorkDoneProgressReport {
+                cancellable: Some(true),
+                message: Some(format!("{} of {total} files", index + 1)),
+                percentage: Some(done),
+            }))
+            .await;
+        }
+    }
+
+    if !cancelled {
+        // clear the diagnostics of files that were deleted or are ignored now
+        for url in session.workspace_diagnostics_files() {
+            if !checked.contains(&url) && session.document(&url).is_err() {
+                session.set_workspace_diagnostics_published(&url, false);
+                session.client.publish_diagnostics(url, vec![], None).await;
+            }
+        }
+    }
+
+    if report_progress {
+        send_progress(WorkDoneProgress::End(WorkDoneProgressEnd {
+            message: cancelled.then(|| "Cancelled".into()),
+        }))
+        .await;
+    }
+}
+
+/// Publishes the diagnostics of a file that is not open, e.g. after it changed on disk or was
+/// closed. Clears them if the file is not a SQL file of the project (anymore).
+pub(crate) async fn publish_file_diagnostics(session: &SessionHandle, url: lsp_types::Url) {
+    if session.pull_diagnostics_enabled() || session.document(&url).is_ok() {
+        return;
+    }
+
+    let diagnostics = {
+        let session = Arc::clone(session);
+        let url = url.clone();
+        tokio::task::spawn_blocking(move || unopened_file_diagnostics(&session, &url)).await
+    };
+
+    let diagnostics = match diagnostics {
+        Ok(Some(diagnostics)) => diagnostics,
+        Ok(None) => return,
+        Err(err) => {
+            tracing::error!("Failed to compute diagnostics of {url}: {err}");
+            return;
+        }
+    };
+
+    // the file might have been opened in the meantime
+    if session.document(&url).is_ok() {
+        return;
+    }
+
+    let had_diagnostics =
+        session.set_workspace_diagnostics_published(&url, !diagnostics.is_empty());
+
+    if had_diagnostics || !diagnostics.is_empty() {
+        session
+            .client
+            .publish_diagnostics(url, diagnostics, None)
+            .await;
+    }
+}
+
+/// Lints a file that is not open. Returns no diagnostics if the file is not a SQL file of the
+/// project, and `None` if it can't be checked.
+fn unopened_file_diagnostics(
+    session: &Session,
+    url: &lsp_types::Url,
+) -> Option<Vec<lsp_types::Diagnostic>> {
+    let path = session.file_path(url).ok()?;
+
+    let is_sql_file = session.fs.path_is_file(path.as_path())
+        && has_sql_extension(&path)
+        && !is_ignored(&*session.workspace, &path);
+
+    if !is_sql_file {
+        return Some(Vec::new());
+    }
+
+    let content = match session.fs.read_file_from_path(&path.to_path_buf()) {
+        Ok(content) => content,
+        Err(err) => {
+            tracing::error!("Failed to read {}: {err:?}", path.display());
+            return None;
+        }
+    };
+
+    match session.compute_content_diagnostics(url, path, content) {
+        Ok(diagnostics) => Some(diagnostics),
+        Err(err) => {
+            tracing::error!("Failed to compute diagnostics of {url}: {err}");
+            None
+        }
+    }
+}
+
+/// Handler for the `window/workDoneProgress/cancel` LSP notification. Stops computing the
+/// diagnostics of files that are not open.
+pub(crate) fn cancel_progress(session: &Session, params: WorkDoneProgressCancelParams) {
+    let run = match &params.token {
+        NumberOrString::String(token) => token
+            .strip_prefix(PROGRESS_TOKEN_PREFIX)
+            .and_then(|run| run.parse().ok()),
+        NumberOrString::Number(_) => None,
+    };
+
+    if run.is_some_and(|run| session.is_workspace_diagnostics_run(run)) {
+        session.cancel_workspace_diagnostics();
+    }
+}
+
+fn full_report(
+    uri: lsp_types::Url,
+    version: Option<i32>,
+    result_id: String,
+    items: Vec<lsp_types::Diagnostic>,
+) -> WorkspaceDocumentDiagnosticReport {
+    WorkspaceDocumentDiagnosticReport::Full(WorkspaceFullDocumentDiagnosticReport {
+        uri,
+        version: version.map(i64::from),
+        full_document_diagnostic_report: FullDocumentDiagnosticReport {
+            result_id: Some(result_id),
+            items,
+        },
+    })
+}
+
+fn unchanged_report(
+    uri: lsp_types::Url,
+    version: Option<i32>,
+    result_id: String,
+) -> WorkspaceDocumentDiagnosticReport {
+    WorkspaceDocumentDiagnosticReport::Unchanged(WorkspaceUnchangedDocumentDiagnosticReport {
+        uri,
+        version: version.map(i64::from),
+        unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport { result_id },
+    })
+}
+
+/// Returns the SQL files in the project folders that are not ignored.
+fn sql_files(session: &Session) -> BTreeSet<PgLSPath> {
+    let roots: Vec<PathBuf> = match session.get_workspace_folders() {
+        Some(folders) => folders
+            .iter()
+            .filter_map(|folder| folder.uri.to_file_path().ok())
+            .collect(),
+        None => session.base_path().into_iter().collect(),
+    };
+
+    let (interner, _) = PathInterner::new();
+    let ctx = SqlFiles {
+        fs: &*session.fs,
+        workspace: &*session.workspace,
+        interner,
+        paths: RwLock::default(),
+    };
+
+    session.fs.traversal(Box::new(|scope: &dyn TraversalScope| {
+        for root in roots {
+            scope.evaluate(&ctx, root);
+        }
+    }));
+
+    ctx.paths.into_inner().unwrap()
+}
+
+/// Collects the SQL files a traversal finds
+struct SqlFiles<'a> {
+    fs: &'a dyn FileSystem,
+    workspace: &'a dyn Workspace,
+    interner: PathInterner,
+    paths: RwLock<BTreeSet<PgLSPath>>,
+}
+
+impl TraversalContext for SqlFiles<'_> {
+    fn interner(&self) -> &PathInterner {
+        &self.interner
+    }
+
+    fn push_diagnostic(&self, error: Error) {
+        tracing::error!("Failed to find SQL files: {error:?}");
+    }
+
+    fn can_handle(&self, pgls_path: &PgLSPath) -> bool {
+        let path = pgls_path.as_path();
+
+        let is_sql_file = self.fs.path_is_file(path) && has_sql_extension(pgls_path);
+
+        (is_sql_file || self.fs.path_is_dir(path)) && !is_ignored(self.workspace, pgls_path)
+    }
+
+    fn handle_path(&self, path: PgLSPath) {
+        self.store_path(path);
+    }
+
+    fn store_path(&self, path: PgLSPath) {
+        self.paths.write().unwrap().insert(path);
+    }
+
+    fn evaluated_paths(&self) -> BTreeSet<PgLSPath> {
+        self.paths.read().unwrap().clone()
+    }
+}
+
+fn has_sql_extension(path: &PgLSPath) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "sql" || ext == "pg")
+}
+
+/// Whether the configuration ignores the path, e.g. via `files.ignore` or `migrations.after`
+fn is_ignored(workspace: &dyn Workspace, path: &PgLSPath) -> bool {
+    workspace
+        .is_path_ignored(IsPathIgnoredParams {
+            pgls_path: path.clone(),
+        })
+        .unwrap_or(true)
+}
@@ -0,0 +1,51 @@
+use pgls_workspace::features::folding_ranges::{FoldingRangeKind, FoldingRangesParams};
+use rustc_hash::FxHashSet;
+use tower_lsp::lsp_types;
+
+use crate::{adapters, diagnostics::LspError, session::Session};
+
+/// Handler for the `textDocument/foldingRange` LSP request. Regions that fit on a single line
+/// are left out, as are regions spanning the same lines as one that comes before them.
+#[tracing::instrument(level = "debug", skip_all, fields(url = %params.text_document.uri), err)]
+pub(crate) fn get_folding_ranges(
+    session: &Session,
+    params: lsp_types::FoldingRangeParams,
+) -> Result<Vec<lsp_types::FoldingRange>, LspError> {
+    let url = params.text_document.uri;
+    let path = session.file_path(&url)?;
+    let doc = session.document(&url)?;
+
+    let result = session
+        .workspace
+        .pull_folding_ranges(FoldingRangesParams { path })?;
+
+    let mut seen = FxHashSet::default();
+    let mut ranges = Vec::new();
+    for folding_range in result.ranges {
+        let range = adapters::to_lsp::range(
+            &doc.line_index,
+            folding_range.range,
+            session.position_encoding(),
+        )?;
+
+        if range.start.line == range.end.line || !seen.insert((range.start.line, range.end.line)) {
+            continue;
+        }
+
+        ranges.push(lsp_types::FoldingRange {
+            start_line: range.start.line,
+            start_character: None,
+            end_line: range.end.line,
+            end_character: None,
+            kind: match folding_range.kind {
+                FoldingRangeKind::Comment => Some(lsp_types::FoldingRangeKind::Comment),
+                FoldingRangeKind::Statement
+                | FoldingRangeKind::Transaction
+                | FoldingRangeKind::FunctionBody => Some(lsp_types::FoldingRangeKind::Region),
+            },
+            collapsed_text: None,
+        });
+    }
+
+    Ok(ranges)
+}
@@ -0,0 +1,55 @@
+use pgls_workspace::features::selection_ranges::SelectionRangesParams;
+use tower_lsp::lsp_types;
+
+use crate::{
+    adapters::{self, get_cursor_position},
+    diagnostics::LspError,
+    session::Session,
+};
+
+/// Handler for the `textDocument/selectionRange` LSP request. Each selection range expands
+/// from the syntax node at the position to the statement that contains it.
+#[tracing::instrument(level = "debug", skip_all, fields(url = %params.text_document.uri), err)]
+pub(crate) fn get_selection_ranges(
+    session: &Session,
+    params: lsp_types::SelectionRangeParams,
+) -> Result<Vec<lsp_types::SelectionRange>, LspError> {
+    let url = params.text_document.uri;
+    let path = session.file_path(&url)?;
+    let doc = session.document(&url)?;
+
+    let positions = params
+        .positions
+        .iter()
+        .map(|position| get_cursor_position(session, &url, *position))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let result = session
+        .workspace
+        .pull_selection_ranges(SelectionRangesParams { path, positions })?;
+
+    params
+        .positions
+        .into_iter()
+        .zip(result.ranges)
+        .map(|(position, ranges)| {
+            let mut selection_range: Option<lsp_types::SelectionRange> = None;
+            for range in ranges.into_iter().rev() {
+                selection_range = Some(lsp_types::SelectionRange {
+                    range: adapters::to_lsp::range(
+                        &doc.line_index,
+                        range,
+                        session.position_encoding(),
+                    )?,
+                    parent: selection_range.map(Box::new),
+                });
+            }
+
+            // positions outside of a statement only select themselves
+            Ok(selection_range.unwrap_or(lsp_types::SelectionRange {
+                range: lsp_types::Range::new(position, position),
+                parent: None,
+            }))
+        })
+        .collect()
+}
@@ -1,4 +1,9 @@
-use crate::{documents::Document, session::Session, utils::apply_document_changes};
+use crate::{
+    documents::Document,
+    handlers,
+    session::{Session, SessionHandle},
+    utils::apply_document_changes,
+};
 use anyhow::Result;
 use pgls_workspace::workspace::{
     ChangeFileParams, CloseFileParams, GetFileContentParams, OpenFileParams,
@@ -77,7 +82,7 @@ pub(crate) async fn did_change(
 /// Handler for `textDocument/didClose` LSP notification
 #[tracing::instrument(level = "debug", skip(session), err)]
 pub(crate) async fn did_close(
-    session: &Session,
+    session: &SessionHandle,
     params: lsp_types::DidCloseTextDocumentParams,
 ) -> Result<()> {
     let url = params.text_document.uri;
@@ -89,12 +94,17 @@ pub(crate) async fn did_close(
 
     session.remove_document(&url);
 
-    let diagnostics = vec![];
-    let version = None;
-    session
-        .client
-        .publish_diagnostics(url, diagnostics, version)
-        .await;
+    if !session.pull_diagnostics_enabled() {
+        let diagnostics = vec![];
+        let version = None;
+        session
+            .client
+            .publish_diagnostics(url.clone(), diagnostics, version)
+            .await;
+    }
+
+    // the file on disk may differ from the closed document
+    handlers::diagnostics::publish_file_diagnostics(session, url).await;
 
     Ok(())
 }
@@ -72,11 +72,12 @@ impl LSPServer {
                 Some(base_path) => {
                     let watchers = ConfigName::file_names()
                         .iter()
-                        .map(|config_name| FileSystemWatcher {
+                        .chain(["**/*.sql", "**/*.pg"].iter())
+                        .map(|pattern| FileSystemWatcher {
                             glob_pattern: GlobPattern::String(format!(
                                 "{}/{}",
                                 base_path.display(),
-                                config_name
+                                pattern
                             )),
                             kind: Some(WatchKind::all()),
                         })
@@ -92,6 +93,13 @@ impl LSPServer {
 
         self.session.register_capabilities(capabilities).await;
     }
+
+    /// Checks the SQL files that are not open in the background
+    fn spawn_workspace_diagnostics(&self) {
+        tokio::spawn(handlers::diagnostics::publish_workspace_diagnostics(
+            self.session.clone(),
+        ));
+    }
 }
 
 #[tower_lsp::async_trait]
@@ -153,10 +161,12 @@ impl LanguageServer for LSPServer {
 
         // Diagnostics are disabled by default, so update them after fetching workspace config
         self.session.update_all_diagnostics().await;
+        self.spawn_workspace_diagnostics();
     }
 
     #[tracing::instrument(level = "info", skip_all)]
     async fn shutdown(&self) -> LspResult<()> {
+        self.session.cancel_workspace_diagnostics();
         Ok(())
     }
 
@@ -167,16 +177,16 @@ impl LanguageServer for LSPServer {
             .await;
         self.setup_capabilities().await;
         self.session.update_all_diagnostics().await;
+        self.spawn_workspace_diagnostics();
     }
 
     #[tracing::instrument(level = "trace", skip_all)]
     async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
-        let file_paths = params
-            .changes
-            .iter()
-            .map(|change| change.uri.to_file_path());
-        for file_path in file_paths {
-            match file_path {
+        let mut changed_files = Vec::new();
+        let mut config_changed = false;
+
+        for change in params.changes {
+            match change.uri.to_file_path() {
                 Ok(file_path) => {
                     let base_path = self.session.base_path();
                     if let Some(base_path) = base_path {
@@ -185,24 +195,37 @@ impl LanguageServer for LSPServer {
                             if ConfigName::file_names()
                                 .contains(&&*watched_file.display().to_string())
                             {
-                                self.session.load_workspace_settings(None).await;
-                                self.setup_capabilities().await;
-                                // self.session.update_all_diagnostics().await;
-                                // for now we are only interested to the configuration file,
-                                // so it's OK to exist the loop
-                                break;
+                                config_changed = true;
+                                continue;
                             }
                         }
                     }
+
+                    changed_files.push(change.uri);
                 }
                 Err(_) => {
                     error!(
-                        "The Workspace root URI {file_path:?} could not be parsed as a filesystem path"
+                        "The Workspace root URI {:?} could not be parsed as a filesystem path",
+                        change.uri
                     );
                     continue;
                 }
             }
         }
+
+        if config_changed {
+            self.session.load_workspace_settings(None).await;
+            self.setup_capabilities().await;
+        }
+
+        for url in changed_files {
+            handlers::diagnostics::publish_file_diagnostics(&self.session, url).await;
+        }
+
+        if config_changed {
+            // the configuration decides which files are checked, so check all of them again
+            self.spawn_workspace_diagnostics();
+        }
     }
 
     #[tracing::instrument(level = "trace", skip_all)]
@@ -286,6 +309,61 @@ impl LanguageServer for LSPServer {
         }
     }
 
+    #[tracing::instrument(level = "trace", skip_all)]
+    async fn completion_resolve(&self, item: CompletionItem) -> LspResult<CompletionItem> {
+        match handlers::completions::resolve_completion_item(&self.session, item) {
+            Ok(result) => LspResult::Ok(result),
+            Err(e) => LspResult::Err(into_lsp_error(e)),
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip_all)]
+    async fn diagnostic(
+        &self,
+        params: DocumentDiagnosticParams,
+    ) -> LspResult<DocumentDiagnosticReportResult> {
+        handlers::diagnostics::pull_document_diagnostics(&self.session, params)
+            .await
+            .map_err(into_lsp_error)
+    }
+
+    #[tracing::instrument(level = "trace", skip_all)]
+    async fn workspace_diagnostic(
+        &self,
+        params: WorkspaceDiagnosticParams,
+    ) -> LspResult<WorkspaceDiagnosticReportResult> {
+        handlers::diagnostics::pull_workspace_diagnostics(&self.session, params)
+            .await
+            .map_err(into_lsp_error)
+    }
+
+    #[tracing::instrument(level = "trace", skip_all)]
+    async fn code_lens(&self, params: CodeLensParams) -> LspResult<Option<Vec<CodeLens>>> {
+        handlers::code_lenses::get_code_lenses(&self.session, params)
+            .map(Some)
+            .map_err(into_lsp_error)
+    }
+
+    #[tracing::instrument(level = "trace", skip_all)]
+    async fn folding_range(
+        &self,
+        params: FoldingRangeParams,
+    ) -> LspResult<Option<Vec<FoldingRange>>> {
+        handlers::folding_ranges::get_folding_ranges(&self.session, params)
+            .map(Some)
+            .map_err(into_lsp_error)
+    }
+
+    #[tracing::instrument(level = "trace", skip_all)]
+    async fn selection_range(
+        &self,
+        params: SelectionRangeParams,
+    ) -> LspResult<Option<Vec<SelectionRange>>> {
+        handlers::selection_ranges::get_selection_ranges(&self.session, params)
+            .map(Some)
+            .map_err(into_lsp_error)
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     async fn code_action(&self, params: CodeActionParams) -> LspResult<Option<CodeActionResponse>> {
         match handlers::code_actions::get_actions(&self.session, params) {
@@ -451,6 +529,15 @@ impl ServerFactory {
             ready(Ok(Some(())))
         });
 
+        // "window/workDoneProgress/cancel" is not handled by tower-lsp
+        builder = builder.custom_method(
+            "window/workDoneProgress/cancel",
+            |server: &LSPServer, params: WorkDoneProgressCancelParams| {
+                handlers::diagnostics::cancel_progress(&server.session, params);
+                ready(())
+            },
+        );
+
         workspace_method!(builder, is_path_ignored);
         workspace_method!(builder, update_settings);
         workspace_method!(builder, get_file_content);
@@ -458,10 +545,25 @@ impl ServerFactory {
         workspace_method!(builder, change_file);
         workspace_method!(builder, close_file);
         workspace_method!(builder, pull_file_diagnostics);
+        workspace_method!(builder, pull_content_diagnostics);
         workspace_method!(builder, get_completions);
+        workspace_method!(builder, resolve_completion_item);
+        workspace_method!(builder, record_completion_usage);
         workspace_method!(builder, register_project_folder);
         workspace_method!(builder, unregister_project_folder);
         workspace_method!(builder, invalidate_schema_cache);
+        workspace_method!(builder, execute_statement);
+        workspace_method!(builder, execute_statements);
+        workspace_method!(builder, statement_execution_progress);
+        workspace_method!(builder, cancel_statement_execution);
+        workspace_method!(builder, select_connection);
+        workspace_method!(builder, describe_statement);
+        workspace_method!(builder, explain_statement);
+        workspace_method!(builder, pull_code_lenses);
+        workspace_method!(builder, pull_folding_ranges);
+        workspace_method!(builder, pull_selection_ranges);
+        workspace_method!(builder, check_function);
+        workspace_method!(builder, function_callers);
 
         let (service, socket) = builder.finish();
         ServerConnection { socket, service }
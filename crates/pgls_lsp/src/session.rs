@@ -1,3 +1,4 @@
+use crate::adapters::line_index::LineIndex;
 use crate::adapters::{PositionEncoding, WideEncoding, negotiated_encoding};
 use crate::diagnostics::LspError;
 use crate::documents::Document;
@@ -16,13 +17,13 @@ use pgls_workspace::configuration::{LoadedConfiguration, load_configuration};
 use pgls_workspace::features;
 use pgls_workspace::workspace::{RegisterProjectFolderParams, UpdateSettingsParams};
 use pgls_workspace::{DynRef, WorkspaceError};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde_json::Value;
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::sync::RwLock;
 use std::sync::atomic::Ordering;
-use std::sync::atomic::{AtomicBool, AtomicU8};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64};
+use std::sync::{Mutex, RwLock};
 use tokio::sync::Notify;
 use tokio::sync::OnceCell;
 use tower_lsp::lsp_types::Url;
@@ -67,6 +68,20 @@ pub(crate) struct Session {
     /// A flag to notify a message to the user when they are using the deprecated config filename
     notified_deprecated_config: AtomicBool,
 
+    /// Incremented whenever diagnostics may change without the documents changing, e.g. when
+    /// the configuration changes. Part of the result ids of pulled diagnostics.
+    diagnostics_generation: AtomicU64,
+
+    /// Set when code lenses were shown before the diagnostics they summarize were computed
+    code_lenses_pending: AtomicBool,
+
+    /// Identifies the latest computation of the diagnostics of files that are not open.
+    /// Earlier computations stop once it changes.
+    workspace_diagnostics_run: AtomicU64,
+
+    /// Files that are not open but have published diagnostics
+    workspace_diagnostics_files: Mutex<FxHashSet<lsp_types::Url>>,
+
     /// File system to read files inside the workspace
     pub(crate) fs: DynRef<'static, dyn FileSystem>,
 
@@ -169,6 +184,10 @@ impl Session {
             config_path: None,
             notified_broken_configuration: AtomicBool::new(false),
             notified_deprecated_config: AtomicBool::new(false),
+            diagnostics_generation: AtomicU64::new(0),
+            code_lenses_pending: AtomicBool::new(false),
+            workspace_diagnostics_run: AtomicU64::new(0),
+            workspace_diagnostics_files: Mutex::default(),
         }
     }
 
@@ -256,60 +275,40 @@ impl Session {
     /// Computes diagnostics for the file matching the provided url and publishes
     /// them to the client. Called from [`handlers::text_document`] when a file's
     /// contents changes.
+    ///
+    /// Does nothing if the client pulls diagnostics, since it requests them again itself
+    /// after changing a document.
     #[tracing::instrument(level = "trace", skip_all, fields(url = display(&url), diagnostic_count), err)]
     pub(crate) async fn update_diagnostics(&self, url: lsp_types::Url) -> Result<(), LspError> {
-        let pgls_path = self.file_path(&url)?;
-        let doc = self.document(&url)?;
-        if self.configuration_status().is_error() && !self.notified_broken_configuration() {
-            self.set_notified_broken_configuration();
-            self.client
-                    .show_message(MessageType::WARNING, "The configuration file has errors. PgLSP will report only parsing errors until the configuration is fixed.")
-                    .await;
+        if self.pull_diagnostics_enabled() {
+            return Ok(());
         }
 
-        let categories = RuleCategoriesBuilder::default().all();
+        let pgls_path = self.file_path(&url)?;
+        let doc = self.document(&url)?;
+        self.notify_broken_configuration().await;
 
-        let diagnostics: Vec<lsp_types::Diagnostic> = {
-            let result = self.workspace.pull_file_diagnostics(
-                features::diagnostics::PullFileDiagnosticsParams {
-                    path: pgls_path.clone(),
-                    max_diagnostics: u32::MAX,
-                    categories: categories.build(),
-                    only: Vec::new(),
-                    skip: Vec::new(),
-                },
-            )?;
-
-            result
-                .diagnostics
-                .into_iter()
-                .filter_map(|d| {
-                    match utils::diagnostic_to_lsp(
-                        d,
-                        &url,
-                        &doc.line_index,
-                        self.position_encoding(),
-                        None,
-                    ) {
-                        Ok(diag) => Some(diag),
-                        Err(err) => {
-                            error!("failed to convert diagnostic to LSP: {err:?}");
-                            None
-                        }
-                    }
-                })
-                .collect()
-        };
+        let diagnostics = self.compute_diagnostics(&url, pgls_path, &doc.line_index)?;
+        self.set_document_diagnostics(&url, doc.version, &diagnostics);
 
         self.client
             .publish_diagnostics(url, diagnostics, Some(doc.version))
             .await;
 
+        self.refresh_pending_code_lenses().await;
+
         Ok(())
     }
 
-    /// Updates diagnostics for every [`Document`] in this [`Session`]
+    /// Updates diagnostics for every [`Document`] in this [`Session`]. If the client pulls
+    /// diagnostics, it is asked to pull them again instead.
     pub(crate) async fn update_all_diagnostics(&self) {
+        if self.pull_diagnostics_enabled() {
+            self.refresh_diagnostics().await;
+            self.refresh_code_lenses().await;
+            return;
+        }
+
         let mut futures: FuturesUnordered<_> = self
             .documents
             .read()
@@ -323,6 +322,226 @@ impl Session {
                 error!("Error while updating diagnostics: {}", e);
             }
         }
+
+        self.refresh_code_lenses().await;
+    }
+
+    /// Updates the diagnostics of a document that changed for reasons other than its content,
+    /// e.g. because it uses another database connection now.
+    pub(crate) async fn invalidate_diagnostics(&self, url: lsp_types::Url) {
+        if self.pull_diagnostics_enabled() {
+            self.refresh_diagnostics().await;
+        } else if let Err(err) = self.update_diagnostics(url).await {
+            error!("Failed to update diagnostics: {}", err);
+        }
+
+        self.refresh_code_lenses().await;
+    }
+
+    /// Invalidates all pulled diagnostics and asks the client to pull them again.
+    async fn refresh_diagnostics(&self) {
+        self.diagnostics_generation.fetch_add(1, Ordering::Relaxed);
+
+        for doc in self.documents.write().unwrap().values_mut() {
+            doc.diagnostics = None;
+        }
+
+        let refresh_support = self
+            .client_capabilities()
+            .and_then(|c| c.workspace.as_ref())
+            .and_then(|c| c.diagnostic.as_ref())
+            .and_then(|c| c.refresh_support)
+            == Some(true);
+
+        if refresh_support {
+            if let Err(err) = self.client.workspace_diagnostic_refresh().await {
+                error!("Failed to refresh diagnostics: {}", err);
+            }
+        }
+    }
+
+    /// Remembers that code lenses were shown without the diagnostics they summarize.
+    pub(crate) fn set_code_lenses_pending(&self) {
+        self.code_lenses_pending.store(true, Ordering::Relaxed);
+    }
+
+    /// Asks the client to request the code lenses again if some were shown before the
+    /// diagnostics they summarize were computed.
+    pub(crate) async fn refresh_pending_code_lenses(&self) {
+        if self.code_lenses_pending.swap(false, Ordering::Relaxed) {
+            self.refresh_code_lenses().await;
+        }
+    }
+
+    /// Asks the client to request the code lenses again if it supports it, e.g. because the
+    /// diagnostics they summarize changed.
+    async fn refresh_code_lenses(&self) {
+        let refresh_support = self
+            .client_capabilities()
+            .and_then(|c| c.workspace.as_ref())
+            .and_then(|c| c.code_lens.as_ref())
+            .and_then(|c| c.refresh_support)
+            == Some(true);
+
+        if refresh_support {
+            if let Err(err) = self.client.code_lens_refresh().await {
+                error!("Failed to refresh code lenses: {}", err);
+            }
+        }
+    }
+
+    /// Remembers the diagnostics computed for a version of a document, so that they can be
+    /// summarized without computing them again.
+    pub(crate) fn set_document_diagnostics(
+        &self,
+        url: &lsp_types::Url,
+        version: i32,
+        diagnostics: &[lsp_types::Diagnostic],
+    ) {
+        if let Some(doc) = self
+            .documents
+            .write()
+            .unwrap()
+            .get_mut(url)
+            .filter(|doc| doc.version == version)
+        {
+            doc.diagnostics = Some(diagnostics.into());
+        }
+    }
+
+    /// Returns the result id of diagnostics pulled for the given document state, e.g. its
+    /// version. It changes whenever diagnostics are invalidated.
+    pub(crate) fn diagnostics_result_id(&self, state: impl std::fmt::Display) -> String {
+        format!(
+            "{}:{state}",
+            self.diagnostics_generation.load(Ordering::Relaxed)
+        )
+    }
+
+    /// Computes the diagnostics of an open file and converts them to LSP diagnostics.
+    pub(crate) fn compute_diagnostics(
+        &self,
+        url: &lsp_types::Url,
+        path: PgLSPath,
+        line_index: &LineIndex,
+    ) -> Result<Vec<lsp_types::Diagnostic>, LspError> {
+        let categories = RuleCategoriesBuilder::default().all();
+
+        let result = self.workspace.pull_file_diagnostics(
+            features::diagnostics::PullFileDiagnosticsParams {
+                path,
+                max_diagnostics: u32::MAX,
+                categories: categories.build(),
+                only: Vec::new(),
+                skip: Vec::new(),
+            },
+        )?;
+
+        Ok(self.to_lsp_diagnostics(result, url, line_index))
+    }
+
+    /// Lints the content of a file that is not open and converts the diagnostics to LSP
+    /// diagnostics. Nothing is checked against the database.
+    pub(crate) fn compute_content_diagnostics(
+        &self,
+        url: &lsp_types::Url,
+        path: PgLSPath,
+        content: String,
+    ) -> Result<Vec<lsp_types::Diagnostic>, LspError> {
+        let doc = Document::new(0, &content);
+
+        let result = self.workspace.pull_content_diagnostics(
+            features::diagnostics::PullContentDiagnosticsParams {
+                path,
+                content,
+                categories: RuleCategoriesBuilder::default().with_lint().build(),
+            },
+        )?;
+
+        Ok(self.to_lsp_diagnostics(result, url, &doc.line_index))
+    }
+
+    fn to_lsp_diagnostics(
+        &self,
+        result: features::diagnostics::PullDiagnosticsResult,
+        url: &lsp_types::Url,
+        line_index: &LineIndex,
+    ) -> Vec<lsp_types::Diagnostic> {
+        result
+            .diagnostics
+            .into_iter()
+            .filter_map(|d| {
+                match utils::diagnostic_to_lsp(d, url, line_index, self.position_encoding(), None) {
+                    Ok(diag) => Some(diag),
+                    Err(err) => {
+                        error!("failed to convert diagnostic to LSP: {err:?}");
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Starts a new computation of the diagnostics of files that are not open, which cancels
+    /// the previous one, and returns its id.
+    pub(crate) fn start_workspace_diagnostics(&self) -> u64 {
+        self.workspace_diagnostics_run
+            .fetch_add(1, Ordering::Relaxed)
+            .wrapping_add(1)
+    }
+
+    /// Cancels the computation of the diagnostics of files that are not open.
+    pub(crate) fn cancel_workspace_diagnostics(&self) {
+        self.workspace_diagnostics_run
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// True if the computation with the given id was neither cancelled nor replaced
+    pub(crate) fn is_workspace_diagnostics_run(&self, run: u64) -> bool {
+        self.workspace_diagnostics_run.load(Ordering::Relaxed) == run
+    }
+
+    /// Records whether diagnostics are published for a file that is not open. Returns whether
+    /// there were before.
+    pub(crate) fn set_workspace_diagnostics_published(
+        &self,
+        url: &lsp_types::Url,
+        published: bool,
+    ) -> bool {
+        let mut files = self.workspace_diagnostics_files.lock().unwrap();
+        if published {
+            !files.insert(url.clone())
+        } else {
+            files.remove(url)
+        }
+    }
+
+    /// Returns the files that are not open but have published diagnostics
+    pub(crate) fn workspace_diagnostics_files(&self) -> Vec<lsp_types::Url> {
+        self.workspace_diagnostics_files
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Warns the user once that only parsing errors are reported while the configuration is broken.
+    pub(crate) async fn notify_broken_configuration(&self) {
+        if self.configuration_status().is_error() && !self.notified_broken_configuration() {
+            self.set_notified_broken_configuration();
+            self.client
+                    .show_message(MessageType::WARNING, "The configuration file has errors. PgLSP will report only parsing errors until the configuration is fixed.")
+                    .await;
+        }
+    }
+
+    /// True if the client pulls diagnostics with `textDocument/diagnostic` and
+    /// `workspace/diagnostic` instead of having them published
+    pub(crate) fn pull_diagnostics_enabled(&self) -> bool {
+        self.client_capabilities()
+            .and_then(|c| c.text_document.as_ref())
+            .is_some_and(|c| c.diagnostic.is_some())
     }
 
     /// Get a [`Document`] matching the provided [`lsp_types::Url`]
@@ -344,6 +563,16 @@ impl Session {
         self.documents.write().unwrap().insert(url, document);
     }
 
+    /// Returns all open [`Document`]s
+    pub(crate) fn documents(&self) -> Vec<(lsp_types::Url, Document)> {
+        self.documents
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(url, doc)| (url.clone(), doc.clone()))
+            .collect()
+    }
+
     /// Remove the [`Document`] matching the provided [`lsp_types::Url`]
     pub(crate) fn remove_document(&self, url: &lsp_types::Url) {
         self.documents.write().unwrap().remove(url);
@@ -830,6 +830,7 @@ async fn test_execute_statement(test_db: PgPool) -> Result<()> {
         db: Some(PartialDatabaseConfiguration {
             database: Some(database),
             host: Some(host),
+            allow_statement_executions_against: Some(StringSet::from_iter(["*/*".to_string()])),
             ..Default::default()
         }),
         ..Default::default()
@@ -952,6 +953,166 @@ async fn test_execute_statement(test_db: PgPool) -> Result<()> {
     Ok(())
 }
 
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_cancel_statement_execution(test_db: PgPool) -> Result<()> {
+    let factory = ServerFactory::default();
+    let mut fs = MemoryFileSystem::default();
+
+    let database = test_db
+        .connect_options()
+        .get_database()
+        .unwrap()
+        .to_string();
+    let host = test_db.connect_options().get_host().to_string();
+
+    let mut conf = PartialConfiguration::init();
+    conf.merge_with(PartialConfiguration {
+        db: Some(PartialDatabaseConfiguration {
+            database: Some(database),
+            host: Some(host),
+            allow_statement_executions_against: Some(StringSet::from_iter(["*/*".to_string()])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+
+    fs.insert(
+        url!("postgres-language-server.jsonc")
+            .to_file_path()
+            .unwrap(),
+        serde_json::to_string_pretty(&conf).unwrap(),
+    );
+
+    let (service, client) = factory
+        .create_with_fs(None, DynRef::Owned(Box::new(fs)))
+        .into_inner();
+
+    let (stream, sink) = client.split();
+    let mut server = Server::new(service);
+
+    let (sender, _) = channel(CHANNEL_BUFFER_SIZE);
+    let reader = tokio::spawn(client_handler(stream, sink, sender));
+
+    server.initialize().await?;
+    server.initialized().await?;
+
+    server.load_configuration().await?;
+
+    let doc_url = url!("test.sql");
+
+    server
+        .open_named_document("select pg_sleep(30);".to_string(), doc_url.clone(), "sql")
+        .await?;
+
+    let code_actions_response = server
+        .request::<CodeActionParams, CodeActionResponse>(
+            "textDocument/codeAction",
+            "_code_action",
+            CodeActionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: doc_url.clone(),
+                },
+                range: Range {
+                    start: Position::new(0, 3),
+                    end: Position::new(0, 3),
+                },
+                context: CodeActionContext::default(),
+                partial_result_params: PartialResultParams::default(),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            },
+        )
+        .await?
+        .unwrap();
+
+    let arguments = code_actions_response
+        .iter()
+        .find_map(|action_or_cmd| match action_or_cmd {
+            lsp::CodeActionOrCommand::CodeAction(code_action) => code_action
+                .command
+                .as_ref()
+                .filter(|cmd| cmd.command == "pgls.executeStatement")
+                .and_then(|cmd| cmd.arguments.clone()),
+            _ => None,
+        })
+        .expect("Did not find executeStatement command!");
+
+    // the statement runs longer than the timeout of Server::request, so the request is sent
+    // to the service directly
+    let execution = server
+        .service
+        .get_mut()
+        .ready()
+        .await
+        .map_err(Error::msg)?
+        .call(
+            Request::build("workspace/executeCommand")
+                .id("_execStmt")
+                .params(to_value(ExecuteCommandParams {
+                    command: "pgls.executeStatement".into(),
+                    arguments,
+                    ..Default::default()
+                })?)
+                .finish(),
+        );
+    let execution = tokio::spawn(execution);
+
+    let is_running = async || {
+        sqlx::query_scalar::<_, bool>(
+            r#"
+            select exists (
+                select 1
+                from pg_stat_activity
+                where state = 'active'
+                    and pid <> pg_backend_pid()
+                    and (query ilike 'fetch%' or query like '%pg_sleep(30)%')
+            )
+        "#,
+        )
+        .fetch_one(&test_db)
+        .await
+        .unwrap()
+    };
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !is_running().await {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .context("the statement should be running")?;
+
+    // the server keeps handling requests while the statement runs
+    server
+        .request::<_, Option<Value>>(
+            "workspace/executeCommand",
+            "_cancelStmt",
+            ExecuteCommandParams {
+                command: "pgls.cancelStatementExecution".into(),
+                arguments: vec![to_value(&doc_url)?],
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let response = tokio::time::timeout(Duration::from_secs(5), execution)
+        .await
+        .context("the cancelled statement should return")??
+        .map_err(Error::msg)?
+        .context("executeCommand returned no response")?;
+    let (_, body) = response.into_parts();
+    let error = body.expect_err("the cancelled statement should fail");
+    assert!(
+        error
+            .message
+            .contains("canceling statement due to user request"),
+        "{error:?}"
+    );
+
+    server.shutdown().await?;
+    reader.abort();
+
+    Ok(())
+}
+
 #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
 async fn test_invalidate_schema_cache(test_db: PgPool) -> Result<()> {
     let factory = ServerFactory::default();
@@ -2454,3 +2615,311 @@ async fn test_crash_on_delete_character() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn test_pull_diagnostics() -> Result<()> {
+    let factory = ServerFactory::default();
+    let mut fs = MemoryFileSystem::default();
+    fs.insert(url!("unopened.sql").to_file_path().unwrap(), "selec 1;");
+
+    let (service, client) = factory
+        .create_with_fs(None, DynRef::Owned(Box::new(fs)))
+        .into_inner();
+    let (stream, sink) = client.split();
+    let mut server = Server::new(service);
+
+    let (sender, _) = channel(CHANNEL_BUFFER_SIZE);
+    let reader = tokio::spawn(client_handler(stream, sink, sender));
+
+    let init: InitializeResult = server
+        .request(
+            "initialize",
+            "_init",
+            InitializeParams {
+                process_id: None,
+                root_path: None,
+                root_uri: Some(url!("")),
+                initialization_options: None,
+                capabilities: ClientCapabilities {
+                    text_document: Some(lsp::TextDocumentClientCapabilities {
+                        diagnostic: Some(lsp::DiagnosticClientCapabilities::default()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                trace: None,
+                workspace_folders: None,
+                client_info: None,
+                locale: None,
+            },
+        )
+        .await?
+        .context("initialize returned None")?;
+    assert!(init.capabilities.diagnostic_provider.is_some());
+
+    server.initialized().await?;
+
+    server.open_document("selec 1;").await?;
+
+    let pull = |previous_result_id| lsp::DocumentDiagnosticParams {
+        text_document: TextDocumentIdentifier {
+            uri: url!("document.sql"),
+        },
+        identifier: None,
+        previous_result_id,
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    };
+
+    let report: lsp::DocumentDiagnosticReportResult = server
+        .request("textDocument/diagnostic", "_pull_diagnostics", pull(None))
+        .await?
+        .context("textDocument/diagnostic returned None")?;
+    let lsp::DocumentDiagnosticReportResult::Report(lsp::DocumentDiagnosticReport::Full(report)) =
+        report
+    else {
+        bail!("expected a full report, got {report:?}");
+    };
+    assert!(!report.full_document_diagnostic_report.items.is_empty());
+
+    // the document did not change, so neither did its diagnostics
+    let report: lsp::DocumentDiagnosticReportResult = server
+        .request(
+            "textDocument/diagnostic",
+            "_pull_diagnostics_again",
+            pull(report.full_document_diagnostic_report.result_id),
+        )
+        .await?
+        .context("textDocument/diagnostic returned None")?;
+    assert!(matches!(
+        report,
+        lsp::DocumentDiagnosticReportResult::Report(lsp::DocumentDiagnosticReport::Unchanged(_))
+    ));
+
+    let report: lsp::WorkspaceDiagnosticReportResult = server
+        .request(
+            "workspace/diagnostic",
+            "_pull_workspace_diagnostics",
+            lsp::WorkspaceDiagnosticParams {
+                identifier: None,
+                previous_result_ids: vec![],
+                work_done_progress_params: WorkDoneProgressParams::default(),
+                partial_result_params: PartialResultParams::default(),
+            },
+        )
+        .await?
+        .context("workspace/diagnostic returned None")?;
+    let lsp::WorkspaceDiagnosticReportResult::Report(report) = report else {
+        bail!("expected a full report, got {report:?}");
+    };
+
+    let unopened = report
+        .items
+        .iter()
+        .find_map(|item| match item {
+            lsp::WorkspaceDocumentDiagnosticReport::Full(report)
+                if report.uri == url!("unopened.sql") =>
+            {
+                Some(report)
+            }
+            _ => None,
+        })
+        .context("expected diagnostics of the unopened file")?;
+    assert!(!unopened.full_document_diagnostic_report.items.is_empty());
+
+    server.shutdown().await?;
+    reader.abort();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_workspace_diagnostics() -> Result<()> {
+    let factory = ServerFactory::default();
+    let mut fs = MemoryFileSystem::default();
+    fs.insert(url!("unopened.sql").to_file_path().unwrap(), "selec 1;");
+
+    let (service, client) = factory
+        .create_with_fs(None, DynRef::Owned(Box::new(fs)))
+        .into_inner();
+    let (stream, sink) = client.split();
+    let mut server = Server::new(service);
+
+    let (sender, mut receiver) = channel(CHANNEL_BUFFER_SIZE);
+    let reader = tokio::spawn(client_handler(stream, sink, sender));
+
+    server.initialize().await?;
+    server.initialized().await?;
+
+    let diagnostics = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            match receiver.next().await {
+                Some(ServerNotification::PublishDiagnostics(msg))
+                    if msg.uri == url!("unopened.sql") =>
+                {
+                    return msg.diagnostics;
+                }
+                Some(_) => continue,
+                None => return Vec::new(),
+            }
+        }
+    })
+    .await
+    .context("expected diagnostics of the unopened file")?;
+
+    assert!(!diagnostics.is_empty());
+
+    server.shutdown().await?;
+    reader.abort();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_code_lenses() -> Result<()> {
+    let factory = ServerFactory::default();
+    let (service, client) = factory.create(None).into_inner();
+    let (stream, sink) = client.split();
+    let mut server = Server::new(service);
+
+    let (sender, _) = channel(CHANNEL_BUFFER_SIZE);
+    let reader = tokio::spawn(client_handler(stream, sink, sender));
+
+    server.initialize().await?;
+    server.initialized().await?;
+
+    server.open_document("select 1;\nselec 2;").await?;
+
+    let lenses: Vec<lsp::CodeLens> = server
+        .request(
+            "textDocument/codeLens",
+            "_code_lens",
+            lsp::CodeLensParams {
+                text_document: TextDocumentIdentifier {
+                    uri: url!("document.sql"),
+                },
+                work_done_progress_params: WorkDoneProgressParams::default(),
+                partial_result_params: PartialResultParams::default(),
+            },
+        )
+        .await?
+        .context("textDocument/codeLens returned None")?;
+
+    let summaries: Vec<(u32, String)> = lenses
+        .into_iter()
+        .filter_map(|lens| {
+            let command = lens.command?;
+            command
+                .command
+                .is_empty()
+                .then_some((lens.range.start.line, command.title))
+        })
+        .collect();
+
+    assert_eq!(
+        summaries,
+        vec![(0, "No problems".to_string()), (1, "1 error".to_string())]
+    );
+
+    server.shutdown().await?;
+    reader.abort();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_folding_and_selection_ranges() -> Result<()> {
+    let factory = ServerFactory::default();
+    let (service, client) = factory.create(None).into_inner();
+    let (stream, sink) = client.split();
+    let mut server = Server::new(service);
+
+    let (sender, _) = channel(CHANNEL_BUFFER_SIZE);
+    let reader = tokio::spawn(client_handler(stream, sink, sender));
+
+    server.initialize().await?;
+    server.initialized().await?;
+
+    server
+        .open_document(
+            "-- create the users table\n\
+             -- and its helpers\n\
+             begin;\n\
+             create table users (\n  id int,\n  email text\n);\n\
+             create function one() returns int as $$\n  select 1;\n$$ language sql;\n\
+             commit;",
+        )
+        .await?;
+
+    let folding_ranges: Vec<lsp::FoldingRange> = server
+        .request(
+            "textDocument/foldingRange",
+            "_folding_range",
+            lsp::FoldingRangeParams {
+                text_document: TextDocumentIdentifier {
+                    uri: url!("document.sql"),
+                },
+                work_done_progress_params: WorkDoneProgressParams::default(),
+                partial_result_params: PartialResultParams::default(),
+            },
+        )
+        .await?
+        .context("textDocument/foldingRange returned None")?;
+
+    let folding_ranges: Vec<(u32, u32, Option<lsp::FoldingRangeKind>)> = folding_ranges
+        .into_iter()
+        .map(|range| (range.start_line, range.end_line, range.kind))
+        .collect();
+
+    // the function body spans the same lines as its statement
+    assert_eq!(
+        folding_ranges,
+        vec![
+            (0, 1, Some(lsp::FoldingRangeKind::Comment)),
+            (2, 10, Some(lsp::FoldingRangeKind::Region)),
+            (3, 6, Some(lsp::FoldingRangeKind::Region)),
+            (7, 9, Some(lsp::FoldingRangeKind::Region)),
+        ]
+    );
+
+    let selection_ranges: Vec<lsp::SelectionRange> = server
+        .request(
+            "textDocument/selectionRange",
+            "_selection_range",
+            lsp::SelectionRangeParams {
+                text_document: TextDocumentIdentifier {
+                    uri: url!("document.sql"),
+                },
+                positions: vec![Position::new(5, 3)],
+                work_done_progress_params: WorkDoneProgressParams::default(),
+                partial_result_params: PartialResultParams::default(),
+            },
+        )
+        .await?
+        .context("textDocument/selectionRange returned None")?;
+
+    let selection_range = selection_ranges
+        .first()
+        .context("expected a selection range")?;
+    assert_eq!(
+        selection_range.range,
+        Range::new(Position::new(5, 2), Position::new(5, 7))
+    );
+
+    let mut outermost = selection_range;
+    while let Some(parent) = outermost.parent.as_deref() {
+        assert!(parent.range.start <= outermost.range.start);
+        assert!(parent.range.end >= outermost.range.end);
+        outermost = parent;
+    }
+    assert_eq!(
+        outermost.range,
+        Range::new(Position::new(3, 0), Position::new(6, 2))
+    );
+
+    server.shutdown().await?;
+    reader.abort();
+
+    Ok(())
+}
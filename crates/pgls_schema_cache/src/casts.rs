@@ -0,0 +1,87 @@
+use crate::schema_cache::SchemaCacheItem;
+
+/// In which contexts a cast may be applied without being explicitly requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CastContext {
+    /// The cast is applied in any context, e.g. `int4` to `int8`.
+    Implicit,
+
+    /// The cast is applied when assigning to a column, e.g. `int8` to `int4`.
+    Assignment,
+
+    /// The cast has to be requested with `CAST` or `::`.
+    Explicit,
+}
+
+impl From<&str> for CastContext {
+    fn from(value: &str) -> Self {
+        match value {
+            "i" => CastContext::Implicit,
+            "a" => CastContext::Assignment,
+            "e" => CastContext::Explicit,
+            _ => panic!("Invalid Cast Context {value}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CastQueried {
+    source_type_id: i64,
+    target_type_id: i64,
+    context: String,
+}
+
+impl From<CastQueried> for Cast {
+    fn from(value: CastQueried) -> Self {
+        Self {
+            source_type_id: value.source_type_id,
+            target_type_id: value.target_type_id,
+            context: CastContext::from(value.context.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cast {
+    pub source_type_id: i64,
+    pub target_type_id: i64,
+    pub context: CastContext,
+}
+
+impl SchemaCacheItem for Cast {
+    type Item = Cast;
+
+    async fn load(pool: &sqlx::PgPool) -> Result<Vec<Self::Item>, sqlx::Error> {
+        let casts = sqlx::query_file_as!(CastQueried, "src/queries/casts.sql")
+            .fetch_all(pool)
+            .await?;
+
+        Ok(casts.into_iter().map(Cast::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use crate::{CastContext, SchemaCache};
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn loads_casts(test_db: PgPool) {
+        let cache = SchemaCache::load(&test_db)
+            .await
+            .expect("Failed to load Schema Cache");
+
+        let int4 = cache.find_type("int4", Some("pg_catalog")).unwrap();
+        let int8 = cache.find_type("int8", Some("pg_catalog")).unwrap();
+
+        assert_eq!(
+            cache.find_cast(int4.id, int8.id).map(|c| c.context),
+            Some(CastContext::Implicit)
+        );
+        assert_eq!(
+            cache.find_cast(int8.id, int4.id).map(|c| c.context),
+            Some(CastContext::Assignment)
+        );
+    }
+}
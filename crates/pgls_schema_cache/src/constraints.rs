@@ -0,0 +1,136 @@
+use crate::schema_cache::SchemaCacheItem;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstraintKind {
+    Check,
+    ForeignKey,
+    NotNull,
+    PrimaryKey,
+    Unique,
+    Trigger,
+    Exclusion,
+}
+
+impl From<&str> for ConstraintKind {
+    fn from(value: &str) -> Self {
+        match value {
+            "c" => ConstraintKind::Check,
+            "f" => ConstraintKind::ForeignKey,
+            "n" => ConstraintKind::NotNull,
+            "p" => ConstraintKind::PrimaryKey,
+            "u" => ConstraintKind::Unique,
+            "t" => ConstraintKind::Trigger,
+            "x" => ConstraintKind::Exclusion,
+            _ => panic!("Invalid Constraint Kind {value}"),
+        }
+    }
+}
+
+impl std::fmt::Display for ConstraintKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let txt = match self {
+            ConstraintKind::Check => "Check",
+            ConstraintKind::ForeignKey => "Foreign Key",
+            ConstraintKind::NotNull => "Not Null",
+            ConstraintKind::PrimaryKey => "Primary Key",
+            ConstraintKind::Unique => "Unique",
+            ConstraintKind::Trigger => "Constraint Trigger",
+            ConstraintKind::Exclusion => "Exclusion",
+        };
+
+        write!(f, "{txt}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ConstraintQueried {
+    name: String,
+    schema: String,
+    table_name: String,
+    kind: String,
+    definition: String,
+    comment: Option<String>,
+}
+
+impl From<ConstraintQueried> for Constraint {
+    fn from(value: ConstraintQueried) -> Self {
+        Self {
+            name: value.name,
+            schema: value.schema,
+            table_name: value.table_name,
+            kind: ConstraintKind::from(value.kind.as_str()),
+            definition: value.definition,
+            comment: value.comment,
+        }
+    }
+}
+
+/// A table constraint, e.g. `CHECK (price > 0)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint {
+    pub name: String,
+    pub schema: String,
+    pub table_name: String,
+    pub kind: ConstraintKind,
+
+    /// The constraint definition as it would appear in `ALTER TABLE ... ADD CONSTRAINT`.
+    pub definition: String,
+
+    pub comment: Option<String>,
+}
+
+impl SchemaCacheItem for Constraint {
+    type Item = Constraint;
+
+    async fn load(pool: &sqlx::PgPool) -> Result<Vec<Self::Item>, sqlx::Error> {
+        let constraints = sqlx::query_file_as!(ConstraintQueried, "src/queries/constraints.sql")
+            .fetch_all(pool)
+            .await?;
+
+        Ok(constraints.into_iter().map(Constraint::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Executor, PgPool};
+
+    use crate::{ConstraintKind, SchemaCache};
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn loads_constraints(test_db: PgPool) {
+        let setup = r#"
+            create table public.products (
+                id serial primary key,
+                price numeric constraint positive_price check (price > 0)
+            );
+        "#;
+
+        test_db
+            .execute(setup)
+            .await
+            .expect("Failed to setup test database");
+
+        let cache = SchemaCache::load(&test_db)
+            .await
+            .expect("Failed to load Schema Cache");
+
+        let check = cache
+            .find_constraints("positive_price", Some("products"), None)
+            .into_iter()
+            .next()
+            .expect("Expected the positive_price constraint");
+
+        assert_eq!(check.kind, ConstraintKind::Check);
+        assert_eq!(check.definition, "CHECK ((price > (0)::numeric))");
+
+        let pkey = cache
+            .find_constraints("products_pkey", None, Some("public"))
+            .into_iter()
+            .next()
+            .expect("Expected the products_pkey constraint");
+
+        assert_eq!(pkey.kind, ConstraintKind::PrimaryKey);
+        assert_eq!(pkey.definition, "PRIMARY KEY (id)");
+    }
+}
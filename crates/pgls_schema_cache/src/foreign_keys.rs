@@ -0,0 +1,118 @@
+use crate::schema_cache::SchemaCacheItem;
+
+/// A foreign key constraint, e.g. `orders.customer_id -> customers.id`.
+///
+/// `columns` and `referenced_columns` have the same length and are ordered
+/// pairwise, so `columns[i]` references `referenced_columns[i]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKey {
+    /// The name of the constraint.
+    pub name: String,
+
+    pub schema: String,
+    pub table_name: String,
+    pub table_oid: i64,
+    pub columns: Vec<String>,
+
+    pub referenced_schema: String,
+    pub referenced_table_name: String,
+    pub referenced_table_oid: i64,
+    pub referenced_columns: Vec<String>,
+}
+
+impl ForeignKey {
+    /// Returns `true` if the constraint connects the two given tables, regardless of direction.
+    pub fn connects(&self, table_a_oid: i64, table_b_oid: i64) -> bool {
+        (self.table_oid == table_a_oid && self.referenced_table_oid == table_b_oid)
+            || (self.table_oid == table_b_oid && self.referenced_table_oid == table_a_oid)
+    }
+
+    /// Pairs of `(column, referenced_column)` that make up the constraint.
+    pub fn column_pairs(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.columns
+            .iter()
+            .map(String::as_str)
+            .zip(self.referenced_columns.iter().map(String::as_str))
+    }
+}
+
+impl SchemaCacheItem for ForeignKey {
+    type Item = ForeignKey;
+
+    async fn load(pool: &sqlx::PgPool) -> Result<Vec<Self::Item>, sqlx::Error> {
+        sqlx::query_file_as!(ForeignKey, "src/queries/foreign_keys.sql")
+            .fetch_all(pool)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Executor, PgPool};
+
+    use crate::SchemaCache;
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn loads_foreign_keys(test_db: PgPool) {
+        let setup = r#"
+            create schema sales;
+
+            create table public.customers (
+                id serial primary key,
+                name text
+            );
+
+            create table sales.orders (
+                id serial primary key,
+                customer_id int references public.customers(id),
+                region text,
+                code text,
+                unique (region, code)
+            );
+
+            create table sales.order_notes (
+                id serial primary key,
+                order_region text,
+                order_code text,
+                foreign key (order_region, order_code) references sales.orders(region, code)
+            );
+        "#;
+
+        test_db
+            .execute(setup)
+            .await
+            .expect("Failed to setup test database");
+
+        let cache = SchemaCache::load(&test_db)
+            .await
+            .expect("Failed to load Schema Cache");
+
+        let orders_fk = cache
+            .foreign_keys
+            .iter()
+            .find(|fk| fk.table_name == "orders")
+            .expect("Expected a foreign key on sales.orders");
+
+        assert_eq!(orders_fk.schema, "sales");
+        assert_eq!(orders_fk.columns, vec!["customer_id".to_string()]);
+        assert_eq!(orders_fk.referenced_schema, "public");
+        assert_eq!(orders_fk.referenced_table_name, "customers");
+        assert_eq!(orders_fk.referenced_columns, vec!["id".to_string()]);
+
+        let notes_fk = cache
+            .foreign_keys
+            .iter()
+            .find(|fk| fk.table_name == "order_notes")
+            .expect("Expected a foreign key on sales.order_notes");
+
+        assert_eq!(
+            notes_fk.column_pairs().collect::<Vec<_>>(),
+            vec![("order_region", "region"), ("order_code", "code")]
+        );
+
+        let customers = cache.find_tables("customers", Some("public"))[0];
+        let orders = cache.find_tables("orders", Some("sales"))[0];
+        assert!(orders_fk.connects(customers.id, orders.id));
+        assert!(!notes_fk.connects(customers.id, orders.id));
+    }
+}
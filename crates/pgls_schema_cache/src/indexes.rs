@@ -0,0 +1,75 @@
+use crate::schema_cache::SchemaCacheItem;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Index {
+    pub name: String,
+    pub schema: String,
+    pub table_name: String,
+
+    /// The `CREATE INDEX` statement that reconstructs the index.
+    pub definition: String,
+
+    pub is_unique: bool,
+    pub is_primary: bool,
+    pub bytes: i64,
+}
+
+impl SchemaCacheItem for Index {
+    type Item = Index;
+
+    async fn load(pool: &sqlx::PgPool) -> Result<Vec<Self::Item>, sqlx::Error> {
+        sqlx::query_file_as!(Index, "src/queries/indexes.sql")
+            .fetch_all(pool)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Executor, PgPool};
+
+    use crate::SchemaCache;
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn loads_indexes(test_db: PgPool) {
+        let setup = r#"
+            create table public.users (
+                id serial primary key,
+                email text not null
+            );
+
+            create unique index users_email_idx on public.users (lower(email));
+        "#;
+
+        test_db
+            .execute(setup)
+            .await
+            .expect("Failed to setup test database");
+
+        let cache = SchemaCache::load(&test_db)
+            .await
+            .expect("Failed to load Schema Cache");
+
+        let email_idx = cache
+            .find_indexes("users_email_idx", Some("public"))
+            .into_iter()
+            .next()
+            .expect("Expected the users_email_idx index");
+
+        assert_eq!(email_idx.table_name, "users");
+        assert!(email_idx.is_unique);
+        assert!(!email_idx.is_primary);
+        assert_eq!(
+            email_idx.definition,
+            "CREATE UNIQUE INDEX users_email_idx ON public.users USING btree (lower(email))"
+        );
+
+        let pkey = cache
+            .find_indexes("users_pkey", None)
+            .into_iter()
+            .next()
+            .expect("Expected the users_pkey index");
+
+        assert!(pkey.is_primary);
+    }
+}
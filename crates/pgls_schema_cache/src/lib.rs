@@ -2,9 +2,14 @@
 
 #![allow(dead_code)]
 
+mod casts;
 mod columns;
+mod constraints;
 mod extensions;
+mod foreign_keys;
 mod functions;
+mod indexes;
+mod operators;
 mod policies;
 mod roles;
 mod schema_cache;
@@ -14,13 +19,18 @@ mod triggers;
 mod types;
 mod versions;
 
+pub use casts::{Cast, CastContext};
 pub use columns::*;
+pub use constraints::{Constraint, ConstraintKind};
 pub use extensions::Extension;
+pub use foreign_keys::ForeignKey;
 pub use functions::{Behavior, Function, FunctionArg, FunctionArgs, ProcKind};
+pub use indexes::Index;
+pub use operators::Operator;
 pub use policies::{Policy, PolicyCommand};
 pub use roles::*;
 pub use schema_cache::SchemaCache;
 pub use schemas::Schema;
 pub use tables::{ReplicaIdentity, Table, TableKind};
-pub use triggers::{Trigger, TriggerAffected, TriggerEvent};
+pub use triggers::{Trigger, TriggerAffected, TriggerEvent, TriggerTiming};
 pub use types::{PostgresType, PostgresTypeAttribute};
@@ -0,0 +1,70 @@
+use crate::{Behavior, schema_cache::SchemaCacheItem};
+
+/// An operator, e.g. `timestamptz - interval`, along with the function that implements it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operator {
+    pub id: i64,
+    pub name: String,
+    pub schema: String,
+
+    /// `None` for prefix operators.
+    pub left_type_id: Option<i64>,
+    pub right_type_id: Option<i64>,
+    pub result_type_id: i64,
+
+    pub function_name: String,
+    pub function_schema: String,
+
+    /// The volatility of the implementing function.
+    pub behavior: Behavior,
+
+    pub comment: Option<String>,
+}
+
+impl SchemaCacheItem for Operator {
+    type Item = Operator;
+
+    async fn load(pool: &sqlx::PgPool) -> Result<Vec<Self::Item>, sqlx::Error> {
+        sqlx::query_file_as!(Operator, "src/queries/operators.sql")
+            .fetch_all(pool)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use crate::{Behavior, SchemaCache};
+
+    #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+    async fn loads_operators(test_db: PgPool) {
+        let cache = SchemaCache::load(&test_db)
+            .await
+            .expect("Failed to load Schema Cache");
+
+        let timestamptz = cache.find_type("timestamptz", Some("pg_catalog")).unwrap();
+        let interval = cache.find_type("interval", Some("pg_catalog")).unwrap();
+
+        let minus = cache
+            .find_operators("-")
+            .into_iter()
+            .find(|o| {
+                o.left_type_id == Some(timestamptz.id) && o.right_type_id == Some(interval.id)
+            })
+            .expect("Expected the timestamptz - interval operator");
+
+        assert_eq!(minus.result_type_id, timestamptz.id);
+        assert_eq!(minus.function_name, "timestamptz_mi_interval");
+        // depends on the `TimeZone` setting
+        assert_eq!(minus.behavior, Behavior::Stable);
+
+        let negation = cache
+            .find_operators("-")
+            .into_iter()
+            .find(|o| o.left_type_id.is_none())
+            .expect("Expected a prefix minus operator");
+
+        assert!(negation.right_type_id.is_some());
+    }
+}
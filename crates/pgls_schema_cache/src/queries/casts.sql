@@ -0,0 +1,6 @@
+select
+  c.castsource :: int8 as "source_type_id!",
+  c.casttarget :: int8 as "target_type_id!",
+  c.castcontext :: text as "context!"
+from
+  pg_catalog.pg_cast c;
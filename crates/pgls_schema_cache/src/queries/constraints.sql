@@ -0,0 +1,14 @@
+select
+  con.conname as "name!",
+  n.nspname as "schema!",
+  tbl.relname as "table_name!",
+  con.contype :: text as "kind!",
+  pg_get_constraintdef(con.oid) as "definition!",
+  obj_description(con.oid, 'pg_constraint') as comment
+from
+  pg_catalog.pg_constraint con
+  join pg_catalog.pg_class tbl on con.conrelid = tbl.oid
+  join pg_catalog.pg_namespace n on tbl.relnamespace = n.oid
+where
+  n.nspname not in ('pg_catalog', 'pg_toast', 'information_schema')
+  and not pg_is_other_temp_schema(n.oid);
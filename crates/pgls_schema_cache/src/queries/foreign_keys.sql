@@ -0,0 +1,36 @@
+select
+  con.conname as "name!",
+  table_ns.nspname as "schema!",
+  tbl.relname as "table_name!",
+  con.conrelid :: int8 as "table_oid!",
+  array(
+    select
+      att.attname :: text
+    from
+      unnest(con.conkey) with ordinality as k (attnum, pos)
+      join pg_catalog.pg_attribute att on att.attrelid = con.conrelid
+      and att.attnum = k.attnum
+    order by
+      k.pos
+  ) as "columns!",
+  ref_ns.nspname as "referenced_schema!",
+  ref_tbl.relname as "referenced_table_name!",
+  con.confrelid :: int8 as "referenced_table_oid!",
+  array(
+    select
+      att.attname :: text
+    from
+      unnest(con.confkey) with ordinality as k (attnum, pos)
+      join pg_catalog.pg_attribute att on att.attrelid = con.confrelid
+      and att.attnum = k.attnum
+    order by
+      k.pos
+  ) as "referenced_columns!"
+from
+  pg_catalog.pg_constraint con
+  join pg_catalog.pg_class tbl on con.conrelid = tbl.oid
+  join pg_catalog.pg_namespace table_ns on tbl.relnamespace = table_ns.oid
+  join pg_catalog.pg_class ref_tbl on con.confrelid = ref_tbl.oid
+  join pg_catalog.pg_namespace ref_ns on ref_tbl.relnamespace = ref_ns.oid
+where
+  con.contype = 'f';
@@ -0,0 +1,16 @@
+select
+  i.relname as "name!",
+  n.nspname as "schema!",
+  t.relname as "table_name!",
+  pg_get_indexdef(ix.indexrelid) as "definition!",
+  ix.indisunique as "is_unique!",
+  ix.indisprimary as "is_primary!",
+  pg_relation_size(i.oid) :: int8 as "bytes!"
+from
+  pg_catalog.pg_index ix
+  join pg_catalog.pg_class i on ix.indexrelid = i.oid
+  join pg_catalog.pg_class t on ix.indrelid = t.oid
+  join pg_catalog.pg_namespace n on i.relnamespace = n.oid
+where
+  n.nspname not in ('pg_catalog', 'pg_toast', 'information_schema')
+  and not pg_is_other_temp_schema(n.oid);
@@ -0,0 +1,20 @@
+select
+  o.oid :: int8 as "id!",
+  o.oprname as "name!",
+  n.nspname as "schema!",
+  nullif(o.oprleft, 0) :: int8 as left_type_id,
+  nullif(o.oprright, 0) :: int8 as right_type_id,
+  o.oprresult :: int8 as "result_type_id!",
+  p.proname as "function_name!",
+  pn.nspname as "function_schema!",
+  case
+    when p.provolatile = 'i' then 'IMMUTABLE'
+    when p.provolatile = 's' then 'STABLE'
+    when p.provolatile = 'v' then 'VOLATILE'
+  end as behavior,
+  obj_description(o.oid, 'pg_operator') as comment
+from
+  pg_catalog.pg_operator o
+  join pg_catalog.pg_namespace n on o.oprnamespace = n.oid
+  join pg_catalog.pg_proc p on o.oprcode = p.oid
+  join pg_catalog.pg_namespace pn on p.pronamespace = pn.oid;
@@ -3,6 +3,13 @@ select
   t.typname as name,
   n.nspname as "schema!",
   format_type (t.oid, null) as "format!",
+  t.typcategory :: text as "category!",
+  t.typispreferred as "is_preferred!",
+  nullif(t.typbasetype, 0) :: int8 as base_type_id,
+  case
+    when t.typcategory = 'A' then nullif(t.typelem, 0) :: int8
+  end as element_type_id,
+  nullif(t.typarray, 0) :: int8 as array_type_id,
   coalesce(t_enums.enums, '[]') as enums,
   coalesce(t_attributes.attributes, '[]') as attributes,
   obj_description (t.oid, 'pg_type') as comment
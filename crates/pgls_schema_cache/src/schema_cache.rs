@@ -7,16 +7,21 @@ use crate::schemas::Schema;
 use crate::tables::Table;
 use crate::types::PostgresType;
 use crate::versions::Version;
-use crate::{Extension, Role, Trigger};
+use crate::{Cast, Constraint, Extension, ForeignKey, Index, Operator, Role, Trigger};
 
 #[derive(Debug, Default)]
 pub struct SchemaCache {
     pub schemas: Vec<Schema>,
     pub tables: Vec<Table>,
     pub functions: Vec<Function>,
+    pub operators: Vec<Operator>,
     pub types: Vec<PostgresType>,
+    pub casts: Vec<Cast>,
     pub version: Version,
     pub columns: Vec<Column>,
+    pub foreign_keys: Vec<ForeignKey>,
+    pub indexes: Vec<Index>,
+    pub constraints: Vec<Constraint>,
     pub policies: Vec<Policy>,
     pub extensions: Vec<Extension>,
     pub triggers: Vec<Trigger>,
@@ -29,9 +34,14 @@ impl SchemaCache {
             schemas,
             tables,
             functions,
+            operators,
             types,
+            casts,
             versions,
             columns,
+            foreign_keys,
+            indexes,
+            constraints,
             policies,
             triggers,
             roles,
@@ -40,9 +50,14 @@ impl SchemaCache {
             Schema::load(pool),
             Table::load(pool),
             Function::load(pool),
+            Operator::load(pool),
             PostgresType::load(pool),
+            Cast::load(pool),
             Version::load(pool),
             Column::load(pool),
+            ForeignKey::load(pool),
+            Index::load(pool),
+            Constraint::load(pool),
             Policy::load(pool),
             Trigger::load(pool),
             Role::load(pool),
@@ -58,9 +73,14 @@ impl SchemaCache {
             schemas,
             tables,
             functions,
+            operators,
             types,
+            casts,
             version,
             columns,
+            foreign_keys,
+            indexes,
+            constraints,
             policies,
             triggers,
             roles,
@@ -102,6 +122,12 @@ impl SchemaCache {
         self.types.iter().find(|t| t.id == id)
     }
 
+    pub fn find_cast(&self, source_type_id: i64, target_type_id: i64) -> Option<&Cast> {
+        self.casts
+            .iter()
+            .find(|c| c.source_type_id == source_type_id && c.target_type_id == target_type_id)
+    }
+
     pub fn find_cols(&self, name: &str, table: Option<&str>, schema: Option<&str>) -> Vec<&Column> {
         let sanitized_name = Self::sanitize_identifier(name);
         self.columns
@@ -148,6 +174,10 @@ impl SchemaCache {
             .collect()
     }
 
+    pub fn find_operators(&self, name: &str) -> Vec<&Operator> {
+        self.operators.iter().filter(|o| o.name == name).collect()
+    }
+
     pub fn find_roles(&self, name: &str) -> Vec<&Role> {
         let sanitized_name = Self::sanitize_identifier(name);
         self.roles
@@ -156,6 +186,76 @@ impl SchemaCache {
             .collect()
     }
 
+    pub fn find_policies(&self, name: &str, table: Option<&str>) -> Vec<&Policy> {
+        let sanitized_name = Self::sanitize_identifier(name);
+        self.policies
+            .iter()
+            .filter(|p| {
+                p.name == sanitized_name
+                    && table
+                        .map(Self::sanitize_identifier)
+                        .as_deref()
+                        .is_none_or(|t| t == p.table_name.as_str())
+            })
+            .collect()
+    }
+
+    pub fn find_triggers(&self, name: &str, table: Option<&str>) -> Vec<&Trigger> {
+        let sanitized_name = Self::sanitize_identifier(name);
+        self.triggers
+            .iter()
+            .filter(|t| {
+                t.name == sanitized_name
+                    && table
+                        .map(Self::sanitize_identifier)
+                        .as_deref()
+                        .is_none_or(|tbl| tbl == t.table_name.as_str())
+            })
+            .collect()
+    }
+
+    pub fn find_extension(&self, name: &str) -> Option<&Extension> {
+        let sanitized_name = Self::sanitize_identifier(name);
+        self.extensions.iter().find(|e| e.name == sanitized_name)
+    }
+
+    pub fn find_indexes(&self, name: &str, schema: Option<&str>) -> Vec<&Index> {
+        let sanitized_name = Self::sanitize_identifier(name);
+        self.indexes
+            .iter()
+            .filter(|i| {
+                i.name == sanitized_name
+                    && schema
+                        .map(Self::sanitize_identifier)
+                        .as_deref()
+                        .is_none_or(|s| s == i.schema.as_str())
+            })
+            .collect()
+    }
+
+    pub fn find_constraints(
+        &self,
+        name: &str,
+        table: Option<&str>,
+        schema: Option<&str>,
+    ) -> Vec<&Constraint> {
+        let sanitized_name = Self::sanitize_identifier(name);
+        self.constraints
+            .iter()
+            .filter(|c| {
+                c.name == sanitized_name
+                    && table
+                        .map(Self::sanitize_identifier)
+                        .as_deref()
+                        .is_none_or(|t| t == c.table_name.as_str())
+                    && schema
+                        .map(Self::sanitize_identifier)
+                        .as_deref()
+                        .is_none_or(|s| s == c.schema.as_str())
+            })
+            .collect()
+    }
+
     fn sanitize_identifier(identifier: &str) -> String {
         identifier.replace('"', "")
     }
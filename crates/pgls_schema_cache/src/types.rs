@@ -42,6 +42,23 @@ pub struct PostgresType {
     pub name: String,
     pub schema: String,
     pub format: String,
+
+    /// The `typcategory`, e.g. `N` for numeric types or `S` for string types.
+    /// Used to find a common type for `CASE` and `COALESCE` branches.
+    pub category: String,
+
+    /// Whether this type is preferred within its category when resolving ambiguous calls.
+    pub is_preferred: bool,
+
+    /// For domains, the type the domain is based on.
+    pub base_type_id: Option<i64>,
+
+    /// For array types, the type of the elements.
+    pub element_type_id: Option<i64>,
+
+    /// The array type with this type as its elements.
+    pub array_type_id: Option<i64>,
+
     pub enums: Enums,
     pub attributes: TypeAttributes,
     pub comment: Option<String>,
@@ -651,6 +651,17 @@ impl<'a> TreesitterContext<'a> {
         }
     }
 
+    /// Iterates over all relations mentioned in the current statement as `(schema, table)` pairs.
+    pub fn iter_mentioned_relations(&self) -> impl Iterator<Item = (Option<&str>, &str)> {
+        self.mentioned_relations
+            .iter()
+            .flat_map(|(schema, tables)| {
+                tables
+                    .iter()
+                    .map(move |table| (schema.as_deref(), table.as_str()))
+            })
+    }
+
     pub fn get_mentioned_table_for_alias(&self, key: &str) -> Option<&String> {
         let sanitized_key = key.replace('"', "");
 
@@ -668,6 +679,18 @@ impl<'a> TreesitterContext<'a> {
         None
     }
 
+    /// All aliases used for the table, e.g. `e` and `m` in `employees e join employees m`.
+    pub fn get_used_aliases_for_table(&self, table_name: &str) -> Vec<String> {
+        let mut aliases: Vec<String> = self
+            .mentioned_table_aliases
+            .iter()
+            .filter(|(_, table)| *table == table_name)
+            .map(|(alias, _)| alias.to_string())
+            .collect();
+        aliases.sort();
+        aliases
+    }
+
     pub fn get_mentioned_columns(
         &self,
         clause: &Option<WrappingClause<'a>>,
@@ -0,0 +1,273 @@
+use pgls_schema_cache::{CastContext, PostgresType, SchemaCache};
+
+/// The outcome of picking an overload of a function or an operator for a set of argument types.
+#[derive(Debug)]
+pub(crate) enum Resolution<T> {
+    Resolved(T),
+
+    /// No candidate accepts the argument types.
+    NotFound,
+
+    /// More than one candidate accepts the argument types equally well.
+    Ambiguous,
+
+    /// The argument types are not fully known, so we can't tell.
+    Undetermined,
+}
+
+/// Implements the implicit coercion rules of Postgres on top of the schema cache.
+///
+/// See <https://www.postgresql.org/docs/current/typeconv.html>.
+pub(crate) struct TypeCoercion<'a> {
+    schema_cache: &'a SchemaCache,
+}
+
+impl<'a> TypeCoercion<'a> {
+    pub fn new(schema_cache: &'a SchemaCache) -> Self {
+        Self { schema_cache }
+    }
+
+    /// Domains are coerced like the type they are based on.
+    pub fn base_type(&self, t: &'a PostgresType) -> &'a PostgresType {
+        let mut current = t;
+        while let Some(base) = current
+            .base_type_id
+            .and_then(|id| self.schema_cache.find_type_by_id(id))
+        {
+            current = base;
+        }
+        current
+    }
+
+    /// Whether a value of type `from` can be used where a value of type `to` is expected.
+    pub fn can_coerce(
+        &self,
+        from: &'a PostgresType,
+        to: &'a PostgresType,
+        context: CastContext,
+    ) -> bool {
+        if from.id == to.id || is_unknown(from) || is_pseudo(from) {
+            return true;
+        }
+
+        if is_pseudo(to) {
+            return match to.name.as_str() {
+                "anyarray" | "anycompatiblearray" => from.element_type_id.is_some(),
+                "anynonarray" | "anycompatiblenonarray" => from.element_type_id.is_none(),
+                "anyenum" => !from.enums.values.is_empty(),
+                "anyrange" | "anycompatiblerange" | "anymultirange" | "anycompatiblemultirange" => {
+                    from.category == "R"
+                }
+                // record, cstring, internal and friends are never written by users
+                _ => true,
+            };
+        }
+
+        let from = self.base_type(from);
+        let to = self.base_type(to);
+        if from.id == to.id {
+            return true;
+        }
+
+        if let Some(cast) = self.schema_cache.find_cast(from.id, to.id) {
+            return cast.context <= context;
+        }
+
+        // any type can be assigned to a string column via its output function
+        if context >= CastContext::Assignment && to.category == "S" {
+            return true;
+        }
+
+        match (from.element_type_id, to.element_type_id) {
+            (Some(from_element), Some(to_element)) => {
+                match (
+                    self.schema_cache.find_type_by_id(from_element),
+                    self.schema_cache.find_type_by_id(to_element),
+                ) {
+                    (Some(from_element), Some(to_element)) => {
+                        self.can_coerce(from_element, to_element, context)
+                    }
+                    _ => true,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Determines the type of `CASE`, `COALESCE`, `GREATEST`/`LEAST` and `UNION` branches.
+    ///
+    /// Returns the two types that can't be matched if there is no common type.
+    pub fn common_type(
+        &self,
+        types: &[&'a PostgresType],
+    ) -> Result<Option<&'a PostgresType>, (&'a PostgresType, &'a PostgresType)> {
+        let Some(first) = types.iter().copied().find(|t| !is_unknown(t)) else {
+            // all inputs are untyped literals
+            return Ok(types
+                .first()
+                .and_then(|_| self.schema_cache.find_type("text", Some("pg_catalog"))));
+        };
+
+        if types.iter().all(|t| t.id == first.id || is_unknown(t)) {
+            return Ok(Some(first));
+        }
+
+        let mut candidate = self.base_type(first);
+        for t in types.iter().copied().filter(|t| !is_unknown(t)) {
+            let t = self.base_type(t);
+            if t.id == candidate.id {
+                continue;
+            }
+
+            if t.category != candidate.category {
+                return Err((candidate, t));
+            }
+
+            if !candidate.is_preferred
+                && self.can_coerce(candidate, t, CastContext::Implicit)
+                && !self.can_coerce(t, candidate, CastContext::Implicit)
+            {
+                candidate = t;
+            }
+        }
+
+        match types
+            .iter()
+            .copied()
+            .find(|t| !self.can_coerce(t, candidate, CastContext::Implicit))
+        {
+            Some(t) => Err((candidate, t)),
+            None => Ok(Some(candidate)),
+        }
+    }
+
+    /// Picks the best candidate for the argument types, following the rules Postgres uses
+    /// to resolve functions and operators.
+    ///
+    /// Arguments of which we don't know the type accept any candidate.
+    pub fn select_candidate<T: Copy>(
+        &self,
+        candidates: Vec<(T, Vec<&'a PostgresType>)>,
+        args: &[Option<&'a PostgresType>],
+    ) -> Resolution<T> {
+        let all_known = args.iter().all(Option::is_some);
+
+        let mut candidates: Vec<(T, Vec<&'a PostgresType>)> = candidates
+            .into_iter()
+            .filter(|(_, params)| {
+                params.len() == args.len()
+                    && params.iter().zip(args).all(|(param, arg)| {
+                        arg.is_none_or(|arg| self.can_coerce(arg, param, CastContext::Implicit))
+                    })
+            })
+            .collect();
+
+        let known_args = || {
+            args.iter()
+                .enumerate()
+                .filter_map(|(i, arg)| arg.filter(|a| !is_unknown(a)).map(|a| (i, a)))
+        };
+
+        // keep the candidates with the most exact matches
+        retain_best(&mut candidates, |params| {
+            known_args()
+                .filter(|(i, arg)| self.base_type(arg).id == self.base_type(params[*i]).id)
+                .count()
+        });
+
+        // keep the candidates that accept preferred types where a conversion is needed
+        retain_best(&mut candidates, |params| {
+            known_args()
+                .filter(|(i, arg)| {
+                    let param = params[*i];
+                    arg.id != param.id && param.is_preferred && param.category == arg.category
+                })
+                .count()
+        });
+
+        let unknown_positions: Vec<usize> = args
+            .iter()
+            .enumerate()
+            .filter(|(_, arg)| arg.is_some_and(is_unknown))
+            .map(|(i, _)| i)
+            .collect();
+
+        // untyped literals prefer the string category, or the preferred type of the only category
+        for &i in &unknown_positions {
+            if candidates.len() <= 1 {
+                break;
+            }
+
+            let categories: Vec<&str> = candidates
+                .iter()
+                .map(|(_, params)| params[i].category.as_str())
+                .collect();
+
+            let category = if categories.contains(&"S") {
+                "S"
+            } else if categories.iter().all(|c| *c == categories[0]) {
+                categories[0]
+            } else {
+                continue;
+            };
+
+            retain_best(&mut candidates, |params| {
+                usize::from(params[i].category == category)
+            });
+            retain_best(&mut candidates, |params| {
+                usize::from(params[i].is_preferred)
+            });
+        }
+
+        // if the known arguments agree on a type, untyped literals are assumed to be of that type
+        if candidates.len() > 1 && !unknown_positions.is_empty() {
+            let mut known_types = known_args().map(|(_, a)| a.id);
+            if let Some(id) = known_types.next() {
+                if known_types.all(|other| other == id) {
+                    retain_best(&mut candidates, |params| {
+                        unknown_positions
+                            .iter()
+                            .filter(|i| params[**i].id == id)
+                            .count()
+                    });
+                }
+            }
+        }
+
+        match candidates.as_slice() {
+            [(candidate, _)] => Resolution::Resolved(*candidate),
+            _ if !all_known => Resolution::Undetermined,
+            [] => Resolution::NotFound,
+            _ => Resolution::Ambiguous,
+        }
+    }
+}
+
+/// Keeps only the candidates with the highest score, unless no candidate scores at all.
+fn retain_best<T>(
+    candidates: &mut Vec<(T, Vec<&PostgresType>)>,
+    score: impl Fn(&[&PostgresType]) -> usize,
+) {
+    let best = candidates
+        .iter()
+        .map(|(_, params)| score(params))
+        .max()
+        .unwrap_or(0);
+
+    if best > 0 {
+        candidates.retain(|(_, params)| score(params) == best);
+    }
+}
+
+pub(crate) fn is_unknown(t: &PostgresType) -> bool {
+    t.name == "unknown" && t.schema == "pg_catalog"
+}
+
+pub(crate) fn is_pseudo(t: &PostgresType) -> bool {
+    t.category == "P"
+}
+
+/// Polymorphic pseudo types like `anyelement` take on the type of the argument they are bound to.
+pub(crate) fn is_polymorphic(t: &PostgresType) -> bool {
+    is_pseudo(t) && t.name.starts_with("any") && t.name != "any"
+}
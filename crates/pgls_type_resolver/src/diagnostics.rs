@@ -0,0 +1,148 @@
+use std::fmt::Display;
+
+/// A type error found by the [`TypeInferrer`](crate::TypeInferrer).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeError {
+    pub kind: TypeErrorKind,
+
+    /// The byte offset of the offending expression within the statement.
+    pub location: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeErrorKind {
+    /// A column that none of the relations in scope have.
+    UnknownColumn { name: String },
+
+    /// No overload of the function accepts the argument types.
+    UnknownFunction {
+        name: String,
+        arg_types: Vec<String>,
+    },
+
+    /// More than one overload of the function accepts the argument types equally well.
+    AmbiguousFunction {
+        name: String,
+        arg_types: Vec<String>,
+    },
+
+    /// No operator with that name accepts the operand types.
+    UnknownOperator {
+        name: String,
+        left: Option<String>,
+        right: String,
+    },
+
+    /// A clause such as `WHERE` got an expression of the wrong type.
+    InvalidArgument {
+        clause: String,
+        expected: String,
+        found: String,
+    },
+
+    /// A value can't be assigned to a column, e.g. in `INSERT` or `UPDATE`.
+    AssignmentMismatch {
+        column: String,
+        expected: String,
+        found: String,
+    },
+
+    /// A column default or generation expression doesn't match the type of the column.
+    DefaultMismatch {
+        column: String,
+        expected: String,
+        found: String,
+    },
+
+    /// The branches of a `CASE`, `COALESCE` or similar expression don't have a common type.
+    NoCommonType {
+        context: String,
+        first: String,
+        second: String,
+    },
+}
+
+impl TypeError {
+    pub(crate) fn new(kind: TypeErrorKind, location: Option<usize>) -> Self {
+        Self { kind, location }
+    }
+}
+
+impl TypeErrorKind {
+    /// Whether the error is about a column, function or operator that doesn't exist.
+    pub fn is_unknown_object(&self) -> bool {
+        matches!(
+            self,
+            TypeErrorKind::UnknownColumn { .. }
+                | TypeErrorKind::UnknownFunction { .. }
+                | TypeErrorKind::UnknownOperator { .. }
+        )
+    }
+
+    /// The SQLSTATE error code Postgres reports for the same error.
+    pub fn sqlstate(&self) -> &'static str {
+        match self {
+            TypeErrorKind::UnknownColumn { .. } => "42703",
+            TypeErrorKind::UnknownFunction { .. } | TypeErrorKind::UnknownOperator { .. } => {
+                "42883"
+            }
+            TypeErrorKind::AmbiguousFunction { .. } => "42725",
+            TypeErrorKind::InvalidArgument { .. }
+            | TypeErrorKind::AssignmentMismatch { .. }
+            | TypeErrorKind::DefaultMismatch { .. }
+            | TypeErrorKind::NoCommonType { .. } => "42804",
+        }
+    }
+}
+
+// The messages mirror the ones Postgres reports for the same errors.
+impl Display for TypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            TypeErrorKind::UnknownColumn { name } => write!(f, "column \"{name}\" does not exist"),
+            TypeErrorKind::UnknownFunction { name, arg_types } => {
+                write!(
+                    f,
+                    "function {name}({}) does not exist",
+                    arg_types.join(", ")
+                )
+            }
+            TypeErrorKind::AmbiguousFunction { name, arg_types } => {
+                write!(f, "function {name}({}) is not unique", arg_types.join(", "))
+            }
+            TypeErrorKind::UnknownOperator { name, left, right } => match left {
+                Some(left) => write!(f, "operator does not exist: {left} {name} {right}"),
+                None => write!(f, "operator does not exist: {name} {right}"),
+            },
+            TypeErrorKind::InvalidArgument {
+                clause,
+                expected,
+                found,
+            } => write!(
+                f,
+                "argument of {clause} must be type {expected}, not type {found}"
+            ),
+            TypeErrorKind::AssignmentMismatch {
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "column \"{column}\" is of type {expected} but expression is of type {found}"
+            ),
+            TypeErrorKind::DefaultMismatch {
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "column \"{column}\" is of type {expected} but default expression is of type {found}"
+            ),
+            TypeErrorKind::NoCommonType {
+                context,
+                first,
+                second,
+            } => write!(f, "{context} types {first} and {second} cannot be matched"),
+        }
+    }
+}
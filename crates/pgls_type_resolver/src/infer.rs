@@ -0,0 +1,1662 @@
+use std::cell::RefCell;
+
+use pgls_query::{
+    NodeEnum, NodeRef,
+    protobuf::{
+        self, AExprKind, AlterTableType, ConstrType, MinMaxOp, SetOperation, SqlValueFunctionOp,
+        SubLinkType, a_const::Val,
+    },
+};
+use pgls_schema_cache::{CastContext, Function, Operator, PostgresType, ProcKind, SchemaCache};
+
+use crate::{
+    coercion::{Resolution, TypeCoercion, is_polymorphic, is_unknown},
+    diagnostics::{TypeError, TypeErrorKind},
+    relations::{Relation, RelationSource, collect_relations, from_item_names, range_var_name},
+    util::get_string_from_node,
+};
+
+/// Columns every table has, but that are not part of the schema cache.
+const SYSTEM_COLUMNS: [(&str, &str); 6] = [
+    ("ctid", "tid"),
+    ("xmin", "xid"),
+    ("xmax", "xid"),
+    ("cmin", "cid"),
+    ("cmax", "cid"),
+    ("tableoid", "oid"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpressionKind {
+    Literal,
+    Parameter(i32),
+    TypeCast,
+    Operator,
+}
+
+/// An expression along with its inferred type.
+#[derive(Debug)]
+pub struct ResolvedExpression<'a> {
+    pub kind: ExpressionKind,
+    pub type_: Option<&'a PostgresType>,
+
+    /// The operator that is applied, if the expression is an operator expression.
+    pub operator: Option<&'a Operator>,
+}
+
+/// The result of looking up a column reference.
+enum ColumnLookup<'a> {
+    /// The column exists. Its type is `None` if we can't tell, e.g. for whole-row references.
+    Found {
+        type_: Option<&'a PostgresType>,
+        nullable: Option<bool>,
+
+        /// Whether the column belongs to a relation on the nullable side of an outer join.
+        outer_joined: bool,
+    },
+
+    NotFound,
+
+    /// At least one of the relations in scope has columns we can't determine.
+    Undetermined,
+}
+
+type RelationColumns<'a> = Vec<OutputColumn<'a>>;
+
+/// A column of the rows a statement returns.
+#[derive(Debug)]
+pub struct OutputColumn<'a> {
+    pub name: String,
+    pub type_: Option<&'a PostgresType>,
+
+    /// Whether the column may be `NULL`, or `None` if we can't tell.
+    pub nullable: Option<bool>,
+}
+
+/// Infers the types of expressions within a statement based on the schema cache,
+/// without a round-trip to the database.
+pub struct TypeInferrer<'a, 'b> {
+    schema_cache: &'a SchemaCache,
+    coercion: TypeCoercion<'a>,
+    root: &'b NodeEnum,
+    relations: Vec<Relation<'b>>,
+
+    /// The relations whose columns are currently being inferred, to guard against recursive CTEs.
+    resolving: RefCell<Vec<usize>>,
+}
+
+impl<'a, 'b> TypeInferrer<'a, 'b> {
+    pub fn new(root: &'b NodeEnum, schema_cache: &'a SchemaCache) -> Self {
+        Self {
+            schema_cache,
+            coercion: TypeCoercion::new(schema_cache),
+            root,
+            relations: collect_relations(root),
+            resolving: RefCell::new(vec![]),
+        }
+    }
+
+    /// Resolves the literal, parameter, cast or operator expression that starts at `offset`.
+    ///
+    /// For operator expressions, `offset` is the position of the operator.
+    pub fn resolve_expression_at(&self, offset: usize) -> Option<ResolvedExpression<'a>> {
+        let offset = i32::try_from(offset).ok()?;
+
+        self.root.iter().find_map(|node| match node {
+            NodeRef::AConst(c) if c.location == offset => Some(ResolvedExpression {
+                kind: ExpressionKind::Literal,
+                type_: self
+                    .infer(node)
+                    .filter(|t| !is_unknown(t))
+                    .or_else(|| self.coerced_type(offset)),
+                operator: None,
+            }),
+            NodeRef::ParamRef(p) if p.location == offset => Some(ResolvedExpression {
+                kind: ExpressionKind::Parameter(p.number),
+                type_: self.infer(node),
+                operator: None,
+            }),
+            NodeRef::TypeCast(c) if c.location == offset => Some(ResolvedExpression {
+                kind: ExpressionKind::TypeCast,
+                type_: self.infer(node),
+                operator: None,
+            }),
+            NodeRef::AExpr(e) if e.location == offset => Some(ResolvedExpression {
+                kind: ExpressionKind::Operator,
+                type_: self.infer(node),
+                operator: self.resolve_operator(e),
+            }),
+            _ => None,
+        })
+    }
+
+    /// Infers the type of an expression.
+    ///
+    /// String literals and `NULL` are of type `unknown` until they are coerced.
+    pub fn infer(&self, node: NodeRef) -> Option<&'a PostgresType> {
+        match node {
+            NodeRef::AConst(c) => self.infer_const(c),
+            NodeRef::TypeCast(c) => self.resolve_type_name(c.type_name.as_ref()?),
+            NodeRef::ColumnRef(c) => match self.lookup_column(c) {
+                ColumnLookup::Found { type_, .. } => type_,
+                ColumnLookup::NotFound | ColumnLookup::Undetermined => None,
+            },
+            NodeRef::ParamRef(p) => self.infer_param(p.number),
+            NodeRef::AExpr(e) => self.infer_a_expr(e),
+            NodeRef::FuncCall(f) => self.infer_func_call(f),
+            NodeRef::BoolExpr(_) | NodeRef::NullTest(_) | NodeRef::BooleanTest(_) => {
+                self.builtin("bool")
+            }
+            NodeRef::SubLink(s) => match s.sub_link_type() {
+                SubLinkType::ExistsSublink | SubLinkType::AllSublink | SubLinkType::AnySublink => {
+                    self.builtin("bool")
+                }
+                SubLinkType::ExprSublink => self
+                    .query_columns(s.subselect.as_deref()?)?
+                    .first()
+                    .and_then(|c| c.type_),
+                _ => None,
+            },
+            NodeRef::CoalesceExpr(c) => self.common_type(&c.args).ok()?,
+            NodeRef::MinMaxExpr(m) => self.common_type(&m.args).ok()?,
+            NodeRef::CaseExpr(c) => self.common_type(&case_results(c)).ok()?,
+            NodeRef::AArrayExpr(a) => {
+                let element = self.common_type(&a.elements).ok()??;
+                self.schema_cache.find_type_by_id(element.array_type_id?)
+            }
+            NodeRef::CollateClause(c) => self.infer_node(c.arg.as_deref()?),
+            NodeRef::SqlvalueFunction(f) => self.infer_sql_value_function(f),
+            _ => None,
+        }
+    }
+
+    /// Infers whether an expression may evaluate to `NULL`.
+    ///
+    /// Columns are nullable if they aren't declared `NOT NULL` or if their relation is on the
+    /// nullable side of an outer join. Operators are assumed to be strict. Returns `None` if we
+    /// can't tell, e.g. for calls of functions other than aggregates.
+    pub fn nullable(&self, node: NodeRef) -> Option<bool> {
+        match node {
+            NodeRef::AConst(c) => Some(c.isnull),
+            NodeRef::TypeCast(c) => self.nullable_node(c.arg.as_deref()?),
+            NodeRef::CollateClause(c) => self.nullable_node(c.arg.as_deref()?),
+            NodeRef::ColumnRef(c) if self.is_outer_joined(c) => Some(true),
+            NodeRef::ColumnRef(c) => match self.lookup_column(c) {
+                ColumnLookup::Found { nullable, .. } => nullable,
+                ColumnLookup::NotFound | ColumnLookup::Undetermined => None,
+            },
+            NodeRef::ParamRef(_) => None,
+            NodeRef::AExpr(e) => match e.kind() {
+                AExprKind::AexprDistinct | AExprKind::AexprNotDistinct => Some(false),
+                AExprKind::AexprNullif => Some(true),
+                AExprKind::Undefined => None,
+                _ => self.nullable_strict(e.lexpr.as_deref().into_iter().chain(e.rexpr.as_deref())),
+            },
+            NodeRef::BoolExpr(b) => self.nullable_strict(&b.args),
+            NodeRef::List(l) => self.nullable_strict(&l.items),
+            NodeRef::NullTest(_) | NodeRef::BooleanTest(_) => Some(false),
+            NodeRef::SubLink(s) => match s.sub_link_type() {
+                SubLinkType::ExistsSublink => Some(false),
+                SubLinkType::AnySublink | SubLinkType::AllSublink => {
+                    let column = self
+                        .query_columns(s.subselect.as_deref()?)?
+                        .into_iter()
+                        .next()?;
+
+                    match (self.nullable_node(s.testexpr.as_deref()?), column.nullable) {
+                        (Some(true), _) | (_, Some(true)) => Some(true),
+                        (Some(false), Some(false)) => Some(false),
+                        _ => None,
+                    }
+                }
+                // a scalar subquery that returns no rows evaluates to `NULL`
+                SubLinkType::ExprSublink => Some(true),
+                _ => None,
+            },
+            NodeRef::CoalesceExpr(c) => self.nullable_coalesce(&c.args),
+            NodeRef::MinMaxExpr(m) => self.nullable_coalesce(&m.args),
+            NodeRef::CaseExpr(c) => {
+                if c.defresult.is_none() {
+                    return Some(true);
+                }
+                self.nullable_strict(&case_results(c))
+            }
+            NodeRef::AArrayExpr(_) | NodeRef::RowExpr(_) | NodeRef::SqlvalueFunction(_) => {
+                Some(false)
+            }
+            NodeRef::FuncCall(f) => self.nullable_func_call(f),
+            _ => None,
+        }
+    }
+
+    /// Whether a column reference points to a relation on the nullable side of an outer join,
+    /// e.g. `o.total` in `from users u left join orders o on ...`.
+    ///
+    /// Qualified references are resolved by their qualifier alone, so this works without knowing
+    /// the columns of the relation.
+    pub fn is_outer_joined(&self, column: &protobuf::ColumnRef) -> bool {
+        if let [.., qualifier, _] = column.fields.as_slice() {
+            let qualifier = get_string_from_node(qualifier);
+            if let Some(relation) = self
+                .relations
+                .iter()
+                .find(|r| r.is_referenced_by(None, Some(&qualifier)))
+            {
+                return relation.outer_joined;
+            }
+        }
+
+        matches!(
+            self.lookup_column(column),
+            ColumnLookup::Found {
+                outer_joined: true,
+                ..
+            }
+        )
+    }
+
+    /// The output columns of a subquery, e.g. of the `SubLink` in `id in (select ...)`.
+    pub fn subquery_columns(&self, query: &protobuf::Node) -> Option<Vec<OutputColumn<'a>>> {
+        self.query_columns(query)
+    }
+
+    /// Resolves the operator of an operator expression, e.g. `-` in `created_at - interval '1 day'`.
+    pub fn resolve_operator(&self, expr: &protobuf::AExpr) -> Option<&'a Operator> {
+        match self.resolve_operator_call(expr)?.0 {
+            Resolution::Resolved(operator) => Some(operator),
+            _ => None,
+        }
+    }
+
+    /// Resolves the function a function call refers to, considering implicit casts of its arguments.
+    pub fn resolve_function(&self, call: &protobuf::FuncCall) -> Option<&'a Function> {
+        match self.resolve_function_call(call)?.0 {
+            Resolution::Resolved(func) => Some(func),
+            _ => None,
+        }
+    }
+
+    /// The columns of the rows the statement returns.
+    ///
+    /// Returns `None` if the statement doesn't return rows or we can't determine all of its columns.
+    pub fn output_columns(&self) -> Option<Vec<OutputColumn<'a>>> {
+        match self.root {
+            NodeEnum::SelectStmt(s) => self.select_columns(s),
+            NodeEnum::InsertStmt(i) => self.returning_columns(&i.returning_list, &i.relation, &[]),
+            NodeEnum::UpdateStmt(u) => {
+                self.returning_columns(&u.returning_list, &u.relation, &u.from_clause)
+            }
+            NodeEnum::DeleteStmt(d) => {
+                self.returning_columns(&d.returning_list, &d.relation, &d.using_clause)
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks the statement for type errors.
+    ///
+    /// Only errors we are sure about are reported: expressions whose type we can't infer,
+    /// e.g. because they refer to a table that isn't in the schema cache, are skipped.
+    pub fn check(&self) -> Vec<TypeError> {
+        let mut errors = vec![];
+
+        let output_names: Vec<&str> = self
+            .root
+            .iter()
+            .filter_map(|n| match n {
+                NodeRef::ResTarget(r) if !r.name.is_empty() => Some(r.name.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        for node in self.root.iter() {
+            match node {
+                NodeRef::ColumnRef(c) => {
+                    if let ColumnLookup::NotFound = self.lookup_column(c) {
+                        let name = column_ref_name(c);
+
+                        // `order by` and `group by` may refer to output columns
+                        if c.fields.len() == 1 && output_names.contains(&name.as_str()) {
+                            continue;
+                        }
+
+                        errors.push(TypeError::new(
+                            TypeErrorKind::UnknownColumn { name },
+                            location(c.location),
+                        ));
+                    }
+                }
+                NodeRef::FuncCall(f) => self.check_func_call(f, &mut errors),
+                NodeRef::AExpr(e) => self.check_a_expr(e, &mut errors),
+                NodeRef::CoalesceExpr(c) => {
+                    self.check_common_type("COALESCE", &c.args, c.location, &mut errors)
+                }
+                NodeRef::MinMaxExpr(m) => {
+                    let context = match m.op() {
+                        MinMaxOp::IsLeast => "LEAST",
+                        _ => "GREATEST",
+                    };
+                    self.check_common_type(context, &m.args, m.location, &mut errors)
+                }
+                NodeRef::CaseExpr(c) => {
+                    self.check_common_type("CASE", &case_results(c), c.location, &mut errors);
+
+                    if c.arg.is_none() {
+                        for when in c.args.iter().filter_map(|a| match a.node.as_ref()? {
+                            NodeEnum::CaseWhen(w) => w.expr.as_deref(),
+                            _ => None,
+                        }) {
+                            self.check_boolean("CASE/WHEN", when, &mut errors);
+                        }
+                    }
+                }
+                NodeRef::SelectStmt(s) => {
+                    if let Some(w) = s.where_clause.as_deref() {
+                        self.check_boolean("WHERE", w, &mut errors);
+                    }
+                    if let Some(h) = s.having_clause.as_deref() {
+                        self.check_boolean("HAVING", h, &mut errors);
+                    }
+                }
+                NodeRef::UpdateStmt(u) => {
+                    if let Some(w) = u.where_clause.as_deref() {
+                        self.check_boolean("WHERE", w, &mut errors);
+                    }
+                    if let Some(target) = u.relation.as_ref() {
+                        self.check_assignments(target, &u.target_list, &mut errors);
+                    }
+                }
+                NodeRef::DeleteStmt(d) => {
+                    if let Some(w) = d.where_clause.as_deref() {
+                        self.check_boolean("WHERE", w, &mut errors);
+                    }
+                }
+                NodeRef::JoinExpr(j) => {
+                    if let Some(quals) = j.quals.as_deref() {
+                        self.check_boolean("JOIN/ON", quals, &mut errors);
+                    }
+                }
+                NodeRef::InsertStmt(i) => {
+                    self.check_insert(i, &mut errors);
+
+                    if let (Some(target), Some(on_conflict)) =
+                        (i.relation.as_ref(), i.on_conflict_clause.as_ref())
+                    {
+                        self.check_assignments(target, &on_conflict.target_list, &mut errors);
+                    }
+                }
+                NodeRef::IndexStmt(i) => {
+                    if let Some(w) = i.where_clause.as_deref() {
+                        self.check_boolean("WHERE", w, &mut errors);
+                    }
+                    if let Some(target) = i.relation.as_ref() {
+                        self.check_index_columns(target, &i.index_params, &mut errors);
+                    }
+                }
+                NodeRef::CreatePolicyStmt(p) => {
+                    if let Some(qual) = p.qual.as_deref() {
+                        self.check_boolean("POLICY", qual, &mut errors);
+                    }
+                    if let Some(with_check) = p.with_check.as_deref() {
+                        self.check_boolean("POLICY", with_check, &mut errors);
+                    }
+                }
+                NodeRef::Constraint(c) if c.contype() == ConstrType::ConstrCheck => {
+                    if let Some(expr) = c.raw_expr.as_deref() {
+                        self.check_boolean("CHECK constraint", expr, &mut errors);
+                    }
+                }
+                NodeRef::ColumnDef(d) => {
+                    let expected = d.type_name.as_ref().and_then(|t| self.resolve_type_name(t));
+
+                    for expr in d.constraints.iter().filter_map(|c| match c.node.as_ref()? {
+                        NodeEnum::Constraint(c)
+                            if matches!(
+                                c.contype(),
+                                ConstrType::ConstrDefault | ConstrType::ConstrGenerated
+                            ) =>
+                        {
+                            c.raw_expr.as_deref()
+                        }
+                        _ => None,
+                    }) {
+                        self.check_default(&d.colname, expected, expr, &mut errors);
+                    }
+                }
+                NodeRef::AlterTableStmt(a) => {
+                    let Some(table_columns) =
+                        a.relation.as_ref().and_then(|r| self.table_columns(r))
+                    else {
+                        continue;
+                    };
+
+                    for cmd in a.cmds.iter().filter_map(|c| match c.node.as_ref()? {
+                        NodeEnum::AlterTableCmd(cmd)
+                            if cmd.subtype() == AlterTableType::AtColumnDefault =>
+                        {
+                            Some(cmd)
+                        }
+                        _ => None,
+                    }) {
+                        let (Some(expr), Some(column)) = (
+                            cmd.def.as_deref(),
+                            table_columns.iter().find(|c| c.name == cmd.name),
+                        ) else {
+                            continue;
+                        };
+
+                        self.check_default(&cmd.name, column.type_, expr, &mut errors);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        errors.sort_by_key(|e| e.location);
+        errors.dedup();
+        errors
+    }
+
+    fn check_func_call(&self, f: &protobuf::FuncCall, errors: &mut Vec<TypeError>) {
+        let Some((resolution, args)) = self.resolve_function_call(f) else {
+            return;
+        };
+
+        let name = f
+            .funcname
+            .iter()
+            .map(get_string_from_node)
+            .collect::<Vec<_>>()
+            .join(".");
+        let arg_types = args.iter().map(|a| format_type(*a)).collect();
+
+        let kind = match resolution {
+            Resolution::NotFound => TypeErrorKind::UnknownFunction { name, arg_types },
+            Resolution::Ambiguous => TypeErrorKind::AmbiguousFunction { name, arg_types },
+            Resolution::Resolved(_) | Resolution::Undetermined => return,
+        };
+
+        errors.push(TypeError::new(kind, location(f.location)));
+    }
+
+    fn check_a_expr(&self, e: &protobuf::AExpr, errors: &mut Vec<TypeError>) {
+        let Some((Resolution::NotFound, left, right)) = self.resolve_operator_call(e) else {
+            return;
+        };
+
+        errors.push(TypeError::new(
+            TypeErrorKind::UnknownOperator {
+                name: operator_name(e).unwrap_or_default(),
+                left: left.map(format_type),
+                right: format_type(right),
+            },
+            location(e.location),
+        ));
+    }
+
+    fn check_common_type(
+        &self,
+        context: &str,
+        nodes: &[protobuf::Node],
+        at: i32,
+        errors: &mut Vec<TypeError>,
+    ) {
+        if let Err((first, second)) = self.common_type(nodes) {
+            errors.push(TypeError::new(
+                TypeErrorKind::NoCommonType {
+                    context: context.to_string(),
+                    first: first.format.clone(),
+                    second: second.format.clone(),
+                },
+                location(at),
+            ));
+        }
+    }
+
+    fn check_boolean(&self, clause: &str, node: &protobuf::Node, errors: &mut Vec<TypeError>) {
+        let (Some(found), Some(bool_)) = (self.infer_node(node), self.builtin("bool")) else {
+            return;
+        };
+
+        if !self
+            .coercion
+            .can_coerce(found, bool_, CastContext::Implicit)
+        {
+            errors.push(TypeError::new(
+                TypeErrorKind::InvalidArgument {
+                    clause: clause.to_string(),
+                    expected: bool_.format.clone(),
+                    found: found.format.clone(),
+                },
+                node_location(node),
+            ));
+        }
+    }
+
+    /// Checks the target columns of an `INSERT` and the values inserted into them.
+    fn check_insert(&self, insert: &protobuf::InsertStmt, errors: &mut Vec<TypeError>) {
+        let Some(target) = insert.relation.as_ref() else {
+            return;
+        };
+        let Some(table_columns) = self.table_columns(target) else {
+            return;
+        };
+
+        let mut columns = vec![];
+        if insert.cols.is_empty() {
+            columns.extend(table_columns.iter().map(|c| (c.name.clone(), c.type_)));
+        } else {
+            for res in insert.cols.iter().filter_map(|c| match c.node.as_ref()? {
+                NodeEnum::ResTarget(r) => Some(r),
+                _ => None,
+            }) {
+                match table_columns.iter().find(|c| c.name == res.name) {
+                    Some(c) => columns.push((c.name.clone(), c.type_)),
+                    None => {
+                        errors.push(TypeError::new(
+                            TypeErrorKind::UnknownColumn {
+                                name: res.name.clone(),
+                            },
+                            location(res.location),
+                        ));
+                        return;
+                    }
+                }
+            }
+        }
+
+        let Some(NodeEnum::SelectStmt(select)) =
+            insert.select_stmt.as_deref().and_then(|n| n.node.as_ref())
+        else {
+            return;
+        };
+
+        if select.values_lists.is_empty() {
+            let Some(values) = self.select_columns(select) else {
+                return;
+            };
+
+            for ((column, expected), found) in columns.iter().zip(values) {
+                self.check_assignment(
+                    column,
+                    *expected,
+                    found.type_,
+                    select_location(select),
+                    errors,
+                );
+            }
+            return;
+        }
+
+        for row in select
+            .values_lists
+            .iter()
+            .filter_map(|r| match r.node.as_ref()? {
+                NodeEnum::List(l) => Some(l),
+                _ => None,
+            })
+        {
+            for ((column, expected), value) in columns.iter().zip(row.items.iter()) {
+                // `default` is always fine
+                if matches!(value.node, Some(NodeEnum::SetToDefault(_))) {
+                    continue;
+                }
+
+                self.check_assignment(
+                    column,
+                    *expected,
+                    self.infer_node(value),
+                    node_location(value),
+                    errors,
+                );
+            }
+        }
+    }
+
+    /// Checks the `SET` clause of an `UPDATE` or an `ON CONFLICT DO UPDATE`.
+    fn check_assignments(
+        &self,
+        target: &protobuf::RangeVar,
+        targets: &[protobuf::Node],
+        errors: &mut Vec<TypeError>,
+    ) {
+        let Some(table_columns) = self.table_columns(target) else {
+            return;
+        };
+
+        for res in targets.iter().filter_map(|t| match t.node.as_ref()? {
+            NodeEnum::ResTarget(r) => Some(r),
+            _ => None,
+        }) {
+            let Some(column) = table_columns.iter().find(|c| c.name == res.name) else {
+                errors.push(TypeError::new(
+                    TypeErrorKind::UnknownColumn {
+                        name: res.name.clone(),
+                    },
+                    location(res.location),
+                ));
+                continue;
+            };
+
+            // assignments to array elements or fields of composite columns are not checked
+            let Some(value) = res.val.as_deref().filter(|_| res.indirection.is_empty()) else {
+                continue;
+            };
+
+            // neither are `set (a, b) = (select ...)` and `set a = default`
+            if matches!(
+                value.node,
+                Some(NodeEnum::MultiAssignRef(_)) | Some(NodeEnum::SetToDefault(_))
+            ) {
+                continue;
+            }
+
+            self.check_assignment(
+                &res.name,
+                column.type_,
+                self.infer_node(value),
+                node_location(value),
+                errors,
+            );
+        }
+    }
+
+    /// Checks the plain columns of an index. Expressions are checked like any other expression.
+    fn check_index_columns(
+        &self,
+        target: &protobuf::RangeVar,
+        params: &[protobuf::Node],
+        errors: &mut Vec<TypeError>,
+    ) {
+        let Some(table_columns) = self.table_columns(target) else {
+            return;
+        };
+
+        for elem in params.iter().filter_map(|p| match p.node.as_ref()? {
+            NodeEnum::IndexElem(e) if !e.name.is_empty() => Some(e),
+            _ => None,
+        }) {
+            if !table_columns.iter().any(|c| c.name == elem.name) {
+                errors.push(TypeError::new(
+                    TypeErrorKind::UnknownColumn {
+                        name: elem.name.clone(),
+                    },
+                    None,
+                ));
+            }
+        }
+    }
+
+    /// Checks a column default or generation expression against the type of the column.
+    fn check_default(
+        &self,
+        column: &str,
+        expected: Option<&'a PostgresType>,
+        expr: &protobuf::Node,
+        errors: &mut Vec<TypeError>,
+    ) {
+        let (Some(expected), Some(found)) = (expected, self.infer_node(expr)) else {
+            return;
+        };
+
+        if !self
+            .coercion
+            .can_coerce(found, expected, CastContext::Assignment)
+        {
+            errors.push(TypeError::new(
+                TypeErrorKind::DefaultMismatch {
+                    column: column.to_string(),
+                    expected: expected.format.clone(),
+                    found: found.format.clone(),
+                },
+                node_location(expr),
+            ));
+        }
+    }
+
+    fn check_assignment(
+        &self,
+        column: &str,
+        expected: Option<&'a PostgresType>,
+        found: Option<&'a PostgresType>,
+        location: Option<usize>,
+        errors: &mut Vec<TypeError>,
+    ) {
+        let (Some(expected), Some(found)) = (expected, found) else {
+            return;
+        };
+
+        if !self
+            .coercion
+            .can_coerce(found, expected, CastContext::Assignment)
+        {
+            errors.push(TypeError::new(
+                TypeErrorKind::AssignmentMismatch {
+                    column: column.to_string(),
+                    expected: expected.format.clone(),
+                    found: found.format.clone(),
+                },
+                location,
+            ));
+        }
+    }
+
+    /// The type an untyped literal at `location` is coerced to by its surrounding expression.
+    fn coerced_type(&self, location: i32) -> Option<&'a PostgresType> {
+        self.root.iter().find_map(|node| match node {
+            NodeRef::TypeCast(c) if child_location(c.arg.as_deref()) == Some(location) => {
+                self.resolve_type_name(c.type_name.as_ref()?)
+            }
+            NodeRef::AExpr(e) => {
+                let type_id = if child_location(e.lexpr.as_deref()) == Some(location) {
+                    self.resolve_operator(e)?.left_type_id
+                } else if child_location(e.rexpr.as_deref()) == Some(location) {
+                    self.resolve_operator(e)?.right_type_id
+                } else {
+                    None
+                };
+
+                type_id.and_then(|id| self.schema_cache.find_type_by_id(id))
+            }
+            NodeRef::FuncCall(f) => {
+                let position = f
+                    .args
+                    .iter()
+                    .position(|a| child_location(Some(a)) == Some(location))?;
+
+                let func = self.resolve_function(f)?;
+                self.parameter_types(func, f.args.len(), f.func_variadic)?
+                    .get(position)
+                    .copied()
+                    .filter(|t| !is_polymorphic(t))
+            }
+            _ => None,
+        })
+    }
+
+    fn infer_node(&self, node: &protobuf::Node) -> Option<&'a PostgresType> {
+        self.infer(node.node.as_ref()?.to_ref())
+    }
+
+    fn nullable_node(&self, node: &protobuf::Node) -> Option<bool> {
+        self.nullable(node.node.as_ref()?.to_ref())
+    }
+
+    /// Strict expressions are `NULL` if any of their arguments is.
+    fn nullable_strict<'n>(
+        &self,
+        args: impl IntoIterator<Item = &'n protobuf::Node>,
+    ) -> Option<bool> {
+        let mut nullable = Some(false);
+        for arg in args {
+            match self.nullable_node(arg) {
+                Some(true) => return Some(true),
+                Some(false) => {}
+                None => nullable = None,
+            }
+        }
+        nullable
+    }
+
+    /// `COALESCE`, `GREATEST` and `LEAST` are only `NULL` if all of their arguments are.
+    fn nullable_coalesce(&self, args: &[protobuf::Node]) -> Option<bool> {
+        let mut nullable = Some(true);
+        for arg in args {
+            match self.nullable_node(arg) {
+                Some(false) => return Some(false),
+                Some(true) => {}
+                None => nullable = None,
+            }
+        }
+        nullable
+    }
+
+    fn nullable_func_call(&self, f: &protobuf::FuncCall) -> Option<bool> {
+        let (_, name) = function_name(f)?;
+        if name == "count" {
+            return Some(false);
+        }
+
+        let is_aggregate = self
+            .resolve_function(f)
+            .is_some_and(|func| matches!(func.kind, ProcKind::Aggregate));
+        if !is_aggregate || f.over.is_some() {
+            return None;
+        }
+
+        // without `GROUP BY`, aggregates over no rows are `NULL`
+        let grouped = matches!(self.root, NodeEnum::SelectStmt(s) if !s.group_clause.is_empty());
+        if !grouped {
+            return Some(true);
+        }
+
+        self.nullable_strict(&f.args)
+    }
+
+    fn infer_const(&self, c: &protobuf::AConst) -> Option<&'a PostgresType> {
+        if c.isnull {
+            return self.builtin("unknown");
+        }
+
+        match c.val.as_ref()? {
+            Val::Ival(_) => self.builtin("int4"),
+            Val::Fval(f) => {
+                // integers that don't fit into an int4 are reported as floats
+                if f.fval.contains(['.', 'e', 'E']) {
+                    self.builtin("numeric")
+                } else {
+                    self.builtin("int8")
+                }
+            }
+            Val::Boolval(_) => self.builtin("bool"),
+            Val::Sval(_) => self.builtin("unknown"),
+            Val::Bsval(_) => self.builtin("bit"),
+        }
+    }
+
+    fn lookup_column(&self, c: &protobuf::ColumnRef) -> ColumnLookup<'a> {
+        let Some(parts) = c
+            .fields
+            .iter()
+            .map(|f| match f.node.as_ref()? {
+                NodeEnum::String(s) => Some(s.sval.as_str()),
+                _ => None,
+            })
+            .collect::<Option<Vec<&str>>>()
+        else {
+            // `*` and `t.*`
+            return ColumnLookup::Undetermined;
+        };
+
+        let (schema, table, column) = match parts.as_slice() {
+            [column] => (None, None, *column),
+            [table, column] => (None, Some(*table), *column),
+            [schema, table, column] => (Some(*schema), Some(*table), *column),
+            _ => return ColumnLookup::Undetermined,
+        };
+
+        let candidates: Vec<usize> = (0..self.relations.len())
+            .filter(|i| self.relations[*i].is_referenced_by(schema, table))
+            .collect();
+
+        if candidates.is_empty() {
+            return ColumnLookup::Undetermined;
+        }
+
+        let mut undetermined = false;
+        for i in &candidates {
+            match self.relation_columns(*i) {
+                Some(columns) => {
+                    if let Some(c) = columns.iter().find(|c| c.name == column) {
+                        let outer_joined = self.relations[*i].outer_joined;
+                        return ColumnLookup::Found {
+                            type_: c.type_,
+                            nullable: if outer_joined { Some(true) } else { c.nullable },
+                            outer_joined,
+                        };
+                    }
+                }
+                None => undetermined = true,
+            }
+        }
+
+        // an unqualified name may also refer to the whole row of a relation
+        if table.is_none()
+            && self
+                .relations
+                .iter()
+                .any(|r| r.is_referenced_by(None, Some(column)))
+        {
+            return ColumnLookup::Found {
+                type_: None,
+                nullable: None,
+                outer_joined: false,
+            };
+        }
+
+        if let Some((_, type_name)) = SYSTEM_COLUMNS.iter().find(|(name, _)| *name == column) {
+            if candidates
+                .iter()
+                .any(|i| matches!(self.relations[*i].source, RelationSource::Table))
+            {
+                return ColumnLookup::Found {
+                    type_: self.builtin(type_name),
+                    nullable: Some(false),
+                    outer_joined: false,
+                };
+            }
+        }
+
+        if undetermined {
+            ColumnLookup::Undetermined
+        } else {
+            ColumnLookup::NotFound
+        }
+    }
+
+    /// The columns of a relation in the `FROM` clause, in order and with aliases applied.
+    ///
+    /// Returns `None` if we can't determine them.
+    fn relation_columns(&self, index: usize) -> Option<RelationColumns<'a>> {
+        if self.resolving.borrow().contains(&index) {
+            return None;
+        }
+
+        self.resolving.borrow_mut().push(index);
+
+        let relation = &self.relations[index];
+        let columns = match relation.source {
+            RelationSource::Table => {
+                self.table_columns_by_name(&relation.name, relation.schema.as_deref())
+            }
+            RelationSource::Function(call) => self.function_columns(call, relation),
+            RelationSource::Query(query) => self.query_columns(query),
+        };
+
+        self.resolving.borrow_mut().retain(|i| *i != index);
+
+        let mut columns = columns?;
+        for (column, alias) in columns.iter_mut().zip(relation.column_aliases.iter()) {
+            column.name = alias.clone();
+        }
+
+        Some(columns)
+    }
+
+    fn table_columns(&self, range_var: &protobuf::RangeVar) -> Option<RelationColumns<'a>> {
+        self.table_columns_by_name(
+            &range_var.relname,
+            (!range_var.schemaname.is_empty()).then_some(range_var.schemaname.as_str()),
+        )
+    }
+
+    fn table_columns_by_name(
+        &self,
+        name: &str,
+        schema: Option<&str>,
+    ) -> Option<RelationColumns<'a>> {
+        let table = self
+            .schema_cache
+            .find_tables(name, schema)
+            .into_iter()
+            .next()?;
+
+        let mut columns: Vec<_> = self
+            .schema_cache
+            .columns
+            .iter()
+            .filter(|c| c.table_oid == table.id)
+            .collect();
+        columns.sort_by_key(|c| c.number);
+
+        Some(
+            columns
+                .into_iter()
+                .map(|c| OutputColumn {
+                    name: c.name.clone(),
+                    type_: self.schema_cache.find_type_by_id(c.type_id),
+                    nullable: Some(c.is_nullable),
+                })
+                .collect(),
+        )
+    }
+
+    /// The columns produced by a set-returning function in the `FROM` clause.
+    fn function_columns(
+        &self,
+        call: &protobuf::FuncCall,
+        relation: &Relation,
+    ) -> Option<RelationColumns<'a>> {
+        let func = self.resolve_function(call)?;
+
+        let out_args: RelationColumns<'a> = func
+            .args
+            .args
+            .iter()
+            .filter(|a| matches!(a.mode.as_str(), "out" | "inout" | "table"))
+            .map(|a| OutputColumn {
+                name: a.name.clone(),
+                type_: self.schema_cache.find_type_by_id(a.type_id),
+                nullable: None,
+            })
+            .collect();
+
+        if !out_args.is_empty() {
+            return Some(out_args);
+        }
+
+        if let Some(relation_id) = func.return_type_relation_id {
+            let table = self
+                .schema_cache
+                .tables
+                .iter()
+                .find(|t| t.id == relation_id)?;
+            return self.table_columns_by_name(&table.name, Some(&table.schema));
+        }
+
+        let return_type = self.infer_func_call(call)?;
+        if return_type.name == "record" {
+            // the columns are defined by a column definition list
+            return None;
+        }
+
+        // scalar functions produce a single column named after the alias or the function
+        let name = relation.alias.clone().unwrap_or(relation.name.clone());
+        Some(vec![OutputColumn {
+            name,
+            type_: Some(return_type),
+            nullable: None,
+        }])
+    }
+
+    /// The output columns of a subquery, a CTE, or a data-modifying statement with `RETURNING`.
+    fn query_columns(&self, query: &protobuf::Node) -> Option<RelationColumns<'a>> {
+        match query.node.as_ref()? {
+            NodeEnum::SelectStmt(s) => self.select_columns(s),
+            NodeEnum::InsertStmt(i) => self.returning_columns(&i.returning_list, &i.relation, &[]),
+            NodeEnum::UpdateStmt(u) => {
+                self.returning_columns(&u.returning_list, &u.relation, &u.from_clause)
+            }
+            NodeEnum::DeleteStmt(d) => {
+                self.returning_columns(&d.returning_list, &d.relation, &d.using_clause)
+            }
+            _ => None,
+        }
+    }
+
+    fn returning_columns(
+        &self,
+        returning_list: &[protobuf::Node],
+        target: &Option<protobuf::RangeVar>,
+        from_clause: &[protobuf::Node],
+    ) -> Option<RelationColumns<'a>> {
+        let mut scope: Vec<String> = target.iter().map(range_var_name).collect();
+        scope.extend(from_item_names(from_clause));
+        self.target_list_columns(returning_list, &scope)
+    }
+
+    fn select_columns(&self, select: &protobuf::SelectStmt) -> Option<RelationColumns<'a>> {
+        // the left-most branch of a set operation determines the column names
+        if select.op() != SetOperation::SetopNone {
+            return self.select_columns(select.larg.as_deref()?);
+        }
+
+        if let Some(NodeEnum::List(first_row)) =
+            select.values_lists.first().and_then(|r| r.node.as_ref())
+        {
+            return Some(
+                first_row
+                    .items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, value)| OutputColumn {
+                        name: format!("column{}", i + 1),
+                        type_: self.infer_node(value),
+                        nullable: self.nullable_node(value),
+                    })
+                    .collect(),
+            );
+        }
+
+        self.target_list_columns(&select.target_list, &from_item_names(&select.from_clause))
+    }
+
+    /// The columns of a target list. `scope` holds the names of the relations that `*` expands to.
+    fn target_list_columns(
+        &self,
+        target_list: &[protobuf::Node],
+        scope: &[String],
+    ) -> Option<RelationColumns<'a>> {
+        if target_list.is_empty() {
+            return None;
+        }
+
+        let mut columns = vec![];
+        for target in target_list {
+            let NodeEnum::ResTarget(res) = target.node.as_ref()? else {
+                return None;
+            };
+
+            let value = res.val.as_deref()?;
+            if let Some(NodeEnum::ColumnRef(c)) = value.node.as_ref() {
+                if let Some((Some(NodeEnum::AStar(_)), qualifier)) = c
+                    .fields
+                    .split_last()
+                    .map(|(last, qualifier)| (last.node.as_ref(), qualifier))
+                {
+                    let table = qualifier.last().map(get_string_from_node);
+                    columns.extend(self.expand_star(table.as_deref(), scope)?);
+                    continue;
+                }
+            }
+
+            let name = if res.name.is_empty() {
+                output_name(value)
+            } else {
+                res.name.clone()
+            };
+
+            columns.push(OutputColumn {
+                name,
+                type_: self.infer_node(value),
+                nullable: self.nullable_node(value),
+            });
+        }
+
+        Some(columns)
+    }
+
+    /// The columns `*` or `table.*` expand to.
+    fn expand_star(&self, table: Option<&str>, scope: &[String]) -> Option<RelationColumns<'a>> {
+        let names: Vec<&str> = match table {
+            Some(table) => vec![table],
+            None => scope.iter().map(String::as_str).collect(),
+        };
+
+        if names.is_empty() {
+            return None;
+        }
+
+        let mut columns = vec![];
+        for name in names {
+            let index = (0..self.relations.len())
+                .find(|i| self.relations[*i].is_referenced_by(None, Some(name)))?;
+            columns.extend(self.relation_columns(index)?);
+        }
+
+        Some(columns)
+    }
+
+    /// Parameters get their type from where they are used, e.g. `id = $1` or `$1::int`.
+    fn infer_param(&self, number: i32) -> Option<&'a PostgresType> {
+        let is_param = |node: Option<&protobuf::Node>| {
+            matches!(
+                node.and_then(|n| n.node.as_ref()),
+                Some(NodeEnum::ParamRef(p)) if p.number == number
+            )
+        };
+
+        self.root.iter().find_map(|node| match node {
+            NodeRef::TypeCast(c) if is_param(c.arg.as_deref()) => {
+                self.resolve_type_name(c.type_name.as_ref()?)
+            }
+            NodeRef::AExpr(e) => {
+                let other = if is_param(e.lexpr.as_deref()) {
+                    e.rexpr.as_deref()
+                } else if is_param(e.rexpr.as_deref()) {
+                    e.lexpr.as_deref()
+                } else {
+                    return None;
+                };
+
+                if is_param(other) {
+                    return None;
+                }
+
+                other
+                    .and_then(|n| self.infer_node(n))
+                    .filter(|t| !is_unknown(t))
+            }
+            _ => None,
+        })
+    }
+
+    fn infer_a_expr(&self, e: &protobuf::AExpr) -> Option<&'a PostgresType> {
+        match e.kind() {
+            AExprKind::AexprOp | AExprKind::AexprLike | AExprKind::AexprIlike => {
+                let (resolution, left, right) = self.resolve_operator_call(e)?;
+                let Resolution::Resolved(operator) = resolution else {
+                    return None;
+                };
+
+                let params = self.operator_parameter_types(operator)?;
+                let args: Vec<_> = left.into_iter().chain([right]).collect();
+                self.return_type(operator.result_type_id, &params, &args)
+            }
+            AExprKind::AexprNullif => self.infer_node(e.lexpr.as_deref()?),
+            AExprKind::AexprOpAny
+            | AExprKind::AexprOpAll
+            | AExprKind::AexprDistinct
+            | AExprKind::AexprNotDistinct
+            | AExprKind::AexprIn
+            | AExprKind::AexprSimilar
+            | AExprKind::AexprBetween
+            | AExprKind::AexprNotBetween
+            | AExprKind::AexprBetweenSym
+            | AExprKind::AexprNotBetweenSym => self.builtin("bool"),
+            AExprKind::Undefined => None,
+        }
+    }
+
+    /// Picks the operator for the operand types.
+    ///
+    /// Returns the resolution along with the types of the left (if any) and right operands.
+    #[allow(clippy::type_complexity)]
+    fn resolve_operator_call(
+        &self,
+        expr: &protobuf::AExpr,
+    ) -> Option<(
+        Resolution<&'a Operator>,
+        Option<Option<&'a PostgresType>>,
+        Option<&'a PostgresType>,
+    )> {
+        if !matches!(
+            expr.kind(),
+            AExprKind::AexprOp | AExprKind::AexprLike | AExprKind::AexprIlike
+        ) {
+            return None;
+        }
+
+        let name = operator_name(expr)?;
+
+        let left = expr.lexpr.as_deref().map(|n| self.infer_node(n));
+        let right = self.infer_node(expr.rexpr.as_deref()?);
+
+        let candidates: Vec<(&'a Operator, Vec<&'a PostgresType>)> = self
+            .schema_cache
+            .find_operators(&name)
+            .into_iter()
+            .filter(|o| o.left_type_id.is_some() == left.is_some())
+            .filter_map(|o| Some((o, self.operator_parameter_types(o)?)))
+            .collect();
+
+        // an untyped literal is assumed to be of the same type as the other operand
+        if let (Some(Some(l)), Some(r)) = (left, right) {
+            let known = match (is_unknown(l), is_unknown(r)) {
+                (true, false) => Some(r),
+                (false, true) => Some(l),
+                _ => None,
+            };
+
+            if let Some((operator, _)) = known.and_then(|known| {
+                candidates
+                    .iter()
+                    .find(|(_, params)| params.iter().all(|p| p.id == known.id))
+            }) {
+                return Some((Resolution::Resolved(*operator), left, right));
+            }
+        }
+
+        let args: Vec<_> = left.into_iter().chain([right]).collect();
+        Some((
+            self.coercion.select_candidate(candidates, &args),
+            left,
+            right,
+        ))
+    }
+
+    fn operator_parameter_types(&self, operator: &Operator) -> Option<Vec<&'a PostgresType>> {
+        operator
+            .left_type_id
+            .into_iter()
+            .chain(operator.right_type_id)
+            .map(|id| self.schema_cache.find_type_by_id(id))
+            .collect()
+    }
+
+    fn infer_func_call(&self, f: &protobuf::FuncCall) -> Option<&'a PostgresType> {
+        let (resolution, args) = self.resolve_function_call(f)?;
+
+        if let Resolution::Resolved(func) = resolution {
+            let params = self.parameter_types(func, args.len(), f.func_variadic)?;
+            return self.return_type(func.return_type_id?, &params, &args);
+        }
+
+        // if we can't pick an overload, we still know the type if all of them agree on it
+        let (schema, name) = function_name(f)?;
+        let candidates: Vec<&Function> = self
+            .schema_cache
+            .find_functions(&name, schema.as_deref())
+            .into_iter()
+            .filter(|func| accepts_arg_count(func, f.args.len()))
+            .collect();
+
+        let return_type_id = candidates.first()?.return_type_id?;
+        if candidates
+            .iter()
+            .any(|c| c.return_type_id != Some(return_type_id))
+        {
+            return None;
+        }
+
+        self.schema_cache
+            .find_type_by_id(return_type_id)
+            .filter(|t| !is_polymorphic(t))
+    }
+
+    /// Picks the overload of the function for the argument types.
+    ///
+    /// Returns the resolution along with the types of the arguments.
+    #[allow(clippy::type_complexity)]
+    fn resolve_function_call(
+        &self,
+        f: &protobuf::FuncCall,
+    ) -> Option<(Resolution<&'a Function>, Vec<Option<&'a PostgresType>>)> {
+        let (schema, name) = function_name(f)?;
+
+        let args: Vec<Option<&'a PostgresType>> = f
+            .args
+            .iter()
+            .map(|a| match a.node.as_ref()? {
+                // named arguments can be passed in any order, so we don't check them
+                NodeEnum::NamedArgExpr(_) => None,
+                _ => self.infer_node(a),
+            })
+            .collect();
+
+        let functions = self.schema_cache.find_functions(&name, schema.as_deref());
+        if functions.is_empty() {
+            return Some((Resolution::NotFound, args));
+        }
+
+        let candidates = functions
+            .into_iter()
+            .filter_map(|func| {
+                Some((
+                    func,
+                    self.parameter_types(func, args.len(), f.func_variadic)?,
+                ))
+            })
+            .collect();
+
+        Some((self.coercion.select_candidate(candidates, &args), args))
+    }
+
+    /// The parameter types of a function when called with `count` arguments.
+    ///
+    /// Returns `None` if the function can't be called with that many arguments.
+    fn parameter_types(
+        &self,
+        func: &Function,
+        count: usize,
+        variadic_call: bool,
+    ) -> Option<Vec<&'a PostgresType>> {
+        if !accepts_arg_count(func, count) {
+            return None;
+        }
+
+        let mut params = vec![];
+        for (i, arg) in func
+            .args
+            .args
+            .iter()
+            .filter(|a| matches!(a.mode.as_str(), "in" | "inout" | "variadic"))
+            .take(count)
+            .enumerate()
+        {
+            let t = self.schema_cache.find_type_by_id(arg.type_id)?;
+
+            // the variadic array is passed as separate arguments, unless `VARIADIC` is used
+            if arg.mode == "variadic" && !variadic_call {
+                let element = match t.element_type_id {
+                    Some(id) => self.schema_cache.find_type_by_id(id)?,
+                    None => t,
+                };
+                params.extend(std::iter::repeat_n(element, count - i));
+                break;
+            }
+
+            params.push(t);
+        }
+
+        Some(params)
+    }
+
+    /// The return type of a function or operator, with polymorphic types bound to the argument types.
+    fn return_type(
+        &self,
+        return_type_id: i64,
+        params: &[&'a PostgresType],
+        args: &[Option<&'a PostgresType>],
+    ) -> Option<&'a PostgresType> {
+        let return_type = self.schema_cache.find_type_by_id(return_type_id)?;
+        if !is_polymorphic(return_type) {
+            return Some(return_type);
+        }
+
+        let compatible_family = return_type.name.starts_with("anycompatible");
+        let polymorphic_args: Vec<(&PostgresType, &'a PostgresType)> = params
+            .iter()
+            .zip(args)
+            .filter(|(p, _)| {
+                is_polymorphic(p) && p.name.starts_with("anycompatible") == compatible_family
+            })
+            .filter_map(|(p, a)| Some((*p, (*a)?)))
+            .collect();
+
+        let bound = polymorphic_args
+            .iter()
+            .filter(|(_, a)| !is_unknown(a))
+            .find_map(|(p, a)| match p.name.as_str() {
+                "anyarray" | "anycompatiblearray" => {
+                    self.schema_cache.find_type_by_id(a.element_type_id?)
+                }
+                "anyelement"
+                | "anynonarray"
+                | "anyenum"
+                | "anycompatible"
+                | "anycompatiblenonarray" => Some(*a),
+                _ => None,
+            });
+
+        // untyped literals are resolved as text
+        let element = match bound {
+            Some(element) => element,
+            None if !polymorphic_args.is_empty()
+                && polymorphic_args.iter().all(|(_, a)| is_unknown(a)) =>
+            {
+                self.builtin("text")?
+            }
+            None => return None,
+        };
+
+        match return_type.name.as_str() {
+            "anyarray" | "anycompatiblearray" => {
+                self.schema_cache.find_type_by_id(element.array_type_id?)
+            }
+            "anyelement"
+            | "anynonarray"
+            | "anyenum"
+            | "anycompatible"
+            | "anycompatiblenonarray" => Some(element),
+            _ => None,
+        }
+    }
+
+    fn infer_sql_value_function(&self, f: &protobuf::SqlValueFunction) -> Option<&'a PostgresType> {
+        let type_name = match f.op() {
+            SqlValueFunctionOp::SvfopCurrentDate => "date",
+            SqlValueFunctionOp::SvfopCurrentTime | SqlValueFunctionOp::SvfopCurrentTimeN => {
+                "timetz"
+            }
+            SqlValueFunctionOp::SvfopCurrentTimestamp
+            | SqlValueFunctionOp::SvfopCurrentTimestampN => "timestamptz",
+            SqlValueFunctionOp::SvfopLocaltime | SqlValueFunctionOp::SvfopLocaltimeN => "time",
+            SqlValueFunctionOp::SvfopLocaltimestamp | SqlValueFunctionOp::SvfopLocaltimestampN => {
+                "timestamp"
+            }
+            SqlValueFunctionOp::SvfopCurrentRole
+            | SqlValueFunctionOp::SvfopCurrentUser
+            | SqlValueFunctionOp::SvfopUser
+            | SqlValueFunctionOp::SvfopSessionUser
+            | SqlValueFunctionOp::SvfopCurrentCatalog
+            | SqlValueFunctionOp::SvfopCurrentSchema => "name",
+            SqlValueFunctionOp::SqlvalueFunctionOpUndefined => return None,
+        };
+
+        self.builtin(type_name)
+    }
+
+    fn resolve_type_name(&self, type_name: &protobuf::TypeName) -> Option<&'a PostgresType> {
+        let (schema, name) = match type_name.names.as_slice() {
+            [name] => (None, get_string_from_node(name)),
+            [schema, name] => (
+                Some(get_string_from_node(schema)),
+                get_string_from_node(name),
+            ),
+            _ => return None,
+        };
+
+        // array types are prefixed with an underscore, e.g. `_int4`
+        let name = if type_name.array_bounds.is_empty() {
+            name
+        } else {
+            format!("_{name}")
+        };
+
+        self.schema_cache.find_type(&name, schema.as_deref())
+    }
+
+    /// The common type of the branches of a `CASE`, `COALESCE` or similar expression.
+    ///
+    /// Branches of which we don't know the type are ignored.
+    #[allow(clippy::type_complexity)]
+    fn common_type(
+        &self,
+        nodes: &[protobuf::Node],
+    ) -> Result<Option<&'a PostgresType>, (&'a PostgresType, &'a PostgresType)> {
+        let types: Vec<&'a PostgresType> =
+            nodes.iter().filter_map(|n| self.infer_node(n)).collect();
+        self.coercion.common_type(&types)
+    }
+
+    fn builtin(&self, name: &str) -> Option<&'a PostgresType> {
+        self.schema_cache.find_type(name, Some("pg_catalog"))
+    }
+}
+
+/// The results of all branches of a `CASE` expression, including the `ELSE` branch.
+fn case_results(c: &protobuf::CaseExpr) -> Vec<protobuf::Node> {
+    c.args
+        .iter()
+        .filter_map(|arg| match arg.node.as_ref()? {
+            NodeEnum::CaseWhen(w) => w.result.as_deref().cloned(),
+            _ => None,
+        })
+        .chain(c.defresult.as_deref().cloned())
+        .collect()
+}
+
+fn function_name(f: &protobuf::FuncCall) -> Option<(Option<String>, String)> {
+    match f.funcname.as_slice() {
+        [name] => Some((None, get_string_from_node(name))),
+        [schema, name] => Some((
+            Some(get_string_from_node(schema)),
+            get_string_from_node(name),
+        )),
+        _ => None,
+    }
+}
+
+fn operator_name(e: &protobuf::AExpr) -> Option<String> {
+    e.name.last().map(get_string_from_node)
+}
+
+fn column_ref_name(c: &protobuf::ColumnRef) -> String {
+    c.fields
+        .iter()
+        .map(get_string_from_node)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// The name Postgres gives to an output column without an alias.
+fn output_name(node: &protobuf::Node) -> String {
+    match node.node.as_ref() {
+        Some(NodeEnum::ColumnRef(c)) => c.fields.last().map(get_string_from_node),
+        Some(NodeEnum::FuncCall(f)) => f.funcname.last().map(get_string_from_node),
+        Some(NodeEnum::TypeCast(c)) => c
+            .arg
+            .as_deref()
+            .map(output_name)
+            .filter(|n| n != "?column?")
+            .or_else(|| {
+                c.type_name
+                    .as_ref()
+                    .and_then(|t| t.names.last())
+                    .map(get_string_from_node)
+            }),
+        _ => None,
+    }
+    .unwrap_or_else(|| "?column?".to_string())
+}
+
+fn format_type(t: Option<&PostgresType>) -> String {
+    t.map(|t| t.format.clone())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn location(location: i32) -> Option<usize> {
+    usize::try_from(location).ok()
+}
+
+/// The start of an expression. Operator expressions are located at their operator,
+/// so we use the location of their first operand instead.
+fn node_location(node: &protobuf::Node) -> Option<usize> {
+    match node.node.as_ref()? {
+        NodeEnum::AConst(c) => location(c.location),
+        NodeEnum::ColumnRef(c) => location(c.location),
+        NodeEnum::ParamRef(p) => location(p.location),
+        NodeEnum::FuncCall(f) => location(f.location),
+        NodeEnum::TypeCast(c) => c
+            .arg
+            .as_deref()
+            .and_then(node_location)
+            .or(location(c.location)),
+        NodeEnum::AExpr(e) => e
+            .lexpr
+            .as_deref()
+            .and_then(node_location)
+            .or(location(e.location)),
+        NodeEnum::BoolExpr(b) => b
+            .args
+            .first()
+            .and_then(node_location)
+            .or(location(b.location)),
+        NodeEnum::NullTest(n) => n.arg.as_deref().and_then(node_location),
+        NodeEnum::BooleanTest(b) => b.arg.as_deref().and_then(node_location),
+        NodeEnum::SubLink(s) => location(s.location),
+        NodeEnum::CaseExpr(c) => location(c.location),
+        NodeEnum::CoalesceExpr(c) => location(c.location),
+        NodeEnum::MinMaxExpr(m) => location(m.location),
+        NodeEnum::AArrayExpr(a) => location(a.location),
+        NodeEnum::RowExpr(r) => location(r.location),
+        NodeEnum::SqlvalueFunction(f) => location(f.location),
+        _ => None,
+    }
+}
+
+fn select_location(select: &protobuf::SelectStmt) -> Option<usize> {
+    select
+        .target_list
+        .first()
+        .and_then(|t| match t.node.as_ref()? {
+            NodeEnum::ResTarget(r) => location(r.location),
+            _ => None,
+        })
+}
+
+fn child_location(node: Option<&protobuf::Node>) -> Option<i32> {
+    match node?.node.as_ref()? {
+        NodeEnum::AConst(c) => Some(c.location),
+        NodeEnum::ParamRef(p) => Some(p.location),
+        _ => None,
+    }
+}
+
+fn accepts_arg_count(func: &Function, count: usize) -> bool {
+    let inputs = func
+        .args
+        .args
+        .iter()
+        .filter(|a| !matches!(a.mode.as_str(), "out" | "table"));
+
+    let (total, required) = inputs.fold((0, 0), |(total, required), arg| {
+        let has_default = arg.has_default.unwrap_or(false);
+        (total + 1, required + usize::from(!has_default))
+    });
+
+    let is_variadic = func.args.args.iter().any(|a| a.mode == "variadic");
+
+    required <= count && (count <= total || is_variadic)
+}
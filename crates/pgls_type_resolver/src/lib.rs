@@ -1,5 +1,10 @@
+mod coercion;
+mod diagnostics;
 mod functions;
-mod types;
+mod infer;
+mod relations;
 mod util;
 
+pub use diagnostics::{TypeError, TypeErrorKind};
 pub use functions::resolve_func_call;
+pub use infer::{ExpressionKind, OutputColumn, ResolvedExpression, TypeInferrer};
@@ -0,0 +1,246 @@
+use pgls_query::{
+    NodeEnum, NodeRef,
+    protobuf::{self, JoinType},
+};
+
+use crate::util::get_string_from_node;
+
+/// Where the columns of a relation come from.
+#[derive(Debug)]
+pub(crate) enum RelationSource<'b> {
+    /// A table, view or materialized view in the schema cache.
+    Table,
+
+    /// A set-returning function in the `FROM` clause, e.g. `from generate_series(1, 10) as n`.
+    Function(&'b protobuf::FuncCall),
+
+    /// A subquery in the `FROM` clause, or a CTE.
+    Query(&'b protobuf::Node),
+}
+
+/// A relation in the `FROM` clause of the statement.
+#[derive(Debug)]
+pub(crate) struct Relation<'b> {
+    pub schema: Option<String>,
+    pub name: String,
+    pub alias: Option<String>,
+
+    /// Column names given by the alias, e.g. `as t(a, b)`.
+    pub column_aliases: Vec<String>,
+
+    pub source: RelationSource<'b>,
+
+    /// Whether the relation is on the nullable side of an outer join, in which case all of its
+    /// columns may be `NULL`.
+    pub outer_joined: bool,
+}
+
+impl Relation<'_> {
+    /// Whether a column reference qualified with `table` (and `schema`) can point to this relation.
+    pub fn is_referenced_by(&self, schema: Option<&str>, table: Option<&str>) -> bool {
+        let Some(table) = table else {
+            return true;
+        };
+
+        match &self.alias {
+            Some(alias) => alias == table && schema.is_none(),
+            None => self.name == table && schema.is_none_or(|s| self.schema.as_deref() == Some(s)),
+        }
+    }
+}
+
+/// Collects every relation the statement reads from or writes to.
+///
+/// The relations of subqueries are collected as well, so that correlated column
+/// references resolve.
+pub(crate) fn collect_relations(root: &NodeEnum) -> Vec<Relation<'_>> {
+    let ctes: Vec<&protobuf::CommonTableExpr> = root
+        .iter()
+        .filter_map(|n| match n {
+            NodeRef::CommonTableExpr(cte) => Some(cte),
+            _ => None,
+        })
+        .collect();
+
+    let outer_joined = outer_joined_names(root);
+    let is_outer_joined = |name: &str| outer_joined.iter().any(|n| n == name);
+
+    let mut relations = vec![];
+
+    for node in root.iter() {
+        match node {
+            NodeRef::RangeVar(r) => {
+                let alias = r.alias.as_ref().map(|a| a.aliasname.clone());
+                let mut column_aliases = r.alias.as_ref().map(alias_columns).unwrap_or_default();
+
+                let cte = ctes
+                    .iter()
+                    .find(|cte| r.schemaname.is_empty() && cte.ctename == r.relname);
+
+                let source = match cte.and_then(|cte| cte.ctequery.as_deref()) {
+                    Some(query) => {
+                        if column_aliases.is_empty() {
+                            column_aliases = cte
+                                .map(|cte| cte.aliascolnames.iter().map(get_string_from_node))
+                                .into_iter()
+                                .flatten()
+                                .collect();
+                        }
+                        RelationSource::Query(query)
+                    }
+                    None => RelationSource::Table,
+                };
+
+                relations.push(Relation {
+                    schema: (!r.schemaname.is_empty()).then(|| r.schemaname.clone()),
+                    name: r.relname.clone(),
+                    alias,
+                    column_aliases,
+                    source,
+                    outer_joined: is_outer_joined(&range_var_name(r)),
+                });
+            }
+            NodeRef::RangeFunction(f) => {
+                // each function is wrapped in a list together with its column definitions
+                let func = f
+                    .functions
+                    .iter()
+                    .find_map(|item| match item.node.as_ref()? {
+                        NodeEnum::List(l) => {
+                            l.items.iter().find_map(|i| match i.node.as_ref()? {
+                                NodeEnum::FuncCall(func) => Some(func),
+                                _ => None,
+                            })
+                        }
+                        _ => None,
+                    });
+
+                let Some(func) = func else {
+                    continue;
+                };
+
+                let name = func
+                    .funcname
+                    .last()
+                    .map(get_string_from_node)
+                    .unwrap_or_default();
+                let alias = f.alias.as_ref().map(|a| a.aliasname.clone());
+
+                relations.push(Relation {
+                    schema: None,
+                    outer_joined: is_outer_joined(alias.as_ref().unwrap_or(&name)),
+                    name,
+                    alias,
+                    column_aliases: f.alias.as_ref().map(alias_columns).unwrap_or_default(),
+                    source: RelationSource::Function(func),
+                });
+            }
+            NodeRef::RangeSubselect(s) => {
+                let (Some(alias), Some(query)) = (s.alias.as_ref(), s.subquery.as_deref()) else {
+                    continue;
+                };
+
+                relations.push(Relation {
+                    schema: None,
+                    name: alias.aliasname.clone(),
+                    alias: None,
+                    column_aliases: alias_columns(alias),
+                    source: RelationSource::Query(query),
+                    outer_joined: is_outer_joined(&alias.aliasname),
+                });
+            }
+            NodeRef::InsertStmt(i) if i.on_conflict_clause.is_some() => {
+                // `excluded` refers to the row proposed for insertion
+                if let Some(target) = i.relation.as_ref() {
+                    relations.push(Relation {
+                        schema: (!target.schemaname.is_empty()).then(|| target.schemaname.clone()),
+                        name: target.relname.clone(),
+                        alias: Some("excluded".to_string()),
+                        column_aliases: vec![],
+                        source: RelationSource::Table,
+                        outer_joined: false,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    relations
+}
+
+/// The names of the relations on the nullable side of an outer join, e.g. `b` in
+/// `a left join b on ...`.
+fn outer_joined_names(root: &NodeEnum) -> Vec<String> {
+    root.iter()
+        .filter_map(|n| match n {
+            NodeRef::JoinExpr(j) => Some(j),
+            _ => None,
+        })
+        .flat_map(|j| {
+            let (left, right) = match j.jointype() {
+                JoinType::JoinLeft => (false, true),
+                JoinType::JoinRight => (true, false),
+                JoinType::JoinFull => (true, true),
+                _ => (false, false),
+            };
+
+            let mut names = vec![];
+            if left {
+                names.extend(from_item_names(j.larg.as_deref()));
+            }
+            if right {
+                names.extend(from_item_names(j.rarg.as_deref()));
+            }
+            names
+        })
+        .collect()
+}
+
+/// The names by which the relations of a `FROM` clause are referenced, in order.
+pub(crate) fn from_item_names<'n>(
+    from_clause: impl IntoIterator<Item = &'n protobuf::Node>,
+) -> Vec<String> {
+    let mut names = vec![];
+    for item in from_clause {
+        match item.node.as_ref() {
+            Some(NodeEnum::RangeVar(r)) => names.push(range_var_name(r)),
+            Some(NodeEnum::RangeSubselect(s)) => {
+                names.extend(s.alias.as_ref().map(|a| a.aliasname.clone()))
+            }
+            Some(NodeEnum::RangeFunction(f)) => match f.alias.as_ref() {
+                Some(alias) => names.push(alias.aliasname.clone()),
+                None => names.extend(
+                    f.functions
+                        .iter()
+                        .filter_map(|item| match item.node.as_ref()? {
+                            NodeEnum::List(l) => l.items.first(),
+                            _ => None,
+                        })
+                        .find_map(|func| match func.node.as_ref()? {
+                            NodeEnum::FuncCall(func) => func.funcname.last(),
+                            _ => None,
+                        })
+                        .map(get_string_from_node),
+                ),
+            },
+            Some(NodeEnum::JoinExpr(j)) => {
+                names.extend(from_item_names(j.larg.as_deref()));
+                names.extend(from_item_names(j.rarg.as_deref()));
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+pub(crate) fn range_var_name(r: &protobuf::RangeVar) -> String {
+    r.alias
+        .as_ref()
+        .map(|a| a.aliasname.clone())
+        .unwrap_or_else(|| r.relname.clone())
+}
+
+fn alias_columns(alias: &protobuf::Alias) -> Vec<String> {
+    alias.colnames.iter().map(get_string_from_node).collect()
+}
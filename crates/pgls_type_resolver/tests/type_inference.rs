@@ -0,0 +1,287 @@
+use pgls_schema_cache::SchemaCache;
+use pgls_type_resolver::TypeInferrer;
+use sqlx::{Executor, PgPool};
+
+static SETUP: &str = r#"
+    create table users (
+        id int4 primary key,
+        name text not null,
+        created_at timestamptz not null default now()
+    );
+
+    create table orders (
+        id int4 primary key,
+        user_id int4 not null references users (id),
+        total numeric
+    );
+
+    create function score(value int8) returns int4 language sql as 'select 1';
+    create function score(value numeric) returns int4 language sql as 'select 1';
+"#;
+
+async fn check(query: &str, test_db: &PgPool) -> Vec<String> {
+    test_db
+        .execute(SETUP)
+        .await
+        .expect("Failed to setup test database");
+
+    let schema_cache = SchemaCache::load(test_db)
+        .await
+        .expect("Failed to load Schema Cache");
+
+    let root = pgls_query::parse(query)
+        .unwrap()
+        .into_root()
+        .expect("Failed to parse query");
+
+    TypeInferrer::new(&root, &schema_cache)
+        .check()
+        .iter()
+        .map(|e| e.to_string())
+        .collect()
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn valid_statements(test_db: PgPool) {
+    let queries = [
+        "select id + 1, name || id, length('abc'), created_at - interval '1 day' from users where id = 1;",
+        "select coalesce(id, 1::int8, '3'), greatest(1, 2.5) from users;",
+        "select u.id, u.ctid, count(*), max(created_at) from users u group by u.id order by 1;",
+        "select n + 1 from generate_series(1, 10) as n;",
+        "select x from unnest(array[1, 2]) as x;",
+        "with recent as (select id as user_id from users) select user_id from recent;",
+        "select sum(total) as total from (select id as total from users) as t order by total;",
+        "insert into users (id, name) values (1.5, 2) on conflict (id) do update set name = excluded.name;",
+        "update users set name = 'new', created_at = now() where id = 1;",
+        "select whatever from some_unknown_table;",
+    ];
+
+    for query in queries {
+        assert_eq!(
+            check(query, &test_db).await,
+            Vec::<String>::new(),
+            "{query}"
+        );
+    }
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn unknown_column(test_db: PgPool) {
+    assert_eq!(
+        check("select id, nope from users;", &test_db).await,
+        vec!["column \"nope\" does not exist"]
+    );
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn operator_does_not_exist(test_db: PgPool) {
+    assert_eq!(
+        check("select id + name, now() + 1.5 from users;", &test_db).await,
+        vec![
+            "operator does not exist: integer + text",
+            "operator does not exist: timestamp with time zone + numeric"
+        ]
+    );
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn function_resolution(test_db: PgPool) {
+    assert_eq!(
+        check(
+            "select score(1), score(1::int8), abs(name) from users;",
+            &test_db
+        )
+        .await,
+        vec![
+            "function score(integer) is not unique",
+            "function abs(text) does not exist"
+        ]
+    );
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn no_common_type(test_db: PgPool) {
+    assert_eq!(
+        check(
+            "select coalesce(id, name), case when id > 1 then created_at else 1 end from users;",
+            &test_db
+        )
+        .await,
+        vec![
+            "COALESCE types integer and text cannot be matched",
+            "CASE types timestamp with time zone and integer cannot be matched"
+        ]
+    );
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn where_must_be_boolean(test_db: PgPool) {
+    assert_eq!(
+        check("delete from users where id;", &test_db).await,
+        vec!["argument of WHERE must be type boolean, not type integer"]
+    );
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn assignment_mismatch(test_db: PgPool) {
+    assert_eq!(
+        check(
+            "insert into users (id, name, created_at) values ('1'::text, 'a', 3);",
+            &test_db
+        )
+        .await,
+        vec![
+            "column \"id\" is of type integer but expression is of type text",
+            "column \"created_at\" is of type timestamp with time zone but expression is of type integer"
+        ]
+    );
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn infers_expression_types(test_db: PgPool) {
+    test_db
+        .execute(SETUP)
+        .await
+        .expect("Failed to setup test database");
+
+    let schema_cache = SchemaCache::load(&test_db)
+        .await
+        .expect("Failed to load Schema Cache");
+
+    let query = "select coalesce(id, 1::int8), array[id] || 1, upper('a') from users;";
+    let root = pgls_query::parse(query).unwrap().into_root().unwrap();
+
+    let pgls_query::NodeEnum::SelectStmt(select) = &root else {
+        panic!("expected a select statement");
+    };
+
+    let inferrer = TypeInferrer::new(&root, &schema_cache);
+    let types: Vec<Option<String>> = select
+        .target_list
+        .iter()
+        .map(|t| match t.node.as_ref() {
+            Some(pgls_query::NodeEnum::ResTarget(r)) => inferrer
+                .infer(r.val.as_ref()?.node.as_ref()?.to_ref())
+                .map(|t| t.format.clone()),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        types,
+        vec![
+            Some("bigint".to_string()),
+            Some("integer[]".to_string()),
+            Some("text".to_string())
+        ]
+    );
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn infers_output_columns(test_db: PgPool) {
+    test_db
+        .execute(SETUP)
+        .await
+        .expect("Failed to setup test database");
+
+    let schema_cache = SchemaCache::load(&test_db)
+        .await
+        .expect("Failed to load Schema Cache");
+
+    let output_columns = |query: &str| {
+        let root = pgls_query::parse(query).unwrap().into_root().unwrap();
+        TypeInferrer::new(&root, &schema_cache)
+            .output_columns()
+            .map(|columns| {
+                columns
+                    .into_iter()
+                    .map(|c| (c.name, c.type_.map(|t| t.format.clone())))
+                    .collect::<Vec<_>>()
+            })
+    };
+
+    assert_eq!(
+        output_columns("select u.*, id + 1 as next, upper(name) from users u;"),
+        Some(vec![
+            ("id".to_string(), Some("integer".to_string())),
+            ("name".to_string(), Some("text".to_string())),
+            (
+                "created_at".to_string(),
+                Some("timestamp with time zone".to_string())
+            ),
+            ("next".to_string(), Some("integer".to_string())),
+            ("upper".to_string(), Some("text".to_string())),
+        ])
+    );
+
+    assert_eq!(
+        output_columns("delete from users where id = 1 returning id;"),
+        Some(vec![("id".to_string(), Some("integer".to_string()))])
+    );
+
+    assert_eq!(output_columns("update users set name = 'a';"), None);
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn infers_nullability(test_db: PgPool) {
+    test_db
+        .execute(SETUP)
+        .await
+        .expect("Failed to setup test database");
+
+    let schema_cache = SchemaCache::load(&test_db)
+        .await
+        .expect("Failed to load Schema Cache");
+
+    let nullability = |query: &str| {
+        let root = pgls_query::parse(query).unwrap().into_root().unwrap();
+        TypeInferrer::new(&root, &schema_cache)
+            .output_columns()
+            .map(|columns| {
+                columns
+                    .into_iter()
+                    .map(|c| (c.name, c.nullable))
+                    .collect::<Vec<_>>()
+            })
+    };
+
+    assert_eq!(
+        nullability("select id, total, coalesce(total, 0) as total_or_zero from orders;"),
+        Some(vec![
+            ("id".to_string(), Some(false)),
+            ("total".to_string(), Some(true)),
+            ("total_or_zero".to_string(), Some(false)),
+        ])
+    );
+
+    // columns on the nullable side of an outer join are nullable even if declared not null
+    assert_eq!(
+        nullability(
+            "select u.name, o.user_id from users u left join orders o on o.user_id = u.id;"
+        ),
+        Some(vec![
+            ("name".to_string(), Some(false)),
+            ("user_id".to_string(), Some(true)),
+        ])
+    );
+
+    assert_eq!(
+        nullability(
+            "select count(*), sum(o.total), u.id + 1 as next from users u join orders o on o.user_id = u.id;"
+        ),
+        Some(vec![
+            ("count".to_string(), Some(false)),
+            ("sum".to_string(), Some(true)),
+            ("next".to_string(), Some(false)),
+        ])
+    );
+
+    assert_eq!(
+        nullability("select case when id > 1 then name end as label, null, $1 from users;"),
+        Some(vec![
+            ("label".to_string(), Some(true)),
+            ("?column?".to_string(), Some(true)),
+            ("?column?".to_string(), None),
+        ])
+    );
+}
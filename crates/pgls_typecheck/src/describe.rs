@@ -0,0 +1,75 @@
+use pgls_schema_cache::SchemaCache;
+use sqlx::postgres::PgTypeInfo;
+use sqlx::{Column, Executor, PgPool, TypeInfo};
+
+use crate::set_search_path;
+
+#[derive(Debug)]
+pub struct DescribeParams<'a> {
+    pub conn: &'a PgPool,
+    pub sql: &'a str,
+    pub ast: &'a pgls_query::NodeEnum,
+    pub schema_cache: &'a SchemaCache,
+    /// Set of glob patterns that will be matched against the schemas in the database.
+    /// Each matching schema will be added to the search_path.
+    pub search_path_patterns: Vec<String>,
+}
+
+/// A column of the rows a statement returns, as described by Postgres.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescribedColumn {
+    pub name: String,
+    pub type_name: String,
+
+    /// `None` if Postgres can't tell, e.g. for computed columns.
+    pub nullable: Option<bool>,
+}
+
+/// Asks Postgres for the columns of the rows the statement returns, without executing it.
+///
+/// Returns `Ok(None)` for statements that never return rows.
+pub async fn describe_sql(
+    params: DescribeParams<'_>,
+) -> Result<Option<Vec<DescribedColumn>>, sqlx::Error> {
+    if !matches!(
+        params.ast,
+        pgls_query::NodeEnum::SelectStmt(_)
+            | pgls_query::NodeEnum::InsertStmt(_)
+            | pgls_query::NodeEnum::UpdateStmt(_)
+            | pgls_query::NodeEnum::DeleteStmt(_)
+            | pgls_query::NodeEnum::MergeStmt(_)
+    ) {
+        return Ok(None);
+    }
+
+    let mut conn = params.conn.acquire().await?;
+
+    // see `check_sql`, prepared statements must not outlive schema changes
+    conn.close_on_drop();
+
+    set_search_path(&mut conn, params.schema_cache, params.search_path_patterns).await?;
+
+    let describe = conn.describe(params.sql).await?;
+
+    Ok(Some(
+        describe
+            .columns()
+            .iter()
+            .enumerate()
+            .map(|(i, column)| DescribedColumn {
+                name: column.name().to_string(),
+                type_name: type_name(column.type_info(), params.schema_cache),
+                nullable: describe.nullable(i),
+            })
+            .collect(),
+    ))
+}
+
+/// Formats the type the way Postgres does, e.g. `integer` instead of `INT4`.
+fn type_name(type_info: &PgTypeInfo, schema_cache: &SchemaCache) -> String {
+    type_info
+        .oid()
+        .and_then(|oid| schema_cache.find_type_by_id(oid.0.into()))
+        .map(|t| t.format.clone())
+        .unwrap_or_else(|| type_info.name().to_string())
+}
@@ -4,6 +4,7 @@ use once_cell::sync::Lazy;
 use pgls_console::markup;
 use pgls_diagnostics::{Advices, Diagnostic, LogCategory, MessageAndDescription, Severity, Visit};
 use pgls_text_size::{TextRange, TextSize};
+use pgls_type_resolver::TypeError;
 use regex::Regex;
 use sqlx::postgres::{PgDatabaseError, PgSeverity};
 
@@ -167,16 +168,7 @@ pub(crate) fn create_type_error(
             .to_original_position(pos)
     });
 
-    let range = original_position.and_then(|pos| {
-        ts.root_node()
-            .named_descendant_for_byte_range(pos.into(), pos.into())
-            .map(|node| {
-                TextRange::new(
-                    node.start_byte().try_into().unwrap(),
-                    node.end_byte().try_into().unwrap(),
-                )
-            })
-    });
+    let range = original_position.and_then(|pos| node_range_at(ts, pos.into()));
 
     let severity = match pg_err.severity() {
         PgSeverity::Panic => Severity::Error,
@@ -279,3 +271,41 @@ pub(crate) fn create_type_error(
         },
     }
 }
+
+/// Creates a diagnostic from a type error found without a round-trip to the database.
+pub(crate) fn create_offline_type_error(
+    error: &TypeError,
+    ts: &tree_sitter::Tree,
+) -> TypecheckDiagnostic {
+    TypecheckDiagnostic {
+        message: error.to_string().into(),
+        severity: Severity::Error,
+        span: error.location.and_then(|pos| node_range_at(ts, pos)),
+        advices: TypecheckAdvices {
+            code: error.kind.sqlstate().to_string(),
+            schema: None,
+            table: None,
+            column: None,
+            data_type: None,
+            constraint: None,
+            detail: None,
+            where_: None,
+            hint: None,
+            line: None,
+            file: None,
+            routine: None,
+        },
+    }
+}
+
+/// The range of the smallest named node at `pos`.
+fn node_range_at(ts: &tree_sitter::Tree, pos: usize) -> Option<TextRange> {
+    ts.root_node()
+        .named_descendant_for_byte_range(pos, pos)
+        .map(|node| {
+            TextRange::new(
+                node.start_byte().try_into().unwrap(),
+                node.end_byte().try_into().unwrap(),
+            )
+        })
+}
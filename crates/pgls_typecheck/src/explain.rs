@@ -0,0 +1,452 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::io;
+use std::time::Duration;
+
+use pgls_console::markup;
+use pgls_diagnostics::{
+    Advices, Category, Diagnostic, LogCategory, MessageAndDescription, Visit, category,
+};
+use pgls_schema_cache::SchemaCache;
+use pgls_text_size::TextRange;
+use serde::Deserialize;
+use sqlx::types::Json;
+use sqlx::{Connection, Executor, PgPool};
+
+use crate::set_search_path;
+
+/// Tables with at least this many live rows are considered large.
+const LARGE_TABLE_ROWS: i64 = 10_000;
+
+/// Nested loops that iterate over at least this many outer rows are reported.
+const LARGE_NESTED_LOOP_ROWS: f64 = 10_000.0;
+
+#[derive(Debug)]
+pub struct ExplainParams<'a> {
+    pub conn: &'a PgPool,
+    pub sql: &'a str,
+    pub schema_cache: &'a SchemaCache,
+    /// Set of glob patterns that will be matched against the schemas in the database.
+    /// Each matching schema will be added to the search_path.
+    pub search_path_patterns: Vec<String>,
+    /// Whether to run `EXPLAIN ANALYZE`. This executes the statement, callers must make sure
+    /// statement executions are allowed against the database.
+    pub analyze: bool,
+    /// Cancels the statement if it runs longer, e.g. an `UPDATE` explained with `ANALYZE`.
+    pub statement_timeout: Duration,
+    /// Cancels the statement if it waits longer for a lock.
+    pub lock_timeout: Duration,
+}
+
+/// Returns whether Postgres can explain the statement.
+pub fn is_explainable(ast: &pgls_query::NodeEnum) -> bool {
+    matches!(
+        ast,
+        pgls_query::NodeEnum::SelectStmt(_)
+            | pgls_query::NodeEnum::InsertStmt(_)
+            | pgls_query::NodeEnum::UpdateStmt(_)
+            | pgls_query::NodeEnum::DeleteStmt(_)
+            | pgls_query::NodeEnum::MergeStmt(_)
+    )
+}
+
+/// Asks Postgres for the plan of the statement.
+///
+/// The statement runs within a transaction that is always rolled back, so that `EXPLAIN
+/// ANALYZE` does not leave any changes behind, and is cancelled once it exceeds the timeouts.
+pub async fn explain_sql(params: ExplainParams<'_>) -> Result<PlanNode, sqlx::Error> {
+    let mut conn = params.conn.acquire().await?;
+
+    // see `check_sql`, prepared statements must not outlive schema changes
+    conn.close_on_drop();
+
+    set_search_path(&mut conn, params.schema_cache, params.search_path_patterns).await?;
+
+    let options = if params.analyze {
+        "ANALYZE, VERBOSE, FORMAT JSON"
+    } else {
+        "VERBOSE, FORMAT JSON"
+    };
+
+    let mut tx = conn.begin().await?;
+    tx.execute(
+        format!(
+            "SET LOCAL statement_timeout = {}; SET LOCAL lock_timeout = {};",
+            params.statement_timeout.as_millis(),
+            params.lock_timeout.as_millis()
+        )
+        .as_str(),
+    )
+    .await?;
+
+    let result: Result<(Json<Vec<ExplainOutput>>,), sqlx::Error> =
+        sqlx::query_as(&format!("EXPLAIN ({options}) {}", params.sql))
+            .fetch_one(&mut *tx)
+            .await;
+    tx.rollback().await?;
+
+    let (Json(outputs),) = result?;
+    outputs
+        .into_iter()
+        .next()
+        .map(|output| output.plan)
+        .ok_or_else(|| sqlx::Error::Protocol("EXPLAIN returned an empty plan".into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct ExplainOutput {
+    #[serde(rename = "Plan")]
+    plan: PlanNode,
+}
+
+/// A node of the plan tree, as returned by `EXPLAIN (FORMAT JSON)`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PlanNode {
+    #[serde(rename = "Node Type")]
+    pub node_type: String,
+    #[serde(rename = "Join Type")]
+    pub join_type: Option<String>,
+    #[serde(rename = "Relation Name")]
+    pub relation_name: Option<String>,
+    #[serde(rename = "Schema")]
+    pub schema: Option<String>,
+    #[serde(rename = "Alias")]
+    pub alias: Option<String>,
+    #[serde(rename = "Index Name")]
+    pub index_name: Option<String>,
+    #[serde(rename = "Startup Cost")]
+    pub startup_cost: f64,
+    #[serde(rename = "Total Cost")]
+    pub total_cost: f64,
+    #[serde(rename = "Plan Rows")]
+    pub plan_rows: f64,
+    #[serde(rename = "Plan Width")]
+    pub plan_width: i64,
+    /// Only set for `EXPLAIN ANALYZE`.
+    #[serde(rename = "Actual Rows")]
+    pub actual_rows: Option<f64>,
+    /// Only set for `EXPLAIN ANALYZE`.
+    #[serde(rename = "Actual Loops")]
+    pub actual_loops: Option<f64>,
+    #[serde(rename = "Filter")]
+    pub filter: Option<String>,
+    #[serde(rename = "Index Cond")]
+    pub index_cond: Option<String>,
+    #[serde(rename = "Hash Cond")]
+    pub hash_cond: Option<String>,
+    #[serde(rename = "Join Filter")]
+    pub join_filter: Option<String>,
+    #[serde(rename = "Plans", default)]
+    pub plans: Vec<PlanNode>,
+}
+
+impl PlanNode {
+    /// The rows the node returns, measured if the plan was analyzed and estimated otherwise.
+    pub fn rows(&self) -> f64 {
+        self.actual_rows.unwrap_or(self.plan_rows)
+    }
+
+    /// Renders the plan tree as text, similar to `EXPLAIN` without `FORMAT JSON`.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        self.render(&mut text, 0);
+        text.trim_end().to_string()
+    }
+
+    fn render(&self, text: &mut String, depth: usize) {
+        let (indent, prefix) = match depth {
+            0 => (String::new(), ""),
+            _ => (" ".repeat(depth * 6 - 6), "->  "),
+        };
+
+        let _ = write!(
+            text,
+            "{indent}{prefix}{}  (cost={:.2}..{:.2} rows={} width={})",
+            self.label(),
+            self.startup_cost,
+            self.total_cost,
+            self.plan_rows,
+            self.plan_width
+        );
+        if let (Some(rows), Some(loops)) = (self.actual_rows, self.actual_loops) {
+            let _ = write!(text, " (actual rows={rows} loops={loops})");
+        }
+        text.push('\n');
+
+        let details_indent = " ".repeat(depth * 6 + 2);
+        for (label, value) in [
+            ("Index Cond", &self.index_cond),
+            ("Hash Cond", &self.hash_cond),
+            ("Join Filter", &self.join_filter),
+            ("Filter", &self.filter),
+        ] {
+            if let Some(value) = value {
+                let _ = writeln!(text, "{details_indent}{label}: {value}");
+            }
+        }
+
+        for plan in &self.plans {
+            plan.render(text, depth + 1);
+        }
+    }
+
+    fn label(&self) -> String {
+        let mut label = match self.join_type.as_deref() {
+            Some("Inner") | None => self.node_type.clone(),
+            Some(join_type) if self.node_type == "Nested Loop" => {
+                format!("Nested Loop {join_type} Join")
+            }
+            Some(join_type) => self
+                .node_type
+                .replace(" Join", &format!(" {join_type} Join")),
+        };
+
+        if let Some(index_name) = &self.index_name {
+            let _ = write!(label, " using {index_name}");
+        }
+
+        if let Some(relation_name) = &self.relation_name {
+            match &self.schema {
+                Some(schema) => {
+                    let _ = write!(label, " on {schema}.{relation_name}");
+                }
+                None => {
+                    let _ = write!(label, " on {relation_name}");
+                }
+            }
+
+            if let Some(alias) = self.alias.as_ref().filter(|alias| *alias != relation_name) {
+                let _ = write!(label, " {alias}");
+            }
+        }
+
+        label
+    }
+
+    fn walk<'a>(&'a self, nodes: &mut Vec<&'a PlanNode>) {
+        nodes.push(self);
+        for plan in &self.plans {
+            plan.walk(nodes);
+        }
+    }
+}
+
+/// Reports the parts of a plan that are likely to be slow on production data.
+pub fn analyse_plan(plan: &PlanNode, schema_cache: &SchemaCache) -> Vec<PlanDiagnostic> {
+    let mut nodes = Vec::new();
+    plan.walk(&mut nodes);
+
+    let mut diagnostics = Vec::new();
+    for node in nodes {
+        match node.node_type.as_str() {
+            "Seq Scan" => diagnostics.extend(analyse_seq_scan(node, schema_cache)),
+            "Nested Loop" => {
+                if let Some(outer) = node.plans.first() {
+                    if outer.rows() >= LARGE_NESTED_LOOP_ROWS {
+                        diagnostics.push(PlanDiagnostic::new(
+                            category!("explain/nestedLoop"),
+                            format!(
+                                "Nested loop over {} outer rows.",
+                                outer.rows()
+                            ),
+                            Some("The inner side is evaluated once per outer row. A hash or merge join is usually faster for inputs of this size, check that the join columns are indexed and the statistics are up to date.".into()),
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    diagnostics
+}
+
+fn analyse_seq_scan(node: &PlanNode, schema_cache: &SchemaCache) -> Option<PlanDiagnostic> {
+    let relation_name = node.relation_name.as_deref()?;
+    let schema = node.schema.as_deref().unwrap_or("public");
+
+    let table = schema_cache
+        .tables
+        .iter()
+        .find(|t| t.name == relation_name && t.schema == schema)?;
+
+    if table.live_rows_estimate < LARGE_TABLE_ROWS {
+        return None;
+    }
+
+    let unindexed_columns = node
+        .filter
+        .as_deref()
+        .map(|filter| unindexed_filter_columns(filter, schema, relation_name, schema_cache))
+        .unwrap_or_default();
+
+    if unindexed_columns.is_empty() {
+        return Some(PlanDiagnostic::new(
+            category!("explain/seqScan"),
+            format!(
+                "Sequential scan on {schema}.{relation_name} with about {} rows.",
+                table.live_rows_estimate
+            ),
+            None,
+        ));
+    }
+
+    Some(PlanDiagnostic::new(
+        category!("explain/missingIndex"),
+        format!(
+            "Sequential scan on {schema}.{relation_name} filters on {}, which is not indexed.",
+            unindexed_columns.join(", ")
+        ),
+        Some(format!(
+            "Consider adding an index: create index on {schema}.{relation_name} ({});",
+            unindexed_columns.join(", ")
+        )),
+    ))
+}
+
+/// Returns the columns of the table that the filter refers to and that are not the leading
+/// column of any index on the table.
+fn unindexed_filter_columns(
+    filter: &str,
+    schema: &str,
+    table: &str,
+    schema_cache: &SchemaCache,
+) -> Vec<String> {
+    let identifiers = filter_identifiers(filter);
+
+    let indexed: HashSet<String> = schema_cache
+        .indexes
+        .iter()
+        .filter(|index| index.schema == schema && index.table_name == table)
+        .filter_map(|index| leading_index_column(&index.definition))
+        .collect();
+
+    schema_cache
+        .columns
+        .iter()
+        .filter(|c| c.schema_name == schema && c.table_name == table)
+        .filter(|c| identifiers.contains(c.name.as_str()) && !indexed.contains(&c.name))
+        .map(|c| c.name.clone())
+        .collect()
+}
+
+/// Collects the unqualified identifiers in a filter expression, ignoring string literals.
+fn filter_identifiers(filter: &str) -> HashSet<&str> {
+    let mut identifiers = HashSet::new();
+    let mut in_literal = false;
+    let mut start: Option<usize> = None;
+
+    for (i, c) in filter.char_indices() {
+        if in_literal {
+            in_literal = c != '\'';
+            continue;
+        }
+
+        if c.is_alphanumeric() || c == '_' || c == '"' {
+            start.get_or_insert(i);
+            continue;
+        }
+
+        if let Some(s) = start.take() {
+            // `users.email` refers to the `email` column
+            if c != '.' {
+                identifiers.insert(filter[s..i].trim_matches('"'));
+            }
+        }
+        in_literal = c == '\'';
+    }
+
+    if let Some(s) = start {
+        identifiers.insert(filter[s..].trim_matches('"'));
+    }
+
+    identifiers
+}
+
+/// Extracts the first key column of a `CREATE INDEX` statement, e.g. `email` for
+/// `CREATE INDEX users_email_idx ON public.users USING btree (email, id)`.
+fn leading_index_column(definition: &str) -> Option<String> {
+    let (_, keys) = definition.split_once(" USING ")?;
+    let (_, keys) = keys.split_once('(')?;
+    let column = keys.split([',', ')']).next()?.trim();
+
+    // expression indexes such as `lower(email)` don't help plain column filters
+    if column.is_empty() || column.contains('(') {
+        return None;
+    }
+
+    Some(column.trim_matches('"').to_string())
+}
+
+/// A finding in the plan of a statement.
+#[derive(Clone, Debug, Diagnostic)]
+#[diagnostic(severity = Warning)]
+pub struct PlanDiagnostic {
+    #[category]
+    category: &'static Category,
+    #[location(span)]
+    span: Option<TextRange>,
+    #[description]
+    #[message]
+    message: MessageAndDescription,
+    #[advice]
+    advice: PlanAdvice,
+}
+
+impl PlanDiagnostic {
+    fn new(category: &'static Category, message: String, hint: Option<String>) -> Self {
+        Self {
+            category,
+            span: None,
+            message: MessageAndDescription::from(message),
+            advice: PlanAdvice { hint },
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct PlanAdvice {
+    hint: Option<String>,
+}
+
+impl Advices for PlanAdvice {
+    fn record(&self, visitor: &mut dyn Visit) -> io::Result<()> {
+        if let Some(hint) = &self.hint {
+            visitor.record_log(LogCategory::Info, &markup! { {hint} })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filter_identifiers, leading_index_column};
+
+    #[test]
+    fn extracts_filter_identifiers() {
+        let identifiers = filter_identifiers("((users.email = 'a b'::text) AND (\"Age\" > 3))");
+
+        assert!(identifiers.contains("email"));
+        assert!(identifiers.contains("Age"));
+        assert!(identifiers.contains("text"));
+        assert!(!identifiers.contains("users"));
+        assert!(!identifiers.contains("a"));
+    }
+
+    #[test]
+    fn extracts_leading_index_column() {
+        assert_eq!(
+            leading_index_column(
+                "CREATE INDEX users_email_idx ON public.users USING btree (email, id)"
+            ),
+            Some("email".into())
+        );
+        assert_eq!(
+            leading_index_column(
+                "CREATE UNIQUE INDEX users_lower_idx ON public.users USING btree (lower(email))"
+            ),
+            None
+        );
+    }
+}
@@ -1,14 +1,19 @@
+pub mod describe;
 pub mod diagnostics;
+pub mod explain;
 pub mod typed_identifier;
 
+pub use describe::{DescribeParams, DescribedColumn, describe_sql};
 pub use diagnostics::TypecheckDiagnostic;
-use diagnostics::create_type_error;
+use diagnostics::{create_offline_type_error, create_type_error};
+pub use explain::{ExplainParams, PlanDiagnostic, PlanNode, analyse_plan, explain_sql};
 use globset::Glob;
 use itertools::Itertools;
 use pgls_schema_cache::SchemaCache;
+use pgls_type_resolver::TypeInferrer;
 use sqlx::postgres::PgDatabaseError;
 pub use sqlx::postgres::PgSeverity;
-use sqlx::{Executor, PgPool};
+use sqlx::{Acquire, Executor, PgConnection, PgPool};
 use typed_identifier::apply_identifiers;
 pub use typed_identifier::{IdentifierReplacement, IdentifierType, TypedIdentifier};
 
@@ -23,21 +28,95 @@ pub struct TypecheckParams<'a> {
     /// Set of glob patterns that will be matched against the schemas in the database.
     /// Each matching schema will be added to the search_path for the typecheck.
     pub search_path_patterns: Vec<String>,
+    /// DDL statements that precede the statement in the same file. They are executed before the
+    /// statement is checked, inside a transaction that is always rolled back. Ignored unless
+    /// `execute_ddl` is set.
+    pub prior_ddl: Vec<String>,
+    /// Whether DDL may be executed inside a transaction that is always rolled back. This takes
+    /// locks on the database, so it should only be set if statement executions are allowed
+    /// against it. Otherwise, DDL is checked against the schema cache.
+    pub execute_ddl: bool,
 }
 
-pub async fn check_sql(
-    params: TypecheckParams<'_>,
-) -> Result<Option<TypecheckDiagnostic>, sqlx::Error> {
-    // Check if the AST is not a supported statement type
-    if !matches!(
-        params.ast,
-        pgls_query::NodeEnum::SelectStmt(_)
+/// How a statement is typechecked.
+enum CheckStrategy {
+    /// Postgres analyses the statement when preparing it, without executing it.
+    Prepare,
+
+    /// Postgres only analyses DDL when executing it, so we run it inside a transaction that is
+    /// always rolled back. Falls back to [CheckStrategy::Offline] if DDL may not be executed.
+    RolledBackTransaction,
+
+    /// The statement would do real work when executed, e.g. build an index, so we check its
+    /// expressions against the schema cache instead. It is never executed, not even after
+    /// replaying prior DDL.
+    Offline,
+}
+
+impl CheckStrategy {
+    fn for_statement(ast: &pgls_query::NodeEnum) -> Option<Self> {
+        match ast {
+            pgls_query::NodeEnum::SelectStmt(_)
             | pgls_query::NodeEnum::InsertStmt(_)
             | pgls_query::NodeEnum::UpdateStmt(_)
             | pgls_query::NodeEnum::DeleteStmt(_)
+            | pgls_query::NodeEnum::MergeStmt(_)
             | pgls_query::NodeEnum::CommonTableExpr(_)
-    ) {
-        return Ok(None);
+            // includes `create materialized view`, the query is analysed but not run
+            | pgls_query::NodeEnum::CreateTableAsStmt(_) => Some(Self::Prepare),
+            pgls_query::NodeEnum::ViewStmt(_)
+            | pgls_query::NodeEnum::CreatePolicyStmt(_)
+            | pgls_query::NodeEnum::CreateStmt(_) => Some(Self::RolledBackTransaction),
+            pgls_query::NodeEnum::IndexStmt(_) | pgls_query::NodeEnum::AlterTableStmt(_) => {
+                Some(Self::Offline)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Errors raised because the object created by the statement already exists in the database.
+const DUPLICATE_OBJECT_ERROR_CODES: [&str; 3] = [
+    "42P07", // duplicate_table
+    "42710", // duplicate_object
+    "42P06", // duplicate_schema
+];
+
+/// Errors that say nothing about the types within the statement, e.g. because the
+/// table created by the statement already exists in the database.
+const IGNORED_ERROR_CODES: [&str; 6] = [
+    "42P07", // duplicate_table
+    "42710", // duplicate_object
+    "42P06", // duplicate_schema
+    "42501", // insufficient_privilege
+    "55P03", // lock_not_available
+    "57014", // query_canceled
+];
+
+pub async fn check_sql(
+    params: TypecheckParams<'_>,
+) -> Result<Option<TypecheckDiagnostic>, sqlx::Error> {
+    let strategy = match CheckStrategy::for_statement(params.ast) {
+        None => return Ok(None),
+        Some(CheckStrategy::RolledBackTransaction) if !params.execute_ddl => CheckStrategy::Offline,
+        Some(strategy) => strategy,
+    };
+
+    let prior_ddl: &[String] = if params.execute_ddl {
+        &params.prior_ddl
+    } else {
+        &[]
+    };
+
+    if matches!(strategy, CheckStrategy::Offline) {
+        // the schema cache doesn't know about the objects created by the prior DDL, so we
+        // can't tell whether an unknown object is an error
+        let errors = TypeInferrer::new(params.ast, params.schema_cache).check();
+        let diagnostic = errors
+            .iter()
+            .find(|e| prior_ddl.is_empty() || !e.kind.is_unknown_object())
+            .map(|e| create_offline_type_error(e, params.tree));
+        return Ok(diagnostic);
     }
 
     let mut conn = params.conn.acquire().await?;
@@ -55,26 +134,43 @@ pub async fn check_sql(
         params.sql,
     );
 
-    let mut search_path_schemas =
-        get_schemas_in_search_path(params.schema_cache, params.search_path_patterns);
+    set_search_path(&mut conn, params.schema_cache, params.search_path_patterns).await?;
 
-    if !search_path_schemas.is_empty() {
-        // Always include public if we have any schemas in search path
-        if !search_path_schemas.contains(&"public") {
-            search_path_schemas.push("public");
+    let sql = typed_replacement.text_replacement().text();
+    let res = match strategy {
+        CheckStrategy::Prepare if prior_ddl.is_empty() => conn.prepare(sql).await.map(|_| ()),
+        CheckStrategy::Prepare => {
+            check_rolled_back(&mut conn, prior_ddl, sql, Check::Prepare).await
         }
-
-        let search_path_query = format!("SET search_path TO {};", search_path_schemas.join(", "));
-        conn.execute(&*search_path_query).await?;
-    }
-
-    let res = conn
-        .prepare(typed_replacement.text_replacement().text())
-        .await;
+        CheckStrategy::RolledBackTransaction => {
+            check_rolled_back(&mut conn, prior_ddl, sql, Check::Execute).await
+        }
+        CheckStrategy::Offline => unreachable!("offline checks don't need a connection"),
+    };
 
     match res {
         Ok(_) => Ok(None),
         Err(sqlx::Error::Database(err)) => {
+            let code = err.code();
+
+            // Postgres stops at the existing object before looking at the rest of the
+            // statement, e.g. the column defaults of a table. The existing object is in the
+            // schema cache though, so we can check the statement against it.
+            if code
+                .as_deref()
+                .is_some_and(|code| DUPLICATE_OBJECT_ERROR_CODES.contains(&code))
+                && prior_ddl.is_empty()
+            {
+                return Ok(check_offline(params.ast, params.schema_cache, params.tree));
+            }
+
+            if code
+                .as_deref()
+                .is_some_and(|code| IGNORED_ERROR_CODES.contains(&code))
+            {
+                return Ok(None);
+            }
+
             let pg_err = err.downcast_ref::<PgDatabaseError>();
             Ok(Some(create_type_error(
                 pg_err,
@@ -86,6 +182,158 @@ pub async fn check_sql(
     }
 }
 
+/// Checks the statement against the schema cache, without a round trip to the database.
+fn check_offline(
+    ast: &pgls_query::NodeEnum,
+    schema_cache: &SchemaCache,
+    tree: &tree_sitter::Tree,
+) -> Option<TypecheckDiagnostic> {
+    let errors = TypeInferrer::new(ast, schema_cache).check();
+    errors.first().map(|e| create_offline_type_error(e, tree))
+}
+
+/// Whether the statement changes the schema and should be replayed before typechecking the
+/// statements that follow it.
+///
+/// Statements that do real work, such as `create index`, `create materialized view` or an
+/// `alter table` that rewrites or scans the table, are not replayed. Neither are statements
+/// that can't run inside a transaction, such as `drop index concurrently`.
+pub fn is_replayable_ddl(ast: &pgls_query::NodeEnum) -> bool {
+    match ast {
+        pgls_query::NodeEnum::AlterTableStmt(stmt) => stmt.cmds.iter().all(|cmd| {
+            matches!(&cmd.node, Some(pgls_query::NodeEnum::AlterTableCmd(cmd)) if is_cheap_alter_table_cmd(cmd))
+        }),
+        pgls_query::NodeEnum::DropStmt(stmt) => !stmt.concurrent,
+        pgls_query::NodeEnum::CreateSchemaStmt(_)
+        | pgls_query::NodeEnum::CreateStmt(_)
+        | pgls_query::NodeEnum::ViewStmt(_)
+        | pgls_query::NodeEnum::CreateEnumStmt(_)
+        | pgls_query::NodeEnum::AlterEnumStmt(_)
+        | pgls_query::NodeEnum::CreateDomainStmt(_)
+        | pgls_query::NodeEnum::CompositeTypeStmt(_)
+        | pgls_query::NodeEnum::CreateSeqStmt(_)
+        | pgls_query::NodeEnum::CreateFunctionStmt(_)
+        | pgls_query::NodeEnum::RenameStmt(_) => true,
+        _ => false,
+    }
+}
+
+/// Whether the command only changes the catalog, without scanning or rewriting the table or
+/// building an index.
+fn is_cheap_alter_table_cmd(cmd: &pgls_query::protobuf::AlterTableCmd) -> bool {
+    use pgls_query::protobuf::{AlterTableType, ConstrType};
+
+    match cmd.subtype() {
+        AlterTableType::AtAddColumn => {
+            let Some(pgls_query::NodeEnum::ColumnDef(column)) =
+                cmd.def.as_ref().and_then(|def| def.node.as_ref())
+            else {
+                return false;
+            };
+
+            // serial columns and volatile defaults fill every existing row
+            let is_serial = column.type_name.as_ref().is_some_and(|t| {
+                t.names.last().is_some_and(|name| {
+                    matches!(&name.node, Some(pgls_query::NodeEnum::String(s)) if s.sval.ends_with("serial"))
+                })
+            });
+
+            !is_serial
+                && column.constraints.iter().all(|c| match &c.node {
+                    Some(pgls_query::NodeEnum::Constraint(c)) => match c.contype() {
+                        ConstrType::ConstrNull | ConstrType::ConstrNotnull => true,
+                        ConstrType::ConstrDefault => {
+                            c.raw_expr.as_ref().is_some_and(|e| is_constant(e))
+                        }
+                        _ => false,
+                    },
+                    _ => false,
+                })
+        }
+        AlterTableType::AtColumnDefault
+        | AlterTableType::AtDropNotNull
+        | AlterTableType::AtDropColumn
+        | AlterTableType::AtDropConstraint
+        | AlterTableType::AtChangeOwner
+        | AlterTableType::AtEnableRowSecurity
+        | AlterTableType::AtDisableRowSecurity
+        | AlterTableType::AtForceRowSecurity
+        | AlterTableType::AtNoForceRowSecurity => true,
+        _ => false,
+    }
+}
+
+/// Whether the expression is a literal, optionally cast to a type.
+fn is_constant(node: &pgls_query::protobuf::Node) -> bool {
+    match &node.node {
+        Some(pgls_query::NodeEnum::AConst(_)) => true,
+        Some(pgls_query::NodeEnum::TypeCast(cast)) => {
+            cast.arg.as_ref().is_some_and(|a| is_constant(a))
+        }
+        _ => false,
+    }
+}
+
+enum Check {
+    Prepare,
+    Execute,
+}
+
+/// Checks the statement inside a transaction that is always rolled back, after replaying
+/// the `prior` statements.
+///
+/// A prior statement that fails is skipped, its error is reported when it is checked itself.
+async fn check_rolled_back(
+    conn: &mut PgConnection,
+    prior: &[String],
+    sql: &str,
+    check: Check,
+) -> Result<(), sqlx::Error> {
+    let mut tx = conn.begin().await?;
+
+    // DDL takes locks, so we neither wait for others nor block them for long
+    tx.execute("SET LOCAL lock_timeout = '1s'; SET LOCAL statement_timeout = '5s';")
+        .await?;
+
+    for statement in prior {
+        let mut savepoint = tx.begin().await?;
+        match savepoint.execute(statement.as_str()).await {
+            Ok(_) => savepoint.commit().await?,
+            Err(_) => savepoint.rollback().await?,
+        }
+    }
+
+    let result = match check {
+        Check::Prepare => tx.prepare(sql).await.map(|_| ()),
+        Check::Execute => tx.execute(sql).await.map(|_| ()),
+    };
+
+    tx.rollback().await?;
+
+    result
+}
+
+/// Sets the search path of the connection to the schemas matching the glob patterns.
+pub(crate) async fn set_search_path(
+    conn: &mut PgConnection,
+    schema_cache: &SchemaCache,
+    glob_patterns: Vec<String>,
+) -> Result<(), sqlx::Error> {
+    let mut search_path_schemas = get_schemas_in_search_path(schema_cache, glob_patterns);
+
+    if !search_path_schemas.is_empty() {
+        // Always include public if we have any schemas in search path
+        if !search_path_schemas.contains(&"public") {
+            search_path_schemas.push("public");
+        }
+
+        let search_path_query = format!("SET search_path TO {};", search_path_schemas.join(", "));
+        conn.execute(&*search_path_query).await?;
+    }
+
+    Ok(())
+}
+
 fn get_schemas_in_search_path(schema_cache: &SchemaCache, glob_patterns: Vec<String>) -> Vec<&str> {
     // iterate over glob_patterns on the outside to keep the order
     glob_patterns
@@ -0,0 +1,65 @@
+use pgls_typecheck::{DescribeParams, DescribedColumn, describe_sql};
+use sqlx::{Executor, PgPool};
+
+async fn describe(query: &str, test_db: &PgPool) -> Option<Vec<DescribedColumn>> {
+    let schema_cache = pgls_schema_cache::SchemaCache::load(test_db)
+        .await
+        .expect("Failed to load Schema Cache");
+
+    let root = pgls_query::parse(query)
+        .unwrap()
+        .into_root()
+        .expect("Failed to parse query");
+
+    describe_sql(DescribeParams {
+        conn: test_db,
+        sql: query,
+        ast: &root,
+        schema_cache: &schema_cache,
+        search_path_patterns: vec![],
+    })
+    .await
+    .expect("Failed to describe query")
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn describes_result_columns(test_db: PgPool) {
+    test_db
+        .execute(
+            r#"
+        create table public.contacts (
+            id serial primary key,
+            name text not null,
+            email text
+        );
+    "#,
+        )
+        .await
+        .expect("Failed to setup test database");
+
+    assert_eq!(
+        describe("select id, email, id + 1 as next from contacts;", &test_db).await,
+        Some(vec![
+            DescribedColumn {
+                name: "id".into(),
+                type_name: "integer".into(),
+                nullable: Some(false),
+            },
+            DescribedColumn {
+                name: "email".into(),
+                type_name: "text".into(),
+                nullable: Some(true),
+            },
+            DescribedColumn {
+                name: "next".into(),
+                type_name: "integer".into(),
+                nullable: None,
+            },
+        ])
+    );
+
+    assert_eq!(
+        describe("create table other (id int);", &test_db).await,
+        None
+    );
+}
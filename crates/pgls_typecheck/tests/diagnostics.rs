@@ -10,6 +10,8 @@ struct TestSetup<'a> {
     setup: Option<&'a str>,
     test_db: &'a PgPool,
     typed_identifiers: Vec<TypedIdentifier>,
+    prior_ddl: Vec<String>,
+    execute_ddl: bool,
 }
 
 impl TestSetup<'_> {
@@ -44,6 +46,8 @@ impl TestSetup<'_> {
             schema_cache: &schema_cache,
             identifiers: self.typed_identifiers,
             search_path_patterns: vec![],
+            prior_ddl: self.prior_ddl,
+            execute_ddl: self.execute_ddl,
         })
         .await;
 
@@ -116,6 +120,8 @@ async fn invalid_column(test_db: PgPool) {
         ),
         test_db: &test_db,
         typed_identifiers: vec![],
+        prior_ddl: vec![],
+        execute_ddl: false,
     }
     .test()
     .await;
@@ -154,6 +160,8 @@ async fn invalid_type_in_function(test_db: PgPool) {
                 is_array: false,
             },
         }],
+        prior_ddl: vec![],
+        execute_ddl: false,
     }
     .test()
     .await;
@@ -173,6 +181,8 @@ async fn invalid_type_in_function(test_db: PgPool) {
                 is_array: false,
             },
         }],
+        prior_ddl: vec![],
+        execute_ddl: false,
     }
     .test()
     .await;
@@ -204,6 +214,8 @@ async fn operator_does_not_exist(test_db: PgPool) {
                 is_array: false,
             },
         }],
+        prior_ddl: vec![],
+        execute_ddl: false,
     }
     .test()
     .await;
@@ -233,6 +245,8 @@ async fn function_parameter_type_with_multiple_words(test_db: PgPool) {
                 is_array: false,
             },
         }],
+        prior_ddl: vec![],
+        execute_ddl: false,
     }
     .test()
     .await;
@@ -251,7 +265,181 @@ async fn function_parameter_type_with_multiple_words(test_db: PgPool) {
                 is_array: false,
             },
         }],
+        prior_ddl: vec![],
+        execute_ddl: false,
     }
     .test()
     .await;
 }
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn invalid_column_in_view(test_db: PgPool) {
+    let setup = r#"
+        create table public.contacts (
+            id serial primary key,
+            name text not null
+        );
+    "#;
+
+    TestSetup {
+        name: "invalid_column_in_view",
+        setup: Some(setup),
+        query: "create view contact_names as select id, nope from public.contacts;",
+        test_db: &test_db,
+        typed_identifiers: vec![],
+        prior_ddl: vec![],
+        execute_ddl: true,
+    }
+    .test()
+    .await;
+
+    TestSetup {
+        name: "invalid_column_in_materialized_view",
+        setup: None,
+        query: "create materialized view contact_names as select id, nope from public.contacts;",
+        test_db: &test_db,
+        typed_identifiers: vec![],
+        prior_ddl: vec![],
+        execute_ddl: false,
+    }
+    .test()
+    .await;
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn policy_must_be_boolean(test_db: PgPool) {
+    let setup = r#"
+        create table public.contacts (
+            id serial primary key,
+            name text not null
+        );
+    "#;
+
+    TestSetup {
+        name: "policy_must_be_boolean",
+        setup: Some(setup),
+        query: "create policy p on public.contacts using (id + 1);",
+        test_db: &test_db,
+        typed_identifiers: vec![],
+        prior_ddl: vec![],
+        execute_ddl: true,
+    }
+    .test()
+    .await;
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn check_constraint_must_be_boolean(test_db: PgPool) {
+    let setup = r#"
+        create table public.contacts (
+            id serial primary key,
+            name text not null
+        );
+    "#;
+
+    TestSetup {
+        name: "check_constraint_must_be_boolean",
+        setup: Some(setup),
+        query: "alter table public.contacts add constraint c check (id + 1);",
+        test_db: &test_db,
+        typed_identifiers: vec![],
+        prior_ddl: vec![],
+        execute_ddl: false,
+    }
+    .test()
+    .await;
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn replays_prior_ddl(test_db: PgPool) {
+    let prior_ddl = vec![
+        "create table public.contacts (id serial primary key, name text not null);".to_string(),
+        "alter table public.contacts add column email text;".to_string(),
+    ];
+
+    TestSetup {
+        name: "replays_prior_ddl_valid",
+        setup: None,
+        query: "insert into public.contacts (name, email) values ('a', 'b');",
+        test_db: &test_db,
+        typed_identifiers: vec![],
+        prior_ddl: prior_ddl.clone(),
+        execute_ddl: true,
+    }
+    .test()
+    .await;
+
+    TestSetup {
+        name: "replays_prior_ddl_invalid",
+        setup: None,
+        query: "select id, phone from public.contacts;",
+        test_db: &test_db,
+        typed_identifiers: vec![],
+        prior_ddl,
+        execute_ddl: true,
+    }
+    .test()
+    .await;
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn checks_defaults_of_existing_tables(test_db: PgPool) {
+    let setup = r#"
+        create table public.contacts (
+            id serial primary key
+        );
+    "#;
+
+    TestSetup {
+        name: "checks_defaults_of_existing_tables",
+        setup: Some(setup),
+        query: "create table public.contacts (id serial primary key, age int default true);",
+        test_db: &test_db,
+        typed_identifiers: vec![],
+        prior_ddl: vec![],
+        execute_ddl: true,
+    }
+    .test()
+    .await;
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn does_not_execute_index_builds_after_prior_ddl(test_db: PgPool) {
+    TestSetup {
+        name: "does_not_execute_index_builds_after_prior_ddl",
+        setup: None,
+        query: "create index concurrently contacts_name_idx on public.contacts (name);",
+        test_db: &test_db,
+        typed_identifiers: vec![],
+        prior_ddl: vec![
+            "create table public.contacts (id serial primary key, name text not null);".to_string(),
+        ],
+        execute_ddl: true,
+    }
+    .test()
+    .await;
+}
+
+#[test]
+fn replays_only_transactional_catalog_changes() {
+    let is_replayable = |sql: &str| {
+        let root = pgls_query::parse(sql).unwrap().into_root().unwrap();
+        pgls_typecheck::is_replayable_ddl(&root)
+    };
+
+    assert!(is_replayable("alter table contacts add column email text;"));
+    assert!(is_replayable(
+        "alter table contacts add column active boolean not null default false;"
+    ));
+    assert!(!is_replayable(
+        "alter table contacts add column id bigserial;"
+    ));
+    assert!(!is_replayable(
+        "alter table contacts add column token uuid default gen_random_uuid();"
+    ));
+    assert!(!is_replayable(
+        "alter table contacts alter column id type bigint;"
+    ));
+    assert!(!is_replayable("create index on contacts (name);"));
+    assert!(!is_replayable("drop index concurrently contacts_name_idx;"));
+}
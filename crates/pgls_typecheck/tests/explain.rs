@@ -0,0 +1,118 @@
+use pgls_schema_cache::SchemaCache;
+use pgls_typecheck::{ExplainParams, analyse_plan, explain_sql};
+use sqlx::{Executor, PgPool};
+use std::time::Duration;
+
+async fn load_schema_cache(test_db: &PgPool, live_rows: i64) -> SchemaCache {
+    let mut schema_cache = SchemaCache::load(test_db)
+        .await
+        .expect("Failed to load Schema Cache");
+
+    // the statistics of a fresh table lag behind, pretend it is large
+    for table in &mut schema_cache.tables {
+        table.live_rows_estimate = live_rows;
+    }
+
+    schema_cache
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn explains_and_analyses_plans(test_db: PgPool) {
+    test_db
+        .execute(
+            r#"
+        create table public.contacts (
+            id serial primary key,
+            name text not null,
+            email text
+        );
+    "#,
+        )
+        .await
+        .expect("Failed to setup test database");
+
+    let schema_cache = load_schema_cache(&test_db, 50_000).await;
+    let sql = "select id from contacts where email = 'a@b.c'";
+
+    let plan = explain_sql(ExplainParams {
+        conn: &test_db,
+        sql,
+        schema_cache: &schema_cache,
+        search_path_patterns: vec![],
+        analyze: false,
+        statement_timeout: Duration::from_secs(30),
+        lock_timeout: Duration::from_secs(5),
+    })
+    .await
+    .expect("Failed to explain query");
+
+    assert_eq!(plan.node_type, "Seq Scan");
+    assert_eq!(plan.relation_name.as_deref(), Some("contacts"));
+    assert!(plan.actual_rows.is_none());
+    assert!(
+        plan.to_text()
+            .starts_with("Seq Scan on public.contacts  (cost="),
+        "{}",
+        plan.to_text()
+    );
+
+    let categories: Vec<_> = analyse_plan(&plan, &schema_cache)
+        .iter()
+        .map(|d| pgls_diagnostics::Diagnostic::category(d).unwrap().name())
+        .collect();
+    assert_eq!(categories, vec!["explain/missingIndex"]);
+
+    let small_tables = load_schema_cache(&test_db, 10).await;
+    assert!(analyse_plan(&plan, &small_tables).is_empty());
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn explain_analyze_rolls_back(test_db: PgPool) {
+    test_db
+        .execute("create table public.events (id serial primary key, name text);")
+        .await
+        .expect("Failed to setup test database");
+
+    let schema_cache = load_schema_cache(&test_db, 0).await;
+
+    let plan = explain_sql(ExplainParams {
+        conn: &test_db,
+        sql: "insert into events (name) values ('a')",
+        schema_cache: &schema_cache,
+        search_path_patterns: vec![],
+        analyze: true,
+        statement_timeout: Duration::from_secs(30),
+        lock_timeout: Duration::from_secs(5),
+    })
+    .await
+    .expect("Failed to explain query");
+
+    assert_eq!(plan.node_type, "ModifyTable");
+    assert!(plan.actual_rows.is_some());
+
+    let (count,): (i64,) = sqlx::query_as("select count(*) from public.events")
+        .fetch_one(&test_db)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn explain_analyze_times_out(test_db: PgPool) {
+    let schema_cache = load_schema_cache(&test_db, 0).await;
+
+    let result = explain_sql(ExplainParams {
+        conn: &test_db,
+        sql: "select pg_sleep(5)",
+        schema_cache: &schema_cache,
+        search_path_patterns: vec![],
+        analyze: true,
+        statement_timeout: Duration::from_millis(100),
+        lock_timeout: Duration::from_secs(5),
+    })
+    .await;
+
+    let err = result.expect_err("the statement should have been cancelled");
+    let code = err.as_database_error().and_then(|e| e.code());
+    assert_eq!(code.as_deref(), Some("57014"));
+}
@@ -47,6 +47,13 @@ impl WorkspaceError {
         Self::CantReadFile(CantReadFile { path })
     }
 
+    pub fn database_connection(message: impl Into<String>) -> Self {
+        Self::DatabaseConnectionError(DatabaseConnectionError {
+            message: message.into(),
+            code: None,
+        })
+    }
+
     pub fn not_found() -> Self {
         Self::NotFound(NotFound)
     }
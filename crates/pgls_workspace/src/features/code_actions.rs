@@ -1,13 +1,17 @@
+use crate::features::describe::StatementSelector;
 use crate::workspace::StatementId;
 use pgls_configuration::RuleSelector;
 use pgls_fs::PgLSPath;
-use pgls_text_size::TextSize;
+use pgls_text_size::{TextRange, TextSize};
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CodeActionsParams {
     pub path: PgLSPath,
     pub cursor_position: TextSize,
+    /// The selected text, if any. Used to offer executing all statements within it.
+    #[serde(default)]
+    pub selection: Option<TextRange>,
     pub only: Vec<RuleSelector>,
     pub skip: Vec<RuleSelector>,
 }
@@ -48,18 +52,170 @@ pub struct CommandAction {
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum CommandActionCategory {
     ExecuteStatement(StatementId),
+    DryRunStatement(StatementId),
+    ExecuteFile,
+    ExecuteRange(TextRange),
+    CancelStatementExecution,
+    /// Selects the named database connection for the file, or the default one if `None`.
+    SelectConnection(Option<String>),
+    DescribeStatement(StatementId),
+    ExplainStatement(StatementId),
+    ExplainAnalyzeStatement(StatementId),
     InvalidateSchemaCache,
+    /// Checks the function a statement creates with `plpgsql_check`.
+    CheckFunction(StatementId),
+    /// Lists the functions and triggers that call the function a statement creates.
+    ShowFunctionCallers(StatementId),
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ExecuteStatementParams {
-    pub statement_id: StatementId,
+    pub statement: StatementSelector,
     pub path: PgLSPath,
+    #[serde(default)]
+    pub options: ExecuteStatementOptions,
+}
+
+#[derive(Debug, Default, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExecuteStatementOptions {
+    /// Run the statement inside a transaction that is rolled back, e.g. to see how many rows
+    /// it would affect.
+    pub dry_run: bool,
+
+    /// The token of the [ExecutionConfirmation] returned for the statement. Statements that
+    /// destroy data, e.g. a `drop table`, are only executed with it.
+    pub confirmation: Option<u64>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ExecuteStatementResult {
     pub message: String,
+
+    /// The rows returned by the statement. `None` if the statement doesn't return rows.
+    pub result_set: Option<ResultSet>,
+
+    /// Set if the statement needs to be confirmed before it is executed. If set, the statement
+    /// was not executed.
+    pub confirmation_required: Option<ExecutionConfirmation>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExecutionConfirmation {
+    /// Why the statements need to be confirmed, to be shown to the user.
+    pub reason: String,
+
+    /// Pass it back to execute the same statements of the same file. It is only valid once.
+    pub token: u64,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExecuteStatementsParams {
+    pub path: PgLSPath,
+
+    /// Only the statements that overlap with this range are executed. If `None`, all
+    /// statements of the file are executed.
+    pub range: Option<TextRange>,
+
+    /// The token of the [ExecutionConfirmation] returned for the statements. If some of them
+    /// destroy data, e.g. a `drop table`, they are only executed with it.
+    #[serde(default)]
+    pub confirmation: Option<u64>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExecuteStatementsResult {
+    pub message: String,
+
+    /// The number of statements that were executed successfully.
+    pub executed: usize,
+
+    /// The number of statements that were to be executed.
+    pub total: usize,
+
+    /// The error that stopped the execution. Statements after it were not executed.
+    pub error: Option<StatementExecutionError>,
+
+    /// Set if the statements need to be confirmed before they are executed. If set, no
+    /// statement was executed.
+    pub confirmation_required: Option<ExecutionConfirmation>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StatementExecutionError {
+    /// The range in the document the error refers to. This is the position Postgres reported
+    /// if it did, and the whole statement otherwise.
+    pub range: TextRange,
+    pub message: String,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StatementExecutionProgressParams {
+    pub path: PgLSPath,
+
+    /// The number of executed statements the caller already knows about. If set, the call
+    /// waits until more statements were executed, the execution ended or a second passed.
+    #[serde(default)]
+    pub known_executed: Option<usize>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StatementExecutionProgress {
+    /// The number of statements that were executed so far.
+    pub executed: usize,
+    pub total: usize,
+    /// The range of the statement that is currently running.
+    pub current: Option<TextRange>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SelectConnectionParams {
+    pub path: PgLSPath,
+
+    /// The name of a connection in `db.connections`, or `None` for the default connection.
+    pub connection: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CancelStatementExecutionParams {
+    pub path: PgLSPath,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CancelStatementExecutionResult {
+    /// The number of statements of the file that were running and got cancelled.
+    pub cancelled: usize,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResultSet {
+    pub columns: Vec<ResultSetColumn>,
+
+    /// The values of each row, in the order of the columns. Booleans, integers, floats and
+    /// `json` values are converted to their JSON equivalent, everything else is represented by
+    /// its Postgres text format.
+    pub rows: Vec<Vec<serde_json::Value>>,
+
+    /// Whether rows were left out because the statement returned more than the configured
+    /// `executionRowLimit`.
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResultSetColumn {
+    pub name: String,
+    pub type_name: String,
 }
@@ -0,0 +1,35 @@
+use pgls_fs::PgLSPath;
+use pgls_text_size::TextRange;
+
+use crate::features::code_actions::CommandActionCategory;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CodeLensesParams {
+    pub path: PgLSPath,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CodeLensesResult {
+    /// All top-level statements of the file, in order.
+    pub statements: Vec<StatementCodeLenses>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StatementCodeLenses {
+    pub range: TextRange,
+
+    /// Lenses whose command is not available, e.g. because there is no database connection,
+    /// are left out.
+    pub lenses: Vec<CodeLens>,
+}
+
+/// A command shown above a statement.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CodeLens {
+    pub title: String,
+    pub command: CommandActionCategory,
+}
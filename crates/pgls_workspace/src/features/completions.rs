@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
-use pgls_completions::CompletionItem;
+use globset::Glob;
+use pgls_completions::{CompletionItem, CompletionItemReference, CompletionText};
 use pgls_fs::PgLSPath;
+use pgls_schema_cache::SchemaCache;
 use pgls_text_size::{TextRange, TextSize};
 
 use crate::workspace::{Document, GetCompletionsFilter, StatementId, WithCSTMapper};
@@ -29,6 +31,35 @@ impl IntoIterator for CompletionsResult {
     }
 }
 
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RecordCompletionUsageParams {
+    /// The File in which the completion was accepted.
+    pub path: PgLSPath,
+    /// The database object of the accepted completion item.
+    pub reference: CompletionItemReference,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResolveCompletionItemParams {
+    /// The File in which the completion was requested.
+    pub path: PgLSPath,
+    /// The Cursor position at which the completion was requested.
+    pub position: TextSize,
+    /// The database object the completion item refers to.
+    pub reference: CompletionItemReference,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResolveCompletionItemResult {
+    /// Markdown documentation of the referenced database object.
+    pub documentation: Option<String>,
+    /// Edits that should be applied alongside the completion, with ranges relative to the file.
+    pub additional_edits: Vec<CompletionText>,
+}
+
 pub(crate) fn get_statement_for_completions(
     doc: &Document,
     position: TextSize,
@@ -74,6 +105,24 @@ pub(crate) fn get_statement_for_completions(
     }
 }
 
+/// Returns the names of all schemas matching the configured search path patterns.
+pub(crate) fn schemas_on_search_path<'a>(
+    schema_cache: &'a SchemaCache,
+    patterns: &[String],
+) -> Vec<&'a str> {
+    patterns
+        .iter()
+        .filter_map(|pattern| Glob::new(pattern).ok().map(|g| g.compile_matcher()))
+        .flat_map(|matcher| {
+            schema_cache
+                .schemas
+                .iter()
+                .filter(move |s| matcher.is_match(s.name.as_str()))
+                .map(|s| s.name.as_str())
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use pgls_text_size::TextSize;
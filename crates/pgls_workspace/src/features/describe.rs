@@ -0,0 +1,105 @@
+use crate::workspace::StatementId;
+use pgls_fs::PgLSPath;
+use pgls_text_size::{TextRange, TextSize};
+
+/// Which statement of a file to describe.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum StatementSelector {
+    /// The statement at the cursor position.
+    Position(TextSize),
+
+    /// The n-th top-level statement of the file, starting at zero.
+    Index(usize),
+
+    Id(StatementId),
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DescribeStatementParams {
+    pub path: PgLSPath,
+    pub statement: StatementSelector,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DescribeStatementResult {
+    /// `None` if there is no such statement or it doesn't return rows.
+    pub description: Option<StatementDescription>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StatementDescription {
+    pub range: TextRange,
+    pub columns: Vec<ResultColumn>,
+    pub source: DescriptionSource,
+}
+
+/// Where the description of a statement comes from.
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum DescriptionSource {
+    /// Postgres described the statement.
+    Database,
+
+    /// The types were inferred from the schema cache, e.g. because the statement refers to
+    /// parameters Postgres can't resolve.
+    SchemaCache,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResultColumn {
+    pub name: String,
+
+    /// `None` if the type can't be determined.
+    pub type_name: Option<String>,
+
+    /// `None` if the nullability can't be determined.
+    pub nullable: Option<bool>,
+}
+
+impl StatementDescription {
+    /// Lists the columns as plain text, one per line.
+    pub fn to_text(&self) -> String {
+        self.columns
+            .iter()
+            .map(|column| {
+                format!(
+                    "{}: {}{}",
+                    column.name,
+                    column.type_name.as_deref().unwrap_or("unknown"),
+                    match column.nullable {
+                        Some(false) => " not null",
+                        _ => "",
+                    }
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Renders the columns as a markdown table.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("| Column | Type | Nullable |\n| --- | --- | --- |\n");
+
+        for column in &self.columns {
+            markdown.push_str(&format!(
+                "| {} | {} | {} |\n",
+                column.name,
+                column.type_name.as_deref().unwrap_or("unknown"),
+                match column.nullable {
+                    Some(true) => "yes",
+                    Some(false) => "no",
+                    None => "unknown",
+                }
+            ));
+        }
+
+        markdown
+    }
+}
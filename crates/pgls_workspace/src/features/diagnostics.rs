@@ -12,11 +12,24 @@ pub struct PullFileDiagnosticsParams {
     pub skip: Vec<RuleSelector>,
 }
 
+/// Checks the given content of a file, e.g. of a file that is not open, without opening it.
+/// Only the analyser runs, nothing is checked against the database.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PullContentDiagnosticsParams {
+    pub path: PgLSPath,
+    pub content: String,
+    pub categories: RuleCategories,
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PullDiagnosticsResult {
     pub diagnostics: Vec<pgls_diagnostics::serde::Diagnostic>,
     pub skipped_diagnostics: u32,
+
+    /// Diagnostics that were silenced by suppression comments.
+    pub suppressed_diagnostics: Vec<pgls_diagnostics::serde::Diagnostic>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
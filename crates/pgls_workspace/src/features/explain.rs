@@ -0,0 +1,41 @@
+use pgls_diagnostics::serde::Diagnostic as SDiagnostic;
+use pgls_fs::PgLSPath;
+use pgls_text_size::TextRange;
+
+use crate::features::describe::StatementSelector;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExplainStatementParams {
+    pub path: PgLSPath,
+    pub statement: StatementSelector,
+
+    /// Run `EXPLAIN ANALYZE`, which executes the statement within a transaction that is rolled
+    /// back. Only honored if statement executions are allowed against the database.
+    pub analyze: bool,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExplainStatementResult {
+    /// `None` if there is no such statement or it can't be explained.
+    pub explanation: Option<StatementExplanation>,
+
+    /// Tells the user why the statement wasn't explained, or wasn't analyzed.
+    pub message: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StatementExplanation {
+    pub range: TextRange,
+
+    /// The plan tree, rendered as text.
+    pub plan: String,
+
+    /// Whether the plan contains the measurements of `EXPLAIN ANALYZE`.
+    pub analyzed: bool,
+
+    /// Findings in the plan, such as sequential scans on large tables.
+    pub diagnostics: Vec<SDiagnostic>,
+}
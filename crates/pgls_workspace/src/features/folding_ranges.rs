@@ -0,0 +1,37 @@
+use pgls_fs::PgLSPath;
+use pgls_text_size::TextRange;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FoldingRangesParams {
+    pub path: PgLSPath,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FoldingRangesResult {
+    /// Sorted by start. Ranges that fit on a single line are included, it's up to the client
+    /// to leave them out.
+    pub ranges: Vec<FoldingRange>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FoldingRange {
+    pub range: TextRange,
+    pub kind: FoldingRangeKind,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum FoldingRangeKind {
+    /// A top-level statement.
+    Statement,
+    /// The statements from a `BEGIN` up to the matching `COMMIT` or `ROLLBACK`.
+    Transaction,
+    /// The body of a `CREATE FUNCTION` or `CREATE PROCEDURE` statement.
+    FunctionBody,
+    /// A block comment, or consecutive line comments.
+    Comment,
+}
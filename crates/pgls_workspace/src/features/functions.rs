@@ -0,0 +1,57 @@
+use pgls_diagnostics::serde::Diagnostic as SDiagnostic;
+use pgls_fs::PgLSPath;
+
+use crate::features::describe::StatementSelector;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CheckFunctionParams {
+    pub path: PgLSPath,
+    pub statement: StatementSelector,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CheckFunctionResult {
+    /// The issues `plpgsql_check` found. `None` if the function wasn't checked.
+    pub diagnostics: Option<Vec<SDiagnostic>>,
+
+    /// Tells the user why the function wasn't checked.
+    pub message: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FunctionCallersParams {
+    pub path: PgLSPath,
+    pub statement: StatementSelector,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FunctionCallersResult {
+    /// The functions and triggers in the database that call the function. `None` if they
+    /// couldn't be looked up.
+    pub callers: Option<Vec<FunctionCaller>>,
+
+    /// Tells the user why the callers couldn't be looked up.
+    pub message: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FunctionCaller {
+    pub kind: FunctionCallerKind,
+
+    /// The signature of a calling function, e.g. `public.archive(integer)`, or the trigger
+    /// and its table, e.g. `audit on public.users`.
+    pub name: String,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum FunctionCallerKind {
+    Function,
+    Trigger,
+}
@@ -1,4 +1,10 @@
 pub mod code_actions;
+pub mod code_lenses;
 pub mod completions;
+pub mod describe;
 pub mod diagnostics;
+pub mod explain;
+pub mod folding_ranges;
+pub mod functions;
 pub mod on_hover;
+pub mod selection_ranges;
@@ -0,0 +1,18 @@
+use pgls_fs::PgLSPath;
+use pgls_text_size::{TextRange, TextSize};
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SelectionRangesParams {
+    pub path: PgLSPath,
+    pub positions: Vec<TextSize>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SelectionRangesResult {
+    /// One entry per requested position, in the same order. Each entry lists the ranges around
+    /// the position from the innermost to the enclosing statement, every range containing the
+    /// previous one. Positions outside of any statement get an empty list.
+    pub ranges: Vec<Vec<TextRange>>,
+}
@@ -7,6 +7,7 @@ pub mod configuration;
 pub mod diagnostics;
 pub mod dome;
 pub mod features;
+mod libpq;
 pub mod matcher;
 pub mod settings;
 pub mod workspace;
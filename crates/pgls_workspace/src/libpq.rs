@@ -0,0 +1,252 @@
+//! Support for the connection conventions of libpq: the `PG*` environment variables,
+//! connection service files and password files.
+//!
+//! See <https://www.postgresql.org/docs/current/libpq-envars.html>,
+//! <https://www.postgresql.org/docs/current/libpq-pgservice.html> and
+//! <https://www.postgresql.org/docs/current/libpq-pgpass.html>.
+
+use std::{env, fs, path::PathBuf, process::Command};
+
+/// Connection parameters as libpq names them. Parameters that are not set are `None`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ConnectionParams {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub user: Option<String>,
+    pub password: Option<String>,
+    pub dbname: Option<String>,
+    pub sslmode: Option<String>,
+    pub sslrootcert: Option<String>,
+    pub sslcert: Option<String>,
+    pub sslkey: Option<String>,
+}
+
+impl ConnectionParams {
+    /// Reads the parameters from the `PG*` environment variables.
+    pub fn from_env() -> Self {
+        let var = |name| env::var(name).ok().filter(|value| !value.is_empty());
+
+        Self {
+            host: var("PGHOST"),
+            port: var("PGPORT").and_then(|port| port.parse().ok()),
+            user: var("PGUSER"),
+            password: var("PGPASSWORD"),
+            dbname: var("PGDATABASE"),
+            sslmode: var("PGSSLMODE"),
+            sslrootcert: var("PGSSLROOTCERT"),
+            sslcert: var("PGSSLCERT"),
+            sslkey: var("PGSSLKEY"),
+        }
+    }
+
+    /// Reads the parameters of the service with the given name from the user's service file
+    /// (`PGSERVICEFILE` or `~/.pg_service.conf`) or the system-wide one in `PGSYSCONFDIR`.
+    pub fn from_service(name: &str) -> Result<Self, String> {
+        let user_file = env::var_os("PGSERVICEFILE")
+            .map(PathBuf::from)
+            .or_else(|| home::home_dir().map(|home| home.join(".pg_service.conf")));
+        let system_file =
+            env::var_os("PGSYSCONFDIR").map(|dir| PathBuf::from(dir).join("pg_service.conf"));
+
+        for file in user_file.iter().chain(system_file.iter()) {
+            let Ok(content) = fs::read_to_string(file) else {
+                continue;
+            };
+            if let Some(params) = parse_service(&content, name) {
+                return Ok(params);
+            }
+        }
+
+        Err(format!("Database connection service \"{name}\" not found"))
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        let value = Some(value.to_string());
+        match key {
+            "host" | "hostaddr" => self.host = value,
+            "port" => self.port = value.and_then(|port| port.parse().ok()),
+            "user" => self.user = value,
+            "password" => self.password = value,
+            "dbname" => self.dbname = value,
+            "sslmode" => self.sslmode = value,
+            "sslrootcert" => self.sslrootcert = value,
+            "sslcert" => self.sslcert = value,
+            "sslkey" => self.sslkey = value,
+            _ => {}
+        }
+    }
+}
+
+/// Returns the parameters of the `[name]` section of a service file.
+fn parse_service(content: &str, name: &str) -> Option<ConnectionParams> {
+    let mut params = None;
+
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            if params.is_some() {
+                break;
+            }
+            if section.trim() == name {
+                params = Some(ConnectionParams::default());
+            }
+            continue;
+        }
+
+        if let (Some(params), Some((key, value))) = (params.as_mut(), line.split_once('=')) {
+            params.set(key.trim(), value.trim());
+        }
+    }
+
+    params
+}
+
+/// Looks up the password for a connection in the password file (`PGPASSFILE` or `~/.pgpass`).
+pub(crate) fn pgpass_password(
+    host: &str,
+    port: u16,
+    database: &str,
+    username: &str,
+) -> Option<String> {
+    let file = env::var_os("PGPASSFILE")
+        .map(PathBuf::from)
+        .or_else(default_pgpass_file)?;
+    let content = fs::read_to_string(file).ok()?;
+
+    find_pgpass_password(&content, &[host, &port.to_string(), database, username])
+}
+
+#[cfg(not(windows))]
+fn default_pgpass_file() -> Option<PathBuf> {
+    home::home_dir().map(|home| home.join(".pgpass"))
+}
+
+#[cfg(windows)]
+fn default_pgpass_file() -> Option<PathBuf> {
+    env::var_os("APPDATA").map(|appdata| PathBuf::from(appdata).join("postgresql/pgpass.conf"))
+}
+
+/// Returns the password of the first `host:port:database:username:password` line whose
+/// fields match the connection. A field of `*` matches anything.
+fn find_pgpass_password(content: &str, connection: &[&str; 4]) -> Option<String> {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .find_map(|line| {
+            let fields = split_pgpass_line(line);
+            let [host, port, database, username, password] = fields.as_slice() else {
+                return None;
+            };
+
+            [host, port, database, username]
+                .iter()
+                .zip(connection)
+                .all(|(field, value)| field.as_str() == "*" || field == value)
+                .then(|| password.clone())
+        })
+}
+
+/// Splits a password file line at unescaped colons and removes the escapes.
+fn split_pgpass_line(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    fields.last_mut().unwrap().push(escaped);
+                }
+            }
+            ':' => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+
+    fields
+}
+
+/// Runs the configured `passwordCommand` in a shell and returns its output without the
+/// trailing newline.
+pub(crate) fn run_password_command(command: &str) -> Result<String, String> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    };
+
+    let output = cmd
+        .output()
+        .map_err(|err| format!("Failed to run password command: {err}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Password command failed with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let password = String::from_utf8(output.stdout)
+        .map_err(|_| "Password command returned invalid UTF-8".to_string())?;
+
+    Ok(password.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_pgpass_password, parse_service};
+
+    #[test]
+    fn parses_service_sections() {
+        let content = "
+# comment
+[other]
+host=other-host
+
+[analytics]
+host = analytics.internal
+port=6432
+dbname=analytics
+sslmode=verify-full
+";
+
+        let params = parse_service(content, "analytics").unwrap();
+        assert_eq!(params.host.as_deref(), Some("analytics.internal"));
+        assert_eq!(params.port, Some(6432));
+        assert_eq!(params.dbname.as_deref(), Some("analytics"));
+        assert_eq!(params.sslmode.as_deref(), Some("verify-full"));
+        assert_eq!(params.user, None);
+
+        assert!(parse_service(content, "missing").is_none());
+    }
+
+    #[test]
+    fn finds_pgpass_passwords() {
+        let content = "
+# comment
+db.internal:5432:app:admin:secret
+*:*:*:reader:pass\\:word
+";
+
+        assert_eq!(
+            find_pgpass_password(content, &["db.internal", "5432", "app", "admin"]),
+            Some("secret".to_string())
+        );
+        assert_eq!(
+            find_pgpass_password(content, &["localhost", "6432", "analytics", "reader"]),
+            Some("pass:word".to_string())
+        );
+        assert_eq!(
+            find_pgpass_password(content, &["db.internal", "5432", "app", "other"]),
+            None
+        );
+    }
+}
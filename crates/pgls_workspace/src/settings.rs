@@ -3,6 +3,7 @@ use globset::Glob;
 use pgls_diagnostics::Category;
 use std::{
     borrow::Cow,
+    collections::BTreeMap,
     num::NonZeroU64,
     path::{Path, PathBuf},
     str::FromStr,
@@ -14,17 +15,20 @@ use tracing::trace;
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use pgls_configuration::{
     ConfigurationDiagnostic, LinterConfiguration, PartialConfiguration, TypecheckConfiguration,
-    database::PartialDatabaseConfiguration,
+    completions::CompletionsConfiguration,
+    database::{KnownHostsPolicy, PartialDatabaseConfiguration, SshTunnelConfiguration},
     diagnostics::InvalidIgnorePattern,
     files::FilesConfiguration,
     migrations::{MigrationsConfiguration, PartialMigrationsConfiguration},
     plpgsql_check::PlPgSqlCheckConfiguration,
 };
+use pgls_env::pgls_env;
 use pgls_fs::PgLSPath;
 use sqlx::postgres::PgConnectOptions;
 
 use crate::{
     WorkspaceError,
+    libpq::ConnectionParams,
     matcher::Matcher,
     workspace::{ProjectKey, WorkspaceData},
 };
@@ -219,6 +223,9 @@ pub struct Settings {
     /// plpgsql_check settings for the workspace
     pub plpgsql_check: PlPgSqlCheckSettings,
 
+    /// Completion settings for the workspace
+    pub completions: CompletionsSettings,
+
     /// Migrations settings
     pub migrations: Option<MigrationSettings>,
 }
@@ -265,6 +272,11 @@ impl Settings {
                 to_plpgsql_check_settings(PlPgSqlCheckConfiguration::from(plpgsql_check));
         }
 
+        // completions part
+        if let Some(completions) = configuration.completions {
+            self.completions = to_completions_settings(CompletionsConfiguration::from(completions));
+        }
+
         // Migrations settings
         if let Some(migrations) = configuration.migrations {
             self.migrations = to_migration_settings(
@@ -318,6 +330,7 @@ fn to_typecheck_settings(conf: TypecheckConfiguration) -> TypecheckSettings {
     TypecheckSettings {
         search_path: conf.search_path.into_iter().collect(),
         enabled: conf.enabled,
+        replay_prior_ddl: conf.replay_prior_ddl,
     }
 }
 
@@ -327,6 +340,12 @@ fn to_plpgsql_check_settings(conf: PlPgSqlCheckConfiguration) -> PlPgSqlCheckSet
     }
 }
 
+fn to_completions_settings(conf: CompletionsConfiguration) -> CompletionsSettings {
+    CompletionsSettings {
+        learn_from_usage: conf.learn_from_usage,
+    }
+}
+
 fn to_file_settings(
     working_directory: Option<PathBuf>,
     config: Option<FilesConfiguration>,
@@ -353,7 +372,18 @@ fn to_file_settings(
             max_size: config.max_size,
             git_ignore,
             ignored_files: to_matcher(working_directory.clone(), Some(&config.ignore))?,
-            included_files: to_matcher(working_directory, Some(&config.include))?,
+            included_files: to_matcher(working_directory.clone(), Some(&config.include))?,
+            connections: config
+                .connections
+                .0
+                .iter()
+                .map(|mapping| {
+                    Ok((
+                        to_matcher(working_directory.clone(), Some(&mapping.include))?,
+                        mapping.connection.clone(),
+                    ))
+                })
+                .collect::<Result<_, WorkspaceError>>()?,
         }),
         _ => None,
     })
@@ -434,6 +464,21 @@ impl Default for LinterSettings {
     }
 }
 
+/// Completion settings for the entire workspace
+#[derive(Debug)]
+pub struct CompletionsSettings {
+    /// Enabled by default
+    pub learn_from_usage: bool,
+}
+
+impl Default for CompletionsSettings {
+    fn default() -> Self {
+        Self {
+            learn_from_usage: true,
+        }
+    }
+}
+
 /// Type checking settings for the entire workspace
 #[derive(Debug)]
 pub struct PlPgSqlCheckSettings {
@@ -454,6 +499,8 @@ pub struct TypecheckSettings {
     pub enabled: bool,
     /// Default search path schemas for type checking
     pub search_path: Vec<String>,
+    /// Disabled by default
+    pub replay_prior_ddl: bool,
 }
 
 impl Default for TypecheckSettings {
@@ -461,6 +508,7 @@ impl Default for TypecheckSettings {
         Self {
             enabled: true,
             search_path: vec!["public".to_string()],
+            replay_prior_ddl: false,
         }
     }
 }
@@ -473,10 +521,34 @@ pub struct DatabaseSettings {
     pub host: String,
     pub port: u16,
     pub username: String,
-    pub password: String,
+    /// The password, if it is not looked up in the password file or with the password command.
+    pub password: Option<String>,
     pub database: String,
+    pub password_command: Option<String>,
+    pub ssl_mode: Option<String>,
+    pub ssl_root_cert: Option<String>,
+    pub ssl_cert: Option<String>,
+    pub ssl_key: Option<String>,
+    /// The SSH tunnel to connect through, if any.
+    pub ssh: Option<SshTunnelSettings>,
     pub conn_timeout_secs: Duration,
     pub allow_statement_executions: bool,
+    pub execution_row_limit: usize,
+    pub execution_timeout_secs: Duration,
+    pub execution_lock_timeout_secs: Duration,
+    /// The named connections, see [DatabaseSettings::connection].
+    pub connections: BTreeMap<String, DatabaseSettings>,
+}
+
+impl DatabaseSettings {
+    /// Returns the settings of the connection with the given name, or these settings if the
+    /// name is `None`.
+    pub fn connection(&self, name: Option<&str>) -> Option<&DatabaseSettings> {
+        match name {
+            Some(name) => self.connections.get(name),
+            None => Some(self),
+        }
+    }
 }
 
 impl Default for DatabaseSettings {
@@ -487,18 +559,148 @@ impl Default for DatabaseSettings {
             host: "127.0.0.1".to_string(),
             port: 5432,
             username: "postgres".to_string(),
-            password: "postgres".to_string(),
+            password: None,
             database: "postgres".to_string(),
+            password_command: None,
+            ssl_mode: None,
+            ssl_root_cert: None,
+            ssl_cert: None,
+            ssl_key: None,
+            ssh: None,
             conn_timeout_secs: Duration::from_secs(10),
             allow_statement_executions: true,
+            execution_row_limit: 1000,
+            execution_timeout_secs: Duration::from_secs(30),
+            execution_lock_timeout_secs: Duration::from_secs(5),
+            connections: BTreeMap::new(),
         }
     }
 }
 
+/// The SSH tunnel to open before connecting to the database
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SshTunnelSettings {
+    pub host: String,
+    pub port: u16,
+    pub user: Option<String>,
+    pub identity_file: Option<String>,
+    pub known_hosts: KnownHostsPolicy,
+    pub known_hosts_file: Option<String>,
+}
+
+impl SshTunnelSettings {
+    /// Returns `None` if no SSH host is configured.
+    fn from_configuration(config: SshTunnelConfiguration) -> Option<Self> {
+        Some(Self {
+            host: config.host?,
+            port: config.port.unwrap_or(22),
+            user: config.user,
+            identity_file: config.identity_file,
+            known_hosts: config.known_hosts.unwrap_or_default(),
+            known_hosts_file: config.known_hosts_file,
+        })
+    }
+}
+
 impl From<PartialDatabaseConfiguration> for DatabaseSettings {
     fn from(value: PartialDatabaseConfiguration) -> Self {
+        let trust_password_command = pgls_env()
+            .pgls_allow_password_command
+            .value()
+            .is_some_and(|value| matches!(value.trim(), "1" | "true"));
+
+        Self::from_configuration(value, trust_password_command)
+    }
+}
+
+impl DatabaseSettings {
+    /// Password commands of the configuration are only kept if `trust_password_command` is set,
+    /// since the configuration may come from an untrusted project.
+    fn from_configuration(
+        mut value: PartialDatabaseConfiguration,
+        trust_password_command: bool,
+    ) -> Self {
+        if !trust_password_command {
+            let connections = value.connections.iter_mut().flat_map(|c| c.0.values_mut());
+            let ignored = [value.password_command.take()]
+                .into_iter()
+                .chain(connections.map(|connection| connection.password_command.take()))
+                .flatten()
+                .count();
+            if ignored > 0 {
+                tracing::warn!(
+                    "Ignoring db.passwordCommand, set {}=true to run it",
+                    pgls_env().pgls_allow_password_command.name()
+                );
+            }
+        }
+
         let d = DatabaseSettings::default();
 
+        // named connections inherit everything they don't set, except for the connection string.
+        // Connections that use a service only inherit settings unrelated to the server, and
+        // credentials are only inherited by connections to the same server.
+        let connections = value
+            .connections
+            .take()
+            .unwrap_or_default()
+            .0
+            .into_iter()
+            .map(|(name, connection)| {
+                let inherited = if connection.service.is_some() {
+                    PartialDatabaseConfiguration {
+                        host: None,
+                        port: None,
+                        username: None,
+                        password: None,
+                        database: None,
+                        ..value.clone()
+                    }
+                } else {
+                    value.clone()
+                };
+                let same_server = connection.connection_string.is_none()
+                    && connection
+                        .host
+                        .as_ref()
+                        .is_none_or(|host| inherited.host.as_ref() == Some(host))
+                    && connection
+                        .port
+                        .is_none_or(|port| inherited.port == Some(port));
+                let (inherited_password, inherited_password_command) = if same_server {
+                    (
+                        inherited.password.clone(),
+                        inherited.password_command.clone(),
+                    )
+                } else {
+                    (None, None)
+                };
+                let password = match connection.password_command {
+                    Some(_) => connection.password,
+                    None => connection.password.or(inherited_password),
+                };
+
+                let settings = DatabaseSettings::from_configuration(
+                    PartialDatabaseConfiguration {
+                        connection_string: connection.connection_string,
+                        host: connection.host.or_else(|| inherited.host.clone()),
+                        port: connection.port.or(inherited.port),
+                        username: connection.username.or_else(|| inherited.username.clone()),
+                        password,
+                        database: connection.database.or_else(|| inherited.database.clone()),
+                        service: connection.service.or_else(|| inherited.service.clone()),
+                        password_command: connection
+                            .password_command
+                            .or(inherited_password_command),
+                        ssh: connection.ssh.or_else(|| inherited.ssh.clone()),
+                        ..inherited
+                    },
+                    trust_password_command,
+                );
+                (name, settings)
+            })
+            .collect();
+
         let connection_string = value.connection_string.and_then(|uri| {
             let trimmed = uri.trim().to_string();
             if trimmed.is_empty() {
@@ -508,16 +710,47 @@ impl From<PartialDatabaseConfiguration> for DatabaseSettings {
             }
         });
 
+        // like libpq, fall back to the parameters of the connection service and the `PG*`
+        // environment variables for everything that is not configured
+        let service = value
+            .service
+            .clone()
+            .or_else(|| std::env::var("PGSERVICE").ok().filter(|s| !s.is_empty()))
+            .map(|name| {
+                ConnectionParams::from_service(&name).unwrap_or_else(|err| {
+                    tracing::warn!("{err}");
+                    ConnectionParams::default()
+                })
+            })
+            .unwrap_or_default();
+        let env = ConnectionParams::from_env();
+
+        let configured_host = value.host.or(service.host).or(env.host);
+
         // "host" OR "connectionString" is the minimum required setting for database features
         // to be enabled.
         let disable_connection = value.disable_connection.is_some_and(|disabled| disabled);
         let enable_connection =
-            (connection_string.is_some() || value.host.as_ref().is_some()) && !disable_connection;
-
-        let mut database = value.database.unwrap_or(d.database);
-        let mut host = value.host.unwrap_or(d.host);
-        let mut port = value.port.unwrap_or(d.port);
-        let mut username = value.username.unwrap_or(d.username);
+            (connection_string.is_some() || configured_host.is_some()) && !disable_connection;
+
+        let mut database = value
+            .database
+            .or(service.dbname)
+            .or(env.dbname)
+            .unwrap_or(d.database);
+        let mut host = configured_host.unwrap_or(d.host);
+        let mut port = value.port.or(service.port).or(env.port).unwrap_or(d.port);
+        let mut username = value
+            .username
+            .or(service.user)
+            .or(env.user)
+            .unwrap_or(d.username);
+
+        // a password command takes precedence over passwords that are not configured explicitly
+        let password = match value.password_command {
+            Some(_) => value.password,
+            None => value.password.or(service.password).or(env.password),
+        };
 
         if let Some(uri) = connection_string.as_ref() {
             let opts = PgConnectOptions::from_str(uri)
@@ -551,9 +784,18 @@ impl From<PartialDatabaseConfiguration> for DatabaseSettings {
 
             port,
             username,
-            password: value.password.unwrap_or(d.password),
+            password,
             database,
             host,
+            password_command: value.password_command,
+            ssl_mode: value.ssl_mode.or(service.sslmode).or(env.sslmode),
+            ssl_root_cert: value
+                .ssl_root_cert
+                .or(service.sslrootcert)
+                .or(env.sslrootcert),
+            ssl_cert: value.ssl_cert.or(service.sslcert).or(env.sslcert),
+            ssl_key: value.ssl_key.or(service.sslkey).or(env.sslkey),
+            ssh: value.ssh.and_then(SshTunnelSettings::from_configuration),
 
             conn_timeout_secs: value
                 .conn_timeout_secs
@@ -561,6 +803,23 @@ impl From<PartialDatabaseConfiguration> for DatabaseSettings {
                 .unwrap_or(d.conn_timeout_secs),
 
             allow_statement_executions,
+
+            execution_row_limit: value
+                .execution_row_limit
+                .map(|limit| limit as usize)
+                .unwrap_or(d.execution_row_limit),
+
+            execution_timeout_secs: value
+                .execution_timeout_secs
+                .map(|s| Duration::from_secs(s.into()))
+                .unwrap_or(d.execution_timeout_secs),
+
+            execution_lock_timeout_secs: value
+                .execution_lock_timeout_secs
+                .map(|s| Duration::from_secs(s.into()))
+                .unwrap_or(d.execution_lock_timeout_secs),
+
+            connections,
         }
     }
 }
@@ -579,6 +838,19 @@ pub struct FilesSettings {
 
     /// gitignore file patterns
     pub git_ignore: Option<Gitignore>,
+
+    /// The named database connections assigned to files, in order of precedence
+    pub connections: Vec<(Matcher, String)>,
+}
+
+impl FilesSettings {
+    /// Returns the name of the database connection assigned to the file, if any.
+    pub fn connection(&self, path: &Path) -> Option<&str> {
+        self.connections
+            .iter()
+            .find(|(matcher, _)| matcher.matches_path(path))
+            .map(|(_, name)| name.as_str())
+    }
 }
 
 /// Migration settings
@@ -619,6 +891,7 @@ impl Default for FilesSettings {
             ignored_files: Matcher::empty(),
             included_files: Matcher::empty(),
             git_ignore: None,
+            connections: Vec::new(),
         }
     }
 }
@@ -626,7 +899,9 @@ impl Default for FilesSettings {
 #[cfg(test)]
 mod tests {
     use biome_deserialize::StringSet;
-    use pgls_configuration::database::PartialDatabaseConfiguration;
+    use pgls_configuration::database::{
+        DatabaseConnectionConfiguration, DatabaseConnections, PartialDatabaseConfiguration,
+    };
 
     use super::DatabaseSettings;
 
@@ -696,4 +971,121 @@ mod tests {
 
         assert!(!config.allow_statement_executions)
     }
+
+    #[test]
+    fn named_connections_inherit_default_settings() {
+        let partial_config = PartialDatabaseConfiguration {
+            allow_statement_executions_against: Some(StringSet::from_iter(vec![String::from(
+                "localhost/*",
+            )])),
+            host: Some("localhost".into()),
+            database: Some("app".into()),
+            connections: Some(DatabaseConnections(
+                [(
+                    "analytics".to_string(),
+                    DatabaseConnectionConfiguration {
+                        database: Some("analytics".into()),
+                        ..Default::default()
+                    },
+                )]
+                .into(),
+            )),
+            ..Default::default()
+        };
+
+        let config = DatabaseSettings::from(partial_config);
+
+        let analytics = config.connection(Some("analytics")).unwrap();
+        assert_eq!(analytics.host, "localhost");
+        assert_eq!(analytics.database, "analytics");
+        assert!(analytics.allow_statement_executions);
+        assert!(config.connection(Some("reporting")).is_none());
+        assert_eq!(config.connection(None).unwrap().database, "app");
+    }
+
+    #[test]
+    fn named_connections_only_inherit_passwords_of_the_same_server() {
+        let partial_config = PartialDatabaseConfiguration {
+            host: Some("localhost".into()),
+            password: Some("secret".into()),
+            connections: Some(DatabaseConnections(
+                [
+                    (
+                        "analytics".to_string(),
+                        DatabaseConnectionConfiguration {
+                            database: Some("analytics".into()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "replica".to_string(),
+                        DatabaseConnectionConfiguration {
+                            host: Some("replica.internal".into()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "pooler".to_string(),
+                        DatabaseConnectionConfiguration {
+                            port: Some(6432),
+                            ..Default::default()
+                        },
+                    ),
+                ]
+                .into(),
+            )),
+            ..Default::default()
+        };
+
+        let config = DatabaseSettings::from(partial_config);
+
+        let password = |name| config.connection(Some(name)).unwrap().password.clone();
+        assert_eq!(password("analytics").as_deref(), Some("secret"));
+        assert_eq!(password("replica"), None);
+        assert_eq!(password("pooler"), None);
+    }
+
+    #[test]
+    fn password_commands_of_the_configuration_need_to_be_trusted() {
+        let partial_config = PartialDatabaseConfiguration {
+            host: Some("localhost".into()),
+            password_command: Some("touch /tmp/pwned".into()),
+            connections: Some(DatabaseConnections(
+                [(
+                    "replica".to_string(),
+                    DatabaseConnectionConfiguration {
+                        host: Some("replica.internal".into()),
+                        password_command: Some("touch /tmp/pwned".into()),
+                        ..Default::default()
+                    },
+                )]
+                .into(),
+            )),
+            ..Default::default()
+        };
+
+        let untrusted = DatabaseSettings::from_configuration(partial_config.clone(), false);
+        assert_eq!(untrusted.password_command, None);
+        assert_eq!(
+            untrusted
+                .connection(Some("replica"))
+                .unwrap()
+                .password_command,
+            None
+        );
+
+        let trusted = DatabaseSettings::from_configuration(partial_config, true);
+        assert_eq!(
+            trusted.password_command.as_deref(),
+            Some("touch /tmp/pwned")
+        );
+        assert_eq!(
+            trusted
+                .connection(Some("replica"))
+                .unwrap()
+                .password_command
+                .as_deref(),
+            Some("touch /tmp/pwned")
+        );
+    }
 }
@@ -13,13 +13,28 @@ use crate::{
     WorkspaceError,
     features::{
         code_actions::{
-            CodeActionsParams, CodeActionsResult, ExecuteStatementParams, ExecuteStatementResult,
+            CancelStatementExecutionParams, CancelStatementExecutionResult, CodeActionsParams,
+            CodeActionsResult, ExecuteStatementParams, ExecuteStatementResult,
+            ExecuteStatementsParams, ExecuteStatementsResult, SelectConnectionParams,
+            StatementExecutionProgress, StatementExecutionProgressParams,
         },
-        completions::{CompletionsResult, GetCompletionsParams},
+        code_lenses::{CodeLensesParams, CodeLensesResult},
+        completions::{
+            CompletionsResult, GetCompletionsParams, RecordCompletionUsageParams,
+            ResolveCompletionItemParams, ResolveCompletionItemResult,
+        },
+        describe::{DescribeStatementParams, DescribeStatementResult},
         diagnostics::{
-            PullDatabaseDiagnosticsParams, PullDiagnosticsResult, PullFileDiagnosticsParams,
+            PullContentDiagnosticsParams, PullDatabaseDiagnosticsParams, PullDiagnosticsResult,
+            PullFileDiagnosticsParams,
+        },
+        explain::{ExplainStatementParams, ExplainStatementResult},
+        folding_ranges::{FoldingRangesParams, FoldingRangesResult},
+        functions::{
+            CheckFunctionParams, CheckFunctionResult, FunctionCallersParams, FunctionCallersResult,
         },
         on_hover::{OnHoverParams, OnHoverResult},
+        selection_ranges::{SelectionRangesParams, SelectionRangesResult},
     },
 };
 
@@ -105,6 +120,12 @@ pub trait Workspace: Send + Sync + RefUnwindSafe {
         params: PullFileDiagnosticsParams,
     ) -> Result<PullDiagnosticsResult, WorkspaceError>;
 
+    /// Retrieves the diagnostics of the given content of a file without opening the file
+    fn pull_content_diagnostics(
+        &self,
+        params: PullContentDiagnosticsParams,
+    ) -> Result<PullDiagnosticsResult, WorkspaceError>;
+
     /// Retrieves the list of diagnostics associated to a database schema
     fn pull_db_diagnostics(
         &self,
@@ -117,13 +138,69 @@ pub trait Workspace: Send + Sync + RefUnwindSafe {
         params: CodeActionsParams,
     ) -> Result<CodeActionsResult, WorkspaceError>;
 
+    /// Retrieves the commands to show above the statements of a file
+    fn pull_code_lenses(
+        &self,
+        params: CodeLensesParams,
+    ) -> Result<CodeLensesResult, WorkspaceError>;
+
+    /// Retrieves the regions of a file that can be folded: statements, transaction blocks,
+    /// function bodies and comments
+    fn pull_folding_ranges(
+        &self,
+        params: FoldingRangesParams,
+    ) -> Result<FoldingRangesResult, WorkspaceError>;
+
+    /// Retrieves the syntax nodes around each position, from the innermost to the statement
+    fn pull_selection_ranges(
+        &self,
+        params: SelectionRangesParams,
+    ) -> Result<SelectionRangesResult, WorkspaceError>;
+
     fn get_completions(
         &self,
         params: GetCompletionsParams,
     ) -> Result<CompletionsResult, WorkspaceError>;
 
+    /// Lazily computes documentation and additional edits for a completion item.
+    fn resolve_completion_item(
+        &self,
+        params: ResolveCompletionItemParams,
+    ) -> Result<ResolveCompletionItemResult, WorkspaceError>;
+
+    /// Records that a completion item was accepted, so similar items rank higher in the future.
+    fn record_completion_usage(
+        &self,
+        params: RecordCompletionUsageParams,
+    ) -> Result<(), WorkspaceError>;
+
     fn on_hover(&self, params: OnHoverParams) -> Result<OnHoverResult, WorkspaceError>;
 
+    /// Describes the columns of the rows a statement returns.
+    fn describe_statement(
+        &self,
+        params: DescribeStatementParams,
+    ) -> Result<DescribeStatementResult, WorkspaceError>;
+
+    /// Asks Postgres for the plan of a statement and reports the parts that are likely slow.
+    fn explain_statement(
+        &self,
+        params: ExplainStatementParams,
+    ) -> Result<ExplainStatementResult, WorkspaceError>;
+
+    /// Checks the PL/pgSQL function a statement creates with `plpgsql_check`.
+    fn check_function(
+        &self,
+        params: CheckFunctionParams,
+    ) -> Result<CheckFunctionResult, WorkspaceError>;
+
+    /// Finds the functions and triggers in the database that call the function a statement
+    /// creates.
+    fn function_callers(
+        &self,
+        params: FunctionCallersParams,
+    ) -> Result<FunctionCallersResult, WorkspaceError>;
+
     /// Register a possible workspace project folder. Returns the key of said project. Use this key when you want to switch to different projects.
     fn register_project_folder(
         &self,
@@ -167,6 +244,30 @@ pub trait Workspace: Send + Sync + RefUnwindSafe {
         params: ExecuteStatementParams,
     ) -> Result<ExecuteStatementResult, WorkspaceError>;
 
+    /// Executes the statements of a file, or the ones within a range, one after another on the
+    /// same connection. Stops at the first statement that fails.
+    fn execute_statements(
+        &self,
+        params: ExecuteStatementsParams,
+    ) -> Result<ExecuteStatementsResult, WorkspaceError>;
+
+    /// Returns how far the execution of the statements of a file got, if any are running.
+    /// Blocks until the progress changes if the caller passes the progress it knows about.
+    fn statement_execution_progress(
+        &self,
+        params: StatementExecutionProgressParams,
+    ) -> Result<Option<StatementExecutionProgress>, WorkspaceError>;
+
+    /// Cancels the statements of a file that are currently being executed.
+    fn cancel_statement_execution(
+        &self,
+        params: CancelStatementExecutionParams,
+    ) -> Result<CancelStatementExecutionResult, WorkspaceError>;
+
+    /// Selects the database connection a file uses, overriding its `-- pgls-connection`
+    /// comment and the `files.connections` configuration.
+    fn select_connection(&self, params: SelectConnectionParams) -> Result<(), WorkspaceError>;
+
     /// Invalidate the schema cache.
     ///
     /// # Arguments
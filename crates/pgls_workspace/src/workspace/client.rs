@@ -99,6 +99,27 @@ where
         self.request("pgls/code_actions", params)
     }
 
+    fn pull_code_lenses(
+        &self,
+        params: crate::features::code_lenses::CodeLensesParams,
+    ) -> Result<crate::features::code_lenses::CodeLensesResult, WorkspaceError> {
+        self.request("pgls/pull_code_lenses", params)
+    }
+
+    fn pull_folding_ranges(
+        &self,
+        params: crate::features::folding_ranges::FoldingRangesParams,
+    ) -> Result<crate::features::folding_ranges::FoldingRangesResult, WorkspaceError> {
+        self.request("pgls/pull_folding_ranges", params)
+    }
+
+    fn pull_selection_ranges(
+        &self,
+        params: crate::features::selection_ranges::SelectionRangesParams,
+    ) -> Result<crate::features::selection_ranges::SelectionRangesResult, WorkspaceError> {
+        self.request("pgls/pull_selection_ranges", params)
+    }
+
     fn execute_statement(
         &self,
         params: crate::features::code_actions::ExecuteStatementParams,
@@ -106,6 +127,35 @@ where
         self.request("pgls/execute_statement", params)
     }
 
+    fn execute_statements(
+        &self,
+        params: crate::features::code_actions::ExecuteStatementsParams,
+    ) -> Result<crate::features::code_actions::ExecuteStatementsResult, WorkspaceError> {
+        self.request("pgls/execute_statements", params)
+    }
+
+    fn statement_execution_progress(
+        &self,
+        params: crate::features::code_actions::StatementExecutionProgressParams,
+    ) -> Result<Option<crate::features::code_actions::StatementExecutionProgress>, WorkspaceError>
+    {
+        self.request("pgls/statement_execution_progress", params)
+    }
+
+    fn select_connection(
+        &self,
+        params: crate::features::code_actions::SelectConnectionParams,
+    ) -> Result<(), WorkspaceError> {
+        self.request("pgls/select_connection", params)
+    }
+
+    fn cancel_statement_execution(
+        &self,
+        params: crate::features::code_actions::CancelStatementExecutionParams,
+    ) -> Result<crate::features::code_actions::CancelStatementExecutionResult, WorkspaceError> {
+        self.request("pgls/cancel_statement_execution", params)
+    }
+
     fn register_project_folder(
         &self,
         params: RegisterProjectFolderParams,
@@ -155,6 +205,13 @@ where
         self.request("pgls/pull_diagnostics", params)
     }
 
+    fn pull_content_diagnostics(
+        &self,
+        params: crate::features::diagnostics::PullContentDiagnosticsParams,
+    ) -> Result<crate::features::diagnostics::PullDiagnosticsResult, WorkspaceError> {
+        self.request("pgls/pull_content_diagnostics", params)
+    }
+
     fn pull_db_diagnostics(
         &self,
         params: crate::features::diagnostics::PullDatabaseDiagnosticsParams,
@@ -169,6 +226,20 @@ where
         self.request("pgls/get_completions", params)
     }
 
+    fn resolve_completion_item(
+        &self,
+        params: crate::features::completions::ResolveCompletionItemParams,
+    ) -> Result<crate::features::completions::ResolveCompletionItemResult, WorkspaceError> {
+        self.request("pgls/resolve_completion_item", params)
+    }
+
+    fn record_completion_usage(
+        &self,
+        params: crate::features::completions::RecordCompletionUsageParams,
+    ) -> Result<(), WorkspaceError> {
+        self.request("pgls/record_completion_usage", params)
+    }
+
     fn on_hover(
         &self,
         params: crate::features::on_hover::OnHoverParams,
@@ -176,6 +247,34 @@ where
         self.request("pgls/on_hover", params)
     }
 
+    fn describe_statement(
+        &self,
+        params: crate::features::describe::DescribeStatementParams,
+    ) -> Result<crate::features::describe::DescribeStatementResult, WorkspaceError> {
+        self.request("pgls/describe_statement", params)
+    }
+
+    fn explain_statement(
+        &self,
+        params: crate::features::explain::ExplainStatementParams,
+    ) -> Result<crate::features::explain::ExplainStatementResult, WorkspaceError> {
+        self.request("pgls/explain_statement", params)
+    }
+
+    fn check_function(
+        &self,
+        params: crate::features::functions::CheckFunctionParams,
+    ) -> Result<crate::features::functions::CheckFunctionResult, WorkspaceError> {
+        self.request("pgls/check_function", params)
+    }
+
+    fn function_callers(
+        &self,
+        params: crate::features::functions::FunctionCallersParams,
+    ) -> Result<crate::features::functions::FunctionCallersResult, WorkspaceError> {
+        self.request("pgls/function_callers", params)
+    }
+
     fn invalidate_schema_cache(&self, all: bool) -> Result<(), WorkspaceError> {
         self.request("pgls/invalidate_schema_cache", all)
     }
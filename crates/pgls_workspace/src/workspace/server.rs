@@ -4,27 +4,38 @@ use std::{
     panic::RefUnwindSafe,
     path::{Path, PathBuf},
     sync::{Arc, RwLock},
+    time::Duration,
 };
 
 use analyser::AnalyserVisitorBuilder;
 use async_helper::run_async;
+use completion_usage::CompletionUsageManager;
 use connection_manager::ConnectionManager;
+use connection_selection::ConnectionSelection;
 use document::{
     CursorPositionFilter, DefaultMapper, Document, ExecuteStatementMapper,
     TypecheckDiagnosticsMapper,
 };
 use futures::{StreamExt, stream};
 use pg_query::convert_to_positional_params;
-use pgls_analyse::{AnalyserOptions, AnalysisFilter};
+use pgls_analyse::{AnalyserOptions, AnalysisFilter, RuleCategories};
 use pgls_analyser::{Analyser, AnalyserConfig, AnalyserParams};
+use pgls_completions::{CompletionItemKind, CompletionResolveParams};
+use pgls_configuration::{ConfigurationDiagnostic, RuleSelector};
 use pgls_diagnostics::{
     Diagnostic, DiagnosticExt, Error, Severity, serde::Diagnostic as SDiagnostic,
 };
 use pgls_fs::{ConfigName, PgLSPath};
-use pgls_typecheck::{IdentifierType, TypecheckParams, TypedIdentifier};
+use pgls_text_size::TextRange;
+use pgls_typecheck::{
+    DescribeParams, ExplainParams, IdentifierType, TypecheckParams, TypedIdentifier,
+};
 use pgls_workspace_macros::ignored_path;
 use schema_cache_manager::SchemaCacheManager;
-use sqlx::{Executor, PgPool};
+use sqlx::PgPool;
+use statement_execution::{
+    ExecutionSettings, PendingConfirmations, RunningExecutions, ScriptStatement,
+};
 use tracing::{debug, info};
 
 use crate::{
@@ -32,14 +43,37 @@ use crate::{
     configuration::to_analyser_rules,
     features::{
         code_actions::{
-            CodeAction, CodeActionKind, CodeActionsParams, CodeActionsResult, CommandAction,
+            CancelStatementExecutionParams, CancelStatementExecutionResult, CodeAction,
+            CodeActionKind, CodeActionsParams, CodeActionsResult, CommandAction,
             CommandActionCategory, ExecuteStatementParams, ExecuteStatementResult,
+            ExecuteStatementsParams, ExecuteStatementsResult, SelectConnectionParams,
+            StatementExecutionError, StatementExecutionProgress, StatementExecutionProgressParams,
+        },
+        code_lenses::{CodeLens, CodeLensesParams, CodeLensesResult, StatementCodeLenses},
+        completions::{
+            CompletionsResult, GetCompletionsParams, RecordCompletionUsageParams,
+            ResolveCompletionItemParams, ResolveCompletionItemResult,
+            get_statement_for_completions, schemas_on_search_path,
+        },
+        describe::{
+            DescribeStatementParams, DescribeStatementResult, DescriptionSource, ResultColumn,
+            StatementDescription, StatementSelector,
+        },
+        diagnostics::{
+            PullContentDiagnosticsParams, PullDiagnosticsResult, PullFileDiagnosticsParams,
+        },
+        explain::{ExplainStatementParams, ExplainStatementResult, StatementExplanation},
+        folding_ranges::{FoldingRangesParams, FoldingRangesResult},
+        functions::{
+            CheckFunctionParams, CheckFunctionResult, FunctionCallersParams, FunctionCallersResult,
         },
-        completions::{CompletionsResult, GetCompletionsParams, get_statement_for_completions},
-        diagnostics::{PullDiagnosticsResult, PullFileDiagnosticsParams},
         on_hover::{OnHoverParams, OnHoverResult},
+        selection_ranges::{SelectionRangesParams, SelectionRangesResult},
+    },
+    settings::{
+        DatabaseSettings, Settings, WorkspaceSettings, WorkspaceSettingsHandle,
+        WorkspaceSettingsHandleMut,
     },
-    settings::{WorkspaceSettings, WorkspaceSettingsHandle, WorkspaceSettingsHandleMut},
     workspace::{AnalyserDiagnosticsMapper, WithCSTandASTMapper},
 };
 
@@ -54,13 +88,20 @@ pub use statement_identifier::StatementId;
 mod analyser;
 mod annotation;
 mod async_helper;
+mod completion_usage;
 mod connection_key;
 mod connection_manager;
+mod connection_selection;
 pub(crate) mod document;
+mod folding_ranges;
+mod function_callers;
 mod migration;
 mod pg_query;
 mod schema_cache_manager;
+mod selection_ranges;
 mod sql_function;
+mod ssh_tunnel;
+mod statement_execution;
 mod statement_identifier;
 mod tree_sitter;
 
@@ -74,6 +115,18 @@ pub(super) struct WorkspaceServer {
     documents: RwLock<HashMap<PgLSPath, Document>>,
 
     connection: ConnectionManager,
+
+    /// Tracks accepted completions per project
+    completion_usage: CompletionUsageManager,
+
+    /// The statements that are currently being executed
+    running_executions: Arc<RunningExecutions>,
+
+    /// The confirmations issued for executing statements that destroy data
+    pending_confirmations: PendingConfirmations,
+
+    /// The database connections open files select
+    connection_selection: ConnectionSelection,
 }
 
 /// The `Workspace` object is long-lived, so we want it to be able to cross
@@ -96,6 +149,10 @@ impl WorkspaceServer {
             documents: RwLock::new(HashMap::new()),
             schema_cache: SchemaCacheManager::new(),
             connection: ConnectionManager::new(),
+            completion_usage: CompletionUsageManager::new(pgls_fs::ensure_cache_dir()),
+            running_executions: Arc::default(),
+            pending_confirmations: PendingConfirmations::default(),
+            connection_selection: ConnectionSelection::default(),
         }
     }
 
@@ -108,10 +165,108 @@ impl WorkspaceServer {
         WorkspaceSettingsHandleMut::new(&self.settings)
     }
 
-    fn get_current_connection(&self) -> Option<PgPool> {
+    /// Returns the pool of the database connection the file uses, or an error if the
+    /// connection can't be set up.
+    fn get_connection(&self, path: &PgLSPath) -> Result<Option<PgPool>, WorkspaceError> {
         let settings = self.workspaces();
-        let settings = settings.settings()?;
-        self.connection.get_pool(&settings.db)
+        let Some(db) = settings
+            .settings()
+            .and_then(|settings| self.connection_selection.db_settings(settings, path))
+        else {
+            return Ok(None);
+        };
+        self.connection.get_pool(db)
+    }
+
+    /// Like [WorkspaceServer::get_connection], for features that work without a database. A
+    /// connection that can't be set up is logged and treated as not configured.
+    fn optional_connection(&self, path: &PgLSPath) -> Option<PgPool> {
+        self.get_connection(path).unwrap_or_else(|err| {
+            tracing::warn!("Continuing without a database connection: {err}");
+            None
+        })
+    }
+
+    /// Describes the rows the statement returns with Postgres. If Postgres can't describe the
+    /// statement, e.g. because it refers to a table created earlier in the file, the types are
+    /// inferred from the schema cache instead.
+    fn describe(
+        &self,
+        path: &PgLSPath,
+        id: &StatementId,
+        range: TextRange,
+        ast: pgls_query::NodeEnum,
+    ) -> Result<Option<StatementDescription>, WorkspaceError> {
+        let Some(pool) = self.get_connection(path)? else {
+            return Ok(None);
+        };
+
+        let schema_cache = self.schema_cache.load(pool.clone())?;
+        let search_path_patterns = self
+            .workspaces()
+            .settings()
+            .map(|s| s.typecheck.search_path.clone())
+            .unwrap_or_default();
+
+        let sql = convert_to_positional_params(id.content());
+        let described = {
+            let schema_cache = Arc::clone(&schema_cache);
+            let ast = ast.clone();
+            run_async(async move {
+                pgls_typecheck::describe_sql(DescribeParams {
+                    conn: &pool,
+                    sql: &sql,
+                    ast: &ast,
+                    schema_cache: schema_cache.as_ref(),
+                    search_path_patterns,
+                })
+                .await
+            })?
+        };
+
+        match described {
+            Ok(columns) => {
+                return Ok(columns.map(|columns| StatementDescription {
+                    range,
+                    columns: columns
+                        .into_iter()
+                        .map(|c| ResultColumn {
+                            name: c.name,
+                            type_name: Some(c.type_name),
+                            nullable: c.nullable,
+                        })
+                        .collect(),
+                    source: DescriptionSource::Database,
+                }));
+            }
+            Err(err) => debug!("Failed to describe statement, inferring its columns: {err}"),
+        }
+
+        let columns = pgls_type_resolver::TypeInferrer::new(&ast, &schema_cache).output_columns();
+
+        Ok(columns.map(|columns| StatementDescription {
+            range,
+            columns: columns
+                .into_iter()
+                .map(|c| ResultColumn {
+                    name: c.name,
+                    type_name: c.type_.map(|t| t.format.clone()),
+                    nullable: c.nullable,
+                })
+                .collect(),
+            source: DescriptionSource::SchemaCache,
+        }))
+    }
+
+    /// Returns the usage model for completions of the current project, if enabled
+    fn get_completion_usage(&self) -> Option<Arc<pgls_completions::CompletionUsage>> {
+        let settings = self.workspaces();
+        if !settings.settings()?.completions.learn_from_usage {
+            return None;
+        }
+
+        let project = self.get_current_project_path()?;
+        Some(self.completion_usage.get(&project))
     }
 
     /// Register a new project in the current workspace
@@ -276,6 +431,9 @@ impl Workspace for WorkspaceServer {
     #[ignored_path(path=&params.path)]
     #[tracing::instrument(level = "info", skip_all, fields(path = params.path.as_path().as_os_str().to_str()), err)]
     fn open_file(&self, params: OpenFileParams) -> Result<(), WorkspaceError> {
+        self.connection_selection
+            .update_content(&params.path, &params.content);
+
         let mut documents = self.documents.write().unwrap();
         documents
             .entry(params.path.clone())
@@ -296,6 +454,8 @@ impl Workspace for WorkspaceServer {
             .remove(&params.path)
             .ok_or_else(WorkspaceError::not_found)?;
 
+        self.connection_selection.remove(&params.path);
+
         Ok(())
     }
 
@@ -306,6 +466,9 @@ impl Workspace for WorkspaceServer {
     ), err)]
     #[ignored_path(path=&params.path)]
     fn change_file(&self, params: super::ChangeFileParams) -> Result<(), WorkspaceError> {
+        self.connection_selection
+            .update_content(&params.path, &params.content);
+
         let mut documents = self.documents.write().unwrap();
 
         match documents.entry(params.path.clone()) {
@@ -352,8 +515,13 @@ impl Workspace for WorkspaceServer {
         let settings = self.workspaces();
         let settings = settings.settings();
 
-        let disabled_reason = match settings {
-            Some(settings) if settings.db.allow_statement_executions => None,
+        let db = settings.and_then(|settings| {
+            self.connection_selection
+                .db_settings(settings, &params.path)
+        });
+
+        let disabled_reason = match db {
+            Some(db) if db.allow_statement_executions => None,
             Some(_) => Some("Statement execution is disabled in the settings.".into()),
             None => Some("Statement execution not allowed against database.".into()),
         };
@@ -363,36 +531,149 @@ impl Workspace for WorkspaceServer {
                 DefaultMapper,
                 CursorPositionFilter::new(params.cursor_position),
             )
-            .map(|(stmt, _, txt)| {
+            .flat_map(|(stmt, _, txt)| {
                 let title = format!(
                     "Execute Statement: {}...",
                     txt.chars().take(50).collect::<String>()
                 );
 
-                CodeAction {
-                    title,
-                    kind: CodeActionKind::Command(CommandAction {
-                        category: CommandActionCategory::ExecuteStatement(stmt),
-                    }),
-                    disabled_reason: disabled_reason.clone(),
-                }
+                [
+                    CodeAction {
+                        title,
+                        kind: CodeActionKind::Command(CommandAction {
+                            category: CommandActionCategory::ExecuteStatement(stmt.clone()),
+                        }),
+                        disabled_reason: disabled_reason.clone(),
+                    },
+                    CodeAction {
+                        title: "Dry Run Statement".into(),
+                        kind: CodeActionKind::Command(CommandAction {
+                            category: CommandActionCategory::DryRunStatement(stmt),
+                        }),
+                        disabled_reason: disabled_reason.clone(),
+                    },
+                ]
             })
             .collect();
 
-        let invalidate_disabled_reason = if self.get_current_connection().is_some() {
-            None
-        } else {
-            Some("No database connection available.".into())
+        let has_statements = parser.iter(DefaultMapper).next().is_some();
+        if has_statements {
+            actions.push(CodeAction {
+                title: "Execute File".into(),
+                kind: CodeActionKind::Command(CommandAction {
+                    category: CommandActionCategory::ExecuteFile,
+                }),
+                disabled_reason: disabled_reason.clone(),
+            });
+        }
+
+        if let Some(selection) = params.selection.filter(|selection| !selection.is_empty()) {
+            actions.push(CodeAction {
+                title: "Execute Selection".into(),
+                kind: CodeActionKind::Command(CommandAction {
+                    category: CommandActionCategory::ExecuteRange(selection),
+                }),
+                disabled_reason: disabled_reason.clone(),
+            });
+        }
+
+        if self.running_executions.is_running(&params.path) {
+            actions.push(CodeAction {
+                title: "Cancel Running Statement".into(),
+                kind: CodeActionKind::Command(CommandAction {
+                    category: CommandActionCategory::CancelStatementExecution,
+                }),
+                disabled_reason: None,
+            });
+        }
+
+        let no_connection_reason = match self.get_connection(&params.path) {
+            Ok(Some(_)) => None,
+            Ok(None) => Some("No database connection available.".to_string()),
+            Err(err) => Some(err.to_string()),
         };
 
+        let explainable_statements: Vec<StatementId> = parser
+            .iter_with_filter(
+                WithCSTandASTMapper,
+                CursorPositionFilter::new(params.cursor_position),
+            )
+            .filter(|(id, _, _, ast)| {
+                id.is_root()
+                    && ast
+                        .as_ref()
+                        .is_some_and(pgls_typecheck::explain::is_explainable)
+            })
+            .map(|(id, ..)| id)
+            .collect();
+
+        // `EXPLAIN ANALYZE` executes the statement
+        let analyze_disabled_reason = no_connection_reason
+            .clone()
+            .or_else(|| disabled_reason.clone());
+
+        for id in explainable_statements {
+            actions.push(CodeAction {
+                title: "Describe Result Columns".into(),
+                kind: CodeActionKind::Command(CommandAction {
+                    category: CommandActionCategory::DescribeStatement(id.clone()),
+                }),
+                disabled_reason: no_connection_reason.clone(),
+            });
+            actions.push(CodeAction {
+                title: "Explain Statement".into(),
+                kind: CodeActionKind::Command(CommandAction {
+                    category: CommandActionCategory::ExplainStatement(id.clone()),
+                }),
+                disabled_reason: no_connection_reason.clone(),
+            });
+            actions.push(CodeAction {
+                title: "Explain Analyze Statement".into(),
+                kind: CodeActionKind::Command(CommandAction {
+                    category: CommandActionCategory::ExplainAnalyzeStatement(id),
+                }),
+                disabled_reason: analyze_disabled_reason.clone(),
+            });
+        }
+
         actions.push(CodeAction {
             title: "Invalidate Schema Cache".into(),
             kind: CodeActionKind::Command(CommandAction {
                 category: CommandActionCategory::InvalidateSchemaCache,
             }),
-            disabled_reason: invalidate_disabled_reason,
+            disabled_reason: no_connection_reason,
         });
 
+        if let Some(settings) = settings {
+            let active = self
+                .connection_selection
+                .connection_name(settings, &params.path);
+
+            if active.is_some() {
+                actions.push(CodeAction {
+                    title: "Use Default Database Connection".into(),
+                    kind: CodeActionKind::Command(CommandAction {
+                        category: CommandActionCategory::SelectConnection(None),
+                    }),
+                    disabled_reason: None,
+                });
+            }
+
+            for name in settings.db.connections.keys() {
+                if active.as_ref() == Some(name) {
+                    continue;
+                }
+
+                actions.push(CodeAction {
+                    title: format!("Use Database Connection: {name}"),
+                    kind: CodeActionKind::Command(CommandAction {
+                        category: CommandActionCategory::SelectConnection(Some(name.clone())),
+                    }),
+                    disabled_reason: None,
+                });
+            }
+        }
+
         Ok(CodeActionsResult { actions })
     }
 
@@ -401,49 +682,269 @@ impl Workspace for WorkspaceServer {
         &self,
         params: ExecuteStatementParams,
     ) -> Result<ExecuteStatementResult, WorkspaceError> {
-        let documents = self.documents.read().unwrap();
-        let parser = documents
-            .get(&params.path)
-            .ok_or(WorkspaceError::not_found())?;
+        let (id, range, ast) = {
+            let documents = self.documents.read().unwrap();
+            let parser = documents
+                .get(&params.path)
+                .ok_or(WorkspaceError::not_found())?;
+
+            let Some((id, range, _, ast)) = select_statement(parser, params.statement) else {
+                return Ok(ExecuteStatementResult {
+                    message: "Statement was not found in document.".into(),
+                    ..Default::default()
+                });
+            };
 
-        let stmt = parser.find(params.statement_id, ExecuteStatementMapper);
+            (id, range, ast)
+        };
 
-        if stmt.is_none() {
+        let Some(ast) = ast else {
             return Ok(ExecuteStatementResult {
-                message: "Statement was not found in document.".into(),
+                message: "Statement is invalid.".into(),
+                ..Default::default()
             });
         };
 
-        let (_id, _range, content, ast) = stmt.unwrap();
+        let execution_settings = {
+            let settings = self.workspaces();
+            match settings.settings().and_then(|settings| {
+                self.connection_selection
+                    .db_settings(settings, &params.path)
+            }) {
+                Some(db) if db.allow_statement_executions => {
+                    ExecutionSettings::new(db, params.options.dry_run)
+                }
+                _ => {
+                    return Ok(ExecuteStatementResult {
+                        message: "Statement execution is not allowed against this database.".into(),
+                        ..Default::default()
+                    });
+                }
+            }
+        };
 
-        if ast.is_none() {
+        let content = id.content().to_string();
+
+        if let Some(reason) = statement_execution::confirmation_reason(&ast, range) {
+            let confirmed = params.options.confirmation.is_some_and(|token| {
+                self.pending_confirmations
+                    .confirm(token, &params.path, &content)
+            });
+            if !confirmed {
+                return Ok(ExecuteStatementResult {
+                    message: "The statement was not executed, it needs to be confirmed.".into(),
+                    confirmation_required: Some(self.pending_confirmations.request(
+                        &params.path,
+                        content,
+                        reason,
+                    )),
+                    ..Default::default()
+                });
+            }
+        }
+
+        let Some(pool) = self.get_connection(&params.path)? else {
             return Ok(ExecuteStatementResult {
-                message: "Statement is invalid.".into(),
+                message: "No database connection available.".into(),
+                ..Default::default()
             });
         };
 
-        let Some(pool) = self.get_current_connection() else {
-            return Ok(ExecuteStatementResult {
+        let cursor_query = statement_execution::is_cursor_query(&ast);
+        let running = Arc::clone(&self.running_executions);
+        let result = run_async(async move {
+            statement_execution::execute_statement(
+                &pool,
+                &content,
+                cursor_query,
+                execution_settings,
+                running,
+                params.path,
+            )
+            .await
+        })??;
+
+        Ok(result)
+    }
+
+    #[ignored_path(path=&params.path)]
+    fn execute_statements(
+        &self,
+        params: ExecuteStatementsParams,
+    ) -> Result<ExecuteStatementsResult, WorkspaceError> {
+        let statements: Vec<_> = {
+            let documents = self.documents.read().unwrap();
+            let parser = documents
+                .get(&params.path)
+                .ok_or(WorkspaceError::not_found())?;
+
+            parser
+                .iter(ExecuteStatementMapper)
+                .filter(|(id, range, ..)| {
+                    id.is_root()
+                        && params.range.is_none_or(|selection| {
+                            selection
+                                .intersect(*range)
+                                .is_some_and(|overlap| !overlap.is_empty())
+                        })
+                })
+                .map(|(_, range, sql, ast)| (range, sql, ast))
+                .collect()
+        };
+
+        let total = statements.len();
+        if total == 0 {
+            return Ok(ExecuteStatementsResult {
+                message: "No statements to execute.".into(),
+                ..Default::default()
+            });
+        }
+
+        // nothing is executed unless all statements can be
+        let mut asts = Vec::with_capacity(total);
+        for (range, _, ast) in &statements {
+            let Some(ast) = ast else {
+                return Ok(failed_before_execution(
+                    total,
+                    StatementExecutionError {
+                        range: *range,
+                        message: "Statement is invalid.".into(),
+                    },
+                ));
+            };
+            asts.push((*range, ast));
+        }
+
+        if let Err(error) = statement_execution::validate_script(asts.iter().copied()) {
+            return Ok(failed_before_execution(total, error));
+        }
+
+        let execution_settings = {
+            let settings = self.workspaces();
+            match settings.settings().and_then(|settings| {
+                self.connection_selection
+                    .db_settings(settings, &params.path)
+            }) {
+                Some(db) if db.allow_statement_executions => ExecutionSettings::new(db, false),
+                _ => {
+                    return Ok(ExecuteStatementsResult {
+                        message: "Statement execution is not allowed against this database.".into(),
+                        total,
+                        ..Default::default()
+                    });
+                }
+            }
+        };
+
+        let reasons: Vec<String> = asts
+            .iter()
+            .filter_map(|(range, ast)| statement_execution::confirmation_reason(ast, *range))
+            .collect();
+        if !reasons.is_empty() {
+            let script: String = statements.iter().map(|(_, sql, _)| sql.as_str()).collect();
+            let confirmed = params.confirmation.is_some_and(|token| {
+                self.pending_confirmations
+                    .confirm(token, &params.path, &script)
+            });
+            if !confirmed {
+                return Ok(ExecuteStatementsResult {
+                    message: "The statements were not executed, they need to be confirmed.".into(),
+                    total,
+                    confirmation_required: Some(self.pending_confirmations.request(
+                        &params.path,
+                        script,
+                        reasons.join(" "),
+                    )),
+                    ..Default::default()
+                });
+            }
+        }
+
+        let Some(pool) = self.get_connection(&params.path)? else {
+            return Ok(ExecuteStatementsResult {
                 message: "No database connection available.".into(),
+                total,
+                ..Default::default()
             });
         };
 
-        let result = run_async(async move { pool.execute(sqlx::query(&content)).await })??;
+        let statements: Vec<ScriptStatement> = statements
+            .into_iter()
+            .map(|(range, sql, _)| ScriptStatement { range, sql })
+            .collect();
+        let running = Arc::clone(&self.running_executions);
+        let result = run_async(async move {
+            statement_execution::execute_script(
+                &pool,
+                &statements,
+                execution_settings,
+                running,
+                params.path,
+            )
+            .await
+        })??;
 
-        Ok(ExecuteStatementResult {
-            message: format!(
-                "Successfully executed statement. Rows affected: {}",
-                result.rows_affected()
+        Ok(result)
+    }
+
+    fn statement_execution_progress(
+        &self,
+        params: StatementExecutionProgressParams,
+    ) -> Result<Option<StatementExecutionProgress>, WorkspaceError> {
+        Ok(match params.known_executed {
+            Some(known_executed) => self.running_executions.wait_for_progress(
+                &params.path,
+                known_executed,
+                Duration::from_secs(1),
             ),
+            None => self.running_executions.progress(&params.path),
         })
     }
 
+    fn cancel_statement_execution(
+        &self,
+        params: CancelStatementExecutionParams,
+    ) -> Result<CancelStatementExecutionResult, WorkspaceError> {
+        let backends = self.running_executions.backends(&params.path);
+        if backends.is_empty() {
+            return Ok(CancelStatementExecutionResult::default());
+        }
+
+        let cancelled = run_async(statement_execution::cancel(backends))??;
+
+        Ok(CancelStatementExecutionResult { cancelled })
+    }
+
+    fn select_connection(&self, params: SelectConnectionParams) -> Result<(), WorkspaceError> {
+        if let Some(name) = &params.connection {
+            let settings = self.workspaces();
+            let known = settings
+                .settings()
+                .is_some_and(|settings| settings.db.connections.contains_key(name));
+            if !known {
+                return Err(ConfigurationDiagnostic::invalid_configuration(format!(
+                    "Unknown database connection \"{name}\". Add it to db.connections."
+                ))
+                .into());
+            }
+        }
+
+        self.connection_selection
+            .select(&params.path, params.connection);
+
+        Ok(())
+    }
+
     fn invalidate_schema_cache(&self, all: bool) -> Result<(), WorkspaceError> {
         if all {
             self.schema_cache.clear_all();
         } else {
-            // Only clear current connection if one exists
-            if let Some(pool) = self.get_current_connection() {
+            // Only clear the default connection if one exists
+            let settings = self.workspaces();
+            if let Some(pool) = settings
+                .settings()
+                .and_then(|settings| self.connection.get_pool(&settings.db).ok().flatten())
+            {
                 self.schema_cache.clear(&pool);
             }
             // If no connection, nothing to clear - just return Ok
@@ -463,10 +964,7 @@ impl Workspace for WorkspaceServer {
             None => {
                 // return an empty result if no settings are available
                 // we might want to return an error here in the future
-                return Ok(PullDiagnosticsResult {
-                    diagnostics: Vec::new(),
-                    skipped_diagnostics: 0,
-                });
+                return Ok(PullDiagnosticsResult::default());
             }
         };
 
@@ -481,22 +979,55 @@ impl Workspace for WorkspaceServer {
          */
         let mut diagnostics: Vec<SDiagnostic> = doc.document_diagnostics().to_vec();
 
+        // a connection that can't be set up, e.g. because its SSH tunnel fails, is reported
+        // with the file instead of failing the whole pull
+        let pool = match self.get_connection(&params.path) {
+            Ok(pool) => pool,
+            Err(err) => {
+                diagnostics.push(SDiagnostic::new(
+                    Error::from(err).with_file_path(params.path.as_path().display().to_string()),
+                ));
+                None
+            }
+        };
+
         /*
          * Type-checking against database connection
          */
         let typecheck_enabled = settings.typecheck.enabled;
         let plpgsql_check_enabled = settings.plpgsql_check.enabled;
         if typecheck_enabled || plpgsql_check_enabled {
-            if let Some(pool) = self.get_current_connection() {
+            if let Some(pool) = pool.clone() {
                 let path_clone = params.path.clone();
                 let schema_cache = self.schema_cache.load(pool.clone())?;
-                let input = doc.iter(TypecheckDiagnosticsMapper).collect::<Vec<_>>();
                 let search_path_patterns = settings.typecheck.search_path.clone();
 
+                // DDL is only executed, even within a rolled back transaction, if allowed
+                let execute_ddl = self
+                    .connection_selection
+                    .db_settings(settings, &params.path)
+                    .is_some_and(|db| db.allow_statement_executions);
+                // each statement is checked against the schema left behind by the DDL before it
+                let replay_prior_ddl = settings.typecheck.replay_prior_ddl && execute_ddl;
+                let mut ddl = vec![];
+                let input = doc
+                    .iter(TypecheckDiagnosticsMapper)
+                    .map(|(id, range, ast, cst, fn_sig)| {
+                        let prior_ddl = ddl.clone();
+                        if replay_prior_ddl
+                            && id.is_root()
+                            && ast.as_ref().is_some_and(pgls_typecheck::is_replayable_ddl)
+                        {
+                            ddl.push(id.content().to_string());
+                        }
+                        (id, range, ast, cst, fn_sig, prior_ddl)
+                    })
+                    .collect::<Vec<_>>();
+
                 // Combined async context for both typecheck and plpgsql_check
                 let async_results = run_async(async move {
                     stream::iter(input)
-                        .map(|(id, range, ast, cst, fn_sig)| {
+                        .map(|(id, range, ast, cst, fn_sig, prior_ddl)| {
                             let pool = pool.clone();
                             let path = path_clone.clone();
                             let schema_cache = Arc::clone(&schema_cache);
@@ -517,6 +1048,8 @@ impl Workspace for WorkspaceServer {
                                                 tree: &cst,
                                                 schema_cache: schema_cache.as_ref(),
                                                 search_path_patterns,
+                                                prior_ddl,
+                                                execute_ddl,
                                                 identifiers: fn_sig
                                                     .map(|s| {
                                                         s.args
@@ -593,114 +1126,45 @@ impl Workspace for WorkspaceServer {
             }
         }
 
-        /*
-         * Below, we'll apply our static linting rules against the statements,
-         * considering the user's settings
-         */
-        let (enabled_rules, disabled_rules) = AnalyserVisitorBuilder::new(settings)
-            .with_linter_rules(&params.only, &params.skip)
-            .finish();
-
-        let options = AnalyserOptions {
-            rules: to_analyser_rules(settings),
-        };
-
-        let filter = AnalysisFilter {
-            categories: params.categories,
-            enabled_rules: Some(enabled_rules.as_slice()),
-            disabled_rules: &disabled_rules,
-        };
-
-        let analyser = Analyser::new(AnalyserConfig {
-            options: &options,
-            filter,
-        });
-
-        let path = params.path.as_path().display().to_string();
-
-        let schema_cache = self
-            .get_current_connection()
-            .and_then(|pool| self.schema_cache.load(pool.clone()).ok());
-
-        let mut analysable_stmts = vec![];
-        for (stmt_root, diagnostic) in doc.iter(AnalyserDiagnosticsMapper) {
-            if let Some(node) = stmt_root {
-                analysable_stmts.push(node);
-            }
-            if let Some(diag) = diagnostic {
-                // ignore the syntax error if we already have more specialized diagnostics for the
-                // same statement.
-                // this is important for create function statements, where we might already have detailed
-                // diagnostics from plpgsql_check.
-                if diagnostics.iter().any(|d| {
-                    d.location().span.is_some_and(|async_loc| {
-                        diag.location()
-                            .span
-                            .is_some_and(|syntax_loc| syntax_loc.contains_range(async_loc))
-                    })
-                }) {
-                    continue;
-                }
-
-                diagnostics.push(SDiagnostic::new(
-                    diag.with_file_path(path.clone())
-                        .with_severity(Severity::Error),
-                ));
-            }
-        }
-
-        diagnostics.extend(
-            analyser
-                .run(AnalyserParams {
-                    stmts: analysable_stmts,
-                    schema_cache: schema_cache.as_deref(),
-                })
-                .into_iter()
-                .map(Error::from)
-                .map(|d| {
-                    let severity = d
-                        .category()
-                        .map(|category| {
-                            settings
-                                .get_severity_from_rule_code(category)
-                                .unwrap_or(Severity::Warning)
-                        })
-                        .unwrap();
-
-                    let span = d.location().span;
-                    SDiagnostic::new(
-                        d.with_file_path(path.clone())
-                            .with_file_span(span)
-                            .with_severity(severity),
-                    )
-                }),
-        );
+        let schema_cache = pool.and_then(|pool| self.schema_cache.load(pool).ok());
 
-        let suppressions = doc.suppressions();
-
-        let disabled_suppression_errors =
-            suppressions.get_disabled_diagnostic_suppressions_as_errors(&disabled_rules);
+        Ok(analyse_document(
+            settings,
+            doc,
+            &params.path,
+            diagnostics,
+            schema_cache.as_deref(),
+            params.categories,
+            &params.only,
+            &params.skip,
+        ))
+    }
 
-        let unused_suppression_errors =
-            suppressions.get_unused_suppressions_as_errors(&diagnostics);
+    #[ignored_path(path=&params.path)]
+    fn pull_content_diagnostics(
+        &self,
+        params: PullContentDiagnosticsParams,
+    ) -> Result<PullDiagnosticsResult, WorkspaceError> {
+        let settings = self.workspaces();
 
-        let suppression_errors: Vec<Error> = suppressions
-            .diagnostics
-            .iter()
-            .chain(disabled_suppression_errors.iter())
-            .chain(unused_suppression_errors.iter())
-            .cloned()
-            .map(Error::from)
-            .collect::<Vec<pgls_diagnostics::Error>>();
+        let Some(settings) = settings.settings() else {
+            return Ok(PullDiagnosticsResult::default());
+        };
 
-        diagnostics.retain(|d| !suppressions.is_suppressed(d));
-        diagnostics.extend(suppression_errors.into_iter().map(SDiagnostic::new));
+        // the document is only parsed for this check, so the file stays unknown to the workspace
+        let doc = Document::new(params.content, 0);
+        let diagnostics = doc.document_diagnostics().to_vec();
 
-        info!("Pulled {:?} diagnostic(s)", diagnostics.len());
-        Ok(PullDiagnosticsResult {
+        Ok(analyse_document(
+            settings,
+            &doc,
+            &params.path,
             diagnostics,
-            skipped_diagnostics: 0,
-        })
+            None,
+            params.categories,
+            &[],
+            &[],
+        ))
     }
 
     fn pull_db_diagnostics(
@@ -719,16 +1183,24 @@ impl Workspace for WorkspaceServer {
         &self,
         params: GetCompletionsParams,
     ) -> Result<CompletionsResult, WorkspaceError> {
-        let documents = self.documents.read().unwrap();
-        let parsed_doc = documents
-            .get(&params.path)
-            .ok_or(WorkspaceError::not_found())?;
+        let usage = self.get_completion_usage();
+
+        let settings = self.workspaces();
+        let search_path_patterns = settings
+            .settings()
+            .map(|s| s.typecheck.search_path.clone())
+            .unwrap_or_else(|| vec!["public".to_string()]);
 
-        let Some(pool) = self.get_current_connection() else {
+        let Some(pool) = self.optional_connection(&params.path) else {
             tracing::debug!("No database connection available. Skipping completions.");
             return Ok(CompletionsResult::default());
         };
 
+        let documents = self.documents.read().unwrap();
+        let parsed_doc = documents
+            .get(&params.path)
+            .ok_or(WorkspaceError::not_found())?;
+
         let schema_cache = self.schema_cache.load(pool.clone())?;
 
         match get_statement_for_completions(parsed_doc, params.position) {
@@ -738,14 +1210,22 @@ impl Workspace for WorkspaceServer {
             }
             Some((id, range, cst)) => {
                 let position = params.position - range.start();
+                let search_path = schemas_on_search_path(&schema_cache, &search_path_patterns);
 
-                let items = pgls_completions::complete(pgls_completions::CompletionParams {
+                let mut items = pgls_completions::complete(pgls_completions::CompletionParams {
                     position,
                     schema: schema_cache.as_ref(),
                     tree: &cst,
                     text: id.content().to_string(),
+                    usage: usage.as_deref(),
+                    search_path: Some(&search_path),
                 });
 
+                // completion ranges are relative to the statement, but clients expect them relative to the file
+                for text in items.iter_mut().filter_map(|i| i.completion_text.as_mut()) {
+                    text.range += range.start();
+                }
+
                 Ok(CompletionsResult { items })
             }
         }
@@ -756,17 +1236,127 @@ impl Workspace for WorkspaceServer {
         path = params.path.as_os_str().to_str(),
         position = params.position.to_string()
     ), err)]
-    fn on_hover(&self, params: OnHoverParams) -> Result<OnHoverResult, WorkspaceError> {
+    fn resolve_completion_item(
+        &self,
+        params: ResolveCompletionItemParams,
+    ) -> Result<ResolveCompletionItemResult, WorkspaceError> {
+        let settings = self.workspaces();
+        let search_path_patterns = settings
+            .settings()
+            .map(|s| s.typecheck.search_path.clone())
+            .unwrap_or_else(|| vec!["public".to_string()]);
+
+        let Some(pool) = self.optional_connection(&params.path) else {
+            tracing::debug!("No database connection available. Skipping completion resolve.");
+            return Ok(ResolveCompletionItemResult::default());
+        };
+
         let documents = self.documents.read().unwrap();
-        let doc = documents
+        let parsed_doc = documents
             .get(&params.path)
             .ok_or(WorkspaceError::not_found())?;
 
-        let Some(pool) = self.get_current_connection() else {
-            tracing::debug!("No database connection available. Skipping completions.");
+        let schema_cache = self.schema_cache.load(pool.clone())?;
+
+        let reference = &params.reference;
+        let schema = reference.schema.as_deref();
+        let hoverable: Option<pgls_hover::Hoverable> = match reference.kind {
+            CompletionItemKind::Table => schema_cache
+                .find_tables(&reference.name, schema)
+                .into_iter()
+                .next()
+                .map(Into::into),
+            CompletionItemKind::Column => schema_cache
+                .find_cols(&reference.name, reference.table.as_deref(), schema)
+                .into_iter()
+                .next()
+                .map(Into::into),
+            CompletionItemKind::Function => schema_cache
+                .find_functions(&reference.name, schema)
+                .into_iter()
+                .next()
+                .map(Into::into),
+            CompletionItemKind::Schema => schema_cache.find_schema(&reference.name).map(Into::into),
+            CompletionItemKind::Role => schema_cache
+                .find_roles(&reference.name)
+                .into_iter()
+                .next()
+                .map(Into::into),
+            CompletionItemKind::Policy | CompletionItemKind::JoinCondition => None,
+        };
+
+        let documentation =
+            hoverable.and_then(|item| pgls_hover::to_markdown(&item, schema_cache.as_ref()));
+
+        let additional_edits = match get_statement_for_completions(parsed_doc, params.position) {
+            None => vec![],
+            Some((id, range, cst)) => {
+                let search_path = schemas_on_search_path(&schema_cache, &search_path_patterns);
+
+                let mut edits =
+                    pgls_completions::resolve_additional_edits(CompletionResolveParams {
+                        position: params.position - range.start(),
+                        schema: schema_cache.as_ref(),
+                        text: id.content().to_string(),
+                        tree: &cst,
+                        reference,
+                        search_path: &search_path,
+                    });
+
+                for edit in edits.iter_mut() {
+                    edit.range += range.start();
+                }
+
+                edits
+            }
+        };
+
+        Ok(ResolveCompletionItemResult {
+            documentation,
+            additional_edits,
+        })
+    }
+
+    #[ignored_path(path=&params.path)]
+    #[tracing::instrument(level = "debug", skip_all, fields(
+        path = params.path.as_os_str().to_str(),
+    ), err)]
+    fn record_completion_usage(
+        &self,
+        params: RecordCompletionUsageParams,
+    ) -> Result<(), WorkspaceError> {
+        let settings = self.workspaces();
+        let learn_from_usage = settings
+            .settings()
+            .is_some_and(|s| s.completions.learn_from_usage);
+
+        if !learn_from_usage {
+            return Ok(());
+        }
+
+        if let Some(project) = self.get_current_project_path() {
+            self.completion_usage.record(&project, &params.reference);
+        }
+
+        Ok(())
+    }
+
+    #[ignored_path(path=&params.path)]
+    #[tracing::instrument(level = "debug", skip_all, fields(
+        path = params.path.as_os_str().to_str(),
+        position = params.position.to_string()
+    ), err)]
+    fn on_hover(&self, params: OnHoverParams) -> Result<OnHoverResult, WorkspaceError> {
+        let Some(pool) = self.optional_connection(&params.path) else {
+            tracing::debug!("No database connection available. Skipping hover.");
             return Ok(OnHoverResult::default());
         };
 
+        let documents = self.documents.read().unwrap();
+        let doc = documents
+            .get(&params.path)
+            .ok_or(WorkspaceError::not_found())?;
+
         let schema_cache = self.schema_cache.load(pool.clone())?;
 
         match doc
@@ -779,7 +1369,7 @@ impl Workspace for WorkspaceServer {
             Some((stmt_id, range, ts_tree, maybe_ast)) => {
                 let position_in_stmt = params.position - range.start();
 
-                let markdown_blocks = pgls_hover::on_hover(pgls_hover::OnHoverParams {
+                let mut markdown_blocks = pgls_hover::on_hover(pgls_hover::OnHoverParams {
                     ts_tree: &ts_tree,
                     schema_cache: &schema_cache,
                     ast: maybe_ast.as_ref(),
@@ -787,11 +1377,582 @@ impl Workspace for WorkspaceServer {
                     stmt_sql: stmt_id.content(),
                 });
 
+                // hovering the leading keyword, e.g. `select`, shows the result columns
+                let leading_keyword_len = stmt_id
+                    .content()
+                    .find(|c: char| !c.is_ascii_alphabetic())
+                    .unwrap_or(stmt_id.content().len());
+
+                if usize::from(position_in_stmt) < leading_keyword_len {
+                    if let Some(ast) = maybe_ast {
+                        if let Some(description) = self
+                            .describe(&params.path, &stmt_id, range, ast)?
+                            .filter(|d| !d.columns.is_empty())
+                        {
+                            markdown_blocks.push(description.to_markdown());
+                        }
+                    }
+                }
+
                 Ok(OnHoverResult { markdown_blocks })
             }
             None => Ok(OnHoverResult::default()),
         }
     }
+
+    #[ignored_path(path=&params.path)]
+    #[tracing::instrument(level = "debug", skip_all, fields(
+        path = params.path.as_os_str().to_str(),
+    ), err)]
+    fn describe_statement(
+        &self,
+        params: DescribeStatementParams,
+    ) -> Result<DescribeStatementResult, WorkspaceError> {
+        let documents = self.documents.read().unwrap();
+        let doc = documents
+            .get(&params.path)
+            .ok_or(WorkspaceError::not_found())?;
+
+        let Some((id, range, _, Some(ast))) = select_statement(doc, params.statement) else {
+            return Ok(DescribeStatementResult::default());
+        };
+
+        Ok(DescribeStatementResult {
+            description: self.describe(&params.path, &id, range, ast)?,
+        })
+    }
+
+    #[ignored_path(path=&params.path)]
+    #[tracing::instrument(level = "debug", skip_all, fields(
+        path = params.path.as_os_str().to_str(),
+    ), err)]
+    fn explain_statement(
+        &self,
+        params: ExplainStatementParams,
+    ) -> Result<ExplainStatementResult, WorkspaceError> {
+        // the document must not stay locked during the round trip to the database
+        let (sql, range) = {
+            let documents = self.documents.read().unwrap();
+            let doc = documents
+                .get(&params.path)
+                .ok_or(WorkspaceError::not_found())?;
+
+            let Some((id, range, _, ast)) = select_statement(doc, params.statement) else {
+                return Ok(ExplainStatementResult::default());
+            };
+
+            if !ast
+                .as_ref()
+                .is_some_and(pgls_typecheck::explain::is_explainable)
+            {
+                return Ok(ExplainStatementResult {
+                    explanation: None,
+                    message: Some(
+                        "Only queries and data modifying statements can be explained.".into(),
+                    ),
+                });
+            }
+
+            (convert_to_positional_params(id.content()), range)
+        };
+
+        let Some(pool) = self.get_connection(&params.path)? else {
+            return Ok(ExplainStatementResult {
+                explanation: None,
+                message: Some("No database connection available.".into()),
+            });
+        };
+
+        let settings = self.workspaces();
+        let settings = settings.settings();
+
+        let db_settings = settings.and_then(|settings| {
+            self.connection_selection
+                .db_settings(settings, &params.path)
+        });
+        let analyze_allowed = db_settings.is_some_and(|db| db.allow_statement_executions);
+        let analyze = params.analyze && analyze_allowed;
+        let message = (params.analyze && !analyze_allowed).then(|| {
+            "Statement execution is not allowed against this database, showing the estimated plan instead.".to_string()
+        });
+        let execution_settings =
+            ExecutionSettings::new(db_settings.unwrap_or(&DatabaseSettings::default()), false);
+        let search_path_patterns = settings
+            .map(|s| s.typecheck.search_path.clone())
+            .unwrap_or_default();
+
+        let schema_cache = self.schema_cache.load(pool.clone())?;
+
+        let plan = {
+            let schema_cache = Arc::clone(&schema_cache);
+            run_async(async move {
+                pgls_typecheck::explain_sql(ExplainParams {
+                    conn: &pool,
+                    sql: &sql,
+                    schema_cache: schema_cache.as_ref(),
+                    search_path_patterns,
+                    analyze,
+                    statement_timeout: execution_settings.statement_timeout,
+                    lock_timeout: execution_settings.lock_timeout,
+                })
+                .await
+            })?
+        };
+
+        let plan = match plan {
+            Ok(plan) => plan,
+            Err(err) => {
+                return Ok(ExplainStatementResult {
+                    explanation: None,
+                    message: Some(format!("Failed to explain statement: {err}")),
+                });
+            }
+        };
+
+        let path = params.path.as_path().display().to_string();
+        let diagnostics = pgls_typecheck::analyse_plan(&plan, &schema_cache)
+            .into_iter()
+            .map(|d| SDiagnostic::new(d.with_file_path(path.clone()).with_file_span(range)))
+            .collect();
+
+        Ok(ExplainStatementResult {
+            explanation: Some(StatementExplanation {
+                range,
+                plan: plan.to_text(),
+                analyzed: analyze,
+                diagnostics,
+            }),
+            message,
+        })
+    }
+
+    #[ignored_path(path=&params.path)]
+    fn pull_code_lenses(
+        &self,
+        params: CodeLensesParams,
+    ) -> Result<CodeLensesResult, WorkspaceError> {
+        // the statements are collected first, so that the documents stay unlocked while
+        // connecting to the database
+        let statements: Vec<_> = {
+            let documents = self.documents.read().unwrap();
+            let doc = documents
+                .get(&params.path)
+                .ok_or(WorkspaceError::not_found())?;
+
+            doc.iter(WithCSTandASTMapper)
+                .filter(|(id, ..)| id.is_root())
+                .map(|(id, range, _, ast)| {
+                    let explainable = ast
+                        .as_ref()
+                        .is_some_and(pgls_typecheck::explain::is_explainable);
+                    let function = match &ast {
+                        Some(pgls_query::NodeEnum::CreateFunctionStmt(create_fn)) => {
+                            Some(is_plpgsql(create_fn))
+                        }
+                        _ => None,
+                    };
+                    (id, range, explainable, function)
+                })
+                .collect()
+        };
+
+        let settings = self.workspaces();
+        let settings = settings.settings();
+
+        let can_execute = settings
+            .and_then(|settings| {
+                self.connection_selection
+                    .db_settings(settings, &params.path)
+            })
+            .is_some_and(|db| db.allow_statement_executions);
+
+        let schema_cache = self
+            .optional_connection(&params.path)
+            .and_then(|pool| self.schema_cache.load(pool).ok());
+        let has_plpgsql_check = schema_cache.as_ref().is_some_and(|schema_cache| {
+            schema_cache
+                .extensions
+                .iter()
+                .any(|e| e.name == "plpgsql_check")
+        });
+
+        let statements = statements
+            .into_iter()
+            .map(|(id, range, explainable, function)| {
+                let mut lenses = vec![];
+                let mut push = |title: &str, command| {
+                    lenses.push(CodeLens {
+                        title: title.into(),
+                        command,
+                    })
+                };
+
+                if can_execute {
+                    push("Run", CommandActionCategory::ExecuteStatement(id.clone()));
+                    push(
+                        "Dry Run",
+                        CommandActionCategory::DryRunStatement(id.clone()),
+                    );
+                }
+
+                if schema_cache.is_some() && explainable {
+                    push(
+                        "Explain",
+                        CommandActionCategory::ExplainStatement(id.clone()),
+                    );
+                }
+
+                if let Some(is_plpgsql) = function {
+                    if has_plpgsql_check && is_plpgsql {
+                        push(
+                            "Run plpgsql_check",
+                            CommandActionCategory::CheckFunction(id.clone()),
+                        );
+                    }
+                    if schema_cache.is_some() {
+                        push(
+                            "Show Callers",
+                            CommandActionCategory::ShowFunctionCallers(id.clone()),
+                        );
+                    }
+                }
+
+                StatementCodeLenses { range, lenses }
+            })
+            .collect();
+
+        Ok(CodeLensesResult { statements })
+    }
+
+    #[ignored_path(path=&params.path)]
+    fn pull_folding_ranges(
+        &self,
+        params: FoldingRangesParams,
+    ) -> Result<FoldingRangesResult, WorkspaceError> {
+        let documents = self.documents.read().unwrap();
+        let doc = documents
+            .get(&params.path)
+            .ok_or(WorkspaceError::not_found())?;
+
+        Ok(FoldingRangesResult {
+            ranges: folding_ranges::folding_ranges(doc),
+        })
+    }
+
+    #[ignored_path(path=&params.path)]
+    fn pull_selection_ranges(
+        &self,
+        params: SelectionRangesParams,
+    ) -> Result<SelectionRangesResult, WorkspaceError> {
+        let documents = self.documents.read().unwrap();
+        let doc = documents
+            .get(&params.path)
+            .ok_or(WorkspaceError::not_found())?;
+
+        Ok(SelectionRangesResult {
+            ranges: params
+                .positions
+                .into_iter()
+                .map(|position| selection_ranges::selection_ranges(doc, position))
+                .collect(),
+        })
+    }
+
+    #[ignored_path(path=&params.path)]
+    #[tracing::instrument(level = "debug", skip_all, fields(
+        path = params.path.as_os_str().to_str(),
+    ), err)]
+    fn check_function(
+        &self,
+        params: CheckFunctionParams,
+    ) -> Result<CheckFunctionResult, WorkspaceError> {
+        let (id, range, ast) = {
+            let documents = self.documents.read().unwrap();
+            let doc = documents
+                .get(&params.path)
+                .ok_or(WorkspaceError::not_found())?;
+
+            let Some((id, range, _, ast)) = select_statement(doc, params.statement) else {
+                return Ok(CheckFunctionResult::default());
+            };
+
+            (id, range, ast)
+        };
+
+        let Some(ast) = ast.filter(|ast| {
+            matches!(ast, pgls_query::NodeEnum::CreateFunctionStmt(create_fn) if is_plpgsql(create_fn))
+        }) else {
+            return Ok(CheckFunctionResult {
+                diagnostics: None,
+                message: Some("Only PL/pgSQL functions can be checked.".into()),
+            });
+        };
+
+        let Some(pool) = self.get_connection(&params.path)? else {
+            return Ok(CheckFunctionResult {
+                diagnostics: None,
+                message: Some("No database connection available.".into()),
+            });
+        };
+
+        let schema_cache = self.schema_cache.load(pool.clone())?;
+
+        if !schema_cache
+            .extensions
+            .iter()
+            .any(|e| e.name == "plpgsql_check")
+        {
+            return Ok(CheckFunctionResult {
+                diagnostics: None,
+                message: Some("The plpgsql_check extension is not installed.".into()),
+            });
+        }
+
+        let sql = id.content().to_string();
+        let result = run_async(async move {
+            pgls_plpgsql_check::check_plpgsql(pgls_plpgsql_check::PlPgSqlCheckParams {
+                conn: &pool,
+                sql: &sql,
+                ast: &ast,
+                schema_cache: schema_cache.as_ref(),
+            })
+            .await
+        })?;
+
+        let diagnostics = match result {
+            Ok(diagnostics) => diagnostics,
+            Err(err) => {
+                return Ok(CheckFunctionResult {
+                    diagnostics: None,
+                    message: Some(format!("Failed to check function: {err}")),
+                });
+            }
+        };
+
+        let path = params.path.as_path().display().to_string();
+        let diagnostics = diagnostics
+            .into_iter()
+            .map(|d| {
+                let span = d.span.map(|span| span + range.start());
+                SDiagnostic::new(
+                    d.with_file_path(path.clone())
+                        .with_file_span(span.unwrap_or(range)),
+                )
+            })
+            .collect();
+
+        Ok(CheckFunctionResult {
+            diagnostics: Some(diagnostics),
+            message: None,
+        })
+    }
+
+    #[ignored_path(path=&params.path)]
+    fn function_callers(
+        &self,
+        params: FunctionCallersParams,
+    ) -> Result<FunctionCallersResult, WorkspaceError> {
+        let ast = {
+            let documents = self.documents.read().unwrap();
+            let doc = documents
+                .get(&params.path)
+                .ok_or(WorkspaceError::not_found())?;
+
+            let Some((_, _, _, ast)) = select_statement(doc, params.statement) else {
+                return Ok(FunctionCallersResult::default());
+            };
+
+            ast
+        };
+
+        let Some(pgls_query::NodeEnum::CreateFunctionStmt(create_fn)) = ast else {
+            return Ok(FunctionCallersResult {
+                callers: None,
+                message: Some("The statement does not create a function.".into()),
+            });
+        };
+
+        let Some((schema, name)) = pgls_query_ext::utils::parse_name(&create_fn.funcname) else {
+            return Ok(FunctionCallersResult::default());
+        };
+
+        let Some(pool) = self.get_connection(&params.path)? else {
+            return Ok(FunctionCallersResult {
+                callers: None,
+                message: Some("No database connection available.".into()),
+            });
+        };
+
+        let schema_cache = self.schema_cache.load(pool)?;
+
+        Ok(FunctionCallersResult {
+            callers: Some(function_callers::find_callers(
+                &schema_cache,
+                schema.as_deref(),
+                &name,
+            )),
+            message: None,
+        })
+    }
+}
+
+fn is_plpgsql(create_fn: &pgls_query::protobuf::CreateFunctionStmt) -> bool {
+    pgls_query_ext::utils::find_option_value(create_fn, "language").as_deref() == Some("plpgsql")
+}
+
+/// Runs the analyser on the statements of the document, adds its diagnostics to the ones found
+/// so far and applies the suppression comments of the document.
+#[allow(clippy::too_many_arguments)]
+fn analyse_document(
+    settings: &Settings,
+    doc: &Document,
+    path: &PgLSPath,
+    mut diagnostics: Vec<SDiagnostic>,
+    schema_cache: Option<&pgls_schema_cache::SchemaCache>,
+    categories: RuleCategories,
+    only: &[RuleSelector],
+    skip: &[RuleSelector],
+) -> PullDiagnosticsResult {
+    /*
+     * Below, we'll apply our static linting rules against the statements,
+     * considering the user's settings
+     */
+    let (enabled_rules, disabled_rules) = AnalyserVisitorBuilder::new(settings)
+        .with_linter_rules(only, skip)
+        .finish();
+
+    let options = AnalyserOptions {
+        rules: to_analyser_rules(settings),
+    };
+
+    let filter = AnalysisFilter {
+        categories,
+        enabled_rules: Some(enabled_rules.as_slice()),
+        disabled_rules: &disabled_rules,
+    };
+
+    let analyser = Analyser::new(AnalyserConfig {
+        options: &options,
+        filter,
+    });
+
+    let path = path.as_path().display().to_string();
+
+    let mut analysable_stmts = vec![];
+    for (stmt_root, diagnostic) in doc.iter(AnalyserDiagnosticsMapper) {
+        if let Some(node) = stmt_root {
+            analysable_stmts.push(node);
+        }
+        if let Some(diag) = diagnostic {
+            // ignore the syntax error if we already have more specialized diagnostics for the
+            // same statement.
+            // this is important for create function statements, where we might already have detailed
+            // diagnostics from plpgsql_check.
+            if diagnostics.iter().any(|d| {
+                d.location().span.is_some_and(|async_loc| {
+                    diag.location()
+                        .span
+                        .is_some_and(|syntax_loc| syntax_loc.contains_range(async_loc))
+                })
+            }) {
+                continue;
+            }
+
+            diagnostics.push(SDiagnostic::new(
+                diag.with_file_path(path.clone())
+                    .with_severity(Severity::Error),
+            ));
+        }
+    }
+
+    diagnostics.extend(
+        analyser
+            .run(AnalyserParams {
+                stmts: analysable_stmts,
+                schema_cache,
+            })
+            .into_iter()
+            .map(Error::from)
+            .map(|d| {
+                let severity = d
+                    .category()
+                    .map(|category| {
+                        settings
+                            .get_severity_from_rule_code(category)
+                            .unwrap_or(Severity::Warning)
+                    })
+                    .unwrap();
+
+                let span = d.location().span;
+                SDiagnostic::new(
+                    d.with_file_path(path.clone())
+                        .with_file_span(span)
+                        .with_severity(severity),
+                )
+            }),
+    );
+
+    let suppressions = doc.suppressions();
+
+    let disabled_suppression_errors =
+        suppressions.get_disabled_diagnostic_suppressions_as_errors(&disabled_rules);
+
+    let unused_suppression_errors = suppressions.get_unused_suppressions_as_errors(&diagnostics);
+
+    let suppression_errors: Vec<Error> = suppressions
+        .diagnostics
+        .iter()
+        .chain(disabled_suppression_errors.iter())
+        .chain(unused_suppression_errors.iter())
+        .cloned()
+        .map(Error::from)
+        .collect::<Vec<pgls_diagnostics::Error>>();
+
+    let (suppressed_diagnostics, mut diagnostics): (Vec<_>, Vec<_>) = diagnostics
+        .into_iter()
+        .partition(|d| suppressions.is_suppressed(d));
+    diagnostics.extend(suppression_errors.into_iter().map(SDiagnostic::new));
+
+    info!("Pulled {:?} diagnostic(s)", diagnostics.len());
+    PullDiagnosticsResult {
+        diagnostics,
+        skipped_diagnostics: 0,
+        suppressed_diagnostics,
+    }
+}
+
+fn failed_before_execution(
+    total: usize,
+    error: StatementExecutionError,
+) -> ExecuteStatementsResult {
+    ExecuteStatementsResult {
+        message: format!("No statement was executed: {}", error.message),
+        total,
+        error: Some(error),
+        ..Default::default()
+    }
+}
+
+/// Finds the statement a [StatementSelector] refers to.
+fn select_statement(
+    doc: &Document,
+    selector: StatementSelector,
+) -> Option<(
+    StatementId,
+    TextRange,
+    Arc<::tree_sitter::Tree>,
+    Option<pgls_query::NodeEnum>,
+)> {
+    match selector {
+        StatementSelector::Position(position) => doc
+            .iter_with_filter(WithCSTandASTMapper, CursorPositionFilter::new(position))
+            .next(),
+        StatementSelector::Index(index) => doc
+            .iter(WithCSTandASTMapper)
+            .filter(|(id, ..)| id.is_root())
+            .nth(index),
+        StatementSelector::Id(id) => doc.find(id, WithCSTandASTMapper),
+    }
 }
 
 /// Returns `true` if `path` is a directory or
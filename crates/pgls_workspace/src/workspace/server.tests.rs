@@ -11,15 +11,22 @@ use pgls_configuration::{
 use pgls_configuration::plpgsql_check::PartialPlPgSqlCheckConfiguration;
 use pgls_diagnostics::Diagnostic;
 use pgls_fs::PgLSPath;
-use pgls_text_size::TextRange;
+use pgls_text_size::{TextRange, TextSize};
 use sqlx::{Executor, PgPool};
 
 use crate::{
     Workspace, WorkspaceError,
-    features::code_actions::ExecuteStatementResult,
+    features::{
+        code_actions::{ExecuteStatementResult, ResultSetColumn},
+        code_lenses::CodeLensesParams,
+        describe::{DescribeStatementParams, DescriptionSource, ResultColumn, StatementSelector},
+        diagnostics::PullContentDiagnosticsParams,
+        explain::ExplainStatementParams,
+        functions::{FunctionCaller, FunctionCallerKind, FunctionCallersParams},
+    },
     workspace::{
-        OpenFileParams, RegisterProjectFolderParams, StatementId, UpdateSettingsParams,
-        server::WorkspaceServer,
+        GetFileContentParams, OpenFileParams, RegisterProjectFolderParams, StatementId,
+        UpdateSettingsParams, server::WorkspaceServer,
     },
 };
 
@@ -108,6 +115,91 @@ async fn test_diagnostics(test_db: PgPool) {
     );
 }
 
+#[test]
+fn test_content_diagnostics_do_not_open_the_file() {
+    let workspace = get_test_workspace(None).expect("Unable to create test workspace");
+
+    let path = PgLSPath::new("unopened.sql");
+
+    let diagnostics = workspace
+        .pull_content_diagnostics(PullContentDiagnosticsParams {
+            path: path.clone(),
+            content: "drop table users;".into(),
+            categories: RuleCategories::all(),
+        })
+        .expect("Unable to pull diagnostics")
+        .diagnostics;
+
+    assert_eq!(
+        diagnostics
+            .iter()
+            .map(|d| d.category().map(|c| c.name()))
+            .collect::<Vec<_>>(),
+        vec![Some("lint/safety/banDropTable")]
+    );
+
+    assert!(
+        workspace
+            .get_file_content(GetFileContentParams { path })
+            .is_err(),
+        "the file should not be open"
+    );
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_suppressed_diagnostics(test_db: PgPool) {
+    let mut conf = PartialConfiguration::init();
+    conf.merge_with(PartialConfiguration {
+        db: Some(PartialDatabaseConfiguration {
+            database: Some(
+                test_db
+                    .connect_options()
+                    .get_database()
+                    .unwrap()
+                    .to_string(),
+            ),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+
+    let workspace = get_test_workspace(Some(conf)).expect("Unable to create test workspace");
+
+    let path = PgLSPath::new("test.sql");
+    let content = r#"
+      -- pgls-ignore lint/safety/banDropTable
+      drop table users;
+    "#;
+
+    workspace
+        .open_file(OpenFileParams {
+            path: path.clone(),
+            content: content.into(),
+            version: 1,
+        })
+        .expect("Unable to open test file");
+
+    let result = workspace
+        .pull_file_diagnostics(crate::workspace::PullFileDiagnosticsParams {
+            path: path.clone(),
+            categories: RuleCategories::all(),
+            max_diagnostics: 100,
+            only: vec![],
+            skip: vec![],
+        })
+        .expect("Unable to pull diagnostics");
+
+    assert!(result.diagnostics.is_empty(), "Expected no diagnostics");
+    assert_eq!(
+        result
+            .suppressed_diagnostics
+            .iter()
+            .map(|d| d.category().map(|c| c.name()))
+            .collect::<Vec<_>>(),
+        vec![Some("lint/safety/banDropTable")]
+    );
+}
+
 #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
 async fn test_syntax_error(test_db: PgPool) {
     let mut conf = PartialConfiguration::init();
@@ -203,9 +295,10 @@ async fn correctly_ignores_files() {
     let execute_statement_result =
         workspace.execute_statement(crate::workspace::ExecuteStatementParams {
             path: path.clone(),
-            statement_id: StatementId::Root {
+            statement: StatementSelector::Id(StatementId::Root {
                 content: Arc::from(content),
-            },
+            }),
+            options: Default::default(),
         });
 
     assert!(execute_statement_result.is_ok_and(|res| res == ExecuteStatementResult::default()));
@@ -582,6 +675,96 @@ async fn test_disable_typecheck(test_db: PgPool) {
     );
 }
 
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_typecheck_replays_prior_ddl(test_db: PgPool) {
+    let mut conf = PartialConfiguration::init();
+    conf.merge_with(PartialConfiguration {
+        db: Some(PartialDatabaseConfiguration {
+            database: Some(
+                test_db
+                    .connect_options()
+                    .get_database()
+                    .unwrap()
+                    .to_string(),
+            ),
+            allow_statement_executions_against: Some(StringSet::from_iter(["*/*".to_string()])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+
+    let workspace = get_test_workspace(Some(conf)).expect("Unable to create test workspace");
+
+    let path = PgLSPath::new("test.sql");
+
+    let content = r#"
+      create table users (
+          id serial primary key,
+          email text not null
+      );
+
+      select email from users where id = 1;
+    "#;
+
+    workspace
+        .open_file(OpenFileParams {
+            path: path.clone(),
+            content: content.into(),
+            version: 1,
+        })
+        .expect("Unable to open test file");
+
+    let typecheck_diagnostics = || {
+        workspace
+            .pull_file_diagnostics(crate::workspace::PullFileDiagnosticsParams {
+                path: path.clone(),
+                categories: RuleCategories::all(),
+                max_diagnostics: 100,
+                only: vec![],
+                skip: vec![],
+            })
+            .expect("Unable to pull diagnostics")
+            .diagnostics
+            .iter()
+            .filter(|d| d.category().is_some_and(|c| c.name() == "typecheck"))
+            .count()
+    };
+
+    // the table doesn't exist in the database
+    assert_eq!(
+        typecheck_diagnostics(),
+        1,
+        "Expected one typecheck diagnostic"
+    );
+
+    let _ = workspace.update_settings(UpdateSettingsParams {
+        configuration: PartialConfiguration {
+            typecheck: Some(PartialTypecheckConfiguration {
+                replay_prior_ddl: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        gitignore_matches: vec![],
+        vcs_base_path: None,
+        workspace_directory: None,
+    });
+
+    assert_eq!(
+        typecheck_diagnostics(),
+        0,
+        "Expected no typecheck diagnostic"
+    );
+
+    let tables: Vec<(String,)> = sqlx::query_as(
+        "select table_name::text from information_schema.tables where table_name = 'users'",
+    )
+    .fetch_all(&test_db)
+    .await
+    .unwrap();
+    assert!(tables.is_empty(), "Replayed DDL must be rolled back");
+}
+
 #[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
 async fn test_named_params(_test_db: PgPool) {
     let conf = PartialConfiguration::init();
@@ -759,6 +942,7 @@ async fn test_search_path_configuration(test_db: PgPool) {
             enabled: Some(true),
             // Adding glob pattern to match the "private" schema
             search_path: Some(StringSet::from_iter(vec!["pr*".to_string()])),
+            ..Default::default()
         }),
         ..Default::default()
     }); // checking with the pattern should yield no diagnostics
@@ -793,3 +977,755 @@ async fn test_search_path_configuration(test_db: PgPool) {
         );
     }
 }
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_describe_statement(test_db: PgPool) {
+    let mut conf = PartialConfiguration::init();
+    conf.merge_with(PartialConfiguration {
+        db: Some(PartialDatabaseConfiguration {
+            database: Some(
+                test_db
+                    .connect_options()
+                    .get_database()
+                    .unwrap()
+                    .to_string(),
+            ),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+
+    let workspace = get_test_workspace(Some(conf)).expect("Unable to create test workspace");
+
+    let setup_sql = r"
+      create table users (
+          id serial primary key,
+          email text
+      );
+    ";
+    test_db.execute(setup_sql).await.expect("setup sql failed");
+
+    let path = PgLSPath::new("test.sql");
+    let content = r#"
+      create table accounts (id int);
+
+      select id, email from users;
+    "#;
+
+    workspace
+        .open_file(OpenFileParams {
+            path: path.clone(),
+            content: content.into(),
+            version: 1,
+        })
+        .expect("Unable to open test file");
+
+    let describe = |statement| {
+        workspace
+            .describe_statement(DescribeStatementParams {
+                path: path.clone(),
+                statement,
+            })
+            .expect("Unable to describe statement")
+            .description
+    };
+
+    assert_eq!(describe(StatementSelector::Index(0)), None);
+
+    let description = describe(StatementSelector::Index(1)).expect("Expected a description");
+    assert_eq!(description.source, DescriptionSource::Database);
+    assert_eq!(
+        description.columns,
+        vec![
+            ResultColumn {
+                name: "id".into(),
+                type_name: Some("integer".into()),
+                nullable: Some(false),
+            },
+            ResultColumn {
+                name: "email".into(),
+                type_name: Some("text".into()),
+                nullable: Some(true),
+            },
+        ]
+    );
+
+    let position = TextSize::new(content.find("email").unwrap().try_into().unwrap());
+    assert_eq!(
+        describe(StatementSelector::Position(position)),
+        Some(description)
+    );
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_explain_statement(test_db: PgPool) {
+    let mut conf = PartialConfiguration::init();
+    conf.merge_with(PartialConfiguration {
+        db: Some(PartialDatabaseConfiguration {
+            database: Some(
+                test_db
+                    .connect_options()
+                    .get_database()
+                    .unwrap()
+                    .to_string(),
+            ),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+
+    let workspace = get_test_workspace(Some(conf)).expect("Unable to create test workspace");
+
+    let setup_sql = r"
+      create table orders (
+          id serial primary key,
+          customer_id int
+      );
+    ";
+    test_db.execute(setup_sql).await.expect("setup sql failed");
+
+    let path = PgLSPath::new("test.sql");
+    let content = r#"
+      create table items (id int);
+
+      select * from orders where customer_id = 1;
+    "#;
+
+    workspace
+        .open_file(OpenFileParams {
+            path: path.clone(),
+            content: content.into(),
+            version: 1,
+        })
+        .expect("Unable to open test file");
+
+    let explain = |statement, analyze| {
+        workspace
+            .explain_statement(ExplainStatementParams {
+                path: path.clone(),
+                statement: StatementSelector::Index(statement),
+                analyze,
+            })
+            .expect("Unable to explain statement")
+    };
+
+    let result = explain(0, false);
+    assert!(result.explanation.is_none());
+    assert!(result.message.is_some());
+
+    let result = explain(1, false);
+    assert!(result.message.is_none());
+    let explanation = result.explanation.expect("Expected an explanation");
+    assert!(!explanation.analyzed);
+    assert!(
+        explanation.plan.contains("Seq Scan on public.orders"),
+        "{}",
+        explanation.plan
+    );
+
+    // statement executions are not allowed against the test database
+    let result = explain(1, true);
+    assert!(result.message.is_some());
+    assert!(
+        !result
+            .explanation
+            .expect("Expected an explanation")
+            .analyzed
+    );
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_code_lenses(test_db: PgPool) {
+    let mut conf = PartialConfiguration::init();
+    conf.merge_with(PartialConfiguration {
+        db: Some(PartialDatabaseConfiguration {
+            database: Some(
+                test_db
+                    .connect_options()
+                    .get_database()
+                    .unwrap()
+                    .to_string(),
+            ),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+
+    let workspace = get_test_workspace(Some(conf)).expect("Unable to create test workspace");
+
+    let setup_sql = r"
+      create function public.notify_user() returns int language sql as 'select 1';
+      create function public.archive() returns int language sql as 'select public.notify_user()';
+    ";
+    test_db.execute(setup_sql).await.expect("setup sql failed");
+
+    let path = PgLSPath::new("test.sql");
+    let content = r#"
+      select 1;
+
+      create or replace function public.notify_user() returns int language sql as 'select 2';
+    "#;
+
+    workspace
+        .open_file(OpenFileParams {
+            path: path.clone(),
+            content: content.into(),
+            version: 1,
+        })
+        .expect("Unable to open test file");
+
+    let result = workspace
+        .pull_code_lenses(CodeLensesParams { path: path.clone() })
+        .expect("Unable to pull code lenses");
+
+    let titles: Vec<Vec<&str>> = result
+        .statements
+        .iter()
+        .map(|statement| {
+            statement
+                .lenses
+                .iter()
+                .map(|lens| lens.title.as_str())
+                .collect()
+        })
+        .collect();
+
+    // statement executions are not allowed against the test database, and the function is
+    // not written in PL/pgSQL
+    assert_eq!(titles, vec![vec!["Explain"], vec!["Show Callers"]]);
+
+    let result = workspace
+        .function_callers(FunctionCallersParams {
+            path: path.clone(),
+            statement: StatementSelector::Index(1),
+        })
+        .expect("Unable to find callers");
+
+    assert_eq!(
+        result.callers,
+        Some(vec![FunctionCaller {
+            kind: FunctionCallerKind::Function,
+            name: "public.archive()".into(),
+        }])
+    );
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_execute_statement_result_sets(test_db: PgPool) {
+    use super::statement_execution::{ExecutionSettings, RunningExecutions, execute_statement};
+    use std::time::Duration;
+
+    let settings = ExecutionSettings {
+        row_limit: 10,
+        statement_timeout: Duration::from_secs(5),
+        lock_timeout: Duration::from_secs(5),
+        dry_run: false,
+    };
+    let running = Arc::new(RunningExecutions::default());
+    let path = PgLSPath::new("test.sql");
+
+    let result = execute_statement(
+        &test_db,
+        "create table events (id int, payload jsonb, at date)",
+        false,
+        settings,
+        Arc::clone(&running),
+        path.clone(),
+    )
+    .await
+    .expect("Unable to execute statement");
+    assert!(result.result_set.is_none());
+
+    test_db
+        .execute(
+            r#"insert into events values (1, '{"a": true}', '2024-01-01'), (2, null, null), (3, '[]', null)"#,
+        )
+        .await
+        .expect("setup sql failed");
+
+    let result = execute_statement(
+        &test_db,
+        "select id, payload, at, id > 1 as later from events order by id",
+        true,
+        ExecutionSettings {
+            row_limit: 2,
+            ..settings
+        },
+        Arc::clone(&running),
+        path.clone(),
+    )
+    .await
+    .expect("Unable to execute statement");
+
+    let result_set = result.result_set.expect("Expected a result set");
+    assert!(result_set.truncated);
+    assert_eq!(
+        result_set.columns,
+        vec![
+            ResultSetColumn {
+                name: "id".into(),
+                type_name: "int4".into(),
+            },
+            ResultSetColumn {
+                name: "payload".into(),
+                type_name: "jsonb".into(),
+            },
+            ResultSetColumn {
+                name: "at".into(),
+                type_name: "date".into(),
+            },
+            ResultSetColumn {
+                name: "later".into(),
+                type_name: "bool".into(),
+            },
+        ]
+    );
+    assert_eq!(
+        result_set.rows,
+        vec![
+            vec![
+                serde_json::json!(1),
+                serde_json::json!({ "a": true }),
+                serde_json::json!("2024-01-01"),
+                serde_json::json!(false),
+            ],
+            vec![
+                serde_json::json!(2),
+                serde_json::Value::Null,
+                serde_json::Value::Null,
+                serde_json::json!(true),
+            ],
+        ]
+    );
+
+    let result = execute_statement(
+        &test_db,
+        "select id from events where false",
+        true,
+        settings,
+        Arc::clone(&running),
+        path.clone(),
+    )
+    .await
+    .expect("Unable to execute statement");
+    let result_set = result.result_set.expect("Expected a result set");
+    assert!(result_set.rows.is_empty());
+    assert_eq!(result_set.columns.len(), 1);
+
+    // values JavaScript can't represent exactly are kept as text
+    let result = execute_statement(
+        &test_db,
+        "select 9007199254740993::int8 as big, 1.50::numeric as amount",
+        true,
+        settings,
+        Arc::clone(&running),
+        path.clone(),
+    )
+    .await
+    .expect("Unable to execute statement");
+    assert_eq!(
+        result.result_set.expect("Expected a result set").rows,
+        vec![vec![
+            serde_json::json!("9007199254740993"),
+            serde_json::json!("1.50"),
+        ]]
+    );
+
+    let result = execute_statement(
+        &test_db,
+        "delete from events where id = 3 returning id",
+        false,
+        settings,
+        Arc::clone(&running),
+        path.clone(),
+    )
+    .await
+    .expect("Unable to execute statement");
+    assert_eq!(
+        result.result_set.expect("Expected a result set").rows,
+        vec![vec![serde_json::json!(3)]]
+    );
+
+    let timed_out = execute_statement(
+        &test_db,
+        "select pg_sleep(1)",
+        true,
+        ExecutionSettings {
+            statement_timeout: Duration::from_millis(100),
+            ..settings
+        },
+        Arc::clone(&running),
+        path.clone(),
+    )
+    .await;
+    assert!(timed_out.is_err());
+
+    // the timeout must not leak into other queries on the pooled connections
+    let statement_timeout: String = sqlx::query_scalar("show statement_timeout")
+        .fetch_one(&test_db)
+        .await
+        .expect("Unable to query statement timeout");
+    assert_eq!(statement_timeout, "0");
+    assert!(!running.is_running(&path));
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_execute_statement_safe_modes(test_db: PgPool) {
+    use super::statement_execution::{
+        ExecutionSettings, RunningExecutions, cancel, execute_statement,
+    };
+    use std::time::Duration;
+
+    let settings = ExecutionSettings {
+        row_limit: 10,
+        statement_timeout: Duration::from_secs(5),
+        lock_timeout: Duration::from_secs(5),
+        dry_run: false,
+    };
+    let running = Arc::new(RunningExecutions::default());
+    let path = PgLSPath::new("test.sql");
+
+    test_db
+        .execute("create table events (id int); insert into events values (1), (2);")
+        .await
+        .expect("setup sql failed");
+
+    let result = execute_statement(
+        &test_db,
+        "delete from events returning id",
+        false,
+        ExecutionSettings {
+            dry_run: true,
+            ..settings
+        },
+        Arc::clone(&running),
+        path.clone(),
+    )
+    .await
+    .expect("Unable to execute statement");
+    assert_eq!(
+        result.result_set.expect("Expected a result set").rows.len(),
+        2
+    );
+    assert!(result.message.ends_with("The changes were rolled back."));
+
+    let count: i64 = sqlx::query_scalar("select count(*) from events")
+        .fetch_one(&test_db)
+        .await
+        .expect("Unable to count rows");
+    assert_eq!(count, 2);
+
+    // a statement waiting for a lock fails after the lock timeout
+    let mut locker = test_db.begin().await.expect("Unable to begin transaction");
+    sqlx::query("lock table events in access exclusive mode")
+        .execute(&mut *locker)
+        .await
+        .expect("Unable to lock table");
+    let lock_timed_out = execute_statement(
+        &test_db,
+        "select * from events",
+        true,
+        ExecutionSettings {
+            lock_timeout: Duration::from_millis(100),
+            ..settings
+        },
+        Arc::clone(&running),
+        path.clone(),
+    )
+    .await;
+    assert!(lock_timed_out.is_err());
+    locker.rollback().await.expect("Unable to roll back");
+
+    let execution = tokio::spawn({
+        let pool = test_db.clone();
+        let running = Arc::clone(&running);
+        let path = path.clone();
+        async move {
+            execute_statement(&pool, "select pg_sleep(10)", true, settings, running, path).await
+        }
+    });
+
+    while !running.is_running(&path) {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    // give the statement time to actually start
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let cancelled = cancel(running.backends(&path))
+        .await
+        .expect("Unable to cancel");
+    assert_eq!(cancelled, 1);
+
+    let result = execution.await.expect("Execution panicked");
+    assert!(result.is_err());
+    assert!(!running.is_running(&path));
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_execute_script(test_db: PgPool) {
+    use super::statement_execution::{
+        ExecutionSettings, RunningExecutions, ScriptStatement, execute_script,
+    };
+    use std::time::Duration;
+
+    let settings = ExecutionSettings {
+        row_limit: 10,
+        statement_timeout: Duration::from_secs(5),
+        lock_timeout: Duration::from_secs(5),
+        dry_run: false,
+    };
+    let running = Arc::new(RunningExecutions::default());
+    let path = PgLSPath::new("test.sql");
+
+    let content = "create table items (id int);\nbegin;\ninsert into items values (1);\ninsert into items (missing) values (2);\ncommit;";
+    let mut offset = 0;
+    let statements: Vec<ScriptStatement> = content
+        .split_inclusive(';')
+        .map(|part| {
+            let sql = part.trim_start();
+            let start = offset + part.len() - sql.len();
+            offset += part.len();
+            ScriptStatement {
+                range: TextRange::at(TextSize::new(start as u32), TextSize::new(sql.len() as u32)),
+                sql: sql.to_string(),
+            }
+        })
+        .collect();
+
+    let result = execute_script(
+        &test_db,
+        &statements,
+        settings,
+        Arc::clone(&running),
+        path.clone(),
+    )
+    .await
+    .expect("Unable to execute script");
+
+    assert_eq!(result.executed, 3);
+    assert_eq!(result.total, 5);
+    let error = result.error.expect("Expected an error");
+    let missing = content.find("missing").unwrap() as u32;
+    assert_eq!(error.range, TextRange::at(missing.into(), 7.into()));
+    assert!(error.message.contains("missing"));
+    assert!(!running.is_running(&path));
+
+    // the failed transaction is rolled back, the statement before it stays committed
+    let count: i64 = sqlx::query_scalar("select count(*) from items")
+        .fetch_one(&test_db)
+        .await
+        .expect("Unable to count rows");
+    assert_eq!(count, 0);
+}
+
+#[cfg(all(test, not(target_os = "windows")))]
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_execute_statements(test_db: PgPool) {
+    use crate::features::code_actions::ExecuteStatementsParams;
+
+    let mut conf = PartialConfiguration::init();
+    conf.merge_with(PartialConfiguration {
+        db: Some(PartialDatabaseConfiguration {
+            database: Some(
+                test_db
+                    .connect_options()
+                    .get_database()
+                    .unwrap()
+                    .to_string(),
+            ),
+            allow_statement_executions_against: Some(StringSet::from_iter(["*/*".to_string()])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+
+    let workspace = get_test_workspace(Some(conf)).expect("Unable to create test workspace");
+
+    let execute = |path: &PgLSPath, content: &str, range: Option<TextRange>| {
+        workspace
+            .open_file(OpenFileParams {
+                path: path.clone(),
+                content: content.into(),
+                version: 1,
+            })
+            .expect("Unable to open test file");
+
+        workspace
+            .execute_statements(ExecuteStatementsParams {
+                path: path.clone(),
+                range,
+                confirmation: None,
+            })
+            .expect("Unable to execute statements")
+    };
+
+    // nothing is executed if a statement cannot run within the transaction
+    let content = "create table items (id int);\nbegin;\ncreate index concurrently items_id_idx on items (id);\ncommit;";
+    let result = execute(&PgLSPath::new("invalid.sql"), content, None);
+    assert_eq!(result.executed, 0);
+    let error = result.error.expect("Expected an error");
+    assert!(error.message.contains("CREATE INDEX CONCURRENTLY"));
+    assert_eq!(
+        error.range.start(),
+        TextSize::new(content.find("create index").unwrap() as u32)
+    );
+
+    let content = "begin;\nreindex index concurrently items_id_idx;\ncommit;";
+    let result = execute(&PgLSPath::new("invalid.sql"), content, None);
+    assert_eq!(result.executed, 0);
+    assert!(
+        result
+            .error
+            .expect("Expected an error")
+            .message
+            .contains("REINDEX CONCURRENTLY")
+    );
+
+    let content = "begin;\nalter type mood add value 'meh';\ncommit;";
+    let result = execute(&PgLSPath::new("invalid.sql"), content, None);
+    assert_eq!(result.executed, 0);
+    assert!(
+        result
+            .error
+            .expect("Expected an error")
+            .message
+            .contains("ADD VALUE")
+    );
+
+    let content = "create table items (id int);\ninsert into items values (1);\ninsert into items (missing) values (2);\ninsert into items values (3);";
+    let path = PgLSPath::new("run.sql");
+    let result = execute(&path, content, None);
+    assert_eq!(result.executed, 2);
+    assert_eq!(result.total, 4);
+    assert_eq!(
+        result.error.expect("Expected an error").range.start(),
+        TextSize::new(content.find("missing").unwrap() as u32)
+    );
+
+    let last = content.rfind("insert").unwrap() as u32;
+    let result = execute(
+        &path,
+        content,
+        Some(TextRange::new(last.into(), (content.len() as u32).into())),
+    );
+    assert_eq!(result.executed, 1);
+    assert!(result.error.is_none());
+
+    let count: i64 = sqlx::query_scalar("select count(*) from items")
+        .fetch_one(&test_db)
+        .await
+        .expect("Unable to count rows");
+    assert_eq!(count, 2);
+}
+
+#[sqlx::test(migrator = "pgls_test_utils::MIGRATIONS")]
+async fn test_selected_connection_is_used_for_completions_and_typecheck(test_db: PgPool) {
+    use pgls_configuration::database::{DatabaseConnectionConfiguration, DatabaseConnections};
+
+    use crate::features::{
+        code_actions::SelectConnectionParams, completions::GetCompletionsParams,
+    };
+
+    let database = test_db
+        .connect_options()
+        .get_database()
+        .unwrap()
+        .to_string();
+    let analytics_database = format!("{database}_analytics");
+
+    test_db
+        .execute(format!(r#"create database "{analytics_database}""#).as_str())
+        .await
+        .expect("Unable to create analytics database");
+    let analytics_db = PgPool::connect_with(
+        test_db
+            .connect_options()
+            .as_ref()
+            .clone()
+            .database(&analytics_database),
+    )
+    .await
+    .expect("Unable to connect to analytics database");
+    analytics_db
+        .execute("create table public.page_views (id int, url text);")
+        .await
+        .expect("setup sql failed");
+
+    let mut conf = PartialConfiguration::init();
+    conf.merge_with(PartialConfiguration {
+        db: Some(PartialDatabaseConfiguration {
+            database: Some(database),
+            connections: Some(DatabaseConnections(
+                [(
+                    "analytics".to_string(),
+                    DatabaseConnectionConfiguration {
+                        database: Some(analytics_database.clone()),
+                        ..Default::default()
+                    },
+                )]
+                .into(),
+            )),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+
+    let workspace = get_test_workspace(Some(conf)).expect("Unable to create test workspace");
+
+    let path = PgLSPath::new("test.sql");
+    let content = "select url from page_views;\nselect * from ";
+    workspace
+        .open_file(OpenFileParams {
+            path: path.clone(),
+            content: content.into(),
+            version: 1,
+        })
+        .expect("Unable to open test file");
+
+    let typecheck_diagnostics = || {
+        workspace
+            .pull_file_diagnostics(crate::workspace::PullFileDiagnosticsParams {
+                path: path.clone(),
+                categories: RuleCategories::all(),
+                max_diagnostics: 100,
+                only: vec![],
+                skip: vec![],
+            })
+            .expect("Unable to pull diagnostics")
+            .diagnostics
+            .iter()
+            .filter(|d| d.category().is_some_and(|c| c.name() == "typecheck"))
+            .count()
+    };
+    let completes_page_views = || {
+        workspace
+            .get_completions(GetCompletionsParams {
+                path: path.clone(),
+                position: TextSize::new(content.len() as u32),
+            })
+            .expect("Unable to get completions")
+            .into_iter()
+            .any(|item| item.label == "page_views")
+    };
+
+    // the table only exists in the analytics database
+    assert_eq!(typecheck_diagnostics(), 1);
+    assert!(!completes_page_views());
+
+    workspace
+        .select_connection(SelectConnectionParams {
+            path: path.clone(),
+            connection: Some("analytics".into()),
+        })
+        .expect("Unable to select connection");
+
+    assert_eq!(typecheck_diagnostics(), 0);
+    assert!(completes_page_views());
+
+    drop(workspace);
+    analytics_db.close().await;
+    test_db
+        .execute(format!(r#"drop database "{analytics_database}" with (force)"#).as_str())
+        .await
+        .expect("Unable to drop analytics database");
+}
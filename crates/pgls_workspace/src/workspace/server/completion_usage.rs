@@ -0,0 +1,148 @@
+use std::{
+    collections::HashMap,
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use pgls_completions::{CompletionItemReference, CompletionUsage};
+use rustc_hash::FxHasher;
+
+/// Recorded completions are written to disk at most this often, and when the manager is dropped.
+const PERSIST_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Keeps track of accepted completion items per project and persists them
+/// in the cache directory, so rankings survive restarts.
+pub struct CompletionUsageManager {
+    dir: PathBuf,
+    usages: RwLock<HashMap<PathBuf, ProjectUsage>>,
+}
+
+struct ProjectUsage {
+    usage: Arc<CompletionUsage>,
+    /// Whether there are recorded items that aren't on disk yet.
+    dirty: bool,
+    persisted_at: Instant,
+}
+
+impl CompletionUsageManager {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            usages: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the usage model of the project, loading it from disk if needed.
+    pub fn get(&self, project: &Path) -> Arc<CompletionUsage> {
+        if let Some(project_usage) = self.usages.read().unwrap().get(project) {
+            return project_usage.usage.clone();
+        }
+
+        let mut usages = self.usages.write().unwrap();
+        usages
+            .entry(project.to_path_buf())
+            .or_insert_with(|| self.load(project))
+            .usage
+            .clone()
+    }
+
+    pub fn record(&self, project: &Path, reference: &CompletionItemReference) {
+        let mut usages = self.usages.write().unwrap();
+        let project_usage = usages
+            .entry(project.to_path_buf())
+            .or_insert_with(|| self.load(project));
+
+        // only copies the model if a completion request still holds on to it
+        Arc::make_mut(&mut project_usage.usage).record(reference);
+        project_usage.dirty = true;
+
+        if project_usage.persisted_at.elapsed() >= PERSIST_INTERVAL {
+            self.persist(project, project_usage);
+        }
+    }
+
+    fn load(&self, project: &Path) -> ProjectUsage {
+        ProjectUsage {
+            usage: Arc::new(self.read_from_disk(project)),
+            dirty: false,
+            persisted_at: Instant::now(),
+        }
+    }
+
+    fn persist(&self, project: &Path, project_usage: &mut ProjectUsage) {
+        if let Err(err) = self.write_to_disk(project, &project_usage.usage) {
+            tracing::warn!("Failed to persist completion usage: {err}");
+        }
+
+        project_usage.dirty = false;
+        project_usage.persisted_at = Instant::now();
+    }
+
+    fn file_path(&self, project: &Path) -> PathBuf {
+        // FxHasher is deterministic, so a project always maps to the same file
+        let mut hasher = FxHasher::default();
+        project.hash(&mut hasher);
+        self.dir
+            .join(format!("completion-usage-{:x}.json", hasher.finish()))
+    }
+
+    fn read_from_disk(&self, project: &Path) -> CompletionUsage {
+        fs::read_to_string(self.file_path(project))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn write_to_disk(&self, project: &Path, usage: &CompletionUsage) -> std::io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.file_path(project), serde_json::to_string(usage)?)
+    }
+}
+
+impl Drop for CompletionUsageManager {
+    fn drop(&mut self) {
+        let usages = std::mem::take(self.usages.get_mut().unwrap());
+
+        for (project, mut project_usage) in usages {
+            if project_usage.dirty {
+                self.persist(&project, &mut project_usage);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use pgls_completions::{CompletionItemKind, CompletionItemReference};
+
+    use super::CompletionUsageManager;
+
+    #[test]
+    fn persists_usage_per_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let reference = CompletionItemReference {
+            kind: CompletionItemKind::Table,
+            name: "users".into(),
+            schema: Some("public".into()),
+            table: None,
+        };
+
+        let manager = CompletionUsageManager::new(dir.path().to_path_buf());
+        manager.record(Path::new("/projects/one"), &reference);
+        manager.record(Path::new("/projects/one"), &reference);
+
+        // recorded items are not written right away, but when the manager is dropped
+        assert!(!manager.file_path(Path::new("/projects/one")).exists());
+        drop(manager);
+
+        // a fresh manager reads the persisted model
+        let manager = CompletionUsageManager::new(dir.path().to_path_buf());
+        assert_eq!(manager.get(Path::new("/projects/one")).count(&reference), 2);
+        assert_eq!(manager.get(Path::new("/projects/two")).count(&reference), 0);
+    }
+}
@@ -1,6 +1,6 @@
 use sqlx::PgPool;
 
-use crate::settings::DatabaseSettings;
+use crate::settings::{DatabaseSettings, SshTunnelSettings};
 
 /// A unique identifier for database connection settings
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -9,6 +9,9 @@ pub(crate) struct ConnectionKey {
     pub port: u16,
     pub username: String,
     pub database: String,
+    /// The SSH tunnel the connection goes through, so that the same database behind
+    /// different bastions gets its own pool
+    pub ssh: Option<SshTunnelSettings>,
 }
 
 impl From<&DatabaseSettings> for ConnectionKey {
@@ -18,6 +21,7 @@ impl From<&DatabaseSettings> for ConnectionKey {
             port: settings.port,
             username: settings.username.clone(),
             database: settings.database.clone(),
+            ssh: settings.ssh.clone(),
         }
     }
 }
@@ -32,12 +36,14 @@ impl From<&PgPool> for ConnectionKey {
                 port: conn.get_port(),
                 username: conn.get_username().to_string(),
                 database: String::new(),
+                ssh: None,
             },
             Some(db) => Self {
                 host: conn.get_host().to_string(),
                 port: conn.get_port(),
                 username: conn.get_username().to_string(),
                 database: db.to_string(),
+                ssh: None,
             },
         }
     }
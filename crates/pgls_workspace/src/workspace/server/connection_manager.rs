@@ -3,28 +3,59 @@ use std::str::FromStr;
 use std::sync::RwLock;
 use std::time::{Duration, Instant};
 
-use sqlx::{PgPool, Postgres, pool::PoolOptions, postgres::PgConnectOptions};
+use sqlx::{
+    PgPool, Postgres,
+    pool::PoolOptions,
+    postgres::{PgConnectOptions, PgSslMode},
+};
 
-use crate::settings::DatabaseSettings;
+use crate::{
+    WorkspaceError,
+    libpq::{pgpass_password, run_password_command},
+    settings::DatabaseSettings,
+};
 
-use super::connection_key::ConnectionKey;
+use super::{connection_key::ConnectionKey, ssh_tunnel::SshTunnel};
+
+const DEFAULT_PASSWORD: &str = "postgres";
+
+/// How long a connection that failed to set up is not retried
+const FAILURE_BACKOFF: Duration = Duration::from_secs(30);
 
 /// Cached connection pool with last access time
 struct CachedPool {
     pool: PgPool,
     last_accessed: Instant,
     idle_timeout: Duration,
+    /// The SSH tunnel the pool connects through, closed with the pool
+    tunnel: Option<SshTunnel>,
+}
+
+impl CachedPool {
+    /// Whether the pool can still connect. The pool has to be recreated once its SSH tunnel
+    /// is gone.
+    fn is_usable(&self) -> bool {
+        self.tunnel.as_ref().is_none_or(SshTunnel::is_alive)
+    }
+}
+
+/// A connection that failed to set up, e.g. because its SSH tunnel could not be opened
+struct FailedConnection {
+    error: String,
+    failed_at: Instant,
 }
 
 #[derive(Default)]
 pub struct ConnectionManager {
     pools: RwLock<HashMap<ConnectionKey, CachedPool>>,
+    failures: RwLock<HashMap<ConnectionKey, FailedConnection>>,
 }
 
 impl ConnectionManager {
     pub fn new() -> Self {
         Self {
             pools: RwLock::new(HashMap::new()),
+            failures: RwLock::new(HashMap::new()),
         }
     }
 
@@ -32,28 +63,67 @@ impl ConnectionManager {
     /// If a pool already exists for these settings, it will be returned.
     /// If not, a new pool will be created if connections are enabled.
     /// Will also clean up idle connections that haven't been accessed for a while.
-    pub(crate) fn get_pool(&self, settings: &DatabaseSettings) -> Option<PgPool> {
+    ///
+    /// Returns an error if the connection can't be set up, e.g. because its SSH tunnel fails.
+    /// Failures are remembered for a while, so that the connection is not set up again on
+    /// every request.
+    pub(crate) fn get_pool(
+        &self,
+        settings: &DatabaseSettings,
+    ) -> Result<Option<PgPool>, WorkspaceError> {
         let key = ConnectionKey::from(settings);
 
         if !settings.enable_connection {
             tracing::info!("Database connection disabled.");
-            return None;
+            return Ok(None);
         }
 
         {
             if let Ok(pools) = self.pools.read() {
-                if let Some(cached_pool) = pools.get(&key) {
-                    return Some(cached_pool.pool.clone());
+                if let Some(cached_pool) = pools.get(&key).filter(|pool| pool.is_usable()) {
+                    return Ok(Some(cached_pool.pool.clone()));
                 }
             }
         }
 
+        if let Some(failure) = self
+            .failures
+            .read()
+            .unwrap()
+            .get(&key)
+            .filter(|failure| failure.failed_at.elapsed() < FAILURE_BACKOFF)
+        {
+            return Err(WorkspaceError::database_connection(failure.error.clone()));
+        }
+
+        // The tunnel is opened without holding the lock, as that can take until the
+        // connection timeout.
+        let (config, tunnel) = match self.connect(settings) {
+            Ok(connection) => connection,
+            Err(err) => {
+                self.failures.write().unwrap().insert(
+                    key,
+                    FailedConnection {
+                        error: err.to_string(),
+                        failed_at: Instant::now(),
+                    },
+                );
+                return Err(err);
+            }
+        };
+        self.failures.write().unwrap().remove(&key);
+
         let mut pools = self.pools.write().unwrap();
 
-        // Double-check after acquiring write lock
-        if let Some(cached_pool) = pools.get_mut(&key) {
+        // Double-check after acquiring write lock. A tunnel opened in the meantime is closed
+        // when ours is dropped.
+        if let Some(cached_pool) = pools.get_mut(&key).filter(|pool| pool.is_usable()) {
             cached_pool.last_accessed = Instant::now();
-            return Some(cached_pool.pool.clone());
+            return Ok(Some(cached_pool.pool.clone()));
+        }
+
+        if pools.remove(&key).is_some() {
+            tracing::warn!("SSH tunnel to the database closed, reconnecting");
         }
 
         // Clean up idle connections before creating new ones to avoid unbounded growth
@@ -72,27 +142,8 @@ impl ConnectionManager {
         });
 
         // Create a new pool
-        let config = if let Some(uri) = settings.connection_string.as_ref() {
-            match PgConnectOptions::from_str(uri) {
-                Ok(options) => options,
-                Err(err) => {
-                    tracing::error!("Failed to parse database connection URI: {err}");
-                    return None;
-                }
-            }
-        } else {
-            PgConnectOptions::new()
-                .host(&settings.host)
-                .port(settings.port)
-                .username(&settings.username)
-                .password(&settings.password)
-                .database(&settings.database)
-        };
-
-        let timeout = settings.conn_timeout_secs;
-
         let pool = PoolOptions::<Postgres>::new()
-            .acquire_timeout(timeout)
+            .acquire_timeout(settings.conn_timeout_secs)
             .acquire_slow_threshold(Duration::from_secs(2))
             .connect_lazy_with(config);
 
@@ -101,10 +152,95 @@ impl ConnectionManager {
             last_accessed: Instant::now(),
             // TODO: add this to the db settings, for now default to five minutes
             idle_timeout: Duration::from_secs(60 * 5),
+            tunnel,
         };
 
         pools.insert(key, cached_pool);
 
-        Some(pool)
+        Ok(Some(pool))
+    }
+
+    /// Builds the options to connect with and opens the SSH tunnel if one is configured.
+    fn connect(
+        &self,
+        settings: &DatabaseSettings,
+    ) -> Result<(PgConnectOptions, Option<SshTunnel>), WorkspaceError> {
+        let config = connect_options(settings)?;
+
+        match settings.ssh.as_ref() {
+            Some(ssh) => {
+                let tunnel = SshTunnel::open(
+                    ssh,
+                    &settings.host,
+                    settings.port,
+                    settings.conn_timeout_secs,
+                )
+                .map_err(WorkspaceError::database_connection)?;
+                let config = config.host("127.0.0.1").port(tunnel.local_port());
+                Ok((config, Some(tunnel)))
+            }
+            None => Ok((config, None)),
+        }
+    }
+}
+
+/// Builds the options to connect with. Passwords that are not configured are taken from the
+/// password command or the password file, like libpq does.
+fn connect_options(settings: &DatabaseSettings) -> Result<PgConnectOptions, WorkspaceError> {
+    let mut config = if let Some(uri) = settings.connection_string.as_ref() {
+        let options = PgConnectOptions::from_str(uri).map_err(|err| {
+            WorkspaceError::database_connection(format!(
+                "Failed to parse database connection URI: {err}"
+            ))
+        })?;
+
+        match settings.password_command.as_deref() {
+            Some(command) => options.password(&password_from_command(command)?),
+            None => options,
+        }
+    } else {
+        let password = match (&settings.password, &settings.password_command) {
+            (Some(password), _) => password.clone(),
+            (None, Some(command)) => password_from_command(command)?,
+            (None, None) => pgpass_password(
+                &settings.host,
+                settings.port,
+                &settings.database,
+                &settings.username,
+            )
+            // the password everyone used before credentials could be configured otherwise
+            .unwrap_or_else(|| DEFAULT_PASSWORD.to_string()),
+        };
+
+        PgConnectOptions::new_without_pgpass()
+            .host(&settings.host)
+            .port(settings.port)
+            .username(&settings.username)
+            .password(&password)
+            .database(&settings.database)
+    };
+
+    if let Some(ssl_mode) = settings.ssl_mode.as_deref() {
+        let mode = PgSslMode::from_str(ssl_mode).map_err(|err| {
+            WorkspaceError::database_connection(format!(
+                "Invalid database SSL mode \"{ssl_mode}\": {err}"
+            ))
+        })?;
+        config = config.ssl_mode(mode);
+    }
+    if let Some(cert) = settings.ssl_root_cert.as_deref() {
+        config = config.ssl_root_cert(cert);
+    }
+    if let Some(cert) = settings.ssl_cert.as_deref() {
+        config = config.ssl_client_cert(cert);
     }
+    if let Some(key) = settings.ssl_key.as_deref() {
+        config = config.ssl_client_key(key);
+    }
+
+    Ok(config)
+}
+
+fn password_from_command(command: &str) -> Result<String, WorkspaceError> {
+    run_password_command(command).map_err(WorkspaceError::database_connection)
 }
@@ -0,0 +1,130 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use pgls_fs::PgLSPath;
+
+use crate::settings::{DatabaseSettings, Settings};
+
+const DIRECTIVE: &str = "pgls-connection:";
+
+/// Keeps track of the database connections open files select, either with a
+/// `-- pgls-connection: <name>` comment or explicitly via
+/// [crate::Workspace::select_connection].
+#[derive(Default)]
+pub struct ConnectionSelection {
+    files: RwLock<HashMap<PgLSPath, FileConnection>>,
+}
+
+#[derive(Default)]
+struct FileConnection {
+    directive: Option<String>,
+    /// `Some(None)` if the default connection was selected explicitly.
+    selected: Option<Option<String>>,
+}
+
+impl ConnectionSelection {
+    /// Updates the connection the file selects with a directive.
+    pub fn update_content(&self, path: &PgLSPath, content: &str) {
+        let directive = connection_directive(content);
+        let mut files = self.files.write().unwrap();
+        match files.get_mut(path) {
+            Some(file) => file.directive = directive,
+            None if directive.is_some() => {
+                files.insert(
+                    path.clone(),
+                    FileConnection {
+                        directive,
+                        selected: None,
+                    },
+                );
+            }
+            None => {}
+        }
+    }
+
+    /// Selects a connection for the file, `None` for the default connection. Takes
+    /// precedence over directives and the `files.connections` configuration.
+    pub fn select(&self, path: &PgLSPath, connection: Option<String>) {
+        self.files
+            .write()
+            .unwrap()
+            .entry(path.clone())
+            .or_default()
+            .selected = Some(connection);
+    }
+
+    pub fn remove(&self, path: &PgLSPath) {
+        self.files.write().unwrap().remove(path);
+    }
+
+    /// Returns the name of the connection the file uses, `None` for the default connection.
+    pub fn connection_name(&self, settings: &Settings, path: &PgLSPath) -> Option<String> {
+        if let Some(file) = self.files.read().unwrap().get(path) {
+            if let Some(selected) = &file.selected {
+                return selected.clone();
+            }
+            if let Some(directive) = &file.directive {
+                return Some(directive.clone());
+            }
+        }
+
+        settings.files.connection(path).map(str::to_string)
+    }
+
+    /// Returns the settings of the connection the file uses. `None` if it refers to a
+    /// connection that is not configured.
+    pub fn db_settings<'a>(
+        &self,
+        settings: &'a Settings,
+        path: &PgLSPath,
+    ) -> Option<&'a DatabaseSettings> {
+        let name = self.connection_name(settings, path);
+        let db = settings.db.connection(name.as_deref());
+        if db.is_none() {
+            tracing::warn!(
+                "{} refers to the unknown database connection {name:?}",
+                path.display()
+            );
+        }
+        db
+    }
+}
+
+/// Returns the connection a `-- pgls-connection: <name>` comment selects. Only the comments
+/// at the start of the content are considered.
+fn connection_directive(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map_while(|line| line.strip_prefix("--"))
+        .find_map(|comment| {
+            let name = comment.trim_start().strip_prefix(DIRECTIVE)?.trim();
+            (!name.is_empty()).then(|| name.to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::connection_directive;
+
+    #[test]
+    fn finds_connection_directives() {
+        assert_eq!(
+            connection_directive("-- pgls-connection: analytics\nselect 1;"),
+            Some("analytics".to_string())
+        );
+        assert_eq!(
+            connection_directive("-- migration\n\n  --pgls-connection:reporting  \nselect 1;"),
+            Some("reporting".to_string())
+        );
+        assert_eq!(
+            connection_directive("select 1;\n-- pgls-connection: reporting\n"),
+            None
+        );
+        assert_eq!(connection_directive("-- pgls-connection:\nselect 1;"), None);
+        assert_eq!(
+            connection_directive("select '-- pgls-connection: analytics';"),
+            None
+        );
+    }
+}
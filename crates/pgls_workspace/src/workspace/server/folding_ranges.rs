@@ -0,0 +1,137 @@
+use pgls_lexer::SyntaxKind;
+use pgls_query::protobuf::TransactionStmtKind;
+use pgls_text_size::TextRange;
+
+use crate::features::folding_ranges::{FoldingRange, FoldingRangeKind};
+
+use super::document::{Document, ExecuteStatementMapper};
+
+/// Computes the foldable regions of a document: every top-level statement, the statements
+/// between `BEGIN` and `COMMIT` or `ROLLBACK`, function bodies and comment blocks.
+pub(crate) fn folding_ranges(doc: &Document) -> Vec<FoldingRange> {
+    let mut ranges = vec![];
+    let mut open_transactions = vec![];
+
+    for (id, range, content, ast) in doc.iter(ExecuteStatementMapper) {
+        if !id.is_root() {
+            continue;
+        }
+
+        ranges.push(FoldingRange {
+            range,
+            kind: FoldingRangeKind::Statement,
+        });
+
+        match ast {
+            Some(pgls_query::NodeEnum::TransactionStmt(stmt)) => match stmt.kind() {
+                TransactionStmtKind::TransStmtBegin | TransactionStmtKind::TransStmtStart => {
+                    open_transactions.push(range.start());
+                }
+                TransactionStmtKind::TransStmtCommit
+                | TransactionStmtKind::TransStmtRollback
+                | TransactionStmtKind::TransStmtPrepare => {
+                    if let Some(start) = open_transactions.pop() {
+                        ranges.push(FoldingRange {
+                            range: TextRange::new(start, range.end()),
+                            kind: FoldingRangeKind::Transaction,
+                        });
+                    }
+                }
+                _ => {}
+            },
+            Some(pgls_query::NodeEnum::CreateFunctionStmt(_)) => {
+                if let Some(body) = function_body_range(&content) {
+                    ranges.push(FoldingRange {
+                        range: body + range.start(),
+                        kind: FoldingRangeKind::FunctionBody,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    ranges.extend(
+        comment_ranges(doc.get_document_content())
+            .into_iter()
+            .map(|range| FoldingRange {
+                range,
+                kind: FoldingRangeKind::Comment,
+            }),
+    );
+
+    ranges.sort_by_key(|r| (r.range.start(), std::cmp::Reverse(r.range.end())));
+    ranges
+}
+
+/// Finds the string literal that follows `AS` in a `CREATE FUNCTION` statement. Bodies written
+/// as `BEGIN ATOMIC ... END` are not string literals and are not found.
+fn function_body_range(statement: &str) -> Option<TextRange> {
+    let lexed = pgls_lexer::lex(statement);
+
+    (0..lexed.len())
+        .skip_while(|idx| lexed.kind(*idx) != SyntaxKind::AS_KW)
+        .find(|idx| {
+            matches!(
+                lexed.kind(*idx),
+                SyntaxKind::STRING | SyntaxKind::ESC_STRING | SyntaxKind::DOLLAR_QUOTED_STRING
+            )
+        })
+        .map(|idx| lexed.range(idx))
+}
+
+/// Finds block comments and runs of comments on consecutive lines. A comment block ends at
+/// the first blank line or token that isn't whitespace.
+fn comment_ranges(content: &str) -> Vec<TextRange> {
+    let lexed = pgls_lexer::lex(content);
+
+    let mut ranges: Vec<TextRange> = vec![];
+    let mut current: Option<TextRange> = None;
+
+    for idx in 0..lexed.len() {
+        match lexed.kind(idx) {
+            SyntaxKind::COMMENT => {
+                let range = lexed.range(idx);
+                current = Some(current.map_or(range, |c| c.cover(range)));
+            }
+            SyntaxKind::SPACE | SyntaxKind::TAB => {}
+            SyntaxKind::LINE_ENDING if lexed.line_ending_count(idx) == 1 => {}
+            _ => ranges.extend(current.take()),
+        }
+    }
+    ranges.extend(current);
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use pgls_text_size::TextRange;
+
+    use super::{comment_ranges, function_body_range};
+
+    #[test]
+    fn groups_comments_on_consecutive_lines() {
+        let content = "-- first\n-- second\n\n-- third\nselect 1;\n/* block\n*/";
+
+        let ranges: Vec<&str> = comment_ranges(content)
+            .into_iter()
+            .map(|range| &content[range])
+            .collect();
+
+        assert_eq!(
+            ranges,
+            vec!["-- first\n-- second", "-- third", "/* block\n*/"]
+        );
+    }
+
+    #[test]
+    fn finds_function_body() {
+        let statement = "create function f() returns void as $$\nbegin\n  perform 1;\nend;\n$$ language plpgsql;";
+
+        assert_eq!(
+            function_body_range(statement),
+            Some(TextRange::new(36.into(), 65.into()))
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use pgls_schema_cache::SchemaCache;
+
+use crate::features::functions::{FunctionCaller, FunctionCallerKind};
+
+/// Finds the functions in the schema cache whose body calls the function with the given name,
+/// and the triggers that execute it. Calls are matched by name, so overloads are not told apart.
+/// Comments and string literals in the bodies are skipped, including dynamic SQL.
+pub(crate) fn find_callers(
+    schema_cache: &SchemaCache,
+    schema: Option<&str>,
+    name: &str,
+) -> Vec<FunctionCaller> {
+    let matches_schema = |candidate: &str| schema.is_none_or(|schema| schema == candidate);
+
+    let functions = schema_cache
+        .functions
+        .iter()
+        .filter(|function| !(function.name == name && matches_schema(&function.schema)))
+        .filter(|function| {
+            function
+                .body
+                .as_deref()
+                .is_some_and(|body| calls(body, name, matches_schema))
+        })
+        .map(|function| FunctionCaller {
+            kind: FunctionCallerKind::Function,
+            name: format!(
+                "{}.{}({})",
+                function.schema,
+                function.name,
+                function.identity_argument_types.as_deref().unwrap_or("")
+            ),
+        });
+
+    let triggers = schema_cache
+        .triggers
+        .iter()
+        .filter(|trigger| trigger.proc_name == name && matches_schema(&trigger.proc_schema))
+        .map(|trigger| FunctionCaller {
+            kind: FunctionCallerKind::Trigger,
+            name: format!(
+                "{} on {}.{}",
+                trigger.name, trigger.table_schema, trigger.table_name
+            ),
+        });
+
+    functions.chain(triggers).collect()
+}
+
+/// Whether the body calls the function, i.e. contains its optionally qualified name followed
+/// by an opening parenthesis.
+fn calls(body: &str, name: &str, matches_schema: impl Fn(&str) -> bool) -> bool {
+    let tokens = tokens(body);
+
+    tokens.iter().enumerate().any(|(index, token)| {
+        if *token != Token::Ident(name.into()) || tokens.get(index + 1) != Some(&Token::Punct('('))
+        {
+            return false;
+        }
+
+        match index.checked_sub(1).map(|index| &tokens[index]) {
+            Some(Token::Punct('.')) => match index.checked_sub(2).map(|index| &tokens[index]) {
+                Some(Token::Ident(qualifier)) => matches_schema(qualifier),
+                _ => false,
+            },
+            _ => true,
+        }
+    })
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    /// An identifier or keyword. Unquoted ones are folded to lower case, like Postgres does.
+    Ident(String),
+    Punct(char),
+}
+
+/// Splits a function body into identifiers and punctuation, leaving out comments, string
+/// literals and parameters.
+fn tokens(body: &str) -> Vec<Token> {
+    let chars: Vec<char> = body.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                // block comments nest
+                let mut depth = 0;
+                while i < chars.len() {
+                    if chars[i] == '/' && chars.get(i + 1) == Some(&'*') {
+                        depth += 1;
+                        i += 2;
+                    } else if chars[i] == '*' && chars.get(i + 1) == Some(&'/') {
+                        depth -= 1;
+                        i += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else {
+                        i += 1;
+                    }
+                }
+            }
+            '\'' => {
+                // the prefix of an escape string was read as an identifier
+                let escapes = matches!(tokens.last(), Some(Token::Ident(prefix)) if prefix == "e")
+                    && i > 0
+                    && chars[i - 1].eq_ignore_ascii_case(&'e');
+                if escapes {
+                    tokens.pop();
+                }
+                i += 1;
+                while i < chars.len() {
+                    match chars[i] {
+                        '\\' if escapes => i += 2,
+                        '\'' if chars.get(i + 1) == Some(&'\'') => i += 2,
+                        '\'' => break,
+                        _ => i += 1,
+                    }
+                }
+                i += 1;
+            }
+            '"' => {
+                let mut ident = String::new();
+                i += 1;
+                while i < chars.len() {
+                    match chars[i] {
+                        '"' if chars.get(i + 1) == Some(&'"') => {
+                            ident.push('"');
+                            i += 2;
+                        }
+                        '"' => break,
+                        c => {
+                            ident.push(c);
+                            i += 1;
+                        }
+                    }
+                }
+                i += 1;
+                tokens.push(Token::Ident(ident));
+            }
+            '$' => {
+                let tag_end = (i + 1..chars.len())
+                    .find(|&end| !(chars[end].is_alphanumeric() || chars[end] == '_'))
+                    .unwrap_or(chars.len());
+                if chars.get(tag_end) == Some(&'$')
+                    && !chars.get(i + 1).is_some_and(char::is_ascii_digit)
+                {
+                    // a dollar-quoted string ends with the same tag
+                    let tag = &chars[i..=tag_end];
+                    i = (tag_end + 1..chars.len())
+                        .find(|&start| chars[start..].starts_with(tag))
+                        .map_or(chars.len(), |start| start + tag.len());
+                } else {
+                    // a parameter, e.g. $1
+                    i = tag_end;
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+                {
+                    i += 1;
+                }
+                let ident: String = chars[start..i].iter().collect();
+                tokens.push(Token::Ident(ident.to_lowercase()));
+            }
+            c if c.is_whitespace() => i += 1,
+            c => {
+                tokens.push(Token::Punct(c));
+                i += 1;
+            }
+        }
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use pgls_schema_cache::{Function, SchemaCache};
+
+    use super::find_callers;
+
+    fn function(schema: &str, name: &str, body: &str) -> Function {
+        Function {
+            schema: schema.into(),
+            name: name.into(),
+            body: Some(body.into()),
+            identity_argument_types: Some("integer".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn finds_functions_calling_by_name() {
+        let schema_cache = SchemaCache {
+            functions: vec![
+                function("public", "archive", "begin perform notify_user(id); end"),
+                function("public", "cleanup", "select public.\"notify_user\" (1)"),
+                function(
+                    "other",
+                    "unrelated",
+                    "begin perform other.notify_user(1); end",
+                ),
+                function("public", "similar", "begin perform notify_users(1); end"),
+                function(
+                    "public",
+                    "notify_user",
+                    "begin perform notify_user(id - 1); end",
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let callers: Vec<String> = find_callers(&schema_cache, Some("public"), "notify_user")
+            .into_iter()
+            .map(|caller| caller.name)
+            .collect();
+
+        assert_eq!(
+            callers,
+            vec!["public.archive(integer)", "public.cleanup(integer)"]
+        );
+    }
+
+    #[test]
+    fn skips_comments_and_strings() {
+        let schema_cache = SchemaCache {
+            functions: vec![
+                function(
+                    "public",
+                    "commented",
+                    "begin -- perform notify_user(id);\n /* notify_user(1) /* nested */ */ end",
+                ),
+                function(
+                    "public",
+                    "quoted",
+                    "begin raise notice 'call notify_user(%)', id; end",
+                ),
+                function(
+                    "public",
+                    "escaped",
+                    "begin raise notice E'it\\'s notify_user(%)', id; end",
+                ),
+                function(
+                    "public",
+                    "dynamic",
+                    "begin execute $q$select notify_user(1)$q$; end",
+                ),
+                function(
+                    "public",
+                    "after_strings",
+                    "begin raise notice 'it''s'; perform $1; perform \"Notify_User\"(1); perform NOTIFY_USER(1); end",
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let callers: Vec<String> = find_callers(&schema_cache, Some("public"), "notify_user")
+            .into_iter()
+            .map(|caller| caller.name)
+            .collect();
+
+        assert_eq!(callers, vec!["public.after_strings(integer)"]);
+    }
+}
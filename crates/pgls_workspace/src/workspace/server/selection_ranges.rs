@@ -0,0 +1,78 @@
+use pgls_text_size::{TextRange, TextSize};
+
+use super::document::{CursorPositionFilter, Document, WithCSTMapper};
+
+/// Collects the ranges around a position, from the innermost syntax node up to the top-level
+/// statement. Statements nested in a SQL function body come before the function that contains
+/// them.
+pub(crate) fn selection_ranges(doc: &Document, position: TextSize) -> Vec<TextRange> {
+    let statements: Vec<_> = doc
+        .iter_with_filter(WithCSTMapper, CursorPositionFilter::new(position))
+        .collect();
+
+    let mut ranges: Vec<TextRange> = vec![];
+    let mut push = |range: TextRange| {
+        if ranges
+            .last()
+            .is_none_or(|last| range != *last && range.contains_range(*last))
+        {
+            ranges.push(range);
+        }
+    };
+
+    // sub-statements are yielded after the statement they are part of
+    for (_, range, tree) in statements.iter().rev() {
+        for node_range in node_ranges(tree, position - range.start()) {
+            push(node_range + range.start());
+        }
+        push(*range);
+    }
+
+    ranges
+}
+
+/// Walks from the smallest node at the offset to the root of the tree.
+fn node_ranges(tree: &tree_sitter::Tree, offset: TextSize) -> Vec<TextRange> {
+    let offset = usize::from(offset);
+
+    let mut node = tree.root_node().descendant_for_byte_range(offset, offset);
+    let mut ranges = vec![];
+
+    while let Some(current) = node {
+        ranges.push(TextRange::new(
+            TextSize::try_from(current.start_byte()).unwrap(),
+            TextSize::try_from(current.end_byte()).unwrap(),
+        ));
+        node = current.parent();
+    }
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use pgls_text_size::TextSize;
+
+    use super::node_ranges;
+
+    #[test]
+    fn expands_from_identifier_to_statement() {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&pgls_treesitter_grammar::LANGUAGE.into())
+            .unwrap();
+
+        let sql = "select id from users where email = 'a'";
+        let tree = parser.parse(sql, None).unwrap();
+
+        let ranges: Vec<&str> = node_ranges(&tree, TextSize::new(28))
+            .into_iter()
+            .map(|range| &sql[range])
+            .collect();
+
+        assert_eq!(ranges.first(), Some(&"email"));
+        assert_eq!(ranges.last(), Some(&sql));
+        assert!(ranges.contains(&"email = 'a'"));
+        assert!(ranges.contains(&"where email = 'a'"));
+    }
+}
@@ -0,0 +1,340 @@
+use std::io::{BufRead, BufReader, Read};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use pgls_configuration::database::KnownHostsPolicy;
+
+use crate::settings::SshTunnelSettings;
+
+/// A local port forwarded to the database through an `ssh` process. The process is killed
+/// when the tunnel is dropped.
+pub(crate) struct SshTunnel {
+    process: Mutex<Child>,
+    local_port: u16,
+}
+
+impl SshTunnel {
+    /// Starts `ssh` to forward a free local port to `target_host:target_port` and waits until
+    /// the tunnel accepts connections.
+    pub fn open(
+        settings: &SshTunnelSettings,
+        target_host: &str,
+        target_port: u16,
+        timeout: Duration,
+    ) -> Result<Self, String> {
+        // ssh would read these as options
+        if settings.host.starts_with('-') {
+            return Err(format!("Invalid SSH host \"{}\"", settings.host));
+        }
+        if let Some(user) = settings.user.as_ref().filter(|user| user.starts_with('-')) {
+            return Err(format!("Invalid SSH user \"{user}\""));
+        }
+
+        let local_port = free_local_port()
+            .map_err(|err| format!("Failed to find a free port for the SSH tunnel: {err}"))?;
+
+        let cmd = ssh_command(settings, local_port, target_host, target_port, timeout);
+        let tunnel = Self::start(cmd, &settings.host, local_port, timeout)?;
+
+        tracing::info!(
+            "Opened SSH tunnel to {target_host}:{target_port} via {} on port {local_port}",
+            settings.host
+        );
+
+        Ok(tunnel)
+    }
+
+    /// Spawns the process that forwards `local_port` and waits until the port accepts
+    /// connections.
+    fn start(
+        mut cmd: Command,
+        host: &str,
+        local_port: u16,
+        timeout: Duration,
+    ) -> Result<Self, String> {
+        let mut process = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("Failed to start ssh: {err}"))?;
+
+        let local_addr = SocketAddr::from((Ipv4Addr::LOCALHOST, local_port));
+        let started = Instant::now();
+
+        loop {
+            if let Some(status) = process.try_wait().ok().flatten() {
+                let mut stderr = String::new();
+                if let Some(mut pipe) = process.stderr.take() {
+                    let _ = pipe.read_to_string(&mut stderr);
+                }
+                return Err(format!(
+                    "SSH tunnel to {host} exited with {status}: {}",
+                    stderr.trim()
+                ));
+            }
+
+            if TcpStream::connect_timeout(&local_addr, Duration::from_millis(100)).is_ok() {
+                break;
+            }
+
+            if started.elapsed() > timeout {
+                let _ = process.kill();
+                let _ = process.wait();
+                return Err(format!("Timed out opening the SSH tunnel to {host}"));
+            }
+
+            std::thread::sleep(Duration::from_millis(50));
+        }
+
+        // keep draining the output so that ssh never blocks on a full pipe
+        if let Some(stderr) = process.stderr.take() {
+            let host = host.to_string();
+            std::thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    tracing::warn!("SSH tunnel to {host}: {line}");
+                }
+            });
+        }
+
+        Ok(Self {
+            process: Mutex::new(process),
+            local_port,
+        })
+    }
+
+    pub fn local_port(&self) -> u16 {
+        self.local_port
+    }
+
+    /// Whether the `ssh` process is still running.
+    pub fn is_alive(&self) -> bool {
+        self.process
+            .lock()
+            .unwrap()
+            .try_wait()
+            .is_ok_and(|status| status.is_none())
+    }
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        let process = self.process.get_mut().unwrap();
+        let _ = process.kill();
+        let _ = process.wait();
+    }
+}
+
+/// The `ssh` invocation that forwards `local_port` to `target_host:target_port`.
+fn ssh_command(
+    settings: &SshTunnelSettings,
+    local_port: u16,
+    target_host: &str,
+    target_port: u16,
+    timeout: Duration,
+) -> Command {
+    let mut cmd = Command::new("ssh");
+    cmd.args(["-N", "-T"])
+        .args(["-o", "BatchMode=yes"])
+        .args(["-o", "ExitOnForwardFailure=yes"])
+        .args(["-o", "ServerAliveInterval=15"])
+        .args(["-o", "ServerAliveCountMax=3"])
+        .args([
+            "-o",
+            &format!("ConnectTimeout={}", timeout.as_secs().max(1)),
+        ])
+        .args(["-o", "LogLevel=ERROR"])
+        .args([
+            "-o",
+            match settings.known_hosts {
+                KnownHostsPolicy::Strict => "StrictHostKeyChecking=yes",
+                KnownHostsPolicy::AcceptNew => "StrictHostKeyChecking=accept-new",
+                KnownHostsPolicy::Off => "StrictHostKeyChecking=no",
+            },
+        ]);
+
+    match (&settings.known_hosts, &settings.known_hosts_file) {
+        (KnownHostsPolicy::Off, _) => {
+            cmd.args(["-o", "UserKnownHostsFile=/dev/null"]);
+        }
+        (_, Some(file)) => {
+            cmd.args(["-o", &format!("UserKnownHostsFile={file}")]);
+        }
+        _ => {}
+    }
+
+    if let Some(identity_file) = &settings.identity_file {
+        cmd.args(["-o", "IdentitiesOnly=yes", "-i", identity_file]);
+    }
+    if let Some(user) = &settings.user {
+        cmd.args(["-l", user]);
+    }
+
+    cmd.args(["-p", &settings.port.to_string()])
+        .args([
+            "-L",
+            &format!("127.0.0.1:{local_port}:{target_host}:{target_port}"),
+        ])
+        .arg("--")
+        .arg(&settings.host);
+
+    cmd
+}
+
+fn free_local_port() -> std::io::Result<u16> {
+    Ok(TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?
+        .local_addr()?
+        .port())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::{Ipv4Addr, TcpListener, TcpStream};
+    use std::process::Command;
+    use std::time::Duration;
+
+    use pgls_configuration::database::KnownHostsPolicy;
+
+    use crate::settings::SshTunnelSettings;
+
+    use super::{SshTunnel, free_local_port, ssh_command};
+
+    /// Set for the test binary when it is started as the `ssh` process of
+    /// [forwards_connections_through_the_tunnel], as `<local port>:<target address>`.
+    const FAKE_SSH_FORWARD: &str = "PGLS_FAKE_SSH_FORWARD";
+
+    fn settings(host: &str) -> SshTunnelSettings {
+        SshTunnelSettings {
+            host: host.into(),
+            port: 22,
+            user: None,
+            identity_file: None,
+            known_hosts: KnownHostsPolicy::Strict,
+            known_hosts_file: None,
+        }
+    }
+
+    #[test]
+    fn reports_unreachable_ssh_servers() {
+        let settings = SshTunnelSettings {
+            port: 1,
+            ..settings("127.0.0.1")
+        };
+
+        let err = SshTunnel::open(&settings, "localhost", 5432, Duration::from_secs(5))
+            .err()
+            .expect("the tunnel should fail");
+
+        assert!(err.starts_with("SSH tunnel to 127.0.0.1 exited"), "{err}");
+    }
+
+    #[test]
+    fn rejects_hosts_and_users_that_look_like_options() {
+        let err = SshTunnel::open(
+            &settings("-oProxyCommand=touch /tmp/pwned"),
+            "localhost",
+            5432,
+            Duration::from_secs(5),
+        )
+        .err()
+        .expect("the host should be rejected");
+        assert!(err.starts_with("Invalid SSH host"), "{err}");
+
+        let settings = SshTunnelSettings {
+            user: Some("-oProxyCommand=touch /tmp/pwned".into()),
+            ..settings("bastion")
+        };
+        let err = SshTunnel::open(&settings, "localhost", 5432, Duration::from_secs(5))
+            .err()
+            .expect("the user should be rejected");
+        assert!(err.starts_with("Invalid SSH user"), "{err}");
+    }
+
+    #[test]
+    fn passes_the_host_after_the_options() {
+        let cmd = ssh_command(
+            &settings("bastion"),
+            6543,
+            "db.internal",
+            5432,
+            Duration::from_secs(5),
+        );
+
+        let args: Vec<_> = cmd
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+
+        assert_eq!(args[args.len() - 2..], ["--", "bastion"]);
+        assert!(
+            args.windows(2)
+                .any(|args| args == ["-L", "127.0.0.1:6543:db.internal:5432"])
+        );
+    }
+
+    #[test]
+    fn forwards_connections_through_the_tunnel() {
+        let database = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let database_addr = database.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for mut stream in database.incoming().map_while(Result::ok) {
+                let _ = stream.write_all(b"hello");
+            }
+        });
+
+        // the test binary plays ssh, see fake_ssh
+        let local_port = free_local_port().unwrap();
+        let test_name = concat!(module_path!(), "::fake_ssh");
+        let mut cmd = Command::new(std::env::current_exe().unwrap());
+        cmd.args([
+            test_name.split_once("::").unwrap().1,
+            "--exact",
+            "--ignored",
+            "--nocapture",
+        ])
+        .env(FAKE_SSH_FORWARD, format!("{local_port}:{database_addr}"));
+
+        let tunnel = SshTunnel::start(cmd, "fake", local_port, Duration::from_secs(10))
+            .expect("the tunnel should open");
+        assert_eq!(tunnel.local_port(), local_port);
+        assert!(tunnel.is_alive());
+
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, local_port)).unwrap();
+        let mut received = String::new();
+        stream.read_to_string(&mut received).unwrap();
+        assert_eq!(received, "hello");
+
+        drop(tunnel);
+        assert!(TcpStream::connect((Ipv4Addr::LOCALHOST, local_port)).is_err());
+    }
+
+    /// Forwards a local port like `ssh -L` does, when started by
+    /// [forwards_connections_through_the_tunnel].
+    #[test]
+    #[ignore = "started by forwards_connections_through_the_tunnel"]
+    fn fake_ssh() {
+        let Ok(forward) = std::env::var(FAKE_SSH_FORWARD) else {
+            return;
+        };
+        let (local_port, target) = forward.split_once(':').unwrap();
+
+        let listener =
+            TcpListener::bind((Ipv4Addr::LOCALHOST, local_port.parse().unwrap())).unwrap();
+        for client in listener.incoming().map_while(Result::ok) {
+            let Ok(server) = TcpStream::connect(target) else {
+                continue;
+            };
+            let (mut client_read, mut server_write) =
+                (client.try_clone().unwrap(), server.try_clone().unwrap());
+            let (mut server_read, mut client_write) = (server, client);
+            std::thread::spawn(move || std::io::copy(&mut client_read, &mut server_write));
+            std::thread::spawn(move || {
+                let _ = std::io::copy(&mut server_read, &mut client_write);
+                let _ = client_write.shutdown(std::net::Shutdown::Both);
+            });
+        }
+    }
+}
@@ -0,0 +1,726 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use futures::TryStreamExt;
+use pgls_analyse::{AnalyserOptions, AnalysisFilter, RuleFilter};
+use pgls_analyser::{AnalysableStatement, Analyser, AnalyserConfig, AnalyserParams};
+use pgls_diagnostics::{Error, PrintDescription};
+use pgls_fs::PgLSPath;
+use pgls_query::NodeEnum;
+use pgls_query::protobuf::{ReindexObjectType, TransactionStmtKind};
+use pgls_text_size::{TextRange, TextSize};
+use sqlx::postgres::{PgDatabaseError, PgErrorPosition, PgRow, PgValueRef};
+use sqlx::{Column, Either, Executor, PgPool, Row, TypeInfo, ValueRef};
+
+use crate::features::code_actions::{
+    ExecuteStatementResult, ExecuteStatementsResult, ExecutionConfirmation, ResultSet,
+    ResultSetColumn, StatementExecutionError, StatementExecutionProgress,
+};
+use crate::settings::DatabaseSettings;
+
+/// The lint rules that flag statements which destroy data. Executing such a statement
+/// requires confirmation, regardless of whether the rules are enabled in the configuration.
+const DESTRUCTIVE_RULES: &[RuleFilter<'static>] = &[
+    RuleFilter::Rule("safety", "banDropColumn"),
+    RuleFilter::Rule("safety", "banDropDatabase"),
+    RuleFilter::Rule("safety", "banDropTable"),
+    RuleFilter::Rule("safety", "banTruncateCascade"),
+];
+
+/// Returns why executing the statement requires confirmation, if it destroys data.
+pub(crate) fn confirmation_reason(stmt: &NodeEnum, range: TextRange) -> Option<String> {
+    let options = AnalyserOptions::default();
+    let analyser = Analyser::new(AnalyserConfig {
+        options: &options,
+        filter: AnalysisFilter::from_enabled_rules(DESTRUCTIVE_RULES),
+    });
+
+    let reasons: Vec<String> = analyser
+        .run(AnalyserParams {
+            stmts: vec![AnalysableStatement {
+                root: stmt.clone(),
+                range,
+            }],
+            schema_cache: None,
+        })
+        .into_iter()
+        .map(|diagnostic| PrintDescription(&Error::from(diagnostic)).to_string())
+        .collect();
+
+    (!reasons.is_empty()).then(|| reasons.join(" "))
+}
+
+/// The confirmations the server asked for. Statements that destroy data are only executed
+/// with the token of a confirmation that was issued for the same statements of the same file.
+#[derive(Default)]
+pub(crate) struct PendingConfirmations {
+    next_token: AtomicU64,
+    pending: Mutex<HashMap<u64, (PgLSPath, String)>>,
+}
+
+impl PendingConfirmations {
+    /// Issues a confirmation for executing `sql` in `path`. Earlier confirmations for the file
+    /// become invalid.
+    pub(crate) fn request(
+        &self,
+        path: &PgLSPath,
+        sql: String,
+        reason: String,
+    ) -> ExecutionConfirmation {
+        let token = self.next_token.fetch_add(1, Ordering::Relaxed);
+
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, (pending_path, _)| pending_path != path);
+        pending.insert(token, (path.clone(), sql));
+
+        ExecutionConfirmation { reason, token }
+    }
+
+    /// Returns whether `token` confirms executing `sql` in `path`. A token is only valid once.
+    pub(crate) fn confirm(&self, token: u64, path: &PgLSPath, sql: &str) -> bool {
+        let mut pending = self.pending.lock().unwrap();
+        match pending.get(&token) {
+            Some((pending_path, pending_sql)) if pending_path == path && pending_sql == sql => {
+                pending.remove(&token);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ExecutionSettings {
+    pub(crate) row_limit: usize,
+    pub(crate) statement_timeout: Duration,
+    pub(crate) lock_timeout: Duration,
+    /// Run the statement inside a transaction that is rolled back.
+    pub(crate) dry_run: bool,
+}
+
+impl ExecutionSettings {
+    pub(crate) fn new(settings: &DatabaseSettings, dry_run: bool) -> Self {
+        Self {
+            row_limit: settings.execution_row_limit,
+            statement_timeout: settings.execution_timeout_secs,
+            lock_timeout: settings.execution_lock_timeout_secs,
+            dry_run,
+        }
+    }
+}
+
+/// Keeps track of the statements that are currently being executed, so that they can be
+/// cancelled.
+#[derive(Default)]
+pub(crate) struct RunningExecutions {
+    next_id: AtomicU64,
+    executions: Mutex<HashMap<u64, RunningExecution>>,
+    /// Notified whenever an execution makes progress or ends.
+    progress_changed: Condvar,
+}
+
+struct RunningExecution {
+    path: PgLSPath,
+    pool: PgPool,
+    backend_pid: i32,
+    progress: StatementExecutionProgress,
+}
+
+impl RunningExecutions {
+    fn register(self: &Arc<Self>, execution: RunningExecution) -> RunningExecutionGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.executions.lock().unwrap().insert(id, execution);
+
+        RunningExecutionGuard {
+            executions: Arc::clone(self),
+            id,
+        }
+    }
+
+    pub(crate) fn is_running(&self, path: &PgLSPath) -> bool {
+        self.executions
+            .lock()
+            .unwrap()
+            .values()
+            .any(|execution| execution.path == *path)
+    }
+
+    /// Returns the progress of the first execution running for `path`.
+    pub(crate) fn progress(&self, path: &PgLSPath) -> Option<StatementExecutionProgress> {
+        Self::find_progress(&self.executions.lock().unwrap(), path)
+    }
+
+    /// Waits until the execution running for `path` executed more than `known_executed`
+    /// statements, or ended, but at most for `timeout`. Returns the progress at that point.
+    pub(crate) fn wait_for_progress(
+        &self,
+        path: &PgLSPath,
+        known_executed: usize,
+        timeout: Duration,
+    ) -> Option<StatementExecutionProgress> {
+        let executions = self.executions.lock().unwrap();
+        let (executions, _) = self
+            .progress_changed
+            .wait_timeout_while(executions, timeout, |executions| {
+                Self::find_progress(executions, path)
+                    .is_some_and(|progress| progress.executed <= known_executed)
+            })
+            .unwrap();
+
+        Self::find_progress(&executions, path)
+    }
+
+    fn find_progress(
+        executions: &HashMap<u64, RunningExecution>,
+        path: &PgLSPath,
+    ) -> Option<StatementExecutionProgress> {
+        executions
+            .values()
+            .find(|execution| execution.path == *path)
+            .map(|execution| execution.progress.clone())
+    }
+
+    /// Returns the connection pools and backend pids of the statements running for `path`.
+    pub(crate) fn backends(&self, path: &PgLSPath) -> Vec<(PgPool, i32)> {
+        self.executions
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|execution| execution.path == *path)
+            .map(|execution| (execution.pool.clone(), execution.backend_pid))
+            .collect()
+    }
+}
+
+/// Removes the execution from [RunningExecutions] once it is done.
+struct RunningExecutionGuard {
+    executions: Arc<RunningExecutions>,
+    id: u64,
+}
+
+impl RunningExecutionGuard {
+    fn set_progress(&self, executed: usize, current: TextRange) {
+        if let Some(execution) = self.executions.executions.lock().unwrap().get_mut(&self.id) {
+            execution.progress.executed = executed;
+            execution.progress.current = Some(current);
+        }
+        self.executions.progress_changed.notify_all();
+    }
+}
+
+impl Drop for RunningExecutionGuard {
+    fn drop(&mut self) {
+        self.executions.executions.lock().unwrap().remove(&self.id);
+        self.executions.progress_changed.notify_all();
+    }
+}
+
+/// Cancels the statements running on the given backends and returns how many were cancelled.
+pub(crate) async fn cancel(backends: Vec<(PgPool, i32)>) -> Result<usize, sqlx::Error> {
+    let mut cancelled = 0;
+    for (pool, backend_pid) in backends {
+        let success: bool = sqlx::query_scalar("select pg_cancel_backend($1)")
+            .bind(backend_pid)
+            .fetch_one(&pool)
+            .await?;
+        if success {
+            cancelled += 1;
+        }
+    }
+    Ok(cancelled)
+}
+
+/// Returns whether the statement is a query that can be read through a cursor, so that only
+/// the rows within the row limit are transferred.
+pub(crate) fn is_cursor_query(stmt: &NodeEnum) -> bool {
+    let NodeEnum::SelectStmt(select) = stmt else {
+        return false;
+    };
+
+    // `select into` creates a table, and cursors can't contain data-modifying CTEs
+    select.into_clause.is_none()
+        && select.with_clause.as_ref().is_none_or(|with| {
+            with.ctes.iter().all(|cte| match &cte.node {
+                Some(NodeEnum::CommonTableExpr(cte)) => matches!(
+                    cte.ctequery.as_ref().and_then(|q| q.node.as_ref()),
+                    Some(NodeEnum::SelectStmt(_))
+                ),
+                _ => false,
+            })
+        })
+}
+
+/// Executes a single statement and collects the rows it returns, at most `row_limit` of them.
+///
+/// The statement is sent via the simple query protocol, so that Postgres returns all values in
+/// their text representation and we don't need to know how to decode every type. Postgres
+/// cancels it if it runs longer than the statement timeout or waits longer than the lock
+/// timeout.
+///
+/// Queries (see [is_cursor_query]) are read through a cursor, other statements that return
+/// rows, e.g. with `returning`, are read completely.
+pub(crate) async fn execute_statement(
+    pool: &PgPool,
+    sql: &str,
+    cursor_query: bool,
+    settings: ExecutionSettings,
+    running: Arc<RunningExecutions>,
+    path: PgLSPath,
+) -> Result<ExecuteStatementResult, sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+
+    let backend_pid: i32 = sqlx::query_scalar("select pg_backend_pid()")
+        .fetch_one(&mut *conn)
+        .await?;
+    let _running = running.register(RunningExecution {
+        path,
+        pool: pool.clone(),
+        backend_pid,
+        progress: StatementExecutionProgress {
+            executed: 0,
+            total: 1,
+            current: None,
+        },
+    });
+
+    // cursors only exist within a transaction
+    let in_transaction = settings.dry_run || cursor_query;
+
+    let result = async {
+        // within a transaction, `set local` keeps the timeouts from outliving it
+        let scope = if in_transaction {
+            conn.execute("begin").await?;
+            "local"
+        } else {
+            "session"
+        };
+        conn.execute(
+            format!(
+                "set {scope} statement_timeout = {}; set {scope} lock_timeout = {};",
+                settings.statement_timeout.as_millis(),
+                settings.lock_timeout.as_millis()
+            )
+            .as_str(),
+        )
+        .await?;
+
+        if cursor_query {
+            collect_cursor_results(&mut conn, sql, settings.row_limit).await
+        } else {
+            collect_results(&mut conn, sql, settings.row_limit).await
+        }
+    }
+    .await;
+
+    // the connection goes back into the pool, so it must not keep the timeouts or the
+    // transaction
+    let cleanup = if settings.dry_run {
+        "rollback"
+    } else if in_transaction {
+        "commit"
+    } else {
+        "reset statement_timeout; reset lock_timeout;"
+    };
+    if conn.execute(cleanup).await.is_err() {
+        conn.close_on_drop();
+    }
+
+    let (rows_affected, result_set) = result?;
+
+    let mut message = match &result_set {
+        None => format!("Successfully executed statement. Rows affected: {rows_affected}"),
+        Some(result_set) if result_set.truncated => format!(
+            "Successfully executed statement. Showing the first {} rows.",
+            result_set.rows.len()
+        ),
+        Some(result_set) => format!(
+            "Successfully executed statement. Rows returned: {}",
+            result_set.rows.len()
+        ),
+    };
+    if settings.dry_run {
+        message = format!("{message} The changes were rolled back.");
+    }
+
+    Ok(ExecuteStatementResult {
+        message,
+        result_set,
+        confirmation_required: None,
+    })
+}
+
+/// A statement executed by [execute_script].
+pub(crate) struct ScriptStatement {
+    pub(crate) range: TextRange,
+    pub(crate) sql: String,
+}
+
+/// Checks whether statements can be executed one after another, before any of them is
+/// executed. Some statements, e.g. `create index concurrently`, cannot run within a
+/// transaction, and a transaction that the statements begin must also be ended by them.
+pub(crate) fn validate_script<'a>(
+    statements: impl IntoIterator<Item = (TextRange, &'a NodeEnum)>,
+) -> Result<(), StatementExecutionError> {
+    let mut transaction_start = None;
+
+    for (range, stmt) in statements {
+        if let NodeEnum::TransactionStmt(stmt) = stmt {
+            match stmt.kind() {
+                TransactionStmtKind::TransStmtBegin | TransactionStmtKind::TransStmtStart => {
+                    transaction_start = Some(range);
+                }
+                TransactionStmtKind::TransStmtCommit
+                | TransactionStmtKind::TransStmtRollback
+                | TransactionStmtKind::TransStmtPrepare => transaction_start = None,
+                _ => {}
+            }
+        } else if let Some(name) = non_transactional_statement(stmt) {
+            if transaction_start.is_some() {
+                return Err(StatementExecutionError {
+                    range,
+                    message: format!("{name} cannot run inside a transaction block."),
+                });
+            }
+        }
+    }
+
+    match transaction_start {
+        Some(range) => Err(StatementExecutionError {
+            range,
+            message: "The transaction is not ended by the executed statements.".into(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Returns the name of the statement if Postgres refuses to run it inside a transaction.
+fn non_transactional_statement(stmt: &NodeEnum) -> Option<&'static str> {
+    match stmt {
+        NodeEnum::IndexStmt(stmt) if stmt.concurrent => Some("CREATE INDEX CONCURRENTLY"),
+        NodeEnum::DropStmt(stmt) if stmt.concurrent => Some("DROP INDEX CONCURRENTLY"),
+        NodeEnum::ReindexStmt(stmt) => {
+            let concurrently = stmt.params.iter().any(|param| {
+                matches!(&param.node, Some(NodeEnum::DefElem(elem)) if elem.defname == "concurrently")
+            });
+            match stmt.kind() {
+                _ if concurrently => Some("REINDEX CONCURRENTLY"),
+                ReindexObjectType::ReindexObjectSchema
+                | ReindexObjectType::ReindexObjectSystem
+                | ReindexObjectType::ReindexObjectDatabase => {
+                    Some("REINDEX SCHEMA, SYSTEM or DATABASE")
+                }
+                _ => None,
+            }
+        }
+        // the added value can't be used before the transaction is committed
+        NodeEnum::AlterEnumStmt(stmt) if stmt.old_val.is_empty() => {
+            Some("ALTER TYPE ... ADD VALUE")
+        }
+        NodeEnum::VacuumStmt(_) => Some("VACUUM"),
+        NodeEnum::CreatedbStmt(_) => Some("CREATE DATABASE"),
+        NodeEnum::DropdbStmt(_) => Some("DROP DATABASE"),
+        _ => None,
+    }
+}
+
+/// Executes the statements one after another on the same connection, so that transactions
+/// they begin span the following statements. Stops at the first statement that fails and
+/// rolls back the transaction it was part of, if any.
+pub(crate) async fn execute_script(
+    pool: &PgPool,
+    statements: &[ScriptStatement],
+    settings: ExecutionSettings,
+    running: Arc<RunningExecutions>,
+    path: PgLSPath,
+) -> Result<ExecuteStatementsResult, sqlx::Error> {
+    let total = statements.len();
+    let mut conn = pool.acquire().await?;
+
+    let backend_pid: i32 = sqlx::query_scalar("select pg_backend_pid()")
+        .fetch_one(&mut *conn)
+        .await?;
+    let running = running.register(RunningExecution {
+        path,
+        pool: pool.clone(),
+        backend_pid,
+        progress: StatementExecutionProgress {
+            executed: 0,
+            total,
+            current: None,
+        },
+    });
+
+    conn.execute(
+        format!(
+            "set statement_timeout = {}; set lock_timeout = {};",
+            settings.statement_timeout.as_millis(),
+            settings.lock_timeout.as_millis()
+        )
+        .as_str(),
+    )
+    .await?;
+
+    let mut executed = 0;
+    let mut error = None;
+    for statement in statements {
+        running.set_progress(executed, statement.range);
+
+        if let Err(err) = conn.execute(statement.sql.as_str()).await {
+            error = Some(script_error(&err, statement));
+            break;
+        }
+        executed += 1;
+    }
+
+    // the connection goes back into the pool, so it must not keep the timeouts or an aborted
+    // transaction
+    let mut cleanup = Vec::new();
+    if error.is_some() {
+        cleanup.push("rollback");
+    }
+    cleanup.push("reset statement_timeout; reset lock_timeout;");
+    for sql in cleanup {
+        if conn.execute(sql).await.is_err() {
+            conn.close_on_drop();
+            break;
+        }
+    }
+
+    let message = match &error {
+        Some(error) => format!(
+            "Executed {executed} of {total} statements, then failed: {}",
+            error.message
+        ),
+        None => format!("Successfully executed {total} statements."),
+    };
+
+    Ok(ExecuteStatementsResult {
+        message,
+        executed,
+        total,
+        error,
+        confirmation_required: None,
+    })
+}
+
+/// Maps the error of a statement to the range in the document it refers to.
+fn script_error(error: &sqlx::Error, statement: &ScriptStatement) -> StatementExecutionError {
+    let Some(db_error) = error.as_database_error() else {
+        return StatementExecutionError {
+            range: statement.range,
+            message: error.to_string(),
+        };
+    };
+
+    let range = db_error
+        .try_downcast_ref::<PgDatabaseError>()
+        .and_then(|db_error| match db_error.position() {
+            Some(PgErrorPosition::Original(position)) => error_range(&statement.sql, position),
+            _ => None,
+        })
+        .map(|range| range + statement.range.start())
+        .unwrap_or(statement.range);
+
+    StatementExecutionError {
+        range,
+        message: db_error.message().to_string(),
+    }
+}
+
+/// Converts the 1-based character position of a Postgres error within `sql` into the range
+/// of the token it points at.
+fn error_range(sql: &str, position: usize) -> Option<TextRange> {
+    let (start, first) = sql.char_indices().nth(position.checked_sub(1)?)?;
+    let len = sql[start..]
+        .find(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '(' | ')'))
+        .unwrap_or(sql.len() - start)
+        .max(first.len_utf8());
+
+    Some(TextRange::at(
+        TextSize::try_from(start).ok()?,
+        TextSize::try_from(len).ok()?,
+    ))
+}
+
+async fn collect_results(
+    conn: &mut sqlx::PgConnection,
+    sql: &str,
+    row_limit: usize,
+) -> Result<(u64, Option<ResultSet>), sqlx::Error> {
+    let mut rows_affected = 0;
+    let mut columns: Option<Vec<ResultSetColumn>> = None;
+    let mut rows = Vec::new();
+    let mut truncated = false;
+
+    {
+        let mut stream = sqlx::raw_sql(sql).fetch_many(&mut *conn);
+        while let Some(item) = stream.try_next().await? {
+            match item {
+                Either::Left(result) => rows_affected += result.rows_affected(),
+                Either::Right(row) => {
+                    if columns.is_none() {
+                        columns = Some(result_set_columns(&row));
+                    }
+
+                    // keep draining the stream, the connection is reused afterwards
+                    if rows.len() < row_limit {
+                        rows.push(row_values(&row)?);
+                    } else {
+                        truncated = true;
+                    }
+                }
+            }
+        }
+    }
+
+    // statements that return no rows still have columns, e.g. a `select` with a false filter
+    if columns.is_none() {
+        columns = describe_columns(conn, sql).await;
+    }
+
+    Ok((
+        rows_affected,
+        columns.map(|columns| ResultSet {
+            columns,
+            rows,
+            truncated,
+        }),
+    ))
+}
+
+/// Reads at most `row_limit` rows of the query through a cursor. Must be called within a
+/// transaction.
+async fn collect_cursor_results(
+    conn: &mut sqlx::PgConnection,
+    sql: &str,
+    row_limit: usize,
+) -> Result<(u64, Option<ResultSet>), sqlx::Error> {
+    conn.execute(format!("declare pgls_result no scroll cursor for {sql}").as_str())
+        .await?;
+
+    // one more row than the limit tells whether the result was truncated
+    let fetch = format!("fetch forward {} from pgls_result", row_limit + 1);
+    let (rows_affected, result_set) = collect_results(conn, &fetch, row_limit).await?;
+
+    conn.execute("close pgls_result").await?;
+
+    // the columns of an empty result are not known from the `fetch`
+    let result_set = match result_set {
+        Some(result_set) if !result_set.columns.is_empty() => Some(result_set),
+        _ => Some(ResultSet {
+            columns: describe_columns(conn, sql).await.unwrap_or_default(),
+            rows: vec![],
+            truncated: false,
+        }),
+    };
+
+    Ok((rows_affected, result_set))
+}
+
+/// The columns the statement returns, as Postgres describes them without executing it.
+async fn describe_columns(
+    conn: &mut sqlx::PgConnection,
+    sql: &str,
+) -> Option<Vec<ResultSetColumn>> {
+    conn.describe(sql).await.ok().and_then(|describe| {
+        (!describe.columns().is_empty()).then(|| {
+            describe
+                .columns()
+                .iter()
+                .map(|column| ResultSetColumn {
+                    name: column.name().to_string(),
+                    type_name: column.type_info().name().to_lowercase(),
+                })
+                .collect()
+        })
+    })
+}
+
+fn result_set_columns(row: &PgRow) -> Vec<ResultSetColumn> {
+    row.columns()
+        .iter()
+        .map(|column| ResultSetColumn {
+            name: column.name().to_string(),
+            type_name: column.type_info().name().to_lowercase(),
+        })
+        .collect()
+}
+
+fn row_values(row: &PgRow) -> Result<Vec<serde_json::Value>, sqlx::Error> {
+    (0..row.len())
+        .map(|index| {
+            let value = row.try_get_raw(index)?;
+            text_value_to_json(value).map_err(sqlx::Error::Decode)
+        })
+        .collect()
+}
+
+/// Converts a value in the text format of Postgres into JSON. Booleans and numbers that
+/// JavaScript represents exactly are converted, `json` and `jsonb` values are parsed, and
+/// everything else, including `int8` and `numeric`, is kept as text.
+fn text_value_to_json(
+    value: PgValueRef<'_>,
+) -> Result<serde_json::Value, sqlx::error::BoxDynError> {
+    if value.is_null() {
+        return Ok(serde_json::Value::Null);
+    }
+
+    let type_name = value.type_info().name().to_string();
+    let text = value.as_str()?;
+
+    let json = match type_name.as_str() {
+        "BOOL" => Some(serde_json::Value::Bool(text == "t")),
+        "INT2" | "INT4" | "OID" => text.parse::<i64>().ok().map(Into::into),
+        "FLOAT4" | "FLOAT8" => text
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(serde_json::Value::Number),
+        "JSON" | "JSONB" => serde_json::from_str(text).ok(),
+        _ => None,
+    };
+
+    Ok(json.unwrap_or_else(|| serde_json::Value::String(text.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use pgls_fs::PgLSPath;
+
+    use super::PendingConfirmations;
+
+    #[test]
+    fn confirms_only_the_issued_statement_once() {
+        let confirmations = PendingConfirmations::default();
+        let path = PgLSPath::new("test.sql");
+        let sql = "drop table users;";
+
+        let token = confirmations
+            .request(&path, sql.into(), "reason".into())
+            .token;
+
+        assert!(!confirmations.confirm(token + 1, &path, sql));
+        assert!(!confirmations.confirm(token, &PgLSPath::new("other.sql"), sql));
+        assert!(!confirmations.confirm(token, &path, "drop table orders;"));
+        assert!(confirmations.confirm(token, &path, sql));
+        assert!(!confirmations.confirm(token, &path, sql));
+    }
+
+    #[test]
+    fn replaces_earlier_confirmations_of_the_file() {
+        let confirmations = PendingConfirmations::default();
+        let path = PgLSPath::new("test.sql");
+
+        let first = confirmations
+            .request(&path, "drop table users;".into(), "reason".into())
+            .token;
+        let second = confirmations
+            .request(&path, "drop table orders;".into(), "reason".into())
+            .token;
+
+        assert!(!confirmations.confirm(first, &path, "drop table users;"));
+        assert!(confirmations.confirm(second, &path, "drop table orders;"));
+    }
+}
@@ -38,6 +38,12 @@ pub fn generate_env_variables(docs_dir: &Path) -> Result<()> {
         env.pgls_config_path.name(),
         env.pgls_config_path.description()
     )?;
+    writeln!(
+        content,
+        "### `{}`\n\n {}\n",
+        env.pgls_allow_password_command.name(),
+        env.pgls_allow_password_command.description()
+    )?;
 
     writeln!(
         content,
@@ -1,6 +1,7 @@
 pub mod cli_doc;
 pub mod default_configuration;
 pub mod env_variables;
+pub mod report_schema;
 pub mod rules_docs;
 pub mod rules_index;
 pub mod rules_sources;
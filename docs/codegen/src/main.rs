@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use docs_codegen::cli_doc::generate_cli_doc;
 use docs_codegen::default_configuration::generate_default_configuration;
 use docs_codegen::env_variables::generate_env_variables;
+use docs_codegen::report_schema::generate_report_schema;
 use docs_codegen::rules_docs::generate_rules_docs;
 use docs_codegen::rules_index::generate_rules_index;
 use docs_codegen::rules_sources::generate_rule_sources;
@@ -26,6 +27,7 @@ fn main() -> anyhow::Result<()> {
     generate_rules_index(&docs_root)?;
     generate_rule_sources(&docs_root)?;
     generate_schema(&docs_root)?;
+    generate_report_schema(&docs_root)?;
     replace_version(&docs_root)?;
 
     Ok(())
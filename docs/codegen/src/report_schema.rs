@@ -0,0 +1,19 @@
+use pgls_cli::{JSON_REPORT_VERSION, JsonReport};
+use schemars::schema_for;
+use serde_json::to_string_pretty;
+use std::{fs, path::Path};
+
+/// Generates the schema of the JSON reporter output.
+///
+/// * `docs_dir`: Path to the docs directory.
+pub fn generate_report_schema(docs_dir: &Path) -> anyhow::Result<()> {
+    let schemas_dir = docs_dir.join("schemas");
+    fs::create_dir_all(&schemas_dir)?;
+
+    let schema_path = schemas_dir.join(format!("json-report-v{JSON_REPORT_VERSION}.json"));
+    let schema_content = to_string_pretty(&schema_for!(JsonReport))?;
+
+    fs::write(schema_path, schema_content)?;
+
+    Ok(())
+}
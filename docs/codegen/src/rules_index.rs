@@ -80,6 +80,12 @@ fn generate_group(
 
 fn extract_group_metadata(group: &str) -> (&str, Markup) {
     match group {
+        "nullability" => (
+            "Nullability",
+            markup! {
+                "Rules that detect queries whose results are affected by NULL values."
+            },
+        ),
         "safety" => (
             "Safety",
             markup! {
@@ -40,6 +40,10 @@ export type RuleCategory = "Lint" | "Action" | "Transformation";
 export interface PullDiagnosticsResult {
 	diagnostics: Diagnostic[];
 	skipped_diagnostics: number;
+	/**
+	 * Diagnostics that were silenced by suppression comments.
+	 */
+	suppressed_diagnostics: Diagnostic[];
 }
 /**
  * Serializable representation for a [Diagnostic](super::Diagnostic).
@@ -62,6 +66,9 @@ export interface Advices {
 	advices: Advice[];
 }
 export type Category =
+	| "lint/nullability/banNotInNullableSubquery"
+	| "lint/nullability/banNullComparison"
+	| "lint/nullability/requireCoalesceOnOuterJoinAggregate"
 	| "lint/safety/addSerialColumn"
 	| "lint/safety/addingFieldWithDefault"
 	| "lint/safety/addingForeignKeyConstraint"
@@ -119,6 +126,7 @@ export type Category =
 	| "splinter/unknown/unknown"
 	| "stdin"
 	| "check"
+	| "baseline"
 	| "configuration"
 	| "database/connection"
 	| "internalError/io"
@@ -127,6 +135,9 @@ export type Category =
 	| "flags/invalid"
 	| "project"
 	| "typecheck"
+	| "explain/missingIndex"
+	| "explain/nestedLoop"
+	| "explain/seqScan"
 	| "plpgsql_check"
 	| "internalError/panic"
 	| "syntax"
@@ -253,6 +264,10 @@ export interface CompletionItem {
 	kind: CompletionItemKind;
 	label: string;
 	preselected: boolean;
+	/**
+	 * The database object this item refers to.
+	 */
+	reference: CompletionItemReference;
 	/**
 	 * String used for sorting by LSP clients.
 	 */
@@ -277,7 +292,22 @@ export type CompletionItemKind =
 	| "column"
 	| "schema"
 	| "policy"
-	| "role";
+	| "role"
+	| "joinCondition";
+/**
+	* Identifies the database object behind a [CompletionItem].
+
+Completion lists are kept lightweight; editors send the reference back when they want to resolve documentation or additional edits for an item. 
+	 */
+export interface CompletionItemReference {
+	kind: CompletionItemKind;
+	name: string;
+	schema?: string;
+	/**
+	 * Only set for columns, policies and join conditions.
+	 */
+	table?: string;
+}
 export interface UpdateSettingsParams {
 	configuration: PartialConfiguration;
 	gitignore_matches: string[];
@@ -292,6 +322,10 @@ export interface PartialConfiguration {
 	 * A field for the [JSON schema](https://json-schema.org/) specification
 	 */
 	$schema?: string;
+	/**
+	 * The configuration for completions
+	 */
+	completions?: PartialCompletionsConfiguration;
 	/**
 	 * The configuration of the database connection
 	 */
@@ -325,6 +359,15 @@ export interface PartialConfiguration {
 	 */
 	vcs?: PartialVcsConfiguration;
 }
+/**
+ * The configuration for completions.
+ */
+export interface PartialCompletionsConfiguration {
+	/**
+	 * if `false`, accepted completions are not recorded and won't affect the ranking of future completions. `true` by default
+	 */
+	learnFromUsage?: boolean;
+}
 /**
  * The configuration of the database connection.
  */
@@ -338,10 +381,26 @@ export interface PartialDatabaseConfiguration {
 	 * A connection string that encodes the full connection setup. When provided, it takes precedence over the individual fields.
 	 */
 	connectionString?: string;
+	/**
+	 * Additional database connections by name. Files use the connection set by a `-- pgls-connection: <name>` comment, or the one `files.connections` assigns to them, and the connection configured here otherwise.
+	 */
+	connections?: {};
 	/**
 	 * The name of the database.
 	 */
 	database?: string;
+	/**
+	 * The time in seconds an executed statement waits for a lock before it is cancelled. Set it to 0 to wait indefinitely.
+	 */
+	executionLockTimeoutSecs?: number;
+	/**
+	 * The maximum number of rows returned when executing a statement.
+	 */
+	executionRowLimit?: number;
+	/**
+	 * The time in seconds after which the execution of a statement is cancelled.
+	 */
+	executionTimeoutSecs?: number;
 	/**
 	 * The host of the database. Required if you want database-related features. All else falls back to sensible defaults.
 	 */
@@ -350,10 +409,38 @@ export interface PartialDatabaseConfiguration {
 	 * The password to connect to the database.
 	 */
 	password?: string;
+	/**
+	 * A command whose output is used as the password, e.g. to read it from a secret manager.
+	 */
+	passwordCommand?: string;
 	/**
 	 * The port of the database.
 	 */
 	port?: number;
+	/**
+	 * The name of a service in the connection service file (`pg_service.conf`) to take the connection parameters from.
+	 */
+	service?: string;
+	/**
+	 * An SSH tunnel to connect through, e.g. if the database is only reachable via a bastion host.
+	 */
+	ssh?: SshTunnelConfiguration;
+	/**
+	 * The path to the client certificate.
+	 */
+	sslCert?: string;
+	/**
+	 * The path to the private key of the client certificate.
+	 */
+	sslKey?: string;
+	/**
+	 * The SSL mode of the connection: `disable`, `allow`, `prefer`, `require`, `verify-ca` or `verify-full`.
+	 */
+	sslMode?: string;
+	/**
+	 * The path to the certificate of the authority to verify the server certificate with.
+	 */
+	sslRootCert?: string;
 	/**
 	 * The username to connect to the database.
 	 */
@@ -364,6 +451,10 @@ export type StringSet = string[];
  * The configuration of the filesystem
  */
 export interface PartialFilesConfiguration {
+	/**
+	 * Assigns named database connections (see `db.connections`) to files. The first entry whose patterns match a file decides its connection.
+	 */
+	connections?: FileConnection[];
 	/**
 	 * A list of Unix shell style patterns. Will ignore files/folders that will match these patterns.
 	 */
@@ -425,6 +516,10 @@ export interface PartialTypecheckConfiguration {
 	 * if `false`, it disables the feature and the typechecker won't be executed. `true` by default
 	 */
 	enabled?: boolean;
+	/**
+	 * if `true`, the DDL statements that precede a statement in the same file are executed before it is typechecked, inside a transaction that is always rolled back. Requires statement executions to be allowed against the database. `false` by default
+	 */
+	replayPriorDdl?: boolean;
 	/**
 	 * Default search path schemas for type checking. Can be a list of schema names or glob patterns like ["public", "app_*"]. If not specified, defaults to ["public"].
 	 */
@@ -457,11 +552,51 @@ If we can't find the configuration, it will attempt to use the current working d
 	 */
 	useIgnoreFile?: boolean;
 }
+/**
+ * An SSH tunnel to the database. The database host and port are resolved by the SSH server.
+ */
+export interface SshTunnelConfiguration {
+	/**
+	 * The host of the SSH server.
+	 */
+	host?: string;
+	/**
+	 * The path to the private key to authenticate with. Uses the SSH agent and the keys `ssh` finds by default if not set.
+	 */
+	identityFile?: string;
+	/**
+	 * How to verify the host key of the SSH server. Defaults to `strict`.
+	 */
+	knownHosts?: KnownHostsPolicy;
+	/**
+	 * The known hosts file to verify the host key with. Defaults to `~/.ssh/known_hosts`.
+	 */
+	knownHostsFile?: string;
+	/**
+	 * The port of the SSH server. Defaults to 22.
+	 */
+	port?: number;
+	/**
+	 * The user to log in to the SSH server with.
+	 */
+	user?: string;
+}
+export interface FileConnection {
+	/**
+	 * The name of the connection in `db.connections`.
+	 */
+	connection: string;
+	/**
+	 * A list of Unix shell style patterns. Files matching any of them use the connection.
+	 */
+	include: StringSet;
+}
 export interface Rules {
 	/**
 	 * It enables ALL rules. The rules that belong to `nursery` won't be enabled.
 	 */
 	all?: boolean;
+	nullability?: Nullability;
 	/**
 	 * It enables the lint rules recommended by Postgres Language Server. `true` by default.
 	 */
@@ -469,6 +604,35 @@ export interface Rules {
 	safety?: Safety;
 }
 export type VcsClientKind = "git";
+/**
+ * How to verify the host key of an SSH server.
+ */
+export type KnownHostsPolicy = "strict" | "acceptNew" | "off";
+/**
+ * A list of rules that belong to this group
+ */
+export interface Nullability {
+	/**
+	 * It enables ALL rules for this group.
+	 */
+	all?: boolean;
+	/**
+	 * NOT IN with a subquery that may return NULL never matches a row.
+	 */
+	banNotInNullableSubquery?: RuleConfiguration_for_Null;
+	/**
+	 * Comparing a value with NULL using = or \<> is never true.
+	 */
+	banNullComparison?: RuleConfiguration_for_Null;
+	/**
+	 * It enables the recommended rules for this group
+	 */
+	recommended?: boolean;
+	/**
+	 * Aggregates over the nullable side of an outer join should be wrapped in COALESCE.
+	 */
+	requireCoalesceOnOuterJoinAggregate?: RuleConfiguration_for_Null;
+}
 /**
  * A list of rules that belong to this group
  */